@@ -4,28 +4,27 @@ use std::env;
 use std::fs::File;
 use std::io::Write;
 use syntax::parse::{parse_crate_from_file, ParseSess};
-use syntax::ast::{ItemKind, Visibility};
+use syntax::ast::{Item, ItemKind, Visibility};
 
 fn main() {
     generate_commands();
 }
 
 /// This build task generates a Rust snippet which, when included later on in
-/// build process, adds logic to construct a HashMap<String, Command> for all
-/// public commands declared in the commands module. This facilitates runtime
-/// command referencing via string, which is required for command mode, as well
-/// as user-defined keymaps.
+/// build process, constructs a Vec<RegisteredCommand> for all public commands
+/// declared in the commands module, described using their doc comments. This
+/// facilitates runtime command referencing via string, which is required for
+/// command mode, as well as user-defined keymaps.
 fn generate_commands() {
     // Create the output file and write the opening lines.
     let current_dir = env::current_dir().expect("Couldn't get the current directory");
     let out_dir = env::var("OUT_DIR").expect("The compiler did not provide $OUT_DIR");
-    let out_file: std::path::PathBuf = [&out_dir, "hash_map"].iter().collect();
+    let out_file: std::path::PathBuf = [&out_dir, "registry"].iter().collect();
     let mut output = File::create(&out_file)
         .expect(&format!("Couldn't create output file: {}", out_file.to_string_lossy()));
     output
-        .write("{\n    let mut commands: HashMap<&'static str, Command> = HashMap::new();\n"
-                   .as_bytes())
-        .expect("Failed to write command hash init");
+        .write("vec![\n".as_bytes())
+        .expect("Failed to write command registry init");
 
     // Parse the crate and get a reference to the command module.
     let session = ParseSess::new();
@@ -41,7 +40,7 @@ fn generate_commands() {
         .node;
 
     // Locate any public methods under the command module
-    // and generate String => Command map entries for them.
+    // and generate registry entries for them.
     if let &ItemKind::Mod(ref module) = command_module {
         for module_item in module.items.iter() {
             if let ItemKind::Mod(ref submodule) = module_item.node {
@@ -49,9 +48,10 @@ fn generate_commands() {
                     if submodule_item.node.descriptive_variant() == "function" &&
                        submodule_item.vis == Visibility::Public {
                         output
-                            .write(format!("    commands.insert(\"{}::{}\", {}::{});\n",
+                            .write(format!("    RegisteredCommand {{ name: \"{}::{}\", description: {:?}, command: {}::{} }},\n",
                                            module_item.ident.name.as_str(),
                                            submodule_item.ident.name.as_str(),
+                                           description(submodule_item),
                                            module_item.ident.name.as_str(),
                                            submodule_item.ident.name.as_str())
                                            .as_bytes())
@@ -64,6 +64,18 @@ fn generate_commands() {
 
     // Finalize the output file.
     output
-        .write("    commands\n}\n".as_bytes())
-        .expect("Failed to write command hash return");
+        .write("]\n".as_bytes())
+        .expect("Failed to write command registry return");
+}
+
+/// Builds a command description from the first paragraph of its doc comment.
+fn description(item: &Item) -> String {
+    item.attrs
+        .iter()
+        .filter(|attr| attr.check_name("doc"))
+        .filter_map(|attr| attr.value_str())
+        .map(|doc| doc.as_str().trim_left_matches("///").trim().to_string())
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<String>>()
+        .join(" ")
 }
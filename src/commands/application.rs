@@ -1,41 +1,336 @@
 use errors::*;
-use commands::{self, Result};
-use input::KeyMap;
+use commands::{self, RegisteredCommand, Result};
+use input::{Key, KeyMap};
 use scribe::Buffer;
+use smallvec::SmallVec;
+use scribe::buffer::Range;
 use std::mem;
-use models::application::{Application, Mode};
+use models::application::{Application, MessageKind, Mode, PendingChange, UndoHistory};
 use models::application::modes::*;
-use util;
+use util::{self, error_log};
+
+/// The largest count that can be typed ahead of a command.
+const MAX_COUNT: usize = 10_000;
 
 pub fn handle_input(app: &mut Application) -> Result {
-    // Listen for and respond to user input.
-    let commands = app.view.last_key().as_ref().and_then(|key| {
-        app.mode_str().and_then(|mode| {
-            app.preferences.borrow().keymap().commands_for(&mode, &key)
-        })
-    });
+    // Listen for and respond to user input, resolving multi-key sequences.
+    let key = match app.view.last_key().clone() {
+        Some(key) => key,
+        None => return Ok(()),
+    };
+
+    // Digits typed in normal mode accumulate into a count for the next command.
+    if accumulate_count(app, &key) {
+        return Ok(());
+    }
+
+    let bindings = match app.mode_str() {
+        Some(mode) => app.key_buffer.push(app.preferences.borrow().keymap(), mode, key),
+        None => return Ok(()),
+    };
+
+    // Keys that aren't bound to anything discard the count they followed.
+    if bindings.is_empty() && app.key_buffer.pending().is_empty() {
+        app.pending_count = None;
+    }
+
+    run_bindings(app, bindings)
+}
+
+/// Runs the commands bound to a pending key sequence's keys individually,
+/// once it's gone too long without being completed.
+pub fn handle_key_sequence_timeout(app: &mut Application) -> Result {
+    let bindings = match app.mode_str() {
+        Some(mode) => app.key_buffer.expire(app.preferences.borrow().keymap(), mode),
+        None => return Ok(()),
+    };
+
+    run_bindings(app, bindings)
+}
+
+/// Runs each key's commands in turn, stopping at the first error. Keys still
+/// pending when the commands switch modes are discarded, since they were
+/// pressed with the previous mode's bindings in mind.
+fn run_bindings(app: &mut Application, bindings: Vec<(Key, SmallVec<[RegisteredCommand; 4]>)>) -> Result {
+    let mode = app.mode_str();
+    let result = run_each_binding(app, bindings);
+    if app.mode_str() != mode {
+        app.key_buffer.clear();
+    }
+
+    result
+}
+
+fn run_each_binding(app: &mut Application, bindings: Vec<(Key, SmallVec<[RegisteredCommand; 4]>)>) -> Result {
+    for (key, coms) in bindings {
+        // Commands rely on the last key, so make sure it reflects the
+        // key that triggered them, rather than one pressed after it.
+        app.view.last_key = Some(key);
+
+        // Operators pending before these commands apply to their motion.
+        let operator_pending = app.pending_operator.is_some();
+
+        let mut result = run_commands(app, &coms);
+
+        // Counts only apply to the commands that immediately follow them.
+        app.pending_count = None;
+
+        // Pastes can only be cycled by the commands immediately following them.
+        expire_paste(app);
+        if operator_pending {
+            if result.is_ok() {
+                result = commands::operator::apply_pending_operator(app);
+            } else {
+                app.pending_operator = None;
+            }
+        }
+        result?;
+    }
+
+    Ok(())
+}
 
-    if let Some(coms) = commands {
-        // Run all commands, stopping at the first error encountered, if any.
-        for com in coms {
-            com(app)?;
+/// Runs all commands, stopping at the first error encountered, if any, and
+/// recording the changes they make as a single history entry. Changes made
+/// to a read-only buffer are discarded instead.
+fn run_commands(app: &mut Application, coms: &[RegisteredCommand]) -> Result {
+    let change = begin_change(app, coms);
+    let mut result = Ok(());
+    for com in coms {
+        result = (com.command)(app);
+        if result.is_err() {
+            break;
         }
     }
+    let rejection = discard_read_only_changes(app, change);
+    let change = if rejection.is_ok() { change } else { None };
+    let change_result = end_change(app, change, coms);
+
+    result.and(rejection).and(change_result)
+}
+
+/// Reverts the changes made to a read-only buffer since `begin_change`,
+/// returning an error if there were any. They're still part of its open
+/// operation group, so undoing them doesn't add an entry to its history.
+fn discard_read_only_changes(app: &mut Application, change: Option<(usize, usize)>) -> Result {
+    let (id, revision) = match change {
+        Some(change) => change,
+        None => return Ok(()),
+    };
+    if !app.read_only_buffers.contains(&id) {
+        return Ok(());
+    }
+
+    let buffer = match app.workspace.current_buffer() {
+        Some(buffer) => buffer,
+        None => return Ok(()),
+    };
+    if buffer.id != Some(id) || app.view.buffer_revision(buffer).ok() == Some(revision) {
+        return Ok(());
+    }
+    buffer.undo();
+
+    bail!("The current buffer is read-only");
+}
+
+/// Groups the changes the commands are about to make into a single entry
+/// in the current buffer's history, returning the buffer's ID and revision
+/// so that the entry can be labelled once they've run. Insert mode sessions
+/// are grouped as a whole, and undos and redos don't add entries at all.
+/// Either way, the group is tracked so that commands don't end it early.
+fn begin_change(app: &mut Application, coms: &[RegisteredCommand]) -> Option<(usize, usize)> {
+    if coms.iter().any(|com| com.name == "buffer::undo" || com.name == "buffer::redo") {
+        return None;
+    }
+
+    let buffer = app.workspace.current_buffer()?;
+    let id = buffer.id?;
+    app.change_group = Some(id);
+    if let Mode::Insert = app.mode {
+        // The session's group is already open.
+        return None;
+    }
+
+    let revision = app.view.buffer_revision(buffer).ok()?;
+    buffer.start_operation_group();
+
+    Some((id, revision))
+}
+
+/// Closes the history entry opened by `begin_change`, labelling it using
+/// the commands that made it, if they changed the buffer. Entering insert
+/// mode leaves the entry open, to be labelled once the mode is left.
+fn end_change(app: &mut Application, change: Option<(usize, usize)>, coms: &[RegisteredCommand]) -> Result {
+    let group = app.change_group.take();
+    let (id, revision) = {
+        let buffer = match app.workspace.current_buffer() {
+            Some(buffer) => buffer,
+            None => return Ok(()),
+        };
+        match (buffer.id, app.view.buffer_revision(buffer)) {
+            (Some(id), Ok(revision)) => (id, revision),
+            _ => return Ok(()),
+        }
+    };
+
+    // Close the group, unless it's become (or remains) an insert mode session.
+    // If the commands switched buffers, the original group is left open;
+    // it'll be closed when that buffer is next changed.
+    let insert_mode = if let Mode::Insert = app.mode { true } else { false };
+    if group == Some(id) && !insert_mode {
+        if let Some(buffer) = app.workspace.current_buffer() {
+            buffer.end_operation_group();
+        }
+    }
+
+    let label = match (change, insert_mode) {
+        // Insert mode was entered; label its changes once it's left.
+        (Some((change_id, change_revision)), true) => {
+            if change_id == id {
+                app.pending_change = Some(PendingChange {
+                    buffer_id: id,
+                    revision: change_revision,
+                    label: history_label(coms),
+                });
+            }
+            return Ok(());
+        }
+        (Some((change_id, change_revision)), false) => {
+            if change_id != id || change_revision == revision {
+                return Ok(());
+            }
+            history_label(coms).unwrap_or_else(|| {
+                coms.last().map(command_label).unwrap_or_else(|| "unknown".to_string())
+            })
+        }
+        // Label sessions entered without an edit using the first one they make.
+        (None, true) => {
+            if let Some(ref mut change) = app.pending_change {
+                if change.buffer_id == id && change.label.is_none() && change.revision != revision {
+                    change.label = history_label(coms);
+                }
+            }
+            return Ok(());
+        }
+        // Insert mode was left; label the session if it changed anything.
+        (None, false) => match app.pending_change.take() {
+            Some(change) => if change.buffer_id == id && change.revision != revision {
+                change.label.unwrap_or_else(|| "insert".to_string())
+            } else {
+                return Ok(());
+            },
+            None => return Ok(()),
+        },
+    };
+
+    app.undo_histories
+        .entry(id)
+        .or_insert_with(UndoHistory::new)
+        .record(label);
 
     Ok(())
 }
 
+/// Describes a change using the first command that edits content, rather
+/// than one that moves the cursor or switches modes, if there is one.
+fn history_label(coms: &[RegisteredCommand]) -> Option<String> {
+    coms.iter()
+        .find(|com| {
+            !com.name.starts_with("cursor::") &&
+                !com.name.starts_with("view::") &&
+                !com.name.starts_with("application::")
+        })
+        .map(command_label)
+}
+
+/// Describes a command using its description, falling back to its
+/// name (e.g. "delete line" for buffer::delete_line).
+fn command_label(com: &RegisteredCommand) -> String {
+    if com.description.is_empty() {
+        com.name.rsplit("::").next().unwrap_or(com.name).replace('_', " ")
+    } else {
+        com.description.to_string()
+    }
+}
+
+/// Opens a buffer listing the current buffer's recent changes, from newest
+/// to oldest, marking its current position in the undo history.
+pub fn show_history(app: &mut Application) -> Result {
+    let id = app.workspace
+        .current_buffer()
+        .ok_or(BUFFER_MISSING)?
+        .id
+        .ok_or("The current buffer has no ID")?;
+    let summary = app.undo_histories
+        .get(&id)
+        .map(|history| history.summary())
+        .ok_or("No changes have been made to the current buffer")?;
+
+    let mut history_buffer = Buffer::new();
+    history_buffer.insert(summary);
+
+    util::add_buffer(history_buffer, app)
+}
+
+/// Forgets the last paste unless it happened during the commands that just
+/// ran, in which case it's marked as stale, to be forgotten after the next.
+fn expire_paste(app: &mut Application) {
+    let fresh = app.last_paste.as_ref().map(|paste| paste.fresh).unwrap_or(false);
+
+    if fresh {
+        if let Some(ref mut paste) = app.last_paste {
+            paste.fresh = false;
+        }
+    } else {
+        app.last_paste = None;
+    }
+}
+
+/// Adds the key to the pending count if it's a digit typed in normal mode
+/// outside of a key sequence. Zero is only treated as part of a count once
+/// one has been started, so that it remains available as a key binding.
+/// Counts are capped at MAX_COUNT, so that a mistyped count can't tie the
+/// editor up repeating a command millions of times.
+fn accumulate_count(app: &mut Application, key: &Key) -> bool {
+    let digit = match (&app.mode, key) {
+        (&Mode::Normal, &Key::Char(c)) if app.key_buffer.pending().is_empty() => c.to_digit(10),
+        _ => None,
+    };
+
+    match (digit, app.pending_count) {
+        (Some(0), None) | (None, _) => false,
+        (Some(digit), count) => {
+            let count = count
+                .unwrap_or(0)
+                .saturating_mul(10)
+                .saturating_add(digit as usize)
+                .min(MAX_COUNT);
+            app.pending_count = Some(count);
+
+            true
+        }
+    }
+}
+
 pub fn switch_to_normal_mode(app: &mut Application) -> Result {
     let _ = commands::buffer::end_command_group(app);
+    remember_selection(app);
     app.mode = Mode::Normal;
+    app.selections.clear();
 
     Ok(())
 }
 
 pub fn switch_to_insert_mode(app: &mut Application) -> Result {
+    if commands::is_read_only(app) {
+        bail!("The current buffer is read-only");
+    }
+
     if app.workspace.current_buffer().is_some() {
         commands::buffer::start_command_group(app)?;
+        remember_selection(app);
         app.mode = Mode::Insert;
+        app.selections.clear();
         commands::view::scroll_to_cursor(app)?;
     } else {
         bail!(BUFFER_MISSING);
@@ -44,6 +339,18 @@ pub fn switch_to_insert_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Records the buffer, anchor, and cursor of an active
+/// selection, so that it can be restored after leaving select mode.
+fn remember_selection(app: &mut Application) {
+    if let Mode::Select(ref mode) = app.mode {
+        if let Some(buffer) = app.workspace.current_buffer() {
+            if let Some(id) = buffer.id {
+                app.last_selection = Some((id, mode.anchor, *buffer.cursor));
+            }
+        }
+    }
+}
+
 pub fn switch_to_jump_mode(app: &mut Application) -> Result {
     let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
 
@@ -85,11 +392,25 @@ pub fn switch_to_second_stage_jump_mode(app: &mut Application) -> Result {
 }
 
 pub fn switch_to_line_jump_mode(app: &mut Application) -> Result {
-    if app.workspace.current_buffer().is_some() {
-        app.mode = Mode::LineJump(LineJumpMode::new());
-    } else {
-        bail!(BUFFER_MISSING);
-    }
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let mut mode = LineJumpMode::new();
+    mode.original_cursor = Some(*buffer.cursor);
+    mode.original_scroll_offset = Some(app.view.scroll_offset(buffer)?);
+    app.mode = Mode::LineJump(mode);
+
+    Ok(())
+}
+
+pub fn switch_to_replace_char_mode(app: &mut Application) -> Result {
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    app.mode = Mode::ReplaceChar;
+
+    Ok(())
+}
+
+pub fn switch_to_separator_mode(app: &mut Application) -> Result {
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    app.mode = Mode::Separator;
 
     Ok(())
 }
@@ -126,6 +447,34 @@ pub fn switch_to_symbol_jump_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Focuses the outline pane, opening it if it isn't already shown.
+pub fn switch_to_outline_mode(app: &mut Application) -> Result {
+    if app.workspace.current_buffer().is_none() {
+        bail!(BUFFER_MISSING);
+    }
+    if app.outline.is_none() {
+        app.outline = Some(Outline::new());
+    }
+    app.update_outline();
+    if app.outline.as_ref().map(|outline| outline.entries.is_empty()).unwrap_or(true) {
+        bail!("No symbols found in the current buffer");
+    }
+    app.mode = Mode::Outline;
+
+    Ok(())
+}
+
+pub fn switch_to_quickfix_mode(app: &mut Application) -> Result {
+    if app.quickfix.is_empty() {
+        bail!("The quickfix list is empty");
+    }
+    let config = app.preferences.borrow().search_select_config();
+    app.mode = Mode::Quickfix(QuickfixMode::new(app.quickfix.to_vec(), config));
+    commands::search_select::search(app)?;
+
+    Ok(())
+}
+
 pub fn switch_to_theme_mode(app: &mut Application) -> Result {
     let config = app.preferences.borrow().search_select_config();
     app.mode = Mode::Theme(
@@ -142,6 +491,7 @@ pub fn switch_to_theme_mode(app: &mut Application) -> Result {
 pub fn switch_to_select_mode(app: &mut Application) -> Result {
     if let Some(buffer) = app.workspace.current_buffer() {
         app.mode = Mode::Select(SelectMode::new(*buffer.cursor.clone()));
+        app.selections.clear();
     } else {
         bail!(BUFFER_MISSING);
     }
@@ -159,14 +509,29 @@ pub fn switch_to_select_line_mode(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Switches to search mode. Searches started with an active selection only
+/// match content within the selected range, as do subsequent searches
+/// started from within search mode.
 pub fn switch_to_search_mode(app: &mut Application) -> Result {
-    if app.workspace.current_buffer().is_some() {
-        app.mode = Mode::Search(
-            SearchMode::new(app.search_query.clone())
-        );
-    } else {
-        bail!(BUFFER_MISSING);
-    }
+    let scope = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+
+        match app.mode {
+            Mode::Select(ref mode) => {
+                Some(SearchScope::new(&Range::new(*buffer.cursor, mode.anchor), &buffer.data()))
+            }
+            Mode::SelectLine(ref mode) => {
+                Some(SearchScope::new(&mode.to_range(&*buffer.cursor), &buffer.data()))
+            }
+            Mode::Search(ref mode) => mode.scope.clone(),
+            _ => None,
+        }
+    };
+    let query = app.search_query.clone();
+    app.mode = match scope {
+        Some(scope) => Mode::Search(SearchMode::scoped(query, scope)),
+        None => Mode::Search(SearchMode::new(query)),
+    };
 
     Ok(())
 }
@@ -209,6 +574,7 @@ pub fn display_quick_start_guide(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Opens a new buffer listing all available commands.
 pub fn display_available_commands(app: &mut Application) -> Result {
     commands::workspace::new_buffer(app)?;
 
@@ -246,6 +612,44 @@ pub fn display_last_error(app: &mut Application) -> Result {
     util::add_buffer(scope_display_buffer, app)
 }
 
+/// Re-runs the command most recently selected in command mode.
+pub fn repeat_last_palette_command(app: &mut Application) -> Result {
+    let name = match app.last_palette_command {
+        Some(name) => name,
+        None => {
+            app.notify("No command mode command to repeat");
+            return Ok(());
+        }
+    };
+    let command = *commands::hash_map()
+        .get(name)
+        .ok_or_else(|| format!("Couldn't find the \"{}\" command", name))?;
+
+    command(app).chain_err(|| format!("Failed to run the \"{}\" command", name))
+}
+
+pub fn show_message_history(app: &mut Application) -> Result {
+    if app.message_history.is_empty() {
+        bail!("No messages to display");
+    }
+
+    let mut history_buffer = Buffer::new();
+    for message in app.message_history.iter() {
+        let kind = match message.kind {
+            MessageKind::Error => "error",
+            MessageKind::Notice => "notice",
+        };
+        history_buffer.insert(format!(
+            "[{}] {}: {}\n",
+            error_log::timestamp(message.time),
+            kind,
+            message.content
+        ));
+    }
+
+    util::add_buffer(history_buffer, app)
+}
+
 pub fn suspend(app: &mut Application) -> Result {
     // We need to clear the cursor or it won't render properly on resume.
     app.view.set_cursor(None);
@@ -262,10 +666,13 @@ pub fn exit(app: &mut Application) -> Result {
 
 #[cfg(test)]
 mod tests {
+    use input::Key;
     use models::Application;
-    use models::application::Mode;
+    use models::application::{ClipboardContent, Mode};
+    use models::application::modes::SearchSelectMode;
     use scribe::Buffer;
     use std::path::PathBuf;
+    use util;
 
     #[test]
     fn display_available_commands_creates_a_new_buffer() {
@@ -283,7 +690,7 @@ mod tests {
         let buffer_data = app.workspace.current_buffer().unwrap().data();
         let mut lines = buffer_data.lines();
         assert_eq!(lines.nth(0), Some("application::display_available_commands"));
-        assert_eq!(lines.last(), Some("workspace::next_buffer"));
+        assert_eq!(lines.last(), Some("workspace::save_session"));
     }
 
     #[test]
@@ -355,4 +762,210 @@ mod tests {
 
         assert!(super::switch_to_path_mode(&mut app).is_err());
     }
+
+    #[test]
+    fn handle_input_accumulates_digits_into_a_count_for_the_next_command() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("\n".repeat(20));
+        app.workspace.add_buffer(buffer);
+
+        for key in &[Key::Char('1'), Key::Char('2')] {
+            app.view.last_key = Some(key.clone());
+            super::handle_input(&mut app).unwrap();
+        }
+        assert_eq!(app.pending_count, Some(12));
+
+        app.view.last_key = Some(Key::Char('j'));
+        super::handle_input(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 12);
+        assert!(app.pending_count.is_none());
+    }
+
+    #[test]
+    fn handle_input_doesnt_start_a_count_with_zero() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        app.view.last_key = Some(Key::Char('0'));
+        super::handle_input(&mut app).unwrap();
+
+        assert!(app.pending_count.is_none());
+    }
+
+    #[test]
+    fn handle_input_caps_the_count() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        for _ in 0..10 {
+            app.view.last_key = Some(Key::Char('9'));
+            super::handle_input(&mut app).unwrap();
+        }
+
+        assert_eq!(app.pending_count, Some(super::MAX_COUNT));
+    }
+
+    #[test]
+    fn handle_input_discards_the_count_when_followed_by_an_unbound_key() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        app.view.last_key = Some(Key::Char('5'));
+        super::handle_input(&mut app).unwrap();
+        app.view.last_key = Some(Key::Ctrl('\\'));
+        super::handle_input(&mut app).unwrap();
+
+        assert!(app.pending_count.is_none());
+    }
+
+    #[test]
+    fn handle_input_rejects_changes_to_read_only_buffers() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        util::diff::open("-amp\n+editor\n", &mut app).unwrap();
+
+        app.view.last_key = Some(Key::Char('x'));
+        assert!(super::handle_input(&mut app).is_err());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "-amp\n+editor\n");
+
+        // Rejected changes are discarded, rather than added to the history.
+        app.view.last_key = Some(Key::Char('u'));
+        let _ = super::handle_input(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "-amp\n+editor\n");
+        assert!(app.undo_histories.values().all(|history| history.summary().lines().count() == 1));
+
+        app.view.last_key = Some(Key::Char('i'));
+        assert!(super::handle_input(&mut app).is_err());
+        if let Mode::Normal = app.mode {
+        } else {
+            panic!("Not in normal mode");
+        }
+    }
+
+    #[test]
+    fn command_mode_rejects_changes_to_read_only_buffers() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        util::diff::open("-amp\n+editor\n", &mut app).unwrap();
+
+        super::switch_to_command_mode(&mut app).unwrap();
+        if let Mode::Command(ref mut mode) = app.mode {
+            mode.query().push_str("buffer::delete_current_line");
+            mode.search();
+        }
+        app.view.last_key = Some(Key::Enter);
+        assert!(super::handle_input(&mut app).is_err());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "-amp\n+editor\n");
+    }
+
+    #[test]
+    fn handle_input_records_changes_in_the_undo_history() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        util::add_buffer(buffer, &mut app).unwrap();
+
+        // Delete a character, insert two more in a single
+        // insert mode session, and then undo the latter.
+        let keys = vec![
+            Key::Char('x'),
+            Key::Char('i'),
+            Key::Char('a'),
+            Key::Char('b'),
+            Key::Esc,
+            Key::Char('l'),
+            Key::Char('u'),
+        ];
+        for key in keys {
+            app.view.last_key = Some(key);
+            super::handle_input(&mut app).unwrap();
+        }
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "mp");
+
+        super::show_history(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "    2 insert char (undone)\n\
+             >   1 delete\n\
+             \x20   0 (original)\n"
+        );
+    }
+
+    #[test]
+    fn handle_input_undoes_a_select_mode_paste_in_a_single_step() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        util::add_buffer(buffer, &mut app).unwrap();
+        app.clipboard.set_content(ClipboardContent::Inline("editor".to_string())).unwrap();
+
+        // Pasting over a selection deletes it first, using its own group.
+        for key in &[Key::Char('v'), Key::Char('l'), Key::Char('p')] {
+            app.view.last_key = Some(key.clone());
+            super::handle_input(&mut app).unwrap();
+        }
+        assert_ne!(app.workspace.current_buffer().unwrap().data(), "amp");
+
+        app.view.last_key = Some(Key::Char('u'));
+        super::handle_input(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp");
+    }
+
+    #[test]
+    fn show_history_fails_without_changes() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        assert!(super::show_history(&mut app).is_err());
+    }
+
+    #[test]
+    fn repeat_last_palette_command_runs_the_command_again() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\ntext\n");
+        app.workspace.add_buffer(buffer);
+
+        // Run a command from command mode.
+        super::switch_to_command_mode(&mut app).unwrap();
+        if let Mode::Command(ref mut mode) = app.mode {
+            mode.query().push_str("cursor::move_down");
+            mode.search();
+        }
+        ::commands::search_select::accept(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 1);
+
+        super::repeat_last_palette_command(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 2);
+    }
+
+    #[test]
+    fn repeat_last_palette_command_is_not_tracked_when_selected() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\ntext\n");
+        app.workspace.add_buffer(buffer);
+
+        // Run a command, and then the repeat command, from command mode.
+        for name in &["cursor::move_down", "application::repeat_last_palette_command"] {
+            super::switch_to_command_mode(&mut app).unwrap();
+            if let Mode::Command(ref mut mode) = app.mode {
+                mode.query().push_str(name);
+                mode.search();
+            }
+            ::commands::search_select::accept(&mut app).unwrap();
+        }
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 2);
+        assert_eq!(app.last_palette_command, Some("cursor::move_down"));
+
+        super::repeat_last_palette_command(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 3);
+    }
+
+    #[test]
+    fn repeat_last_palette_command_does_nothing_without_a_previous_command() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        super::repeat_last_palette_command(&mut app).unwrap();
+
+        assert!(app.message_history.iter().last().is_some());
+    }
 }
@@ -2,15 +2,33 @@ use errors::*;
 use commands::{self, Result};
 use std::mem;
 use input::Key;
-use util;
+use util::{self, line_ending, url, FileFormat};
+use util::uuid::{self, RandomSource, SystemRandom};
+use util::char_info::CharInfo;
 use util::token::{Direction, adjacent_token_position};
-use models::application::{Application, ClipboardContent, Mode};
-use models::application::modes::ConfirmMode;
-use scribe::buffer::{Buffer, Position, Range};
+use models::application::{Application, ClipboardContent, Mode, Paste, UndoHistory};
+use models::application::modes::{Choice, ConfirmMode, LineFilterMode, SelectableListMode, ShellCommandMode};
+use scribe::buffer::{Buffer, LineRange, Position, Range};
+use std::cmp::Ordering;
+use std::fs;
+use std::process;
+use unicode_segmentation::UnicodeSegmentation;
+
+const MAX_CLOSED_BUFFERS: usize = 10;
 
 pub fn save(app: &mut Application) -> Result {
-    remove_trailing_whitespace(app)?;
-    ensure_trailing_newline(app)?;
+    let (trim_trailing_whitespace, insert_final_newline) = {
+        let preferences = app.preferences.borrow();
+        let path = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.path.as_ref();
+
+        (preferences.trim_trailing_whitespace(path), preferences.insert_final_newline(path))
+    };
+    if trim_trailing_whitespace {
+        remove_trailing_whitespace(app)?;
+    }
+    if insert_final_newline {
+        ensure_trailing_newline(app)?;
+    }
 
     // Slight duplication here, but we need to check for a buffer path without
     // borrowing the buffer for the full scope of this save command. That will
@@ -23,11 +41,20 @@ pub fn save(app: &mut Application) -> Result {
         .path.is_some();
 
     if path_set {
-        app.workspace
-            .current_buffer()
-            .ok_or(BUFFER_MISSING)?
-            .save()
-            .chain_err(|| "Unable to save buffer")
+        // Buffers are edited as UTF-8 with LF line endings;
+        // re-apply the line ending and encoding they're saved with.
+        let (id, path) = {
+            let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+            (buffer.id, buffer.path.clone())
+        };
+        let format = util::file_format(app, id, path.as_ref());
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        line_ending::save_buffer(buffer, format)?;
+
+        // Don't mistake this save for an external change.
+        app.file_watcher.reset();
+
+        Ok(())
     } else {
         commands::application::switch_to_path_mode(app)?;
         if let Mode::Path(ref mut mode) = app.mode {
@@ -38,10 +65,79 @@ pub fn save(app: &mut Application) -> Result {
     }
 }
 
+/// Saves every modified buffer that has a path, leaving the current buffer selected.
+pub fn save_all(app: &mut Application) -> Result {
+    let id = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.id;
+    let mut result = Ok(());
+
+    loop {
+        let savable = app.workspace
+            .current_buffer()
+            .map(|buffer| buffer.modified() && buffer.path.is_some())
+            .unwrap_or(false);
+        if savable {
+            result = save(app);
+            if result.is_err() { break; }
+        }
+
+        app.workspace.next_buffer();
+        if app.workspace.current_buffer().and_then(|buffer| buffer.id) == id {
+            break;
+        }
+    }
+    select_buffer(app, id);
+
+    result
+}
+
+/// Switches the encoding used when saving the current buffer
+/// to the next supported one (e.g. from UTF-8 to Latin-1).
+pub fn force_encoding(app: &mut Application) -> Result {
+    let id = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.id.ok_or("Buffer has no ID")?;
+    let encoding = {
+        let format = app.file_formats.entry(id).or_insert_with(FileFormat::default);
+        format.encoding = format.encoding.next();
+
+        format.encoding
+    };
+    app.notify(format!("Buffer will be saved as {}", encoding));
+
+    Ok(())
+}
+
+/// Prompts for a new path for the current buffer's file,
+/// which is moved on disk (and in git) once accepted.
+pub fn rename_file(app: &mut Application) -> Result {
+    app.workspace
+        .current_buffer()
+        .ok_or(BUFFER_MISSING)?
+        .path.as_ref()
+        .ok_or(BUFFER_PATH_MISSING)?;
+
+    commands::application::switch_to_path_mode(app)?;
+    if let Mode::Path(ref mut mode) = app.mode {
+        mode.rename_on_accept = true;
+    }
+
+    Ok(())
+}
+
 pub fn reload(app: &mut Application) -> Result {
-    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.reload().chain_err(|| {
-        "Unable to reload buffer."
-    })
+    let (id, format) = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let format = line_ending::reload_buffer(buffer)
+            .chain_err(|| "Unable to reload buffer.")?;
+
+        (buffer.id, format)
+    };
+
+    // The file's format may have changed, and the buffer's history is gone.
+    if let Some(id) = id {
+        app.file_formats.insert(id, format);
+        app.undo_histories.insert(id, UndoHistory::new());
+    }
+
+    Ok(())
 }
 
 pub fn delete(app: &mut Application) -> Result {
@@ -78,7 +174,13 @@ pub fn delete_token(app: &mut Application) -> Result {
 }
 
 pub fn delete_current_line(app: &mut Application) -> Result {
+    // Extend the selection to cover any counted lines below.
+    let count = app.take_count();
     commands::application::switch_to_select_line_mode(app)?;
+    if count > 1 {
+        app.pending_count = Some(count - 1);
+        commands::cursor::move_down(app)?;
+    }
     commands::selection::copy_and_delete(app)?;
     commands::application::switch_to_normal_mode(app)?;
     commands::view::scroll_to_cursor(app)?;
@@ -145,16 +247,367 @@ pub fn merge_next_line(app: &mut Application) -> Result {
     });
     buffer.insert(merged_lines);
     buffer.cursor.move_to(target_position);
-    buffer.end_operation_group();
+    util::history::end_operation_group(buffer, app.change_group);
+
+    Ok(())
+}
+
+/// Reflows the selected lines (or the paragraph surrounding the cursor) so
+/// that they fit within the `text_width` preference, breaking lines between
+/// words. Wrapped lines share the indentation of the first line.
+pub fn hard_wrap(app: &mut Application) -> Result {
+    let text_width = app.preferences.borrow().text_width();
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let data = buffer.data();
+    let lines: Vec<&str> = data.lines().collect();
+
+    let (start, end) = match app.mode {
+        Mode::Select(ref mode) => ordered_lines(mode.anchor.line, buffer.cursor.line),
+        Mode::SelectLine(ref mode) => ordered_lines(mode.anchor, buffer.cursor.line),
+        _ => paragraph(&lines, buffer.cursor.line).ok_or("No paragraph found at the cursor")?,
+    };
+    let end = end.min(lines.len().checked_sub(1).ok_or("Nothing to wrap")?);
+    if start > end {
+        bail!("Nothing to wrap");
+    }
+
+    let wrapped_lines = wrap_lines(&lines[start..end + 1], text_width);
+    if wrapped_lines.is_empty() {
+        bail!("Nothing to wrap");
+    }
+
+    // Replace the lines with their wrapped
+    // equivalent, as a single operation.
+    let start_position = Position { line: start, offset: 0 };
+    buffer.start_operation_group();
+    buffer.delete_range(Range::new(
+        start_position,
+        Position { line: end, offset: lines[end].graphemes(true).count() }
+    ));
+    buffer.cursor.move_to(start_position);
+    buffer.insert(wrapped_lines.join("\n"));
+    util::history::end_operation_group(buffer, app.change_group);
+
+    match app.mode {
+        Mode::Select(_) | Mode::SelectLine(_) => {
+            commands::application::switch_to_normal_mode(app)?;
+        }
+        _ => (),
+    }
+    commands::view::scroll_to_cursor(app)?;
+
+    Ok(())
+}
+
+fn ordered_lines(first: usize, second: usize) -> (usize, usize) {
+    if first <= second {
+        (first, second)
+    } else {
+        (second, first)
+    }
+}
+
+/// The first and last lines of the block of non-blank lines
+/// containing the specified line, if it isn't blank itself.
+fn paragraph(lines: &[&str], line: usize) -> Option<(usize, usize)> {
+    if lines.get(line)?.trim().is_empty() {
+        return None;
+    }
+
+    util::line_run(lines, line)
+}
+
+/// Joins the words in the specified lines, and splits them into lines no
+/// longer than the width, prefixed with the first line's indentation.
+fn wrap_lines(lines: &[&str], width: usize) -> Vec<String> {
+    let indentation: String = lines
+        .first()
+        .map(|line| line.chars().take_while(|c| c.is_whitespace()).collect())
+        .unwrap_or_default();
+
+    wrap_words(lines.iter().flat_map(|line| line.split_whitespace()), &indentation, width)
+}
+
+/// Splits the words into lines no longer than the width (including the prefix
+/// that starts each line). Words longer than the width aren't broken up;
+/// they're placed on their own line instead.
+fn wrap_words<'a, I: Iterator<Item=&'a str>>(words: I, prefix: &str, width: usize) -> Vec<String> {
+    let prefix_width = prefix.graphemes(true).count();
+
+    let mut wrapped_lines = Vec::new();
+    let mut current_line = prefix.to_string();
+    let mut current_width = prefix_width;
+    for word in words {
+        let word_width = word.graphemes(true).count();
+
+        if current_width > prefix_width {
+            if current_width + 1 + word_width > width {
+                wrapped_lines.push(mem::replace(&mut current_line, prefix.to_string()));
+                current_width = prefix_width;
+            } else {
+                current_line.push(' ');
+                current_width += 1;
+            }
+        }
+        current_line.push_str(word);
+        current_width += word_width;
+    }
+    if current_width > prefix_width {
+        wrapped_lines.push(current_line);
+    }
+
+    wrapped_lines
+}
+
+/// Reflows the comment block containing the cursor so that it fits within
+/// the `text_width` preference, re-prefixing each line with the comment
+/// marker. Lines containing only the marker separate comment paragraphs.
+pub fn reflow_comment(app: &mut Application) -> Result {
+    let text_width = app.preferences.borrow().text_width();
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let data = buffer.data();
+    let lines: Vec<&str> = data.lines().collect();
+    let current_line = lines.get(buffer.cursor.line).ok_or(CURRENT_LINE_MISSING)?;
+
+    let marker = comment_marker(current_line).ok_or("The current line isn't a comment")?;
+    let is_comment = |index: usize| {
+        comment_marker(lines[index]) == Some(marker) &&
+            !lines[index].trim_left()[marker.len()..].trim().is_empty()
+    };
+    if !is_comment(buffer.cursor.line) {
+        bail!("The current line doesn't have any comment text to reflow");
+    }
+
+    let mut start = buffer.cursor.line;
+    while start > 0 && is_comment(start - 1) {
+        start -= 1;
+    }
+    let mut end = buffer.cursor.line;
+    while end + 1 < lines.len() && is_comment(end + 1) {
+        end += 1;
+    }
+
+    // Strip the markers, and re-apply them (following
+    // the first line's indentation) when wrapping.
+    let indentation: String = lines[start].chars().take_while(|c| c.is_whitespace()).collect();
+    let prefix = format!("{}{} ", indentation, marker);
+    let words = lines[start..end + 1]
+        .iter()
+        .flat_map(|line| line.trim_left()[marker.len()..].split_whitespace());
+    let wrapped_lines = wrap_words(words, &prefix, text_width);
+
+    let start_position = Position { line: start, offset: 0 };
+    buffer.start_operation_group();
+    buffer.delete_range(Range::new(
+        start_position,
+        Position { line: end, offset: lines[end].graphemes(true).count() }
+    ));
+    buffer.cursor.move_to(start_position);
+    buffer.insert(wrapped_lines.join("\n"));
+    util::history::end_operation_group(buffer, app.change_group);
+    commands::view::scroll_to_cursor(app)?;
+
+    Ok(())
+}
+
+/// The comment marker that starts the line (following any indentation), if
+/// any. Markers are tried longest first, so that doc comments (e.g. "///")
+/// keep their markers, and must be followed by whitespace (or the end of the
+/// line), so that lines like "#[derive(Debug)]" aren't mistaken for comments.
+fn comment_marker(line: &str) -> Option<&'static str> {
+    let content = line.trim_left();
+
+    ["///", "//!", "//", "#", "--"]
+        .iter()
+        .find(|marker| {
+            content.starts_with(*marker) &&
+                content[marker.len()..].chars().next().map(|c| c.is_whitespace()).unwrap_or(true)
+        })
+        .cloned()
+}
+
+/// Swaps the current line (or selected lines) with the line above.
+pub fn move_line_up(app: &mut Application) -> Result {
+    move_lines(app, Direction::Backward)
+}
+
+/// Swaps the current line (or selected lines) with the line below.
+pub fn move_line_down(app: &mut Application) -> Result {
+    move_lines(app, Direction::Forward)
+}
+
+fn move_lines(app: &mut Application, direction: Direction) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let data = buffer.data();
+    let lines: Vec<&str> = data.lines().collect();
+
+    let (start, end) = match app.mode {
+        Mode::SelectLine(ref mode) => ordered_lines(mode.anchor, buffer.cursor.line),
+        _ => (buffer.cursor.line, buffer.cursor.line),
+    };
+
+    // Don't bother if we're already at the boundary.
+    if end >= lines.len() {
+        return Ok(());
+    }
+    let (region_start, region_end, moved_lines) = match direction {
+        Direction::Backward => {
+            if start == 0 {
+                return Ok(());
+            }
+            let mut moved_lines = lines[start..end + 1].to_vec();
+            moved_lines.push(lines[start - 1]);
+
+            (start - 1, end, moved_lines)
+        }
+        Direction::Forward => {
+            if end + 1 >= lines.len() {
+                return Ok(());
+            }
+            let mut moved_lines = vec![lines[end + 1]];
+            moved_lines.extend_from_slice(&lines[start..end + 1]);
+
+            (start, end + 1, moved_lines)
+        }
+    };
+    let shift = |line: usize| match direction {
+        Direction::Backward => line - 1,
+        Direction::Forward => line + 1,
+    };
+
+    // Replace the affected lines with their reordered
+    // equivalent, and move the cursor along with them.
+    let target_position = Position {
+        line: shift(buffer.cursor.line),
+        offset: buffer.cursor.offset,
+    };
+    buffer.start_operation_group();
+    buffer.delete_range(Range::new(
+        Position { line: region_start, offset: 0 },
+        Position { line: region_end, offset: lines[region_end].graphemes(true).count() }
+    ));
+    buffer.cursor.move_to(Position { line: region_start, offset: 0 });
+    buffer.insert(moved_lines.join("\n"));
+    buffer.cursor.move_to(target_position);
+    util::history::end_operation_group(buffer, app.change_group);
+
+    if let Mode::SelectLine(ref mut mode) = app.mode {
+        mode.anchor = shift(mode.anchor);
+    }
+    commands::view::scroll_to_cursor(app)?;
 
     Ok(())
 }
 
+/// Swaps the current line with the one above it and moves the cursor to the
+/// start of the next line, so that repeated use drags a line down through
+/// the buffer (like Emacs' `transpose-lines`). Does nothing on the first line.
+pub fn transpose_lines(app: &mut Application) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let data = buffer.data();
+    let lines: Vec<&str> = data.lines().collect();
+    let line = buffer.cursor.line;
+
+    if line == 0 || line >= lines.len() {
+        return Ok(());
+    }
+
+    buffer.start_operation_group();
+    buffer.delete_range(Range::new(
+        Position { line: line - 1, offset: 0 },
+        Position { line, offset: lines[line].graphemes(true).count() }
+    ));
+    buffer.cursor.move_to(Position { line: line - 1, offset: 0 });
+    buffer.insert(format!("{}\n{}", lines[line], lines[line - 1]));
+    if !buffer.cursor.move_to(Position { line: line + 1, offset: 0 }) {
+        // There's no next line; stay on the transposed one.
+        buffer.cursor.move_to(Position { line, offset: 0 });
+    }
+    util::history::end_operation_group(buffer, app.change_group);
+
+    commands::view::scroll_to_cursor(app)
+}
+
+/// Sorts the selected lines by the first number each contains (e.g. so that
+/// "file2" precedes "file10"). Lines without numbers follow those with them,
+/// sorted lexicographically.
+pub fn sort_lines_numeric(app: &mut Application) -> Result {
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let (start, end) = match app.mode {
+            Mode::Select(ref mode) => ordered_lines(mode.anchor.line, buffer.cursor.line),
+            Mode::SelectLine(ref mode) => ordered_lines(mode.anchor, buffer.cursor.line),
+            _ => bail!("Can't sort lines outside of select mode"),
+        };
+        let data = buffer.data();
+        let lines: Vec<&str> = data.lines().collect();
+        let end = end.min(lines.len().checked_sub(1).ok_or("Nothing to sort")?);
+
+        let mut sorted_lines = lines[start..end + 1].to_vec();
+        sorted_lines.sort_by(|a, b| compare_numerically(a, b));
+
+        let start_position = Position { line: start, offset: 0 };
+        buffer.start_operation_group();
+        buffer.delete_range(Range::new(
+            start_position,
+            Position { line: end, offset: lines[end].graphemes(true).count() }
+        ));
+        buffer.cursor.move_to(start_position);
+        buffer.insert(sorted_lines.join("\n"));
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+
+    commands::application::switch_to_normal_mode(app)
+}
+
+fn compare_numerically(first: &str, second: &str) -> Ordering {
+    match (first_number(first), first_number(second)) {
+        (Some(first_number), Some(second_number)) => {
+            first_number
+                .partial_cmp(&second_number)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| first.cmp(second))
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => first.cmp(second),
+    }
+}
+
+/// Parses the first number in the line, including its fractional part and,
+/// unless the number is attached to a word (e.g. "file-2"), its sign.
+fn first_number(line: &str) -> Option<f64> {
+    let characters: Vec<char> = line.chars().collect();
+    let start = characters.iter().position(|c| c.is_digit(10))?;
+
+    let mut number: String = characters[start..]
+        .iter()
+        .take_while(|c| c.is_digit(10))
+        .collect();
+    let fraction: String = characters[start + number.len()..]
+        .iter()
+        .skip(1)
+        .take_while(|c| c.is_digit(10))
+        .collect();
+    if characters.get(start + number.len()) == Some(&'.') && !fraction.is_empty() {
+        number = format!("{}.{}", number, fraction);
+    }
+
+    let negative = start > 0 &&
+        characters[start - 1] == '-' &&
+        (start == 1 || !characters[start - 2].is_alphanumeric());
+    if negative {
+        number.insert(0, '-');
+    }
+
+    number.parse().ok()
+}
+
 pub fn close(app: &mut Application) -> Result {
     // Build confirmation check conditions.
-    let (unmodified, empty) =
+    let (unmodified, empty, path_set) =
         if let Some(buf) = app.workspace.current_buffer() {
-            (!buf.modified(), buf.data().is_empty())
+            (!buf.modified(), buf.data().is_empty(), buf.path.is_some())
         } else {
             bail!(BUFFER_MISSING);
         };
@@ -167,14 +620,104 @@ pub fn close(app: &mut Application) -> Result {
 
     if unmodified || empty || confirm_mode {
         // Clean up view-related data for the buffer.
-        app.view.forget_buffer(
-            app.workspace.current_buffer().ok_or(BUFFER_MISSING)?
-        )?;
+        forget_buffer(app)?;
+        remember_closed_buffer(app);
         app.workspace.close_current_buffer();
     } else {
-        // Display a confirmation prompt before closing a modified buffer.
-        let confirm_mode = ConfirmMode::new(close);
-        app.mode = Mode::Confirm(confirm_mode);
+        // Ask what to do with the buffer's changes before closing it. Buffers
+        // without a path would need to prompt for one before they could be
+        // saved, so they can only be discarded.
+        let mut choices = Vec::new();
+        if path_set {
+            choices.push(Choice::new('s', "save", save_and_close));
+        }
+        choices.push(Choice::new('d', "discard", close));
+        choices.push(Choice::new('c', "cancel", commands::application::switch_to_normal_mode));
+        app.mode = Mode::Confirm(ConfirmMode::with_choices("Unsaved changes", choices));
+    }
+
+    Ok(())
+}
+
+/// Reopens the most recently closed buffer, restoring its cursor position.
+pub fn reopen_closed_buffer(app: &mut Application) -> Result {
+    let (path, position) = app.closed_buffers.pop().ok_or("No closed buffers to reopen")?;
+    util::open_buffer(&path, app)?;
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_to(position);
+
+    commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
+}
+
+/// Adds the current buffer's path and cursor position to the stack used by
+/// `reopen_closed_buffer`, dropping the oldest entry once it's full. Buffers
+/// without a path can't be reopened, and are skipped.
+fn remember_closed_buffer(app: &mut Application) {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        if let Some(ref path) = buffer.path {
+            app.closed_buffers.push((path.clone(), *buffer.cursor.clone()));
+        }
+    }
+    if app.closed_buffers.len() > MAX_CLOSED_BUFFERS {
+        app.closed_buffers.remove(0);
+    }
+}
+
+pub fn save_and_close(app: &mut Application) -> Result {
+    save(app)?;
+    close(app)
+}
+
+/// Removes the current buffer's file from disk (and the repository index, if
+/// it's tracked) and closes the buffer, once the user has confirmed it.
+pub fn delete_file(app: &mut Application) -> Result {
+    let path = app.workspace
+        .current_buffer()
+        .ok_or(BUFFER_MISSING)?
+        .path.clone()
+        .ok_or("Can't delete a buffer without a file")?;
+
+    if let Mode::Confirm(_) = app.mode {
+    } else {
+        app.mode = Mode::Confirm(ConfirmMode::new(delete_file));
+        return Ok(());
+    }
+
+    // Resolve the path before the file is removed, for use with git.
+    let absolute_path = path.canonicalize().chain_err(|| {
+        format!("Couldn't find {}", path.to_string_lossy())
+    })?;
+    fs::remove_file(&path).chain_err(|| {
+        format!("Couldn't delete {}", path.to_string_lossy())
+    })?;
+
+    if let Some(ref repo) = app.repository {
+        let repo_path = repo
+            .workdir()
+            .ok_or("No path found for the repository")?
+            .canonicalize()
+            .chain_err(|| "Couldn't resolve the repository path")?;
+
+        if let Ok(relative_path) = absolute_path.strip_prefix(&repo_path) {
+            let mut index = repo.index().chain_err(|| "Couldn't get the repository index")?;
+
+            // Leave untracked files alone.
+            if index.get_path(relative_path, 0).is_some() {
+                index.remove_path(relative_path).chain_err(|| "Failed to remove path from index.")?;
+                index.write().chain_err(|| "Failed to write index.")?;
+            }
+        }
+    }
+
+    // Closing a buffer selects the one preceding it; move focus to the next one.
+    let id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+    app.workspace.next_buffer();
+    let next_id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+    select_buffer(app, id);
+
+    forget_buffer(app)?;
+    app.workspace.close_current_buffer();
+    if next_id != id {
+        select_buffer(app, next_id);
     }
 
     Ok(())
@@ -206,8 +749,6 @@ pub fn close_others(app: &mut Application) -> Result {
                 break;
             } else if buf.modified() && !buf.data().is_empty() {
                 modified_buffer = true;
-            } else {
-                app.view.forget_buffer(buf)?;
             }
         }
 
@@ -220,6 +761,8 @@ pub fn close_others(app: &mut Application) -> Result {
 
         // We haven't broken from the loop, so we're not back
         // at the original buffer; close the current buffer.
+        forget_buffer(app)?;
+        remember_closed_buffer(app);
         app.workspace.close_current_buffer();
     }
 
@@ -227,17 +770,46 @@ pub fn close_others(app: &mut Application) -> Result {
 }
 
 pub fn close_others_confirm(app: &mut Application) -> Result {
-    if let Some(buf) = app.workspace.current_buffer() {
-        app.view.forget_buffer(buf)?;
-    }
+    forget_buffer(app)?;
+    remember_closed_buffer(app);
     app.workspace.close_current_buffer();
     commands::application::switch_to_normal_mode(app)?;
 
     Ok(())
 }
 
+/// Cleans up view-related data, file format, undo history, and read-only
+/// status for the current buffer, ahead of it being closed.
+fn forget_buffer(app: &mut Application) -> Result {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        app.view.forget_buffer(buffer)?;
+        if let Some(id) = buffer.id {
+            app.file_formats.remove(&id);
+            app.undo_histories.remove(&id);
+            app.read_only_buffers.remove(&id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Cycles through the workspace until the buffer with the specified ID is
+/// selected, stopping if we wind up back where we started.
+fn select_buffer(app: &mut Application, id: Option<usize>) {
+    let starting_id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+
+    while app.workspace.current_buffer().and_then(|buffer| buffer.id) != id {
+        app.workspace.next_buffer();
+
+        if app.workspace.current_buffer().and_then(|buffer| buffer.id) == starting_id {
+            break;
+        }
+    }
+}
+
 pub fn backspace(app: &mut Application) -> Result {
     let mut outdent = false;
+    let auto_pairs = app.preferences.borrow().auto_pairs();
 
     if let Some(buffer) = app.workspace.current_buffer() {
         if buffer.cursor.offset == 0 {
@@ -252,6 +824,11 @@ pub fn backspace(app: &mut Application) -> Result {
                 .ok_or(CURRENT_LINE_MISSING)?;
             if current_line.chars().all(|c| c.is_whitespace()) {
                 outdent = true
+            } else if auto_pairs && surrounded_by_pair(buffer) {
+                // Remove both halves of the empty pair.
+                buffer.cursor.move_left();
+                buffer.delete();
+                buffer.delete();
             } else {
                 buffer.cursor.move_left();
                 buffer.delete();
@@ -268,11 +845,32 @@ pub fn backspace(app: &mut Application) -> Result {
 }
 
 pub fn insert_char(app: &mut Application) -> Result {
+    let auto_pairs = app.preferences.borrow().auto_pairs();
+
     if let Some(buffer) = app.workspace.current_buffer() {
         if let Some(Key::Char(character)) = *app.view.last_key() {
-            // TODO: Drop explicit call to to_string().
-            buffer.insert(character.to_string());
-            buffer.cursor.move_right();
+            let next_character = character_at(buffer, *buffer.cursor.clone());
+            let previous_character = previous_character(buffer);
+
+            if auto_pairs && is_closing_character(character) && next_character == Some(character) {
+                // Type over the existing closing character.
+                buffer.cursor.move_right();
+            } else if let (true, Some(closing_character)) = (auto_pairs, closing_character(character)) {
+                // Quotes are frequently used as apostrophes; only
+                // pair them when they aren't following a word.
+                let follows_word = previous_character.map(|c| c.is_alphanumeric()).unwrap_or(false);
+
+                if is_closing_character(character) && follows_word {
+                    buffer.insert(character.to_string());
+                } else {
+                    buffer.insert(format!("{}{}", character, closing_character));
+                }
+                buffer.cursor.move_right();
+            } else {
+                // TODO: Drop explicit call to to_string().
+                buffer.insert(character.to_string());
+                buffer.cursor.move_right();
+            }
         } else {
             bail!("No character to insert");
         }
@@ -284,6 +882,51 @@ pub fn insert_char(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// The closing counterpart for auto-paired characters.
+fn closing_character(character: char) -> Option<char> {
+    match character {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '"' => Some('"'),
+        '\'' => Some('\''),
+        _ => None,
+    }
+}
+
+fn is_closing_character(character: char) -> bool {
+    match character {
+        ')' | ']' | '}' | '"' | '\'' => true,
+        _ => false,
+    }
+}
+
+fn character_at(buffer: &Buffer, position: Position) -> Option<char> {
+    let next_position = Position{ line: position.line, offset: position.offset + 1 };
+
+    buffer
+        .read(&Range::new(position, next_position))
+        .and_then(|data| data.chars().next())
+}
+
+fn previous_character(buffer: &Buffer) -> Option<char> {
+    if buffer.cursor.offset == 0 {
+        return None;
+    }
+
+    character_at(buffer, Position{ line: buffer.cursor.line, offset: buffer.cursor.offset - 1 })
+}
+
+/// Whether the cursor sits between an opening character and its counterpart.
+fn surrounded_by_pair(buffer: &Buffer) -> bool {
+    let next_character = character_at(buffer, *buffer.cursor.clone());
+
+    previous_character(buffer)
+        .and_then(closing_character)
+        .map(|closing_character| Some(closing_character) == next_character)
+        .unwrap_or(false)
+}
+
 pub fn display_current_scope(app: &mut Application) -> Result {
     let scope_display_buffer = {
         let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
@@ -300,30 +943,291 @@ pub fn display_current_scope(app: &mut Application) -> Result {
     util::add_buffer(scope_display_buffer, app)
 }
 
-/// Inserts a newline character at the current cursor position.
-/// Also performs automatic indentation, basing the indent off
-/// of the previous line's leading whitespace.
-pub fn insert_newline(app: &mut Application) -> Result {
-    if let Some(buffer) = app.workspace.current_buffer() {
-        // Insert the newline character.
-        buffer.insert("\n");
+/// Prompts for another open buffer, and opens a unified
+/// diff between it and the current buffer in a new buffer.
+pub fn diff_buffers(app: &mut Application) -> Result {
+    let mut paths = Vec::new();
+    {
+        let id = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.id;
+        loop {
+            app.workspace.next_buffer();
+            let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+            if buffer.id == id {
+                break;
+            }
+            if let Some(ref path) = buffer.path {
+                paths.push(path.to_string_lossy().into_owned());
+            }
+        }
+    }
+    if paths.is_empty() {
+        bail!("There are no other buffers to diff against");
+    }
 
-        // Get the cursor position before moving it to the start of the new line.
-        let position = buffer.cursor.clone();
-        buffer.cursor.move_down();
-        buffer.cursor.move_to_start_of_line();
+    let config = app.preferences.borrow().search_select_config();
+    app.mode = Mode::DiffBuffers(SelectableListMode::new("DIFF", paths, config));
+    commands::search_select::search(app)
+}
 
-        // Get a slice of the buffer up to and including the current line.
+/// Lists the available syntaxes, to override the
+/// current buffer's detected syntax with the selected one.
+pub fn set_syntax(app: &mut Application) -> Result {
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let mut syntaxes: Vec<String> = app.workspace.syntax_set
+        .syntaxes()
+        .iter()
+        .map(|syntax| syntax.name.clone())
+        .collect();
+    syntaxes.sort();
+
+    let config = app.preferences.borrow().search_select_config();
+    app.mode = Mode::Syntax(SelectableListMode::new("SYNTAX", syntaxes, config));
+    commands::search_select::search(app)
+}
+
+/// Displays the codepoint, name, UTF-8 bytes, and category of the character
+/// under the cursor (or each of the characters forming it, if it's composed
+/// of several), to help identify invisible or look-alike characters.
+pub fn inspect_char(app: &mut Application) -> Result {
+    let description = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
         let data = buffer.data();
-        let end_of_current_line = data
-            .lines()
-            .nth(position.line)
-            .map(|l| (l.as_ptr() as usize) + l.len())
-            .unwrap();
-        let offset = end_of_current_line - (data.as_str().as_ptr() as usize);
-        let (previous_content, _) = data.split_at(offset);
+        let line = data.lines().nth(buffer.cursor.line).ok_or(CURRENT_LINE_MISSING)?;
+        let grapheme = line
+            .graphemes(true)
+            .nth(buffer.cursor.offset)
+            .ok_or("No character found at the cursor")?;
+
+        grapheme
+            .chars()
+            .map(|character| CharInfo::new(character).to_string())
+            .collect::<Vec<String>>()
+            .join("; ")
+    };
+    app.notify(description);
 
-        // Searching backwards, copy the nearest non-blank line's indent content.
+    Ok(())
+}
+
+/// Toggles the case of the character under the cursor, and moves past it.
+/// Characters without case (e.g. digits) are skipped over unchanged.
+pub fn toggle_char_case(app: &mut Application) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let position = *buffer.cursor.clone();
+    let grapheme = {
+        let data = buffer.data();
+        let line = data.lines().nth(position.line).ok_or(CURRENT_LINE_MISSING)?;
+
+        line.graphemes(true)
+            .nth(position.offset)
+            .ok_or("No character found at the cursor")?
+            .to_string()
+    };
+    let toggled: String = grapheme
+        .chars()
+        .flat_map(|character| {
+            if character.is_lowercase() {
+                character.to_uppercase().collect::<Vec<char>>()
+            } else if character.is_uppercase() {
+                character.to_lowercase().collect()
+            } else {
+                vec![character]
+            }
+        })
+        .collect();
+
+    // Some characters (e.g. ß) change length when their case is toggled;
+    // advance past all of the replacement, rather than a single character.
+    let toggled_width = toggled.graphemes(true).count();
+    if toggled != grapheme {
+        buffer.start_operation_group();
+        buffer.delete_range(Range::new(
+            position,
+            Position{ line: position.line, offset: position.offset + 1 }
+        ));
+        buffer.insert(toggled);
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+    buffer.cursor.move_to(Position{
+        line: position.line,
+        offset: position.offset + toggled_width
+    });
+
+    Ok(())
+}
+
+/// Replaces the character under the cursor with the last key press,
+/// leaving the cursor in place. Does nothing at the end of a line.
+pub fn replace_char(app: &mut Application) -> Result {
+    let character = match *app.view.last_key() {
+        Some(Key::Char(character)) => character,
+        _ => bail!("Last key press wasn't a character"),
+    };
+
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let position = *buffer.cursor.clone();
+        let has_character = buffer.data()
+            .lines()
+            .nth(position.line)
+            .map(|line| line.graphemes(true).nth(position.offset).is_some())
+            .unwrap_or(false);
+
+        if has_character {
+            buffer.start_operation_group();
+            buffer.delete_range(Range::new(
+                position,
+                Position{ line: position.line, offset: position.offset + 1 }
+            ));
+            buffer.insert(character.to_string());
+            util::history::end_operation_group(buffer, app.change_group);
+        }
+    }
+
+    commands::application::switch_to_normal_mode(app)
+}
+
+/// Opens the URL under the cursor using the configured browser command.
+pub fn open_url_under_cursor(app: &mut Application) -> Result {
+    let url = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let data = buffer.data();
+        let line = data.lines().nth(buffer.cursor.line).ok_or(CURRENT_LINE_MISSING)?;
+
+        url::url_at(line, buffer.cursor.offset)
+    };
+    let url = match url {
+        Some(url) => url,
+        None => {
+            app.notify("No URL found under the cursor");
+            return Ok(());
+        }
+    };
+
+    let command = app.preferences.borrow().browser_command();
+    let (program, arguments) = command.split_first().ok_or("No browser command configured")?;
+    process::Command::new(program)
+        .args(arguments)
+        .arg(&url)
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .spawn()
+        .chain_err(|| format!("Couldn't run browser command \"{}\"", program))?;
+    app.notify(format!("Opened {}", url));
+
+    Ok(())
+}
+
+/// Prompts for a shell command, inserting its output at the cursor.
+pub fn insert_command_output(app: &mut Application) -> Result {
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    app.mode = Mode::ShellCommand(ShellCommandMode::new());
+
+    Ok(())
+}
+
+/// Prompts for a pattern, keeping only the lines that contain it,
+/// within the selected lines or, outside of select line mode, the buffer.
+pub fn keep_lines(app: &mut Application) -> Result {
+    switch_to_line_filter_mode(app, true)
+}
+
+/// Prompts for a pattern, deleting the lines that contain it, within
+/// the selected lines or, outside of select line mode, the buffer.
+pub fn delete_lines(app: &mut Application) -> Result {
+    switch_to_line_filter_mode(app, false)
+}
+
+fn switch_to_line_filter_mode(app: &mut Application, keep: bool) -> Result {
+    let lines = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        match app.mode {
+            Mode::SelectLine(ref mode) => {
+                let (start, end) = ordered_lines(mode.anchor, buffer.cursor.line);
+                Some(LineRange::new(start, end))
+            }
+            _ => None,
+        }
+    };
+    app.mode = Mode::LineFilter(LineFilterMode::new(keep, lines));
+
+    Ok(())
+}
+
+/// Removes ANSI escape sequences (e.g. colors in pasted terminal
+/// output) from the selection or, outside of select mode, the buffer.
+pub fn strip_ansi(app: &mut Application) -> Result {
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let range = match app.mode {
+            Mode::Select(ref mode) => Range::new(*buffer.cursor, mode.anchor),
+            Mode::SelectLine(ref mode) => {
+                let line_range = LineRange::new(mode.anchor, buffer.cursor.line);
+                util::inclusive_range(&line_range, buffer)
+            }
+            _ => Range::new(Position{ line: 0, offset: 0 }, end_position(&buffer.data())),
+        };
+        let content = buffer.read(&range).ok_or("Couldn't read the content to strip")?;
+        let stripped_content = util::ansi::strip(&content);
+        if stripped_content == content {
+            bail!("No ANSI escape sequences found");
+        }
+
+        buffer.start_operation_group();
+        buffer.delete_range(range.clone());
+        buffer.cursor.move_to(range.start());
+        buffer.insert(stripped_content);
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+
+    match app.mode {
+        Mode::Select(_) | Mode::SelectLine(_) => {
+            commands::application::switch_to_normal_mode(app)?;
+        }
+        _ => (),
+    }
+    commands::view::scroll_to_cursor(app)?;
+
+    Ok(())
+}
+
+/// The position following the last character in the content. Like scribe,
+/// only bare newlines end a line; a CRLF pair is a single grapheme within it.
+fn end_position(content: &str) -> Position {
+    content.graphemes(true).fold(Position { line: 0, offset: 0 }, |position, grapheme| {
+        if grapheme == "\n" {
+            Position { line: position.line + 1, offset: 0 }
+        } else {
+            Position { line: position.line, offset: position.offset + 1 }
+        }
+    })
+}
+
+/// Inserts a newline character at the current cursor position.
+/// Also performs automatic indentation, basing the indent off
+/// of the previous line's leading whitespace.
+pub fn insert_newline(app: &mut Application) -> Result {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        // Insert the newline character.
+        buffer.insert("\n");
+
+        // Get the cursor position before moving it to the start of the new line.
+        let position = buffer.cursor.clone();
+        buffer.cursor.move_down();
+        buffer.cursor.move_to_start_of_line();
+
+        // Get a slice of the buffer up to and including the current line.
+        let data = buffer.data();
+        let end_of_current_line = data
+            .lines()
+            .nth(position.line)
+            .map(|l| (l.as_ptr() as usize) + l.len())
+            .unwrap();
+        let offset = end_of_current_line - (data.as_str().as_ptr() as usize);
+        let (previous_content, _) = data.split_at(offset);
+
+        // Searching backwards, copy the nearest non-blank line's indent content.
         let nearest_non_blank_line = previous_content.lines().rev().find(|line| !line.is_empty());
         let indent_content = match nearest_non_blank_line {
             Some(line) => line.chars().take_while(|&c| c.is_whitespace()).collect(),
@@ -382,7 +1286,7 @@ pub fn indent_line(app: &mut Application) -> Result {
         });
         buffer.insert(tab_content.clone());
     }
-    buffer.end_operation_group();
+    util::history::end_operation_group(buffer, app.change_group);
 
     // Move to the original position, shifted to compensate for the indent.
     buffer.cursor.move_to(target_position);
@@ -455,7 +1359,7 @@ pub fn outdent_line(app: &mut Application) -> Result {
     }
 
     // Finish grouping the individual outdent operations as one.
-    buffer.end_operation_group();
+    util::history::end_operation_group(buffer, app.change_group);
 
     Ok(())
 }
@@ -504,28 +1408,48 @@ pub fn start_command_group(app: &mut Application) -> Result {
 }
 
 pub fn end_command_group(app: &mut Application) -> Result {
-    app.workspace
-        .current_buffer()
-        .ok_or(BUFFER_MISSING)?
-        .end_operation_group();
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    util::history::end_operation_group(buffer, app.change_group);
 
     Ok(())
 }
 
 pub fn undo(app: &mut Application) -> Result {
-    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.undo();
+    if let Some(id) = step_history(app, |buffer| buffer.undo())? {
+        if let Some(history) = app.undo_histories.get_mut(&id) {
+            history.undo();
+        }
+    }
     commands::view::scroll_to_cursor(app).chain_err(|| {
         "Couldn't scroll to cursor after undoing."
     })
 }
 
 pub fn redo(app: &mut Application) -> Result {
-    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.redo();
+    if let Some(id) = step_history(app, |buffer| buffer.redo())? {
+        if let Some(history) = app.undo_histories.get_mut(&id) {
+            history.redo();
+        }
+    }
     commands::view::scroll_to_cursor(app).chain_err(|| {
         "Couldn't scroll to cursor after redoing."
     })
 }
 
+/// Undoes or redoes a change in the current buffer, returning its ID
+/// if that changed its content, so that its labelled history can follow.
+fn step_history<F: FnOnce(&mut Buffer)>(app: &mut Application, step: F) -> ::errors::Result<Option<usize>> {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let revision = app.view.buffer_revision(buffer).ok();
+    step(buffer);
+
+    if revision.is_some() && revision != app.view.buffer_revision(buffer).ok() {
+        Ok(buffer.id)
+    } else {
+        Ok(None)
+    }
+}
+
 pub fn paste(app: &mut Application) -> Result {
     let insert_below = match app.mode {
         Mode::Select(_) | Mode::SelectLine(_) | Mode::Search(_) => {
@@ -537,10 +1461,10 @@ pub fn paste(app: &mut Application) -> Result {
         _ => true,
     };
 
-    // TODO: Clean up duplicate buffer.insert(content.clone()) calls.
-    if let Some(buffer) = app.workspace.current_buffer() {
+    let pasted_range = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
         match *app.clipboard.get_content() {
-            ClipboardContent::Inline(ref content) => buffer.insert(content.clone()),
+            ClipboardContent::Inline(ref content) => Some(insert_content(buffer, content)),
             ClipboardContent::Block(ref content) => {
                 let original_cursor_position = *buffer.cursor.clone();
                 let line = original_cursor_position.line;
@@ -559,30 +1483,106 @@ pub fn paste(app: &mut Application) -> Result {
                                 line,
                                 offset: line_content.len(),
                             });
-                            buffer.insert(format!("\n{}", content));
+                            buffer.start_operation_group();
+                            buffer.insert("\n");
+                            buffer.cursor.move_to(Position {
+                                line: line + 1,
+                                offset: 0,
+                            });
+                            let range = insert_content(buffer, content);
+                            util::history::end_operation_group(buffer, app.change_group);
                             buffer.cursor.move_to(original_cursor_position);
+
+                            Some(range)
                         } else {
                             // We're on a trailing newline, which doesn't
                             // have any data; just insert the content here.
-                            buffer.insert(content.clone());
+                            Some(insert_content(buffer, content))
                         }
                     } else {
-                        buffer.insert(content.clone());
+                        Some(insert_content(buffer, content))
                     }
                 } else {
-                    buffer.insert(content.clone());
+                    Some(insert_content(buffer, content))
                 }
             }
-            ClipboardContent::None => (),
+            ClipboardContent::None => None,
         }
-    } else {
-        bail!(BUFFER_MISSING);
+    };
+    let buffer_id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+    if let (Some(buffer_id), Some(range)) = (buffer_id, pasted_range) {
+        app.last_paste = Some(Paste {
+            buffer_id,
+            range,
+            ring_index: 0,
+            fresh: true,
+        });
     }
     commands::view::scroll_to_cursor(app)?;
 
     Ok(())
 }
 
+/// Replaces the content inserted by the last paste with the next older
+/// clipboard entry, cycling back to the most recent one after the oldest.
+/// Only available immediately after pasting (or cycling).
+pub fn paste_cycle(app: &mut Application) -> Result {
+    let paste = app.last_paste
+        .take()
+        .ok_or("Clipboard content can only be cycled immediately after pasting")?;
+    if app.clipboard.ring_len() < 2 {
+        bail!("No older clipboard content to cycle through");
+    }
+    let ring_index = (paste.ring_index + 1) % app.clipboard.ring_len();
+    let content = match app.clipboard.ring_entry(ring_index) {
+        Some(&ClipboardContent::Inline(ref content)) |
+        Some(&ClipboardContent::Block(ref content)) => content.clone(),
+        _ => bail!("Couldn't find older clipboard content"),
+    };
+
+    let range = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        if buffer.id != Some(paste.buffer_id) {
+            bail!("Clipboard content can only be cycled in the buffer it was pasted into");
+        }
+
+        // Pasting never leaves the cursor beyond the pasted content,
+        // so its position is unaffected by replacing that content.
+        let cursor_position = *buffer.cursor.clone();
+        buffer.start_operation_group();
+        buffer.delete_range(paste.range.clone());
+        buffer.cursor.move_to(paste.range.start());
+        let range = insert_content(buffer, &content);
+        util::history::end_operation_group(buffer, app.change_group);
+        buffer.cursor.move_to(cursor_position);
+
+        range
+    };
+    app.last_paste = Some(Paste {
+        range,
+        ring_index,
+        fresh: true,
+        ..paste
+    });
+    commands::view::scroll_to_cursor(app)?;
+
+    Ok(())
+}
+
+/// Inserts the content at the cursor, returning the range it occupies.
+fn insert_content(buffer: &mut Buffer, content: &str) -> Range {
+    let start = *buffer.cursor.clone();
+    let extent = end_position(content);
+    let end = if extent.line == 0 {
+        Position { line: start.line, offset: start.offset + extent.offset }
+    } else {
+        Position { line: start.line + extent.line, offset: extent.offset }
+    };
+    buffer.insert(content);
+
+    Range::new(start, end)
+}
+
 pub fn paste_above(app: &mut Application) -> Result {
     let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
 
@@ -697,6 +1697,83 @@ pub fn ensure_trailing_newline(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Cleans up the current buffer as a single change: CRLF line breaks are
+/// converted to LF and the configured line ending is applied on save (unless it's
+/// set to `auto`), indentation is converted to match the tab settings (if enabled),
+/// and trailing whitespace and a final newline are handled as they are on save.
+pub fn normalize_buffer(app: &mut Application) -> Result {
+    let (line_ending, indentation, trim_trailing_whitespace, insert_final_newline) = {
+        let preferences = app.preferences.borrow();
+        let path = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.path.as_ref();
+        let indentation = if preferences.normalize_indentation() {
+            Some((preferences.soft_tabs(path), preferences.tab_width(path)))
+        } else {
+            None
+        };
+
+        (
+            preferences.line_endings(path),
+            indentation,
+            preferences.trim_trailing_whitespace(path),
+            preferences.insert_final_newline(path),
+        )
+    };
+
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let mut content = buffer.data();
+        if let Some(line_ending) = line_ending {
+            content = content.replace("\r\n", "\n");
+            if let Some(id) = buffer.id {
+                app.file_formats.entry(id).or_insert_with(FileFormat::default).line_ending = line_ending;
+            }
+        }
+        let normalized_content = match indentation {
+            Some((soft_tabs, tab_width)) => content
+                .split('\n')
+                .map(|line| convert_indentation(line, soft_tabs, tab_width))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            None => content,
+        };
+
+        buffer.start_operation_group();
+        if normalized_content != buffer.data() {
+            let original_position = *buffer.cursor.clone();
+            buffer.delete_range(Range::new(Position{ line: 0, offset: 0 }, end_position(&buffer.data())));
+            buffer.cursor.move_to(Position{ line: 0, offset: 0 });
+            buffer.insert(normalized_content);
+            buffer.cursor.move_to(original_position);
+        }
+    }
+    if trim_trailing_whitespace {
+        remove_trailing_whitespace(app)?;
+    }
+    if insert_final_newline {
+        ensure_trailing_newline(app)?;
+    }
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    util::history::end_operation_group(buffer, app.change_group);
+
+    commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
+}
+
+/// Rewrites the line's leading whitespace using spaces or tabs (falling back
+/// to spaces for any remainder narrower than a tab), preserving its width.
+fn convert_indentation(line: &str, soft_tabs: bool, tab_width: usize) -> String {
+    let content = line.trim_left_matches(|c: char| c == ' ' || c == '\t');
+    let width = line[..line.len() - content.len()].chars().fold(0, |width, c| {
+        if c == '\t' { (width / tab_width + 1) * tab_width } else { width + 1 }
+    });
+    let indentation = if soft_tabs {
+        " ".repeat(width)
+    } else {
+        format!("{}{}", "\t".repeat(width / tab_width), " ".repeat(width % tab_width))
+    };
+
+    format!("{}{}", indentation, content)
+}
+
 pub fn insert_tab(app: &mut Application) -> Result {
     let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
     let tab_content = app.preferences.borrow().tab_content(buffer.path.as_ref());
@@ -711,13 +1788,87 @@ pub fn insert_tab(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Inserts a randomly generated (version 4) UUID at the cursor,
+/// formatted according to the `uuid_format` preference.
+pub fn insert_uuid(app: &mut Application) -> Result {
+    insert_uuid_using(app, &mut SystemRandom)
+}
+
+fn insert_uuid_using<R: RandomSource>(app: &mut Application, source: &mut R) -> Result {
+    let format = app.preferences.borrow().uuid_format();
+    let id = uuid::generate(source, format)?;
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+
+    buffer.start_operation_group();
+    buffer.insert(id.as_str());
+    util::history::end_operation_group(buffer, app.change_group);
+
+    // Move the cursor to the end of the inserted content.
+    for _ in 0..id.len() {
+        buffer.cursor.move_right();
+    }
+
+    Ok(())
+}
+
+/// Closes the innermost markup tag left open before the cursor, inserting
+/// its closing tag at the cursor. Does nothing if every tag is closed.
+pub fn insert_closing_tag(app: &mut Application) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let preceding_content = buffer
+        .read(&Range::new(Position{ line: 0, offset: 0 }, *buffer.cursor.clone()))
+        .unwrap_or_default();
+
+    if let Some(tag) = util::markup::unclosed_tag(&preceding_content) {
+        buffer.start_operation_group();
+        buffer.insert(format!("</{}>", tag));
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+
+    Ok(())
+}
+
+/// Inserts a line below the current one, consisting of the last key press
+/// repeated to the text width (e.g. a section divider), moving onto it.
+pub fn insert_separator(app: &mut Application) -> Result {
+    let character = match *app.view.last_key() {
+        Some(Key::Char(character)) => character,
+        _ => bail!("Last key press wasn't a character"),
+    };
+    let text_width = app.preferences.borrow().text_width();
+
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let line = buffer.cursor.line;
+        let line_length = buffer.data()
+            .lines()
+            .nth(line)
+            .map(|content| content.graphemes(true).count())
+            .unwrap_or(0);
+
+        buffer.start_operation_group();
+        buffer.cursor.move_to(Position{ line, offset: line_length });
+        buffer.insert(format!("\n{}", character.to_string().repeat(text_width)));
+        buffer.cursor.move_to(Position{ line: line + 1, offset: 0 });
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+
+    commands::application::switch_to_normal_mode(app)
+}
+
 #[cfg(test)]
 mod tests {
     use commands;
-    use models::application::{ClipboardContent, Mode};
+    use input::Key;
+    use models::application::{ClipboardContent, Mode, Preferences, UndoHistory};
+    use models::application::modes::SearchSelectMode;
     use scribe::Buffer;
     use scribe::buffer::Position;
+    use std::fs;
     use std::path::Path;
+    use util::{Encoding, FileFormat, LineEnding};
+    use util::test_helpers::TempDir;
+    use yaml::YamlLoader;
 
     #[test]
     fn insert_newline_uses_current_line_indentation() {
@@ -872,6 +2023,20 @@ mod tests {
         assert_eq!(app.workspace.current_buffer().unwrap().data(), "editor");
     }
 
+    #[test]
+    fn delete_current_line_deletes_counted_lines() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\nbuffer\nlines");
+        app.workspace.add_buffer(buffer);
+
+        app.pending_count = Some(3);
+        super::delete_current_line(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "lines");
+        assert!(app.pending_count.is_none());
+    }
+
     #[test]
     fn indent_line_inserts_two_spaces_at_start_of_line() {
         let mut app = ::models::Application::new(&Vec::new()).unwrap();
@@ -1157,6 +2322,218 @@ mod tests {
                    "amp\neditor\n");
     }
 
+    #[test]
+    fn save_preserves_crlf_line_endings_by_default() {
+        let dir = TempDir::new("save-crlf");
+        let path = dir.join("amp.txt");
+        fs::write(&path, "amp\r\neditor\r\n").unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+
+        // Ensure that carriage returns are stripped for editing.
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\neditor\n");
+
+        super::save(&mut app).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "amp\r\neditor\r\n");
+    }
+
+    #[test]
+    fn undo_leaves_the_content_of_converted_files_intact() {
+        let dir = TempDir::new("undo-converted");
+        let path = dir.join("amp.txt");
+        fs::write(&path, &[b'c', b'a', b'f', 0xE9, b'\r', b'\n']).unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+
+        // Decoding and converting line endings aren't undoable changes.
+        super::undo(&mut app).ok();
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "café\n");
+        assert!(!buffer.modified());
+    }
+
+    #[test]
+    fn reload_normalizes_crlf_files_in_place() {
+        let dir = TempDir::new("reload-crlf");
+        let path = dir.join("amp.txt");
+        fs::write(&path, "amp\r\neditor\r\n").unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+        let id = app.workspace.current_buffer().unwrap().id;
+
+        fs::write(&path, "amp\r\ntext\r\n").unwrap();
+        super::reload(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "amp\ntext\n");
+        assert_eq!(buffer.id, id);
+        assert_eq!(buffer.path, Some(path.clone()));
+        assert!(!buffer.modified());
+        assert_eq!(id.and_then(|id| app.file_formats.get(&id)).map(|format| format.line_ending), Some(LineEnding::Crlf));
+    }
+
+    #[test]
+    fn reload_picks_up_changes_to_the_files_format() {
+        let dir = TempDir::new("reload-format");
+        let path = dir.join("amp.txt");
+        fs::write(&path, "amp\neditor\n").unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+        let id = app.workspace.current_buffer().unwrap().id.unwrap();
+
+        fs::write(&path, &[b'c', b'a', b'f', 0xE9, b'\r', b'\n']).unwrap();
+        super::reload(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "café\n");
+        assert_eq!(
+            app.file_formats.get(&id),
+            Some(&FileFormat{ line_ending: LineEnding::Crlf, encoding: Encoding::Latin1 })
+        );
+
+        // The buffer is saved using the file's new format.
+        app.workspace.current_buffer().unwrap().insert("le ");
+        super::save(&mut app).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), vec![b'l', b'e', b' ', b'c', b'a', b'f', 0xE9, b'\r', b'\n']);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_writes_through_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new("save-symlink");
+        let path = dir.join("amp.txt");
+        let link_path = dir.join("link.txt");
+        fs::write(&path, "amp\r\neditor\r\n").unwrap();
+        symlink(&path, &link_path).unwrap();
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        ::util::open_buffer(&link_path, &mut app).unwrap();
+
+        app.workspace.current_buffer().unwrap().insert("text ");
+        super::save(&mut app).unwrap();
+
+        // The link is left in place, and its target updated.
+        assert!(fs::symlink_metadata(&link_path).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "text amp\r\neditor\r\n");
+        assert!(!app.workspace.current_buffer().unwrap().modified());
+    }
+
+    #[test]
+    fn save_keeps_carriage_returns_that_arent_line_endings() {
+        let dir = TempDir::new("save-carriage-returns");
+        let path = dir.join("amp.txt");
+        fs::write(&path, "amp\r\neditor\rtext\r\n").unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\neditor\rtext\n");
+
+        super::save(&mut app).unwrap();
+
+        // The file is written in place, leaving nothing else behind.
+        assert_eq!(fs::read_to_string(&path).unwrap(), "amp\r\neditor\rtext\r\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+        assert!(!app.workspace.current_buffer().unwrap().modified());
+    }
+
+    #[test]
+    fn save_preserves_latin1_encoding() {
+        let dir = TempDir::new("save-latin1");
+        let path = dir.join("amp.txt");
+        fs::write(&path, &[b'c', b'a', b'f', 0xE9, b'\n']).unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+
+        // Ensure that the content is decoded for editing.
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "café\n");
+
+        super::save(&mut app).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), vec![b'c', b'a', b'f', 0xE9, b'\n']);
+    }
+
+    #[test]
+    fn save_leaves_latin1_files_untouched_when_content_cant_be_encoded() {
+        let dir = TempDir::new("save-latin1-unrepresentable");
+        let path = dir.join("amp.txt");
+        fs::write(&path, &[b'c', b'a', b'f', 0xE9, b'\n']).unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+        app.workspace.current_buffer().unwrap().insert("€");
+
+        let error = super::save(&mut app).unwrap_err();
+        assert!(error.to_string().contains("buffer::force_encoding"));
+        assert_eq!(fs::read(&path).unwrap(), vec![b'c', b'a', b'f', 0xE9, b'\n']);
+        assert!(app.workspace.current_buffer().unwrap().modified());
+    }
+
+    #[test]
+    fn save_all_saves_modified_buffers_and_keeps_the_current_one_selected() {
+        let dir = TempDir::new("save-all");
+        let first_path = dir.join("first.txt");
+        let second_path = dir.join("second.txt");
+        fs::write(&first_path, "amp\n").unwrap();
+        fs::write(&second_path, "editor\n").unwrap();
+        let mut app = ::models::Application::new(&vec![
+            String::new(),
+            first_path.to_string_lossy().into_owned(),
+            second_path.to_string_lossy().into_owned(),
+        ]).unwrap();
+
+        // Modify both buffers, as well as an unsaved one without a path.
+        for _ in 0..2 {
+            app.workspace.current_buffer().unwrap().insert("modified ");
+            app.workspace.next_buffer();
+        }
+        let mut buffer = Buffer::new();
+        buffer.insert("scratch");
+        app.workspace.add_buffer(buffer);
+
+        super::save_all(&mut app).unwrap();
+
+        assert_eq!(fs::read_to_string(&first_path).unwrap(), "modified amp\n");
+        assert_eq!(fs::read_to_string(&second_path).unwrap(), "modified editor\n");
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "scratch");
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn force_encoding_changes_the_saved_encoding() {
+        let dir = TempDir::new("force-encoding");
+        let path = dir.join("amp.txt");
+        fs::write(&path, &[b'c', b'a', b'f', 0xE9, b'\n']).unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+
+        // Latin-1 is the last supported encoding; cycle back to UTF-8.
+        super::force_encoding(&mut app).unwrap();
+        super::save(&mut app).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "café\n");
+    }
+
+    #[test]
+    fn save_converts_crlf_line_endings_when_lf_is_preferred() {
+        let dir = TempDir::new("save-lf-preferred");
+        let path = dir.join("amp.txt");
+        fs::write(&path, "amp\r\neditor\r\n").unwrap();
+        let mut app = ::models::Application::new(
+            &vec![String::new(), path.to_string_lossy().into_owned()]
+        ).unwrap();
+        let data = YamlLoader::load_from_str("line_endings: lf").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+
+        super::save(&mut app).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "amp\neditor\n");
+    }
+
     #[test]
     fn save_switches_to_path_mode_when_path_is_missing() {
         let mut app = ::models::Application::new(&Vec::new()).unwrap();
@@ -1213,7 +2590,47 @@ mod tests {
     }
 
     #[test]
-    fn paste_inserts_on_line_below_when_pasting_block_data() {
+    fn paste_cycle_replaces_pasted_content_with_older_clipboard_entries() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor");
+        buffer.cursor.move_to(Position{ line: 0, offset: 4 });
+        app.workspace.add_buffer(buffer);
+        for content in &["first\nentry ", "second ", "third "] {
+            app.clipboard.set_content(ClipboardContent::Inline(content.to_string())).unwrap();
+        }
+
+        commands::buffer::paste(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp third editor");
+
+        commands::buffer::paste_cycle(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp second editor");
+
+        commands::buffer::paste_cycle(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp first\nentry editor");
+
+        // Multi-line content is tracked correctly, and cycling wraps around.
+        commands::buffer::paste_cycle(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp third editor");
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 4 });
+    }
+
+    #[test]
+    fn paste_cycle_fails_without_a_preceding_paste() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+        for content in &["first", "second"] {
+            app.clipboard.set_content(ClipboardContent::Inline(content.to_string())).unwrap();
+        }
+
+        assert!(commands::buffer::paste_cycle(&mut app).is_err());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp");
+    }
+
+    #[test]
+    fn paste_inserts_on_line_below_when_pasting_block_data() {
         let mut app = ::models::Application::new(&Vec::new()).unwrap();
         let mut buffer = Buffer::new();
         buffer.insert("amp\neditor");
@@ -1515,6 +2932,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn close_only_offers_to_save_buffers_with_a_path() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("data");
+        app.workspace.add_buffer(buffer);
+        commands::buffer::close(&mut app).unwrap();
+
+        if let Mode::Confirm(ref mode) = app.mode {
+            assert!(mode.command_for(&Key::Char('s')).is_none());
+            assert!(mode.command_for(&Key::Char('d')).is_some());
+        } else {
+            panic!("Not in confirm mode");
+        }
+    }
+
     #[test]
     fn close_skips_confirmation_when_buffer_is_empty() {
         let mut app = ::models::Application::new(&Vec::new()).unwrap();
@@ -1547,6 +2980,187 @@ mod tests {
         assert!(app.workspace.current_buffer().is_none());
     }
 
+    #[test]
+    fn close_forgets_the_buffers_undo_history_and_file_format() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+        let id = app.workspace.current_buffer().unwrap().id.unwrap();
+        app.undo_histories.insert(id, UndoHistory::new());
+        app.file_formats.insert(id, FileFormat::default());
+
+        commands::buffer::close(&mut app).unwrap();
+
+        assert!(app.undo_histories.get(&id).is_none());
+        assert!(app.file_formats.get(&id).is_none());
+    }
+
+    #[test]
+    fn set_syntax_lists_the_available_syntaxes() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+        let syntax_count = app.workspace.syntax_set.syntaxes().len();
+
+        commands::buffer::set_syntax(&mut app).unwrap();
+
+        if let Mode::Syntax(ref mode) = app.mode {
+            assert_eq!(
+                mode.results().count(),
+                syntax_count.min(mode.config().max_results)
+            );
+        } else {
+            panic!("Not in syntax mode");
+        }
+    }
+
+    #[test]
+    fn set_syntax_applies_the_selected_syntax_to_the_buffer() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("fn amp() {}");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::set_syntax(&mut app).unwrap();
+        if let Mode::Syntax(ref mut mode) = app.mode {
+            mode.query().push_str("Rust");
+            mode.search();
+        }
+        commands::search_select::accept(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().syntax_definition.as_ref().unwrap().name,
+            "Rust"
+        );
+    }
+
+    #[test]
+    fn normalize_buffer_cleans_up_line_endings_whitespace_and_indentation() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str("line_endings: crlf\ntab_width: 4\nnormalize_indentation: true").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+        let mut buffer = Buffer::new();
+        buffer.insert("fn amp() {  \r\n\tif ready {\r\n    \tgo(); \r\n\t}\r\n}");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::normalize_buffer(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "fn amp() {\n    if ready {\n        go();\n    }\n}\n"
+        );
+
+        // The configured line ending is applied when the buffer is saved.
+        let id = app.workspace.current_buffer().unwrap().id.unwrap();
+        assert_eq!(app.file_formats.get(&id).map(|format| format.line_ending), Some(LineEnding::Crlf));
+
+        // The cleanup is reverted with a single undo.
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "fn amp() {  \r\n\tif ready {\r\n    \tgo(); \r\n\t}\r\n}"
+        );
+    }
+
+    #[test]
+    fn normalize_buffer_preserves_carriage_returns_outside_of_line_breaks() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str("line_endings: lf").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\reditor\r\n");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::normalize_buffer(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\reditor\n");
+    }
+
+    #[test]
+    fn normalize_buffer_uses_hard_tabs_and_preserves_carriage_returns_when_configured() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str("soft_tabs: false\ntab_width: 2\nnormalize_indentation: true").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\r\n     editor\n");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::normalize_buffer(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "amp\r\n\t\t editor\n"
+        );
+    }
+
+    #[test]
+    fn normalize_buffer_leaves_indentation_alone_unless_enabled() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str("tab_width: 4").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+        let mut buffer = Buffer::new();
+        buffer.insert("fn amp() {  \n\tgo();\n  }");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::normalize_buffer(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "fn amp() {\n\tgo();\n  }\n"
+        );
+    }
+
+    #[test]
+    fn insert_closing_tag_closes_the_innermost_open_tag() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("<div>");
+        buffer.cursor.move_to(Position{ line: 0, offset: 5 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::insert_closing_tag(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "<div></div>");
+
+        // The closing tag is inserted as a single change.
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "<div>");
+    }
+
+    #[test]
+    fn insert_closing_tag_does_nothing_for_self_closing_or_closed_tags() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        for content in &["<br/>", "<div></div>"] {
+            let mut buffer = Buffer::new();
+            buffer.insert(*content);
+            buffer.cursor.move_to(Position{ line: 0, offset: content.len() });
+            app.workspace.add_buffer(buffer);
+
+            commands::buffer::insert_closing_tag(&mut app).unwrap();
+            assert_eq!(app.workspace.current_buffer().unwrap().data(), *content);
+        }
+    }
+
+    #[test]
+    fn reopen_closed_buffer_restores_the_buffer_and_cursor_position() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        ::util::open_buffer(Path::new("LICENSE"), &mut app).unwrap();
+        let path = app.workspace.current_buffer().unwrap().path.clone();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 2, offset: 3 });
+
+        commands::buffer::close(&mut app).unwrap();
+        assert!(app.workspace.current_buffer().map(|buffer| buffer.path.clone()) != Some(path.clone()));
+
+        commands::buffer::reopen_closed_buffer(&mut app).unwrap();
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.path, path);
+        assert_eq!(*buffer.cursor, Position{ line: 2, offset: 3 });
+    }
+
+    #[test]
+    fn reopen_closed_buffer_skips_buffers_without_a_path() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        commands::buffer::close(&mut app).unwrap();
+
+        assert!(app.closed_buffers.is_empty());
+        assert!(commands::buffer::reopen_closed_buffer(&mut app).is_err());
+    }
+
     #[test]
     fn close_others_skips_confirmation_when_all_other_buffers_are_empty_or_unmodified() {
         let mut app = ::models::Application::new(&Vec::new()).unwrap();
@@ -1592,13 +3206,57 @@ mod tests {
         }
 
         // Confirm the command.
-        commands::confirm::confirm_command(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char('y'));
+        commands::confirm::choose(&mut app).unwrap();
 
         assert_eq!(app.workspace.current_buffer().unwrap().data(), "");
         app.workspace.next_buffer();
         assert_eq!(app.workspace.current_buffer().unwrap().data(), "");
     }
 
+    #[test]
+    fn delete_file_removes_file_and_buffer_after_confirmation() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let dir = TempDir::new("delete-file");
+        let path = dir.join("amp.txt");
+        fs::write(&path, "amp\n").unwrap();
+
+        // Open the file, followed by another buffer, and then
+        // cycle back to the file so that it precedes the other.
+        app.workspace.add_buffer(Buffer::from_file(&path).unwrap());
+        let mut other_buffer = Buffer::new();
+        other_buffer.insert("other");
+        app.workspace.add_buffer(other_buffer);
+        app.workspace.next_buffer();
+        assert_eq!(app.workspace.current_buffer().unwrap().path, Some(path.clone()));
+
+        commands::buffer::delete_file(&mut app).unwrap();
+        if let Mode::Confirm(_) = app.mode {
+        } else {
+            panic!("Not in confirm mode");
+        }
+        assert!(path.exists());
+
+        app.view.last_key = Some(Key::Char('y'));
+        commands::confirm::choose(&mut app).unwrap();
+        assert!(!path.exists());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "other");
+        app.workspace.next_buffer();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "other");
+    }
+
+    #[test]
+    fn delete_file_refuses_buffers_without_a_path() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        assert!(commands::buffer::delete_file(&mut app).is_err());
+        if let Mode::Normal = app.mode {
+        } else {
+            panic!("Not in normal mode");
+        }
+    }
+
     #[test]
     fn close_others_works_when_current_buffer_is_last() {
         let mut app = ::models::Application::new(&Vec::new()).unwrap();
@@ -1645,4 +3303,640 @@ mod tests {
         app.workspace.next_buffer();
         assert_eq!(app.workspace.current_buffer().unwrap().data(), "two");
     }
+
+    #[test]
+    fn insert_char_pairs_opening_brackets_when_auto_pairs_are_enabled() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        enable_auto_pairs(&mut app);
+        app.workspace.add_buffer(Buffer::new());
+
+        app.view.last_key = Some(Key::Char('('));
+        commands::buffer::insert_char(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "()");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 1 });
+    }
+
+    #[test]
+    fn insert_char_types_over_closing_characters_when_auto_pairs_are_enabled() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        enable_auto_pairs(&mut app);
+        let mut buffer = Buffer::new();
+        buffer.insert("()");
+        buffer.cursor.move_to(Position{ line: 0, offset: 1 });
+        app.workspace.add_buffer(buffer);
+
+        app.view.last_key = Some(Key::Char(')'));
+        commands::buffer::insert_char(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "()");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 2 });
+    }
+
+    #[test]
+    fn insert_char_doesnt_pair_characters_when_auto_pairs_are_disabled() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+
+        app.view.last_key = Some(Key::Char('('));
+        commands::buffer::insert_char(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "(");
+    }
+
+    #[test]
+    fn backspace_removes_empty_pairs_when_auto_pairs_are_enabled() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        enable_auto_pairs(&mut app);
+        let mut buffer = Buffer::new();
+        buffer.insert("amp()");
+        buffer.cursor.move_to(Position{ line: 0, offset: 4 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::backspace(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "amp");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 3 });
+    }
+
+    #[test]
+    fn hard_wrap_breaks_the_paragraph_on_spaces_and_preserves_indentation() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        set_text_width(&mut app, 20);
+        let mut buffer = Buffer::new();
+        buffer.insert("  the quick brown fox jumps over the lazy dog\n\nnext paragraph\n");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::hard_wrap(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "  the quick brown\n  fox jumps over the\n  lazy dog\n\nnext paragraph\n"
+        );
+    }
+
+    #[test]
+    fn hard_wrap_joins_short_lines_in_the_paragraph_around_the_cursor() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        set_text_width(&mut app, 20);
+        let mut buffer = Buffer::new();
+        buffer.insert("first\n\namp is a text\neditor\n\nlast");
+        buffer.cursor.move_to(Position{ line: 3, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::hard_wrap(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "first\n\namp is a text editor\n\nlast"
+        );
+    }
+
+    #[test]
+    fn hard_wrap_does_not_break_words_longer_than_the_text_width() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        set_text_width(&mut app, 20);
+        let mut buffer = Buffer::new();
+        buffer.insert("supercalifragilisticexpialidocious word");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::hard_wrap(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "supercalifragilisticexpialidocious\nword"
+        );
+    }
+
+    #[test]
+    fn hard_wrap_only_wraps_selected_lines() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        set_text_width(&mut app, 9);
+        let mut buffer = Buffer::new();
+        buffer.insert("one two three\nfour five six\nseven");
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+        commands::cursor::move_down(&mut app).unwrap();
+
+        commands::buffer::hard_wrap(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "one two\nthree\nfour five\nsix\nseven"
+        );
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn hard_wrap_fails_on_a_blank_line() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n\neditor");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        assert!(commands::buffer::hard_wrap(&mut app).is_err());
+    }
+
+    #[test]
+    fn reflow_comment_rewraps_the_comment_block_with_its_prefix() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        set_text_width(&mut app, 40);
+        let mut buffer = Buffer::new();
+        buffer.insert(
+            "fn amp() {\n    // The quick brown fox jumps over the lazy\n    // dog, and then\n    // jumps over it once more, for good measure.\n    editor();\n}"
+        );
+        buffer.cursor.move_to(Position{ line: 2, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::reflow_comment(&mut app).unwrap();
+
+        let data = app.workspace.current_buffer().unwrap().data();
+        assert_eq!(
+            data,
+            "fn amp() {\n    // The quick brown fox jumps over\n    // the lazy dog, and then jumps over\n    // it once more, for good measure.\n    editor();\n}"
+        );
+        for line in data.lines().skip(1).take(3) {
+            assert!(line.trim_left().starts_with("// "));
+            assert!(line.chars().count() <= 40);
+        }
+    }
+
+    #[test]
+    fn reflow_comment_stops_at_lines_containing_only_the_marker() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        set_text_width(&mut app, 40);
+        let mut buffer = Buffer::new();
+        buffer.insert("# first\n# paragraph\n#\n# second\n# paragraph");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::reflow_comment(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "# first paragraph\n#\n# second\n# paragraph"
+        );
+    }
+
+    #[test]
+    fn reflow_comment_keeps_doc_comment_markers() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        set_text_width(&mut app, 40);
+        let mut buffer = Buffer::new();
+        buffer.insert("/// Amp is a\n/// text editor.\n#[derive(Debug)]\nstruct Amp;");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::reflow_comment(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "/// Amp is a text editor.\n#[derive(Debug)]\nstruct Amp;"
+        );
+    }
+
+    #[test]
+    fn reflow_comment_fails_on_attribute_and_directive_lines() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("#[derive(Debug)]\n#include <stdio.h>");
+        app.workspace.add_buffer(buffer);
+
+        assert!(commands::buffer::reflow_comment(&mut app).is_err());
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 1, offset: 0 });
+        assert!(commands::buffer::reflow_comment(&mut app).is_err());
+    }
+
+    #[test]
+    fn reflow_comment_fails_on_code_lines() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("-- comment\nselect 1;");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        assert!(commands::buffer::reflow_comment(&mut app).is_err());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "-- comment\nselect 1;");
+    }
+
+    #[test]
+    fn move_line_up_swaps_the_current_line_with_the_one_above() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        buffer.cursor.move_to(Position{ line: 1, offset: 1 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::move_line_up(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "b\na\nc");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 1 });
+    }
+
+    #[test]
+    fn move_line_down_swaps_the_current_line_with_the_one_below() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::move_line_down(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "a\nc\nb");
+        assert_eq!(*buffer.cursor, Position{ line: 2, offset: 0 });
+    }
+
+    #[test]
+    fn move_line_up_does_nothing_on_the_first_line() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::move_line_up(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "a\nb\nc");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 0 });
+    }
+
+    #[test]
+    fn move_line_down_does_nothing_on_the_last_line() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        buffer.cursor.move_to(Position{ line: 2, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::move_line_down(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "a\nb\nc");
+        assert_eq!(*buffer.cursor, Position{ line: 2, offset: 0 });
+    }
+
+    #[test]
+    fn move_line_down_moves_the_selected_lines_in_select_line_mode() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc\nd");
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+        commands::cursor::move_down(&mut app).unwrap();
+
+        commands::buffer::move_line_down(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "c\na\nb\nd");
+        assert_eq!(buffer.cursor.line, 2);
+        match app.mode {
+            Mode::SelectLine(ref mode) => assert_eq!(mode.anchor, 1),
+            _ => panic!("Not in select line mode"),
+        }
+    }
+
+    #[test]
+    fn move_line_up_undoes_as_a_single_operation() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        buffer.cursor.move_to(Position{ line: 2, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::move_line_up(&mut app).unwrap();
+        commands::buffer::undo(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "a\nb\nc");
+    }
+
+    #[test]
+    fn transpose_lines_swaps_the_current_line_with_the_previous_one() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::transpose_lines(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "b\na\nc");
+        assert_eq!(*buffer.cursor, Position{ line: 2, offset: 0 });
+    }
+
+    #[test]
+    fn transpose_lines_does_nothing_on_the_first_line() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        buffer.cursor.move_to(Position{ line: 0, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::transpose_lines(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "a\nb\nc");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 0 });
+    }
+
+    #[test]
+    fn transpose_lines_undoes_as_a_single_operation() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::transpose_lines(&mut app).unwrap();
+        commands::buffer::undo(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "a\nb\nc");
+    }
+
+    #[test]
+    fn inspect_char_describes_the_character_under_the_cursor() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\na\u{A0}b");
+        buffer.cursor.move_to(Position{ line: 1, offset: 1 });
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::inspect_char(&mut app).unwrap();
+
+        assert_eq!(
+            app.message_history.iter().last().map(|message| message.content.as_str()),
+            Some("U+00A0 NO-BREAK SPACE (UTF-8: C2 A0, separator)")
+        );
+    }
+
+    #[test]
+    fn insert_uuid_inserts_a_hyphenated_uuid_by_default() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("id: ");
+        buffer.cursor.move_to(Position{ line: 0, offset: 4 });
+        app.workspace.add_buffer(buffer);
+
+        super::insert_uuid_using(&mut app, &mut ::util::uuid::FixedRandom(0x12)).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "id: 12121212-1212-4212-9212-121212121212");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 40 });
+    }
+
+    #[test]
+    fn insert_uuid_respects_the_uuid_format_preference() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::new());
+        let data = YamlLoader::load_from_str("uuid_format: plain").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+
+        super::insert_uuid_using(&mut app, &mut ::util::uuid::FixedRandom(0x12)).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "12121212121242129212121212121212");
+    }
+
+    #[test]
+    fn toggle_char_case_toggles_and_advances_past_each_character() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("aB1");
+        app.workspace.add_buffer(buffer);
+
+        for (data, offset) in vec![("AB1", 1), ("Ab1", 2), ("Ab1", 3)] {
+            commands::buffer::toggle_char_case(&mut app).unwrap();
+
+            let buffer = app.workspace.current_buffer().unwrap();
+            assert_eq!(buffer.data(), data);
+            assert_eq!(*buffer.cursor, Position{ line: 0, offset });
+        }
+    }
+
+    #[test]
+    fn toggle_char_case_handles_multi_byte_characters() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("éÉ");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::toggle_char_case(&mut app).unwrap();
+        commands::buffer::toggle_char_case(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "Éé");
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "ÉÉ");
+    }
+
+    #[test]
+    fn replace_char_replaces_the_character_under_the_cursor() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("abc");
+        buffer.cursor.move_to(Position{ line: 0, offset: 1 });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_replace_char_mode(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char('X'));
+
+        commands::buffer::replace_char(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "aXc");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 1 });
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn replace_char_does_nothing_on_an_empty_line() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("abc\n\ndef");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+        app.view.last_key = Some(Key::Char('X'));
+
+        commands::buffer::replace_char(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "abc\n\ndef");
+        assert_eq!(*buffer.cursor, Position{ line: 1, offset: 0 });
+    }
+
+    #[test]
+    fn insert_separator_inserts_a_line_of_the_character_below_the_cursor() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+        set_text_width(&mut app, 10);
+        commands::application::switch_to_separator_mode(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char('-'));
+
+        commands::buffer::insert_separator(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "amp\n----------\neditor");
+        assert_eq!(*buffer.cursor, Position{ line: 1, offset: 0 });
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+
+        // The separator is inserted as a single change.
+        buffer.undo();
+        assert_eq!(buffer.data(), "amp\neditor");
+    }
+
+    #[test]
+    fn open_url_under_cursor_notifies_when_there_is_no_url() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("see https://amp.rs");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::open_url_under_cursor(&mut app).unwrap();
+
+        assert_eq!(
+            app.message_history.iter().last().map(|message| message.content.as_str()),
+            Some("No URL found under the cursor")
+        );
+    }
+
+    #[test]
+    fn inspect_char_fails_at_the_end_of_a_line() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        buffer.cursor.move_to(Position{ line: 0, offset: 3 });
+        app.workspace.add_buffer(buffer);
+
+        assert!(commands::buffer::inspect_char(&mut app).is_err());
+    }
+
+    #[test]
+    fn strip_ansi_removes_escape_sequences_from_the_buffer() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("\u{1B}[1;32mok\u{1B}[0m [1/2]\n\u{1B}[31mfailed\u{1B}[0m [2/2]\n");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::strip_ansi(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "ok [1/2]\nfailed [2/2]\n"
+        );
+    }
+
+    #[test]
+    fn strip_ansi_only_changes_the_selected_lines_in_select_line_mode() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("\u{1B}[31mamp\u{1B}[0m\n\u{1B}[32meditor\u{1B}[0m");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+
+        commands::buffer::strip_ansi(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "\u{1B}[31mamp\u{1B}[0m\neditor"
+        );
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn strip_ansi_fails_when_there_is_nothing_to_strip() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("[plain] text");
+        app.workspace.add_buffer(buffer);
+
+        assert!(commands::buffer::strip_ansi(&mut app).is_err());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "[plain] text");
+    }
+
+    #[test]
+    fn sort_lines_numeric_sorts_the_selected_lines_by_number() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("file10\nfile2\nfile1\nend");
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+        commands::cursor::move_down(&mut app).unwrap();
+        commands::cursor::move_down(&mut app).unwrap();
+
+        commands::buffer::sort_lines_numeric(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "file1\nfile2\nfile10\nend"
+        );
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn compare_numerically_orders_mixed_lines() {
+        let mut lines = vec![
+            "beta",
+            "10 apples",
+            "-2.5 degrees",
+            "alpha",
+            "3.75 km",
+            "file-1",
+            "x = -7",
+            "3.5 km",
+        ];
+        lines.sort_by(|a, b| super::compare_numerically(a, b));
+
+        assert_eq!(
+            lines,
+            vec![
+                "x = -7",
+                "-2.5 degrees",
+                "file-1",
+                "3.5 km",
+                "3.75 km",
+                "10 apples",
+                "alpha",
+                "beta",
+            ]
+        );
+    }
+
+    #[test]
+    fn compare_numerically_breaks_ties_lexicographically() {
+        use std::cmp::Ordering;
+
+        assert_eq!(super::compare_numerically("b2", "a2"), Ordering::Greater);
+        assert_eq!(super::compare_numerically("2.0", "2"), Ordering::Greater);
+        assert_eq!(super::compare_numerically("v1.", "v1"), Ordering::Greater);
+    }
+
+    fn set_text_width(app: &mut ::models::Application, width: usize) {
+        let data = YamlLoader::load_from_str(&format!("text_width: {}", width)).unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+    }
+
+    fn enable_auto_pairs(app: &mut ::models::Application) {
+        let data = YamlLoader::load_from_str("auto_pairs: true").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+    }
 }
@@ -1,14 +1,72 @@
 use commands::{self, Result};
 use models::application::{Application, Mode};
 
-pub fn confirm_command(app: &mut Application) -> Result {
+/// Runs the choice bound to the last key pressed, ignoring unbound keys.
+pub fn choose(app: &mut Application) -> Result {
     let command =
       if let Mode::Confirm(ref mode) = app.mode {
-          mode.command
+          app.view.last_key().as_ref().and_then(|key| mode.command_for(key))
       } else {
-          bail!("Can't confirm command outside of confirm mode");
+          bail!("Can't choose outside of confirm mode");
       };
 
-    command(app)?;
-    commands::application::switch_to_normal_mode(app)
+    if let Some(command) = command {
+        command(app)?;
+        commands::application::switch_to_normal_mode(app)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use commands;
+    use input::Key;
+    use models::Application;
+    use models::application::Mode;
+    use models::application::modes::{Choice, ConfirmMode};
+    use scribe::Buffer;
+
+    fn enter_confirm_mode(app: &mut Application) {
+        app.mode = Mode::Confirm(ConfirmMode::with_choices("Unsaved changes", vec![
+            Choice::new('d', "discard", commands::buffer::close),
+            Choice::new('c', "cancel", commands::application::switch_to_normal_mode),
+        ]));
+    }
+
+    #[test]
+    fn choose_runs_the_command_bound_to_the_last_key() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("data");
+        app.workspace.add_buffer(buffer);
+        enter_confirm_mode(&mut app);
+
+        app.view.last_key = Some(Key::Char('d'));
+        super::choose(&mut app).unwrap();
+
+        assert!(app.workspace.current_buffer().is_none());
+        if let Mode::Normal = app.mode {
+        } else {
+            panic!("Not in normal mode");
+        }
+    }
+
+    #[test]
+    fn choose_ignores_unbound_keys() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("data");
+        app.workspace.add_buffer(buffer);
+        enter_confirm_mode(&mut app);
+
+        app.view.last_key = Some(Key::Char('x'));
+        super::choose(&mut app).unwrap();
+
+        assert!(app.workspace.current_buffer().is_some());
+        if let Mode::Confirm(_) = app.mode {
+        } else {
+            panic!("Not in confirm mode");
+        }
+    }
 }
@@ -1,39 +1,127 @@
 use errors::*;
 use commands::{self, Result};
-use util::token::{Direction, adjacent_token_position};
+use util::token::{Direction, adjacent_token_position, adjacent_word_position, token_end_position};
 use models::application::Application;
-use scribe::buffer::Position;
+use scribe::buffer::{Buffer, Position};
+use unicode_segmentation::UnicodeSegmentation;
+use util::spell_check;
 use super::{application, buffer};
 
 pub fn move_up(app: &mut Application) -> Result {
-    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_up();
+    let count = app.take_count();
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    for _ in 0..count {
+        buffer.cursor.move_up();
+    }
     commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
 }
 
 pub fn move_down(app: &mut Application) -> Result {
-    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_down();
+    let count = app.take_count();
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    for _ in 0..count {
+        buffer.cursor.move_down();
+    }
     commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
 }
 
 pub fn move_left(app: &mut Application) -> Result {
-    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_left();
+    let wrap_cursor = app.preferences.borrow().wrap_cursor();
+    let count = app.take_count();
+
+    if let Some(buffer) = app.workspace.current_buffer() {
+        for _ in 0..count {
+            if wrap_cursor && move_across_line_boundary(buffer, Direction::Backward) {
+                continue;
+            } else {
+                buffer.cursor.move_left();
+            }
+        }
+    } else {
+        bail!(BUFFER_MISSING);
+    }
     commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
 }
 
 pub fn move_right(app: &mut Application) -> Result {
-    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_right();
+    let wrap_cursor = app.preferences.borrow().wrap_cursor();
+    let count = app.take_count();
+
+    if let Some(buffer) = app.workspace.current_buffer() {
+        for _ in 0..count {
+            if wrap_cursor && move_across_line_boundary(buffer, Direction::Forward) {
+                continue;
+            } else {
+                buffer.cursor.move_right();
+            }
+        }
+    } else {
+        bail!(BUFFER_MISSING);
+    }
+    commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
+}
+
+/// Toggles whether horizontal movement wraps across line
+/// boundaries, overriding the `wrap_cursor` preference.
+pub fn toggle_wrap_cursor(app: &mut Application) -> Result {
+    let wrap_cursor = !app.preferences.borrow().wrap_cursor();
+    app.preferences.borrow_mut().set_wrap_cursor(wrap_cursor);
+    app.notify(if wrap_cursor {
+        "Cursor wrapping enabled"
+    } else {
+        "Cursor wrapping disabled"
+    });
+
+    Ok(())
+}
+
+/// Moves the cursor to the count-specified screen column on the current
+/// line (e.g. `12|`), expanding tabs and clamping to the end of the line.
+pub fn move_to_column(app: &mut Application) -> Result {
+    let column = app.take_count();
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let tab_width = app.preferences.borrow().tab_width(buffer.path.as_ref());
+    let line = buffer.cursor.line;
+    let offset = {
+        let data = buffer.data();
+        let content = data.lines().nth(line).unwrap_or("");
+
+        column_offset(content, column.saturating_sub(1), tab_width)
+    };
+    buffer.cursor.move_to(Position{ line, offset });
+
     commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves to the first column or, if the `smart_home` preference is enabled,
+/// to the first non-whitespace character, unless the cursor is already there.
 pub fn move_to_start_of_line(app: &mut Application) -> Result {
-    app.workspace
-        .current_buffer()
-        .ok_or(BUFFER_MISSING)?
-        .cursor
-        .move_to_start_of_line();
+    let smart_home = app.preferences.borrow().smart_home();
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let first_word_offset = if smart_home {
+        first_word_offset(buffer)
+    } else {
+        None
+    };
+
+    match first_word_offset {
+        Some(offset) if offset != buffer.cursor.offset => {
+            let line = buffer.cursor.line;
+            buffer.cursor.move_to(Position{ line, offset });
+        }
+        _ => buffer.cursor.move_to_start_of_line(),
+    }
     commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
 }
 
+/// The offset of the first non-whitespace character on the cursor line.
+fn first_word_offset(buffer: &Buffer) -> Option<usize> {
+    buffer.data()
+        .lines()
+        .nth(buffer.cursor.line)
+        .and_then(|line| line.chars().position(|character| !character.is_whitespace()))
+}
+
 pub fn move_to_end_of_line(app: &mut Application) -> Result {
     app.workspace
         .current_buffer()
@@ -174,6 +262,34 @@ pub fn move_to_start_of_next_token(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
 }
 
+/// Moves to the start of the previous word, treating any run of
+/// non-whitespace characters (e.g. `foo.bar`) as a single word.
+pub fn move_to_start_of_previous_word(app: &mut Application) -> Result {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        let position = adjacent_word_position(buffer, Direction::Backward)
+            .ok_or("Couldn't find previous word")?;
+
+        buffer.cursor.move_to(position);
+    } else {
+        bail!(BUFFER_MISSING);
+    }
+    commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
+}
+
+/// Moves to the start of the next word, treating any run of
+/// non-whitespace characters (e.g. `foo.bar`) as a single word.
+pub fn move_to_start_of_next_word(app: &mut Application) -> Result {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        let position = adjacent_word_position(buffer, Direction::Forward)
+            .ok_or("Couldn't find next word")?;
+
+        buffer.cursor.move_to(position);
+    } else {
+        bail!(BUFFER_MISSING);
+    }
+    commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
+}
+
 pub fn move_to_end_of_current_token(app: &mut Application) -> Result {
     if let Some(buffer) = app.workspace.current_buffer() {
         let position = adjacent_token_position(
@@ -189,16 +305,155 @@ pub fn move_to_end_of_current_token(app: &mut Application) -> Result {
     commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
 }
 
+pub fn move_to_end_of_token(app: &mut Application) -> Result {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        let position = token_end_position(buffer).ok_or("Couldn't find next token")?;
+
+        buffer.cursor.move_to(position);
+    } else {
+        bail!(BUFFER_MISSING);
+    }
+    commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
+}
+
 pub fn append_to_current_token(app: &mut Application) -> Result {
     move_to_end_of_current_token(app)?;
     application::switch_to_insert_mode(app)
 }
 
+/// Moves the cursor to the start of the next misspelled word in a comment
+/// or string, wrapping around to the first one at the end of the buffer.
+pub fn move_to_next_misspelling(app: &mut Application) -> Result {
+    let dictionary = app.preferences.borrow().spell_check_dictionary()?;
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let tokens = buffer.tokens()
+        .chain_err(|| "No tokens available for the current buffer")?;
+    let misspellings = spell_check::misspellings(tokens.iter(), &dictionary);
+    let target = misspellings
+        .iter()
+        .find(|range| range.start() > *buffer.cursor)
+        .or_else(|| misspellings.first())
+        .map(|range| range.start())
+        .ok_or("No misspellings found")?;
+    buffer.cursor.move_to(target);
+
+    commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
+}
+
+/// Moves the cursor to the next occurrence of an annotation pattern (e.g.
+/// TODO), wrapping around to the first one at the end of the buffer.
+pub fn next_annotation(app: &mut Application) -> Result {
+    move_to_annotation(app, Direction::Forward)
+}
+
+/// Moves the cursor to the previous occurrence of an annotation pattern,
+/// wrapping around to the last one at the start of the buffer.
+pub fn prev_annotation(app: &mut Application) -> Result {
+    move_to_annotation(app, Direction::Backward)
+}
+
+fn move_to_annotation(app: &mut Application, direction: Direction) -> Result {
+    let patterns = app.preferences.borrow().annotation_patterns();
+    let wrapped = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let mut positions: Vec<Position> = patterns
+            .iter()
+            .flat_map(|pattern| buffer.search(pattern))
+            .collect();
+        positions.sort_by_key(|position| (position.line, position.offset));
+        positions.dedup();
+
+        let cursor = *buffer.cursor;
+        let (target, wrapped) = match direction {
+            Direction::Forward => {
+                match positions.iter().find(|&&position| position > cursor) {
+                    Some(&position) => (Some(position), false),
+                    None => (positions.first().cloned(), true),
+                }
+            }
+            Direction::Backward => {
+                match positions.iter().rev().find(|&&position| position < cursor) {
+                    Some(&position) => (Some(position), false),
+                    None => (positions.last().cloned(), true),
+                }
+            }
+        };
+        let target = target.ok_or_else(|| {
+            format!("No annotations found (searched for {})", patterns.join(", "))
+        })?;
+        buffer.cursor.move_to(target);
+
+        wrapped
+    };
+
+    if wrapped {
+        let notice = match direction {
+            Direction::Forward => "Reached the end of the buffer; continued from the top",
+            Direction::Backward => "Reached the start of the buffer; continued from the bottom",
+        };
+        app.notify(notice);
+    }
+
+    commands::view::scroll_to_cursor(app).chain_err(|| SCROLL_TO_CURSOR_FAILED)
+}
+
+/// Moves the cursor to the start of the next line when it's at the end of
+/// the current one (or the end of the previous line when it's at the start
+/// of the current one), returning whether a boundary was crossed.
+fn move_across_line_boundary(buffer: &mut Buffer, direction: Direction) -> bool {
+    let data = buffer.data();
+    let line = buffer.cursor.line;
+    let line_length = |line| {
+        data.lines()
+            .nth(line)
+            .map(|content: &str| content.graphemes(true).count())
+            .unwrap_or(0)
+    };
+
+    let target = match direction {
+        Direction::Forward if buffer.cursor.offset >= line_length(line) => {
+            Position{ line: line + 1, offset: 0 }
+        }
+        Direction::Backward if buffer.cursor.offset == 0 && line > 0 => {
+            Position{ line: line - 1, offset: line_length(line - 1) }
+        }
+        _ => return false,
+    };
+
+    buffer.cursor.move_to(target)
+}
+
+/// Returns the offset of the grapheme displayed at the specified zero-based
+/// screen column, or the end of the line if it's too short. Like the buffer
+/// renderer, tabs cover every column up to the next tab stop, and any other
+/// grapheme covers a single column.
+fn column_offset(line: &str, column: usize, tab_width: usize) -> usize {
+    let mut screen_column = 0;
+    let mut offset = 0;
+
+    for grapheme in line.graphemes(true) {
+        let width = if grapheme == "\t" {
+            (screen_column / tab_width + 1) * tab_width - screen_column
+        } else {
+            1
+        };
+        if screen_column + width > column {
+            break;
+        }
+
+        screen_column += width;
+        offset += 1;
+    }
+
+    offset
+}
+
 #[cfg(test)]
 mod tests {
     use scribe::Buffer;
     use scribe::buffer::Position;
-    use models::application::Application;
+    use models::application::{Application, Preferences};
+    use yaml::YamlLoader;
 
     #[test]
     fn move_to_first_word_of_line_works() {
@@ -289,6 +544,33 @@ mod tests {
                    });
     }
 
+    #[test]
+    fn move_to_start_of_next_word_skips_punctuation() {
+        let mut app = set_up_application("foo.bar baz");
+
+        // Token motion stops at the punctuation.
+        super::move_to_start_of_next_token(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position{ line: 0, offset: 3 });
+
+        // Word motion jumps straight to the next whitespace-delimited word.
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 0 });
+        super::move_to_start_of_next_word(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position{ line: 0, offset: 8 });
+    }
+
+    #[test]
+    fn move_to_start_of_previous_word_skips_punctuation() {
+        let mut app = set_up_application("foo.bar baz");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 8 });
+
+        super::move_to_start_of_previous_word(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position{ line: 0, offset: 0 });
+    }
+
     #[test]
     fn move_to_end_of_current_token_works() {
         // Set up the application and run the command.
@@ -311,6 +593,54 @@ mod tests {
                    });
     }
 
+    #[test]
+    fn move_to_end_of_token_lands_on_the_last_character_of_the_current_token() {
+        let mut app = set_up_application("amp editor");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position {
+            line: 0,
+            offset: 0,
+        });
+
+        super::move_to_end_of_token(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 0,
+                       offset: 2,
+                   });
+    }
+
+    #[test]
+    fn move_to_end_of_token_advances_to_the_end_of_the_next_token() {
+        let mut app = set_up_application("amp editor");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position {
+            line: 0,
+            offset: 2,
+        });
+
+        super::move_to_end_of_token(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 0,
+                       offset: 9,
+                   });
+    }
+
+    #[test]
+    fn move_down_advances_by_the_pending_count_and_resets_it() {
+        let mut app = set_up_application("1\n2\n3\n4\n5\n6\n7\n8");
+        app.pending_count = Some(5);
+
+        super::move_down(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 5);
+        assert!(app.pending_count.is_none());
+
+        // Without a count, the cursor moves a single line.
+        super::move_down(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 6);
+    }
+
     #[test]
     fn append_to_current_token_works() {
         // Set up the application.
@@ -373,6 +703,225 @@ mod tests {
         });
     }
 
+    #[test]
+    fn move_left_and_right_step_over_grapheme_clusters() {
+        // Cursor offsets are grapheme indices, so the joined family emoji and the
+        // combining accent are each stepped over as a single unit by default.
+        // Insert a marker after moving to show where the cursor has landed.
+        let expectations = [
+            (1, "👨‍👩‍👧xe\u{301}a"),
+            (2, "👨‍👩‍👧e\u{301}xa"),
+        ];
+        for &(moves, expected) in &expectations {
+            let mut app = set_up_application("👨‍👩‍👧e\u{301}a");
+            for _ in 0..moves {
+                super::move_right(&mut app).unwrap();
+            }
+            app.workspace.current_buffer().unwrap().insert("x");
+            assert_eq!(app.workspace.current_buffer().unwrap().data(), expected);
+        }
+
+        let expectations = [
+            (1, "👨‍👩‍👧e\u{301}xa"),
+            (2, "👨‍👩‍👧xe\u{301}a"),
+            (3, "x👨‍👩‍👧e\u{301}a"),
+        ];
+        for &(moves, expected) in &expectations {
+            let mut app = set_up_application("👨‍👩‍👧e\u{301}a");
+            app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 3 });
+            for _ in 0..moves {
+                super::move_left(&mut app).unwrap();
+            }
+            app.workspace.current_buffer().unwrap().insert("x");
+            assert_eq!(app.workspace.current_buffer().unwrap().data(), expected);
+        }
+    }
+
+    #[test]
+    fn move_right_wraps_to_the_next_line_when_wrap_cursor_is_enabled() {
+        let mut app = set_up_application("ab\ncd");
+        app.preferences.borrow_mut().set_wrap_cursor(true);
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 2 });
+
+        super::move_right(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 1, offset: 0 });
+    }
+
+    #[test]
+    fn move_right_stops_at_the_end_of_the_line_when_wrap_cursor_is_disabled() {
+        let mut app = set_up_application("ab\ncd");
+        app.preferences.borrow_mut().set_wrap_cursor(false);
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 2 });
+
+        super::move_right(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 2 });
+    }
+
+    #[test]
+    fn move_right_wraps_after_a_multi_codepoint_grapheme_at_the_end_of_the_line() {
+        let mut app = set_up_application("e\u{301}\ncd");
+        app.preferences.borrow_mut().set_wrap_cursor(true);
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 1 });
+
+        super::move_right(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 1, offset: 0 });
+    }
+
+    #[test]
+    fn move_left_wraps_to_the_end_of_the_previous_line_when_wrap_cursor_is_enabled() {
+        let mut app = set_up_application("ab\ncd");
+        app.preferences.borrow_mut().set_wrap_cursor(true);
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 1, offset: 0 });
+
+        super::move_left(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 2 });
+    }
+
+    #[test]
+    fn move_left_stops_at_the_start_of_the_line_when_wrap_cursor_is_disabled() {
+        let mut app = set_up_application("ab\ncd");
+        app.preferences.borrow_mut().set_wrap_cursor(false);
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 1, offset: 0 });
+
+        super::move_left(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 1, offset: 0 });
+    }
+
+    #[test]
+    fn toggle_wrap_cursor_flips_the_preference() {
+        let mut app = set_up_application("ab\ncd");
+        app.preferences.borrow_mut().set_wrap_cursor(false);
+
+        super::toggle_wrap_cursor(&mut app).unwrap();
+
+        assert!(app.preferences.borrow().wrap_cursor());
+    }
+
+    #[test]
+    fn move_to_column_expands_tabs_using_the_tab_width() {
+        let mut app = set_up_application("\tamp\n\tamp");
+        let data = YamlLoader::load_from_str("tab_width: 4").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+        app.pending_count = Some(5);
+
+        super::move_to_column(&mut app).unwrap();
+
+        // The tab covers the first four columns, so column five is the "a".
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 1 });
+    }
+
+    #[test]
+    fn move_to_column_lands_on_the_correct_offset_with_the_default_tab_width() {
+        let mut app = set_up_application("\tamp\n\tamp");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 1, offset: 0 });
+        app.pending_count = Some(5);
+
+        super::move_to_column(&mut app).unwrap();
+
+        // The tab covers the first two columns, so column five is the "p".
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 1, offset: 3 });
+    }
+
+    #[test]
+    fn move_to_column_treats_multi_codepoint_graphemes_as_a_single_column() {
+        let mut app = set_up_application("e\u{301}amp");
+        app.pending_count = Some(2);
+
+        super::move_to_column(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 1 });
+    }
+
+    #[test]
+    fn move_to_column_clamps_to_the_end_of_the_line() {
+        let mut app = set_up_application("\tamp");
+        app.pending_count = Some(50);
+
+        super::move_to_column(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 4 });
+    }
+
+    #[test]
+    fn move_to_column_moves_to_the_first_column_without_a_count() {
+        let mut app = set_up_application("\tamp");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 3 });
+
+        super::move_to_column(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 0 });
+    }
+
+    #[test]
+    fn move_to_start_of_line_alternates_between_first_word_and_column_when_smart_home_is_enabled() {
+        let mut app = set_up_application("    amp editor");
+        let data = YamlLoader::load_from_str("smart_home: true").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 9 });
+
+        super::move_to_start_of_line(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 4 });
+
+        super::move_to_start_of_line(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 0 });
+
+        super::move_to_start_of_line(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 4 });
+    }
+
+    #[test]
+    fn move_to_start_of_line_moves_to_the_first_column_by_default() {
+        let mut app = set_up_application("    amp editor");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 9 });
+
+        super::move_to_start_of_line(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 0 });
+    }
+
+    #[test]
+    fn next_annotation_moves_to_the_next_annotation_and_wraps() {
+        let mut app = set_up_application("amp\n// TODO: one\neditor\n# FIXME: two\n");
+
+        super::next_annotation(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 1, offset: 3 });
+
+        super::next_annotation(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 3, offset: 2 });
+        assert!(app.message_history.iter().last().is_none());
+
+        super::next_annotation(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 1, offset: 3 });
+        assert!(app.message_history.iter().last().is_some());
+    }
+
+    #[test]
+    fn prev_annotation_moves_to_the_previous_annotation_and_wraps() {
+        let mut app = set_up_application("amp\n// TODO: one\neditor\n# FIXME: two\n");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 2, offset: 0 });
+
+        super::prev_annotation(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 1, offset: 3 });
+
+        super::prev_annotation(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 3, offset: 2 });
+    }
+
+    #[test]
+    fn next_annotation_uses_preferred_patterns() {
+        let mut app = set_up_application("TODO\nHACK\n");
+        let data = YamlLoader::load_from_str("annotation_patterns: [HACK]").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+
+        super::next_annotation(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 1, offset: 0 });
+    }
+
     fn set_up_application(content: &str) -> Application {
         let mut app = ::models::Application::new(&Vec::new()).unwrap();
         let mut buffer = Buffer::new();
@@ -0,0 +1,221 @@
+use errors::*;
+use commands::{self, Result};
+use input::Key;
+use models::application::{Application, Mode};
+use models::application::modes::{CharSearch, FindCharMode};
+use scribe::buffer::Position;
+use unicode_segmentation::UnicodeSegmentation;
+use util::token::Direction;
+
+pub fn find_char_forward(app: &mut Application) -> Result {
+    switch_to_find_char_mode(app, CharSearch{ direction: Direction::Forward, till: false })
+}
+
+pub fn find_char_backward(app: &mut Application) -> Result {
+    switch_to_find_char_mode(app, CharSearch{ direction: Direction::Backward, till: false })
+}
+
+pub fn till_char_forward(app: &mut Application) -> Result {
+    switch_to_find_char_mode(app, CharSearch{ direction: Direction::Forward, till: true })
+}
+
+pub fn till_char_backward(app: &mut Application) -> Result {
+    switch_to_find_char_mode(app, CharSearch{ direction: Direction::Backward, till: true })
+}
+
+/// Runs the pending search using the last key press as its target character.
+pub fn accept_char(app: &mut Application) -> Result {
+    let search = if let Mode::FindChar(ref mode) = app.mode {
+        mode.search
+    } else {
+        bail!("Can't accept a character outside of find char mode");
+    };
+    let character = match *app.view.last_key() {
+        Some(Key::Char(character)) => character,
+        _ => bail!("Last key press wasn't a character"),
+    };
+
+    app.last_char_search = Some((search, character));
+    commands::application::switch_to_normal_mode(app)?;
+    run_search(app, search, character, false)
+}
+
+pub fn repeat_find(app: &mut Application) -> Result {
+    let (search, character) = app.last_char_search.ok_or("No previous character search")?;
+
+    run_search(app, search, character, true)
+}
+
+pub fn repeat_find_reverse(app: &mut Application) -> Result {
+    let (search, character) = app.last_char_search.ok_or("No previous character search")?;
+
+    run_search(app, search.reversed(), character, true)
+}
+
+fn switch_to_find_char_mode(app: &mut Application, search: CharSearch) -> Result {
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    app.mode = Mode::FindChar(FindCharMode::new(search));
+
+    Ok(())
+}
+
+fn run_search(app: &mut Application, search: CharSearch, character: char, repeat: bool) -> Result {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        let data = buffer.data();
+        let current_line = data
+            .lines()
+            .nth(buffer.cursor.line)
+            .ok_or(CURRENT_LINE_MISSING)?;
+        let offset = char_offset(current_line, buffer.cursor.offset, search, character, repeat)
+            .ok_or_else(|| format!("Couldn't find \"{}\" on the current line", character))?;
+
+        let line = buffer.cursor.line;
+        buffer.cursor.move_to(Position{ line, offset });
+    } else {
+        bail!(BUFFER_MISSING);
+    }
+    commands::view::scroll_to_cursor(app)
+}
+
+/// Finds the offset targeted by a character search on the specified line.
+/// Offsets are grapheme indices, matching the cursor's. Repeated till searches
+/// ignore an occurrence adjacent to the starting offset, so that they don't
+/// leave the cursor stuck in place.
+fn char_offset(line: &str, offset: usize, search: CharSearch, character: char, repeat: bool) -> Option<usize> {
+    let skip = if search.till && repeat { 1 } else { 0 };
+    let adjustment = if search.till { 1 } else { 0 };
+    let target = character.to_string();
+
+    match search.direction {
+        Direction::Forward => line
+            .graphemes(true)
+            .enumerate()
+            .skip(offset + 1 + skip)
+            .find(|&(_, grapheme)| grapheme == target)
+            .map(|(index, _)| index - adjustment),
+        Direction::Backward => line
+            .graphemes(true)
+            .enumerate()
+            .take(offset.checked_sub(skip).unwrap_or(0))
+            .filter(|&(_, grapheme)| grapheme == target)
+            .last()
+            .map(|(index, _)| index + adjustment),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commands;
+    use input::Key;
+    use models::application::{Application, Mode};
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+
+    #[test]
+    fn find_char_forward_moves_to_the_next_occurrence() {
+        let mut app = set_up_application("a,b,c", 1);
+
+        super::find_char_forward(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char(','));
+        super::accept_char(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 3 });
+        if let Mode::Normal = app.mode {
+        } else {
+            panic!("Not in normal mode");
+        }
+    }
+
+    #[test]
+    fn find_char_forward_counts_grapheme_clusters_as_single_offsets() {
+        let mut app = set_up_application("e\u{301},b", 0);
+
+        super::find_char_forward(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char(','));
+        super::accept_char(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 1 });
+    }
+
+    #[test]
+    fn till_char_forward_stops_before_the_next_occurrence() {
+        let mut app = set_up_application("a,b,c", 1);
+
+        super::till_char_forward(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char(','));
+        super::accept_char(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 2 });
+    }
+
+    #[test]
+    fn find_char_backward_moves_to_the_previous_occurrence() {
+        let mut app = set_up_application("a,b,c", 4);
+
+        super::find_char_backward(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char('b'));
+        super::accept_char(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 2 });
+    }
+
+    #[test]
+    fn till_char_backward_stops_after_the_previous_occurrence() {
+        let mut app = set_up_application("a,b,c", 4);
+
+        super::till_char_backward(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char('a'));
+        super::accept_char(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 1 });
+    }
+
+    #[test]
+    fn repeat_find_repeats_the_last_search_in_either_direction() {
+        let mut app = set_up_application("a,b,c", 0);
+
+        super::find_char_forward(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char(','));
+        super::accept_char(&mut app).unwrap();
+        super::repeat_find(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 3 });
+
+        super::repeat_find_reverse(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 1 });
+    }
+
+    #[test]
+    fn repeat_find_doesnt_get_stuck_on_adjacent_till_targets() {
+        let mut app = set_up_application("a,b,c", 0);
+
+        super::till_char_forward(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char(','));
+        super::accept_char(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 0 });
+
+        super::repeat_find(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 2 });
+    }
+
+    #[test]
+    fn accept_char_fails_when_the_character_isnt_found() {
+        let mut app = set_up_application("a,b,c", 0);
+
+        super::find_char_forward(&mut app).unwrap();
+        app.view.last_key = Some(Key::Char('z'));
+
+        assert!(super::accept_char(&mut app).is_err());
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 0 });
+    }
+
+    fn set_up_application(content: &str, offset: usize) -> Application {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert(content);
+        buffer.cursor.move_to(Position{ line: 0, offset });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_normal_mode(&mut app).unwrap();
+
+        app
+    }
+}
@@ -3,6 +3,9 @@ use commands::{self, Result};
 use git2;
 use models::application::{Application, ClipboardContent, Mode};
 use regex::Regex;
+use std::path::Path;
+use util::blame::BlameCache;
+use util::{self, diff};
 
 pub fn add(app: &mut Application) -> Result {
     let repo = app.repository.as_ref().ok_or("No repository available")?;
@@ -95,3 +98,298 @@ pub fn copy_remote_url(app: &mut Application) -> Result {
 
     Ok(())
 }
+
+pub fn show_diff(app: &mut Application) -> Result {
+    let content = file_content(app)?;
+    let buffer_path = app.workspace
+        .current_buffer()
+        .ok_or(BUFFER_MISSING)?
+        .path.clone()
+        .ok_or(BUFFER_PATH_MISSING)?;
+    let diff = {
+        let repo = app.repository.as_ref().ok_or("No repository available")?;
+        let repo_path = repo.workdir().ok_or("No path found for the repository")?;
+        let relative_path = buffer_path.strip_prefix(repo_path).chain_err(|| {
+            "Failed to build a relative buffer path"
+        })?;
+
+        diff_against_head(repo, relative_path, &content)?
+    };
+
+    diff::open(&diff, app)
+}
+
+/// Builds a unified diff between the path's HEAD blob and the provided content.
+fn diff_against_head(repo: &git2::Repository, relative_path: &Path, content: &[u8]) -> ::errors::Result<String> {
+    let status = repo.status_file(relative_path).chain_err(|| {
+        "Couldn't get status info for the specified path"
+    })?;
+    if status.contains(git2::STATUS_WT_NEW) || status.contains(git2::STATUS_INDEX_NEW) {
+        bail!("The current file isn't tracked by the repository");
+    }
+
+    let head_object = repo
+        .revparse_single(&format!("HEAD:{}", relative_path.to_string_lossy()))
+        .chain_err(|| "Couldn't find the current file in HEAD")?;
+    let head_blob = head_object.as_blob().ok_or("The current file isn't a blob in HEAD")?;
+
+    diff::unified(head_blob.content(), relative_path, content, relative_path)
+        .chain_err(|| "Couldn't diff the current file")
+}
+
+/// The current buffer's content as it would be written to disk, with its
+/// line ending and encoding re-applied, for comparison with the repository.
+fn file_content(app: &mut Application) -> ::errors::Result<Vec<u8>> {
+    let (id, path) = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        (buffer.id, buffer.path.clone())
+    };
+    let format = util::file_format(app, id, path.as_ref());
+    let data = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.data();
+
+    format.encode(&data)
+}
+
+pub fn stage_hunk(app: &mut Application) -> Result {
+    // Diff the content as it'd be written to disk, so that the
+    // file's line endings and encoding don't register as changes.
+    let content = file_content(app)?;
+    let repo = app.repository.as_ref().ok_or("No repository available")?;
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let buffer_path = buffer.path.as_ref().ok_or(BUFFER_PATH_MISSING)?;
+    let repo_path = repo.workdir().ok_or("No path found for the repository")?;
+    let relative_path = buffer_path.strip_prefix(repo_path).chain_err(|| {
+        "Failed to build a relative buffer path"
+    })?;
+
+    let mut index = repo.index().chain_err(|| "Couldn't get the repository index")?;
+    let mut entry = index.get_path(relative_path, 0).ok_or(
+        "The current file isn't tracked by the repository"
+    )?;
+    let index_blob = repo.find_blob(entry.id).chain_err(|| {
+        "Couldn't find the current file in the index"
+    })?;
+
+    // Diff against the index, rather than HEAD, so that
+    // previously staged hunks aren't considered again.
+    let mut patch = git2::Patch::from_blob_and_buffer(
+        &index_blob,
+        Some(relative_path),
+        &content,
+        Some(relative_path),
+        None
+    ).chain_err(|| "Couldn't diff the current file")?;
+
+    // Diff line numbers are one-based.
+    let cursor_line = buffer.cursor.line + 1;
+    let hunk_index = (0..patch.num_hunks()).find(|&i| {
+        patch.hunk(i).map(|(hunk, _)| {
+            let start = hunk.new_start() as usize;
+            let end = start + (hunk.new_lines() as usize).max(1);
+
+            start <= cursor_line && cursor_line < end
+        }).unwrap_or(false)
+    }).ok_or("No changes found at the cursor")?;
+
+    let staged_content = apply_hunk(&mut patch, hunk_index, index_blob.content())?;
+    let staged_blob = repo.blob(&staged_content).chain_err(|| {
+        "Couldn't write the staged content to the repository"
+    })?;
+
+    entry.id = staged_blob;
+    entry.file_size = staged_content.len() as u32;
+    index.add(&entry).chain_err(|| "Failed to add hunk to index.")?;
+    index.write().chain_err(|| "Failed to write index.")
+}
+
+/// Applies a single hunk from the patch to its original content,
+/// leaving the content outside of the hunk untouched.
+fn apply_hunk(patch: &mut git2::Patch, hunk_index: usize, original: &[u8]) -> ::errors::Result<Vec<u8>> {
+    let (hunk, line_count) = patch.hunk(hunk_index).chain_err(|| {
+        "Couldn't read the diff hunk"
+    })?;
+
+    // Split the original content into lines, preserving their terminators.
+    let mut original_lines = Vec::new();
+    let mut line_start = 0;
+    for (offset, byte) in original.iter().enumerate() {
+        if *byte == b'\n' {
+            original_lines.push(&original[line_start..offset + 1]);
+            line_start = offset + 1;
+        }
+    }
+    if line_start < original.len() {
+        original_lines.push(&original[line_start..]);
+    }
+
+    // Hunk line numbers are one-based, except when a hunk has no
+    // original lines, in which case they refer to the preceding line.
+    let old_lines = hunk.old_lines() as usize;
+    let hunk_start = if old_lines == 0 {
+        hunk.old_start() as usize
+    } else {
+        hunk.old_start() as usize - 1
+    };
+    let hunk_end = (hunk_start + old_lines).min(original_lines.len());
+
+    let mut content = Vec::with_capacity(original.len());
+    for line in &original_lines[..hunk_start] {
+        content.extend_from_slice(line);
+    }
+    for line_index in 0..line_count {
+        let line = patch.line_in_hunk(hunk_index, line_index).chain_err(|| {
+            "Couldn't read the diff hunk's lines"
+        })?;
+
+        // Keep context and added lines, dropping removed ones.
+        match line.origin() {
+            ' ' | '+' => content.extend_from_slice(line.content()),
+            _ => (),
+        }
+    }
+    for line in &original_lines[hunk_end..] {
+        content.extend_from_slice(line);
+    }
+
+    Ok(content)
+}
+
+pub fn toggle_blame(app: &mut Application) -> Result {
+    if app.blame.is_some() {
+        app.blame = None;
+    } else {
+        app.repository.as_ref().ok_or("No repository available")?;
+        app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        app.blame = Some(BlameCache::new());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use models::Application;
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+    use std::fs;
+    use std::path::Path;
+    use util;
+    use util::test_helpers::{commit_file, TempDir};
+
+    #[test]
+    fn show_diff_opens_a_buffer_with_the_current_files_changes() {
+        // Set up a repository with a committed file.
+        let dir = TempDir::new("show-diff");
+        let repo = commit_file(&dir, "file.txt", "amp\neditor\n");
+        let file_path = dir.join("file.txt");
+
+        // Modify the file's second line.
+        fs::write(&file_path, "amp\ntext editor\n").unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.repository = Some(repo);
+        app.workspace.add_buffer(Buffer::from_file(&file_path).unwrap());
+        super::show_diff(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        let data = buffer.data();
+        let lines: Vec<&str> = data.lines().collect();
+        assert!(buffer.path.is_none());
+        assert!(app.read_only_buffers.contains(&buffer.id.unwrap()));
+        assert!(lines.contains(&"-editor"));
+        assert!(lines.contains(&"+text editor"));
+        assert!(lines.contains(&" amp"));
+    }
+
+    #[test]
+    fn show_diff_compares_files_using_their_original_line_endings() {
+        // Set up a repository with a committed file using CRLF line endings.
+        let dir = TempDir::new("show-diff-crlf");
+        let repo = commit_file(&dir, "file.txt", "amp\r\neditor\r\n");
+        let file_path = dir.join("file.txt");
+
+        // Modify the file's second line.
+        fs::write(&file_path, "amp\r\ntext editor\r\n").unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.repository = Some(repo);
+        util::load_buffer(&file_path, &mut app).unwrap();
+        super::show_diff(&mut app).unwrap();
+
+        // The unchanged first line isn't considered a change.
+        let data = app.workspace.current_buffer().unwrap().data();
+        assert!(!data.contains("-amp"));
+        assert!(data.contains("+text editor"));
+    }
+
+    #[test]
+    fn stage_hunk_only_stages_the_hunk_at_the_cursor() {
+        let original: Vec<String> = (1..21).map(|line| format!("line {}\n", line)).collect();
+        let mut modified = original.clone();
+        modified[1] = String::from("modified line 2\n");
+        modified[17] = String::from("modified line 18\n");
+
+        // Set up a repository with a committed file.
+        let dir = TempDir::new("stage-hunk");
+        let repo = commit_file(&dir, "file.txt", &original.concat());
+        let file_path = dir.join("file.txt");
+
+        // Modify two distant lines, so that they're in separate hunks.
+        fs::write(&file_path, modified.concat()).unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.repository = Some(repo);
+        app.workspace.add_buffer(Buffer::from_file(&file_path).unwrap());
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 1, offset: 0 });
+        super::stage_hunk(&mut app).unwrap();
+
+        let mut expected = original.clone();
+        expected[1] = String::from("modified line 2\n");
+
+        let repo = app.repository.as_ref().unwrap();
+        let index = repo.index().unwrap();
+        let entry = index.get_path(Path::new("file.txt"), 0).unwrap();
+        let blob = repo.find_blob(entry.id).unwrap();
+        assert_eq!(blob.content(), expected.concat().as_bytes());
+    }
+
+    #[test]
+    fn stage_hunk_stages_content_using_its_original_line_endings() {
+        let original: Vec<String> = (1..21).map(|line| format!("line {}\r\n", line)).collect();
+        let mut modified = original.clone();
+        modified[1] = String::from("modified line 2\r\n");
+        modified[17] = String::from("modified line 18\r\n");
+
+        // Set up a repository with a committed file using CRLF line endings.
+        let dir = TempDir::new("stage-hunk-crlf");
+        let repo = commit_file(&dir, "file.txt", &original.concat());
+        let file_path = dir.join("file.txt");
+
+        // Modify two distant lines, so that they're in separate hunks.
+        fs::write(&file_path, modified.concat()).unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.repository = Some(repo);
+        util::load_buffer(&file_path, &mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 1, offset: 0 });
+        super::stage_hunk(&mut app).unwrap();
+
+        let mut expected = original.clone();
+        expected[1] = String::from("modified line 2\r\n");
+
+        let repo = app.repository.as_ref().unwrap();
+        let index = repo.index().unwrap();
+        let entry = index.get_path(Path::new("file.txt"), 0).unwrap();
+        let blob = repo.find_blob(entry.id).unwrap();
+        assert_eq!(blob.content(), expected.concat().as_bytes());
+    }
+
+    #[test]
+    fn show_diff_fails_without_a_repository() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.repository = None;
+        app.workspace.add_buffer(Buffer::new());
+
+        assert!(super::show_diff(&mut app).is_err());
+    }
+}
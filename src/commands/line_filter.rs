@@ -0,0 +1,152 @@
+use errors::*;
+use commands::{self, Result};
+use input::Key;
+use models::application::{Application, Mode};
+use scribe::buffer::LineRange;
+use util;
+
+pub fn push_char(app: &mut Application) -> Result {
+    let last_key = app.view.last_key().as_ref().ok_or("View hasn't tracked a key press")?;
+    if let Key::Char(c) = *last_key {
+        if let Mode::LineFilter(ref mut mode) = app.mode {
+            mode.push_char(c);
+        } else {
+            bail!("Cannot push char outside of line filter mode");
+        }
+    } else {
+        bail!("Last key press wasn't a character");
+    }
+    Ok(())
+}
+
+pub fn pop_char(app: &mut Application) -> Result {
+    if let Mode::LineFilter(ref mut mode) = app.mode {
+        mode.pop_char();
+    } else {
+        bail!("Cannot pop char outside of line filter mode");
+    }
+    Ok(())
+}
+
+/// Keeps (or deletes) the lines containing the entered pattern, matched
+/// literally, as it is when searching. Lines outside of the mode's
+/// range (if it has one) are left untouched.
+pub fn accept(app: &mut Application) -> Result {
+    let (pattern, keep, lines) = match app.mode {
+        Mode::LineFilter(ref mode) => (
+            mode.input.clone(),
+            mode.keep,
+            mode.lines.as_ref().map(|lines| LineRange::new(lines.start(), lines.end()))
+        ),
+        _ => bail!("Cannot filter lines outside of line filter mode"),
+    };
+    if pattern.is_empty() {
+        bail!("Please provide a pattern to match");
+    }
+
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let lines = lines.unwrap_or_else(|| {
+            LineRange::new(0, buffer.line_count().saturating_sub(1))
+        });
+        let range = util::inclusive_range(&lines, buffer);
+        let content = buffer.read(&range).ok_or("Couldn't read the lines to filter")?;
+        let filtered_content = filter_lines(&content, &pattern, keep);
+
+        if filtered_content != content {
+            buffer.start_operation_group();
+            buffer.delete_range(range.clone());
+            buffer.cursor.move_to(range.start());
+            buffer.insert(filtered_content);
+            util::history::end_operation_group(buffer, app.change_group);
+        }
+    }
+    app.mode = Mode::Normal;
+
+    commands::view::scroll_to_cursor(app)
+}
+
+/// Keeps only the lines that contain the pattern or, when not keeping
+/// them, only those that don't. A trailing newline is preserved.
+fn filter_lines(content: &str, pattern: &str, keep: bool) -> String {
+    let trailing_newline = content.ends_with('\n');
+    let body = if trailing_newline {
+        &content[..content.len() - 1]
+    } else {
+        content
+    };
+
+    let lines: Vec<&str> = body
+        .split('\n')
+        .filter(|line| line.contains(pattern) == keep)
+        .collect();
+    let mut filtered_content = lines.join("\n");
+    if trailing_newline && !lines.is_empty() {
+        filtered_content.push('\n');
+    }
+
+    filtered_content
+}
+
+#[cfg(test)]
+mod tests {
+    use commands;
+    use models::application::{Application, Mode};
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+
+    fn filter(app: &mut Application, pattern: &str) -> commands::Result {
+        if let Mode::LineFilter(ref mut mode) = app.mode {
+            mode.input = pattern.to_string();
+        }
+
+        super::accept(app)
+    }
+
+    fn set_up_application(content: &str) -> Application {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert(content);
+        app.workspace.add_buffer(buffer);
+
+        app
+    }
+
+    #[test]
+    fn keep_lines_keeps_only_matching_lines() {
+        let mut app = set_up_application("foo\nbar\nfoobar");
+        commands::buffer::keep_lines(&mut app).unwrap();
+        filter(&mut app, "foo").unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "foo\nfoobar");
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+
+        // The filtering is undone as a single change.
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "foo\nbar\nfoobar");
+    }
+
+    #[test]
+    fn delete_lines_deletes_matching_lines() {
+        let mut app = set_up_application("foo\nbar\nfoobar");
+        commands::buffer::delete_lines(&mut app).unwrap();
+        filter(&mut app, "foo").unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "bar");
+    }
+
+    #[test]
+    fn delete_lines_only_filters_selected_lines() {
+        let mut app = set_up_application("foo\nbar\nfoo\nfoobar\nfoo\n");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 1, offset: 0 });
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 3, offset: 0 });
+        commands::buffer::delete_lines(&mut app).unwrap();
+        filter(&mut app, "foo").unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "foo\nbar\nfoo\n");
+    }
+}
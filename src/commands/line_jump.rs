@@ -6,24 +6,24 @@ use scribe::buffer::Position;
 
 pub fn accept_input(app: &mut Application) -> Result {
     if let Mode::LineJump(ref mode) = app.mode {
-        // Try parsing an integer from the input.
-        let line_number = mode
-            .input
-            .parse::<usize>()
-            .chain_err(|| "Couldn't parse a line number from the provided input.")?;
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let line_number = target_line_number(&mode.input, buffer.line_count())?;
 
         // Ignore zero-value line numbers.
         if line_number > 0 {
-            let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
 
             // Input values won't be zero-indexed; map the value so
             // that we can use it for a zero-indexed buffer position.
             let target_line = line_number - 1;
 
-            // Build an ideal target position to which we'll try moving.
+            // Build an ideal target position to which we'll try moving,
+            // using the offset from before any preview moved the cursor.
+            let offset = mode.original_cursor
+                .map(|cursor| cursor.offset)
+                .unwrap_or(buffer.cursor.offset);
             let mut target_position = Position {
                 line: target_line,
-                offset: buffer.cursor.offset,
+                offset,
             };
 
             if !buffer.cursor.move_to(target_position) {
@@ -51,6 +51,86 @@ pub fn accept_input(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Restores the cursor and scroll position from before
+/// any preview, and switches back to normal mode.
+pub fn cancel(app: &mut Application) -> Result {
+    if let Mode::LineJump(ref mode) = app.mode {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        if let Some(cursor) = mode.original_cursor {
+            buffer.cursor.move_to(cursor);
+        }
+        if let Some(offset) = mode.original_scroll_offset {
+            app.view.set_scroll_offset(buffer, offset)?;
+        }
+    } else {
+        bail!("Can't cancel line jump outside of line jump mode.");
+    }
+
+    commands::application::switch_to_normal_mode(app)
+}
+
+/// Moves the cursor to the line described by the partially entered input,
+/// so that it can be previewed before the jump is confirmed. Input that
+/// doesn't (yet) describe a line restores the original position instead.
+fn preview(app: &mut Application) -> Result {
+    if let Mode::LineJump(ref mut mode) = app.mode {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let line_count = buffer.line_count();
+        let target_line = target_line_number(&mode.input, line_count)
+            .ok()
+            .and_then(|line_number| line_number.checked_sub(1))
+            .filter(|&line| line < line_count);
+        mode.preview_line = target_line;
+
+        match target_line {
+            Some(line) => {
+                let offset = mode.original_cursor.map(|cursor| cursor.offset).unwrap_or(0);
+                if !buffer.cursor.move_to(Position { line, offset }) {
+                    buffer.cursor.move_to(Position { line, offset: 0 });
+                }
+                app.view.scroll_to_center(buffer)?;
+            }
+            None => {
+                if let Some(cursor) = mode.original_cursor {
+                    buffer.cursor.move_to(cursor);
+                }
+                if let Some(offset) = mode.original_scroll_offset {
+                    app.view.set_scroll_offset(buffer, offset)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a (one-based) line number or buffer percentage from the input.
+fn target_line_number(input: &str, line_count: usize) -> ::errors::Result<usize> {
+    if input.ends_with('%') {
+        let percent = input
+            .trim_right_matches('%')
+            .parse::<usize>()
+            .chain_err(|| "Couldn't parse a percentage from the provided input.")?;
+
+        Ok(percent_line_number(percent, line_count))
+    } else {
+        input
+            .parse::<usize>()
+            .chain_err(|| "Couldn't parse a line number from the provided input.")
+    }
+}
+
+/// Maps a percentage of the buffer to a (one-based) line
+/// number, like Vim's `N%`, clamped to the buffer's lines.
+fn percent_line_number(percent: usize, line_count: usize) -> usize {
+    let line_number = percent
+        .min(100)
+        .saturating_mul(line_count)
+        .saturating_add(99) / 100;
+
+    line_number.max(1).min(line_count)
+}
+
 pub fn push_search_char(app: &mut Application) -> Result {
     let key = app.view.last_key().as_ref().ok_or("View hasn't tracked a key press")?;
 
@@ -64,7 +144,7 @@ pub fn push_search_char(app: &mut Application) -> Result {
         bail!("Last key press wasn't a character")
     }
 
-    Ok(())
+    preview(app)
 }
 
 pub fn pop_search_char(app: &mut Application) -> Result {
@@ -74,12 +154,13 @@ pub fn pop_search_char(app: &mut Application) -> Result {
         bail!("Can't pop search character outside of search insert mode")
     };
 
-    Ok(())
+    preview(app)
 }
 
 #[cfg(test)]
 mod tests {
     use commands;
+    use input::Key;
     use scribe::Buffer;
     use scribe::buffer::Position;
     use models::application::Mode;
@@ -181,4 +262,86 @@ mod tests {
             _ => false,
         });
     }
+
+    #[test]
+    fn accept_input_moves_cursor_to_requested_percentage_of_buffer() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("1\n2\n3\n4\n5\n6\n7\n8\n9\n10");
+
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_line_jump_mode(&mut app).unwrap();
+        match app.mode {
+            Mode::LineJump(ref mut mode) => mode.input = "50%".to_string(),
+            _ => (),
+        };
+        commands::line_jump::accept_input(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 4,
+                       offset: 0,
+                   });
+    }
+
+    #[test]
+    fn accept_input_moves_cursor_to_last_line_for_one_hundred_percent() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("1\n2\n3\n4\n5\n6\n7\n8\n9\n10");
+
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_line_jump_mode(&mut app).unwrap();
+        match app.mode {
+            Mode::LineJump(ref mut mode) => mode.input = "100%".to_string(),
+            _ => (),
+        };
+        commands::line_jump::accept_input(&mut app).unwrap();
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 9,
+                       offset: 0,
+                   });
+    }
+
+    #[test]
+    fn percent_line_number_is_clamped_to_buffer_bounds() {
+        assert_eq!(super::percent_line_number(0, 10), 1);
+        assert_eq!(super::percent_line_number(15, 10), 2);
+        assert_eq!(super::percent_line_number(250, 10), 10);
+        assert_eq!(super::percent_line_number(usize::max_value(), 10), 10);
+    }
+
+    #[test]
+    fn input_previews_the_target_line_and_cancel_restores_the_cursor() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12");
+        buffer.cursor.move_to(Position { line: 2, offset: 0 });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_line_jump_mode(&mut app).unwrap();
+
+        for c in "10".chars() {
+            app.view.last_key = Some(Key::Char(c));
+            commands::line_jump::push_search_char(&mut app).unwrap();
+        }
+
+        match app.mode {
+            Mode::LineJump(ref mode) => assert_eq!(mode.preview_line, Some(9)),
+            _ => panic!("Not in line jump mode"),
+        };
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 9);
+
+        commands::line_jump::cancel(&mut app).unwrap();
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 2,
+                       offset: 0,
+                   });
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
 }
@@ -6,21 +6,53 @@ pub mod application;
 pub mod buffer;
 pub mod confirm;
 pub mod cursor;
+pub mod find_char;
 pub mod git;
 pub mod jump;
+pub mod line_filter;
 pub mod line_jump;
+pub mod operator;
+pub mod outline;
 pub mod path;
 pub mod preferences;
+pub mod quickfix;
 pub mod search;
 pub mod selection;
 pub mod search_select;
+pub mod shell_command;
 pub mod view;
 pub mod workspace;
 
 pub type Command = fn(&mut Application) -> Result;
 pub type Result = errors::Result<()>;
 
+/// A command, along with the name used to reference it (e.g. in keymaps)
+/// and a description derived from its doc comment, if it has one.
+#[derive(Clone, Copy)]
+pub struct RegisteredCommand {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub command: Command,
+}
+
+/// All of the public commands declared in this module's submodules.
+pub fn registry() -> Vec<RegisteredCommand> {
+    include!(concat!(env!("OUT_DIR"), "/registry"))
+}
+
 pub fn hash_map() -> HashMap<&'static str, Command> {
-    include!(concat!(env!("OUT_DIR"), "/hash_map"))
+    registry()
+        .into_iter()
+        .map(|registered_command| (registered_command.name, registered_command.command))
+        .collect()
+}
+
+/// Whether the current buffer is read-only (e.g. a diff).
+pub fn is_read_only(app: &mut Application) -> bool {
+    app.workspace
+        .current_buffer()
+        .and_then(|buffer| buffer.id)
+        .map(|id| app.read_only_buffers.contains(&id))
+        .unwrap_or(false)
 }
 
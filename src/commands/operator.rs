@@ -0,0 +1,133 @@
+use errors::*;
+use commands::{self, Result};
+use models::application::{Application, ClipboardContent, Operator, PendingOperator};
+use scribe::buffer::Range;
+
+pub fn delete(app: &mut Application) -> Result {
+    set_pending_operator(app, Operator::Delete)
+}
+
+pub fn change(app: &mut Application) -> Result {
+    set_pending_operator(app, Operator::Change)
+}
+
+pub fn yank(app: &mut Application) -> Result {
+    set_pending_operator(app, Operator::Yank)
+}
+
+/// Applies the pending operator to the range between its starting position
+/// and the cursor. Commands that don't move the cursor cancel the operator.
+pub fn apply_pending_operator(app: &mut Application) -> Result {
+    let pending_operator = app.pending_operator.take().ok_or("No pending operator")?;
+    let range = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let range = Range::new(pending_operator.start, *buffer.cursor);
+        if range.start() == range.end() {
+            return Ok(());
+        }
+
+        let data = buffer.read(&range).ok_or("Couldn't read the operator's range from buffer")?;
+        let copied = app.clipboard.set_content(ClipboardContent::Inline(data));
+
+        // Yanking is the only operator whose sole purpose is to copy,
+        // so clipboard failures are only worth reporting in that case.
+        if pending_operator.operator == Operator::Yank {
+            copied?;
+        }
+
+        range
+    };
+
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    match pending_operator.operator {
+        Operator::Delete | Operator::Change => buffer.delete_range(range.clone()),
+        Operator::Yank => (),
+    }
+    buffer.cursor.move_to(range.start());
+
+    if pending_operator.operator == Operator::Change {
+        commands::application::switch_to_insert_mode(app)?;
+    }
+    commands::view::scroll_to_cursor(app)
+}
+
+fn set_pending_operator(app: &mut Application, operator: Operator) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    app.pending_operator = Some(PendingOperator {
+        operator,
+        start: *buffer.cursor,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use commands;
+    use models::Application;
+    use models::application::Mode;
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+
+    #[test]
+    fn delete_operator_removes_the_range_to_the_next_token() {
+        let mut app = set_up_application("amp editor buffer", 4);
+
+        super::delete(&mut app).unwrap();
+        commands::cursor::move_to_start_of_next_token(&mut app).unwrap();
+        super::apply_pending_operator(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "amp buffer");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 4 });
+    }
+
+    #[test]
+    fn delete_operator_removes_the_range_to_the_end_of_the_line() {
+        let mut app = set_up_application("amp editor\nbuffer", 3);
+
+        super::delete(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+        super::apply_pending_operator(&mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "amp\nbuffer");
+        assert_eq!(*buffer.cursor, Position{ line: 0, offset: 3 });
+    }
+
+    #[test]
+    fn change_operator_removes_the_range_and_switches_to_insert_mode() {
+        let mut app = set_up_application("amp editor", 0);
+
+        super::change(&mut app).unwrap();
+        commands::cursor::move_to_start_of_next_token(&mut app).unwrap();
+        super::apply_pending_operator(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "editor");
+        if let Mode::Insert = app.mode {
+        } else {
+            panic!("Not in insert mode");
+        }
+    }
+
+    #[test]
+    fn operators_are_cancelled_by_commands_that_dont_move_the_cursor() {
+        let mut app = set_up_application("amp editor", 0);
+
+        super::delete(&mut app).unwrap();
+        super::apply_pending_operator(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp editor");
+        assert!(app.pending_operator.is_none());
+    }
+
+    fn set_up_application(content: &str, offset: usize) -> Application {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert(content);
+        buffer.cursor.move_to(Position{ line: 0, offset });
+        app.workspace.add_buffer(buffer);
+
+        app
+    }
+}
@@ -0,0 +1,105 @@
+use errors::*;
+use commands::{self, Result};
+use models::application::Application;
+
+const OUTLINE_MISSING: &str = "The outline isn't open";
+
+pub fn select_next(app: &mut Application) -> Result {
+    let outline = app.outline.as_mut().ok_or(OUTLINE_MISSING)?;
+    if !outline.entries.is_empty() {
+        outline.entries.select_next();
+    }
+
+    Ok(())
+}
+
+pub fn select_previous(app: &mut Application) -> Result {
+    let outline = app.outline.as_mut().ok_or(OUTLINE_MISSING)?;
+    if !outline.entries.is_empty() {
+        outline.entries.select_previous();
+    }
+
+    Ok(())
+}
+
+/// Moves the cursor to the selected outline entry's symbol,
+/// returning focus to the buffer while leaving the outline open.
+pub fn accept(app: &mut Application) -> Result {
+    {
+        let outline = app.outline.as_ref().ok_or(OUTLINE_MISSING)?;
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let position = outline.entries
+            .selection()
+            .ok_or("Couldn't find a selected outline entry")?
+            .symbol
+            .position;
+
+        if !buffer.cursor.move_to(position) {
+            bail!("Couldn't move to the selected symbol's position");
+        }
+    }
+
+    commands::application::switch_to_normal_mode(app)?;
+    commands::view::scroll_cursor_to_center(app)
+}
+
+#[cfg(test)]
+mod tests {
+    use models::application::{Application, Mode};
+    use models::application::modes::{Outline, OutlineEntry, Symbol};
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+    use util::SelectableVec;
+
+    fn entry(token: &str, line: usize) -> OutlineEntry {
+        OutlineEntry {
+            symbol: Symbol {
+                token: String::from(token),
+                position: Position{ line, offset: 3 },
+            },
+            depth: 0,
+        }
+    }
+
+    #[test]
+    fn accept_moves_to_the_selected_symbol_and_returns_to_normal_mode() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("fn amp() {}\nfn editor() {}");
+        app.workspace.add_buffer(buffer);
+        let mut outline = Outline::new();
+        outline.entries = SelectableVec::new(vec![entry("amp", 0), entry("editor", 1)]);
+        app.outline = Some(outline);
+        app.mode = Mode::Outline;
+
+        super::select_next(&mut app).unwrap();
+        super::accept(&mut app).unwrap();
+
+        assert_eq!(
+            *app.workspace.current_buffer().unwrap().cursor,
+            Position{ line: 1, offset: 3 }
+        );
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+        assert!(app.outline.is_some());
+    }
+
+    #[test]
+    fn select_commands_do_nothing_without_entries() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.outline = Some(Outline::new());
+        app.mode = Mode::Outline;
+
+        assert!(super::select_next(&mut app).is_ok());
+        assert!(super::select_previous(&mut app).is_ok());
+    }
+
+    #[test]
+    fn select_commands_fail_when_the_outline_is_closed() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+
+        assert!(super::select_next(&mut app).is_err());
+    }
+}
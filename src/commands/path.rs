@@ -1,8 +1,13 @@
 use errors::*;
 use commands::{self, Result};
+use git2::Repository;
 use input::Key;
 use models::application::{Application, Mode};
-use std::path::PathBuf;
+use scribe::buffer::Range;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use util;
 
 pub fn push_char(app: &mut Application) -> Result {
     let last_key = app.view.last_key().as_ref().ok_or("View hasn't tracked a key press")?;
@@ -28,19 +33,39 @@ pub fn pop_char(app: &mut Application) -> Result {
 }
 
 pub fn accept_path(app: &mut Application) -> Result {
-    let save_on_accept =
-        if let Mode::Path(ref mut mode) = app.mode {
-            let current_buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let glob = match app.mode {
+        Mode::Path(ref mode) if mode.open_glob_on_accept => Some(mode.input.clone()),
+        _ => None,
+    };
+    if let Some(glob) = glob {
+        return open_matching_paths(app, &glob);
+    }
+    let extraction = match app.mode {
+        Mode::Path(ref mode) => mode.extract_range.clone().map(|range| (range, mode.input.clone())),
+        _ => None,
+    };
+    if let Some((range, path_name)) = extraction {
+        return extract_range(app, range, &path_name);
+    }
+
+    let (path, save_on_accept, rename_on_accept) =
+        if let Mode::Path(ref mode) = app.mode {
+            app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
             let path_name = mode.input.clone();
             if path_name.is_empty() {
                 bail!("Please provide a non-empty path")
             }
-            current_buffer.path = Some(PathBuf::from(path_name));
-            mode.save_on_accept
+            (PathBuf::from(path_name), mode.save_on_accept, mode.rename_on_accept)
         } else {
             bail!("Cannot accept path outside of path mode");
         };
 
+    if rename_on_accept {
+        rename_current_file(app, path)?;
+    } else {
+        app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.path = Some(path);
+    }
+
     app.workspace.update_current_syntax().chain_err(||
         "Failed to update buffer's syntax definition"
     )?;
@@ -53,13 +78,159 @@ pub fn accept_path(app: &mut Application) -> Result {
     }
 }
 
+/// Opens the workspace files matching the glob, skipping those excluded from
+/// open mode or ignored by git, up to the configured maximum number of files.
+fn open_matching_paths(app: &mut Application, glob: &str) -> Result {
+    if glob.is_empty() {
+        bail!("Please provide a non-empty glob")
+    }
+
+    let (exclusions, limit) = {
+        let preferences = app.preferences.borrow();
+        (preferences.open_mode_exclusions()?.unwrap_or_default(), preferences.open_glob_limit())
+    };
+    let mut paths = util::glob::find_unignored(
+        &app.workspace.path,
+        glob,
+        &exclusions,
+        app.repository.as_ref()
+    )?;
+    if paths.is_empty() {
+        bail!(format!("No files match {}", glob));
+    }
+
+    let match_count = paths.len();
+    paths.truncate(limit);
+    app.mode = Mode::Normal;
+    for path in paths.iter() {
+        util::open_buffer(path, app)?;
+    }
+    if match_count > limit {
+        app.notify(format!(
+            "Only opened {} of the {} files matching {}; see the open_glob_limit preference",
+            limit,
+            match_count,
+            glob
+        ));
+    }
+
+    Ok(())
+}
+
+/// Moves the range's content from the current buffer to a new file at the
+/// specified path, creating its parent directories as needed, and opens
+/// the new file unless the `open_extracted_file` preference is disabled.
+fn extract_range(app: &mut Application, range: Range, path_name: &str) -> Result {
+    if path_name.is_empty() {
+        bail!("Please provide a non-empty path")
+    }
+    let path = PathBuf::from(path_name);
+    if path.exists() {
+        bail!(format!("{} already exists", path.to_string_lossy()));
+    }
+
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let content = buffer.read(&range).ok_or("Couldn't read selected data from buffer")?;
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).chain_err(|| {
+                    format!("Couldn't create {}", parent.to_string_lossy())
+                })?;
+            }
+        }
+        File::create(&path)
+            .and_then(|mut file| file.write_all(content.as_bytes()))
+            .chain_err(|| format!("Couldn't write {}", path.to_string_lossy()))?;
+
+        buffer.start_operation_group();
+        buffer.delete_range(range.clone());
+        buffer.cursor.move_to(range.start());
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+    app.mode = Mode::Normal;
+
+    if app.preferences.borrow().open_extracted_file() {
+        util::open_buffer(&path, app)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the current buffer's file to the specified path, creating its parent
+/// directories as needed, and records the rename in the repository's index
+/// if the file is tracked.
+fn rename_current_file(app: &mut Application, path: PathBuf) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let original_path = buffer.path.clone().ok_or(BUFFER_PATH_MISSING)?;
+    if path.exists() {
+        bail!(format!("{} already exists", path.to_string_lossy()));
+    }
+
+    // Resolve the original path before it's moved, for use with git.
+    let absolute_original_path = original_path.canonicalize().chain_err(|| {
+        format!("Couldn't find {}", original_path.to_string_lossy())
+    })?;
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).chain_err(|| {
+                format!("Couldn't create {}", parent.to_string_lossy())
+            })?;
+        }
+    }
+    fs::rename(&original_path, &path).chain_err(|| {
+        format!("Couldn't rename {}", original_path.to_string_lossy())
+    })?;
+    buffer.path = Some(path.clone());
+
+    if let Some(ref repo) = app.repository {
+        let absolute_path = path.canonicalize().chain_err(|| {
+            format!("Couldn't find {}", path.to_string_lossy())
+        })?;
+        let repo_path = repo
+            .workdir()
+            .ok_or("No path found for the repository")?
+            .canonicalize()
+            .chain_err(|| "Couldn't resolve the repository path")?;
+
+        // Only files within the repository need to be updated.
+        if let (Ok(relative_original_path), Ok(relative_path)) = (
+            absolute_original_path.strip_prefix(&repo_path),
+            absolute_path.strip_prefix(&repo_path)
+        ) {
+            rename_index_entry(repo, relative_original_path, relative_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces a tracked path in the repository index with its renamed equivalent.
+fn rename_index_entry(repo: &Repository, original_path: &Path, path: &Path) -> Result {
+    let mut index = repo.index().chain_err(|| "Couldn't get the repository index")?;
+
+    // Leave untracked files alone.
+    if index.get_path(original_path, 0).is_none() {
+        return Ok(());
+    }
+
+    index.remove_path(original_path).chain_err(|| "Failed to remove original path from index.")?;
+    index.add_path(path).chain_err(|| "Failed to add renamed path to index.")?;
+    index.write().chain_err(|| "Failed to write index.")
+}
+
 #[cfg(test)]
 mod tests {
     use commands;
     use models::Application;
-    use models::application::Mode;
+    use models::application::{Mode, Preferences};
     use scribe::Buffer;
+    use std::fs;
     use std::path::{PathBuf, Path};
+    use util::test_helpers::{commit_file, TempDir};
+    use yaml::YamlLoader;
 
     #[test]
     fn accept_path_sets_buffer_path_based_on_input_and_switches_to_normal_mode() {
@@ -146,4 +317,118 @@ mod tests {
             "Rust"
         );
     }
+
+    #[test]
+    fn accept_path_renames_tracked_files_on_disk_and_in_the_index() {
+        // Set up a repository with a tracked file.
+        let dir = TempDir::new("rename-file");
+        let repo = commit_file(&dir, "original.txt", "amp\n");
+        let original_path = dir.join("original.txt");
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.repository = Some(repo);
+        app.workspace.add_buffer(Buffer::from_file(&original_path).unwrap());
+
+        // Rename the file into a directory that doesn't yet exist.
+        let path = dir.join("renamed").join("file.txt");
+        commands::buffer::rename_file(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input = path.to_string_lossy().into_owned();
+        }
+        super::accept_path(&mut app).unwrap();
+
+        assert!(!original_path.exists());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "amp\n");
+        assert_eq!(app.workspace.current_buffer().unwrap().path, Some(path.clone()));
+
+        let index = app.repository.as_ref().unwrap().index().unwrap();
+        assert!(index.get_path(Path::new("original.txt"), 0).is_none());
+        assert!(index.get_path(Path::new("renamed/file.txt"), 0).is_some());
+    }
+
+    #[test]
+    fn accept_path_doesnt_rename_over_existing_files() {
+        let dir = TempDir::new("rename-file-existing");
+        let original_path = dir.join("original.txt");
+        let path = dir.join("existing.txt");
+        fs::write(&original_path, "amp\n").unwrap();
+        fs::write(&path, "editor\n").unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.add_buffer(Buffer::from_file(&original_path).unwrap());
+        commands::buffer::rename_file(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input = path.to_string_lossy().into_owned();
+        }
+
+        assert!(super::accept_path(&mut app).is_err());
+        assert!(original_path.exists());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "editor\n");
+    }
+
+    fn create_glob_tree(name: &str) -> TempDir {
+        let root = TempDir::new(name);
+        fs::create_dir_all(root.join("src/view")).unwrap();
+        for path in &["src/main.rs", "src/view/mod.rs", "src/notes.md", "build.rs"] {
+            fs::write(root.join(path), "amp\n").unwrap();
+        }
+
+        root
+    }
+
+    fn open_paths(app: &mut Application) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        let first_id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+        loop {
+            if let Some(path) = app.workspace.current_buffer().and_then(|buffer| buffer.path.clone()) {
+                paths.push(path);
+            }
+            app.workspace.next_buffer();
+            if app.workspace.current_buffer().and_then(|buffer| buffer.id) == first_id {
+                break;
+            }
+        }
+        paths.sort();
+
+        paths
+    }
+
+    #[test]
+    fn accept_path_opens_files_matching_a_glob() {
+        let root = create_glob_tree("open-glob");
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.path = root.path().to_path_buf();
+
+        commands::workspace::open_glob(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input = String::from("src/**/*.rs");
+        }
+        super::accept_path(&mut app).unwrap();
+
+        assert_eq!(
+            open_paths(&mut app),
+            vec![root.join("src/main.rs"), root.join("src/view/mod.rs")]
+        );
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn accept_path_limits_the_number_of_files_opened_from_a_glob() {
+        let root = create_glob_tree("open-glob-limit");
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.path = root.path().to_path_buf();
+        let data = YamlLoader::load_from_str("open_glob_limit: 1").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+
+        commands::workspace::open_glob(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input = String::from("**/*.rs");
+        }
+        super::accept_path(&mut app).unwrap();
+
+        assert_eq!(open_paths(&mut app), vec![root.join("build.rs")]);
+    }
 }
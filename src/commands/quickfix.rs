@@ -0,0 +1,174 @@
+use errors::*;
+use commands::{self, Result};
+use models::application::{quickfix, Application, Event, Mode};
+use scribe::buffer::Position;
+use std::thread;
+use util::{self, SelectableVec};
+
+const QUICKFIX_LIST_EMPTY: &str = "The quickfix list is empty";
+
+/// Moves to the next entry in the quickfix list, wrapping to the first.
+pub fn next(app: &mut Application) -> Result {
+    if app.quickfix.is_empty() {
+        bail!(QUICKFIX_LIST_EMPTY);
+    }
+    app.quickfix.select_next();
+
+    open_current(app)
+}
+
+/// Moves to the previous entry in the quickfix list, wrapping to the last.
+pub fn previous(app: &mut Application) -> Result {
+    if app.quickfix.is_empty() {
+        bail!(QUICKFIX_LIST_EMPTY);
+    }
+    app.quickfix.select_previous();
+
+    open_current(app)
+}
+
+/// Opens the file referenced by the current quickfix
+/// entry, moving the cursor to the start of its line.
+pub fn open_current(app: &mut Application) -> Result {
+    let entry = app.quickfix.selection().cloned().ok_or(QUICKFIX_LIST_EMPTY)?;
+    util::open_buffer(&entry.path, app)?;
+
+    if let Some(buffer) = app.workspace.current_buffer() {
+        if !buffer.cursor.move_to(Position{ line: entry.line, offset: 0 }) {
+            buffer.cursor.move_to_last_line();
+        }
+    }
+    app.mode = Mode::Normal;
+    commands::view::scroll_cursor_to_center(app)
+}
+
+/// Searches the workspace's files for lines matching the current search
+/// query in a separate thread, populating the quickfix list with them and
+/// moving to the first once the search is complete.
+pub fn search_workspace(app: &mut Application) -> Result {
+    let query = app.search_query.clone().ok_or(SEARCH_QUERY_MISSING)?;
+    let exclusions = app.preferences.borrow().open_mode_exclusions()?.unwrap_or_default();
+    let root = app.workspace.path.clone();
+    let events = app.event_channel.clone();
+
+    thread::spawn(move || {
+        let results = quickfix::search_files(&root, &query, &exclusions)
+            .map_err(|error| error.to_string());
+        let _ = events.send(Event::WorkspaceSearchComplete(query, results));
+    });
+    app.notify("Searching the workspace...");
+
+    Ok(())
+}
+
+/// Populates the quickfix list with the current buffer's lines that have
+/// trailing whitespace or mixed indentation, and then moves to the first.
+pub fn lint_whitespace(app: &mut Application) -> Result {
+    let entries = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let path = buffer.path.clone().ok_or(BUFFER_PATH_MISSING)?;
+        let soft_tabs = app.preferences.borrow().soft_tabs(Some(&path));
+
+        quickfix::lint_whitespace(path, &buffer.data(), soft_tabs)
+    };
+    if entries.is_empty() {
+        app.notify("No whitespace problems found");
+        return Ok(());
+    }
+
+    let entry_count = entries.len();
+    app.quickfix = SelectableVec::new(entries);
+    open_current(app)?;
+    app.notify(format!("Found {} whitespace problems", entry_count));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use models::application::{Application, QuickfixEntry};
+    use scribe::buffer::Position;
+    use std::fs;
+    use std::path::PathBuf;
+    use util::SelectableVec;
+    use util::test_helpers::TempDir;
+
+    fn create_files(name: &str) -> TempDir {
+        let root = TempDir::new(name);
+        fs::write(root.join("first.txt"), "amp\neditor\namp\n").unwrap();
+        fs::write(root.join("second.txt"), "buffer\namp\n").unwrap();
+
+        root
+    }
+
+    fn entry(path: PathBuf, line: usize) -> QuickfixEntry {
+        QuickfixEntry { path, line, message: String::from("amp") }
+    }
+
+    fn current_location(app: &mut Application) -> (Option<PathBuf>, Position) {
+        let buffer = app.workspace.current_buffer().unwrap();
+
+        (buffer.path.clone(), *buffer.cursor)
+    }
+
+    #[test]
+    fn next_opens_the_referenced_file_at_the_entry_line() {
+        let root = create_files("quickfix-next");
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.quickfix = SelectableVec::new(vec![
+            entry(root.join("first.txt"), 2),
+            entry(root.join("second.txt"), 1),
+        ]);
+
+        super::next(&mut app).unwrap();
+
+        assert_eq!(
+            current_location(&mut app),
+            (Some(root.join("second.txt")), Position{ line: 1, offset: 0 })
+        );
+    }
+
+    #[test]
+    fn next_and_previous_wrap_around_the_list() {
+        let root = create_files("quickfix-wrap");
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.quickfix = SelectableVec::new(vec![
+            entry(root.join("first.txt"), 0),
+            entry(root.join("first.txt"), 2),
+        ]);
+
+        super::previous(&mut app).unwrap();
+        assert_eq!(app.quickfix.selected_index(), 1);
+        assert_eq!(current_location(&mut app).1, Position{ line: 2, offset: 0 });
+
+        super::next(&mut app).unwrap();
+        assert_eq!(app.quickfix.selected_index(), 0);
+        assert_eq!(current_location(&mut app).1, Position{ line: 0, offset: 0 });
+    }
+
+    #[test]
+    fn next_fails_when_the_list_is_empty() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+
+        assert!(super::next(&mut app).is_err());
+    }
+
+    #[test]
+    fn lint_whitespace_populates_the_list_with_problem_lines() {
+        let root = TempDir::new("quickfix-lint");
+        fs::write(root.join("lint.txt"), "amp\neditor  \n  \tbuffer\n").unwrap();
+        let mut app = Application::new(&Vec::new()).unwrap();
+        ::util::open_buffer(&root.join("lint.txt"), &mut app).unwrap();
+
+        super::lint_whitespace(&mut app).unwrap();
+
+        assert_eq!(
+            app.quickfix.iter().map(|entry| entry.line).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            current_location(&mut app),
+            (Some(root.join("lint.txt")), Position{ line: 1, offset: 0 })
+        );
+    }
+}
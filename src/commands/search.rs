@@ -10,7 +10,7 @@ pub fn move_to_previous_result(app: &mut Application) -> Result {
         bail!("Can't move to search result outside of search mode");
     }
 
-    commands::view::scroll_cursor_to_center(app)
+    scroll_to_result(app)
         .chain_err(|| SCROLL_TO_CURSOR_FAILED)?;
     move_to_current_result(app)
 }
@@ -22,7 +22,7 @@ pub fn move_to_next_result(app: &mut Application) -> Result {
         bail!("Can't move to search result outside of search mode");
     }
 
-    commands::view::scroll_cursor_to_center(app)
+    scroll_to_result(app)
         .chain_err(|| SCROLL_TO_CURSOR_FAILED)?;
     move_to_current_result(app)
 }
@@ -41,12 +41,21 @@ pub fn move_to_current_result(app: &mut Application) -> Result {
         bail!("Can't move to search result outside of search mode");
     }
 
-    commands::view::scroll_cursor_to_center(app)
+    scroll_to_result(app)
         .chain_err(|| SCROLL_TO_CURSOR_FAILED)?;
 
     Ok(())
 }
 
+/// Brings the cursor into view, centering it if configured to do so.
+fn scroll_to_result(app: &mut Application) -> Result {
+    if app.preferences.borrow().center_on_search() {
+        commands::view::scroll_cursor_to_center(app)
+    } else {
+        commands::view::scroll_to_cursor(app)
+    }
+}
+
 pub fn accept_query(app: &mut Application) -> Result {
     if let Mode::Search(ref mut mode) = app.mode {
         // Disable insert sub-mode.
@@ -70,6 +79,17 @@ pub fn clear_query(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Clears the active search query and its highlighted results, if any.
+pub fn clear_search(app: &mut Application) -> Result {
+    app.search_query = None;
+    if let Mode::Search(ref mut mode) = app.mode {
+        mode.input = None;
+        mode.results = None;
+    }
+
+    Ok(())
+}
+
 pub fn push_search_char(app: &mut Application) -> Result {
     let key = app.view.last_key().as_ref().ok_or("View hasn't tracked a key press")?;
 
@@ -137,8 +157,9 @@ mod tests {
     use scribe::Buffer;
     use scribe::buffer::Position;
     use models::Application;
-    use models::application::Mode;
+    use models::application::{Mode, Preferences};
     use commands;
+    use yaml::YamlLoader;
 
     #[test]
     fn move_to_previous_result_moves_cursor_to_previous_result() {
@@ -285,4 +306,118 @@ mod tests {
                        offset: 0,
                    });
     }
+
+    #[test]
+    fn accept_query_centers_distant_matches_when_center_on_search_is_enabled() {
+        let mut app = search_for_distant_match("center_on_search: true");
+
+        // The test terminal's nine-line buffer region has the match
+        // line preceded by four lines when it's vertically centered.
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.cursor.line, 20);
+        assert_eq!(app.view.scroll_offset(buffer).unwrap(), 16);
+    }
+
+    #[test]
+    fn accept_query_scrolls_distant_matches_into_view_when_center_on_search_is_disabled() {
+        let mut app = search_for_distant_match("center_on_search: false");
+
+        // The match line is scrolled to the bottom of the buffer region.
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.cursor.line, 20);
+        assert_eq!(app.view.scroll_offset(buffer).unwrap(), 12);
+    }
+
+    #[test]
+    fn clear_search_removes_query_and_highlights() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor\nedit\nedit");
+        app.workspace.add_buffer(buffer);
+
+        app.search_query = Some(String::from("ed"));
+        commands::application::switch_to_search_mode(&mut app).unwrap();
+        commands::search::accept_query(&mut app).unwrap();
+        commands::search::clear_search(&mut app).unwrap();
+
+        assert_eq!(app.search_query, None);
+        assert!(match app.mode {
+            Mode::Search(ref mode) => mode.highlights().is_none(),
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn clear_search_does_nothing_without_an_active_search() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        commands::search::clear_search(&mut app).unwrap();
+
+        assert_eq!(app.search_query, None);
+    }
+
+    fn search_for_distant_match(preferences: &str) -> Application {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str(preferences).unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+
+        let mut buffer = Buffer::new();
+        buffer.insert(format!("{}amp\n{}", "\n".repeat(20), "\n".repeat(20)));
+        app.workspace.add_buffer(buffer);
+
+        app.search_query = Some(String::from("amp"));
+        commands::application::switch_to_search_mode(&mut app).unwrap();
+        commands::search::accept_query(&mut app).unwrap();
+
+        app
+    }
+
+    #[test]
+    fn accept_query_limits_results_to_the_active_selection() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\namp amp\namp");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        // Select the second line, and search within it.
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+        commands::application::switch_to_search_mode(&mut app).unwrap();
+        if let Mode::Search(ref mut mode) = app.mode {
+            mode.input = Some(String::from("amp"));
+        }
+        commands::search::accept_query(&mut app).unwrap();
+
+        let result_lines: Vec<usize> = match app.mode {
+            Mode::Search(ref mode) => {
+                mode.results.as_ref().unwrap().iter().map(|result| result.start().line).collect()
+            }
+            _ => panic!("Application isn't in search mode"),
+        };
+        assert_eq!(result_lines, vec![1, 1]);
+    }
+
+    #[test]
+    fn switch_to_search_mode_keeps_the_scope_of_an_active_search() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\namp amp\namp");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+        commands::application::switch_to_search_mode(&mut app).unwrap();
+        let scope = match app.mode {
+            Mode::Search(ref mode) => mode.scope.clone(),
+            _ => None,
+        };
+
+        // Start another search from within search mode.
+        commands::application::switch_to_search_mode(&mut app).unwrap();
+
+        assert!(scope.is_some());
+        assert!(match app.mode {
+            Mode::Search(ref mode) => mode.scope == scope,
+            _ => false,
+        });
+    }
 }
@@ -2,9 +2,11 @@ use errors::*;
 use commands::{self, application, Result};
 use input::Key;
 use std::mem;
+use std::path::Path;
 use models::application::modes::open::DisplayablePath;
-use models::application::{Application, Mode};
+use models::application::{quickfix, Application, Mode};
 use models::application::modes::SearchSelectMode;
+use util::{self, SelectableVec};
 
 pub fn accept(app: &mut Application) -> Result {
     // Consume the application mode. This is necessary because the selection in
@@ -16,7 +18,12 @@ pub fn accept(app: &mut Application) -> Result {
         Mode::Command(ref mode) => {
             let selection = mode.selection().ok_or("No command selected")?;
 
-            // Run the selected command.
+            // Run the selected command, tracking it so that it can be repeated.
+            // The repeat command itself isn't tracked; repeating it would
+            // otherwise have it call itself until the stack overflows.
+            if selection.name != "application::repeat_last_palette_command" {
+                app.last_palette_command = Some(selection.name);
+            }
             (selection.command)(app)?;
         },
         Mode::Open(ref mut mode) => {
@@ -24,16 +31,44 @@ pub fn accept(app: &mut Application) -> Result {
                 .selection()
                 .ok_or("Couldn't find a selected path to open")?;
 
-            app.workspace
-                .open_buffer(&path)
-                .chain_err(|| "Couldn't open a buffer for the specified path.")?;
-            app.view.initialize_buffer(app.workspace.current_buffer().unwrap())?;
+            util::open_buffer(&path, app)?;
+
+            // Report whitespace problems without leaving the opened file.
+            if app.preferences.borrow().lint_whitespace_on_open() {
+                let entries = {
+                    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+                    let soft_tabs = app.preferences.borrow().soft_tabs(Some(path));
 
+                    quickfix::lint_whitespace(path.clone(), &buffer.data(), soft_tabs)
+                };
+                if !entries.is_empty() {
+                    app.notify(format!("Found {} whitespace problems", entries.len()));
+                    app.quickfix = SelectableVec::new(entries);
+                }
+            }
         },
         Mode::Theme(ref mut mode) => {
             let theme_key = mode.selection().ok_or("No theme selected")?;
             app.preferences.borrow_mut().set_theme(theme_key.as_str());
         },
+        Mode::DiffBuffers(ref mut mode) => {
+            let path = mode.selection().ok_or("No buffer selected")?;
+            let diff = util::diff::current_buffer_against(&mut app.workspace, Path::new(path))?;
+
+            util::diff::open(&diff, app)?;
+        },
+        Mode::Syntax(ref mut mode) => {
+            let name = mode.selection().ok_or("No syntax selected")?;
+            let syntax_definition = app.workspace.syntax_set
+                .find_syntax_by_name(name)
+                .cloned()
+                .ok_or("Couldn't find the selected syntax")?;
+            let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+            buffer.syntax_definition = Some(syntax_definition);
+
+            // Highlighting is cached, so it needs to be redone from scratch.
+            app.view.invalidate_highlighting(buffer)?;
+        },
         Mode::SymbolJump(ref mut mode) => {
             let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
             let position = mode
@@ -45,6 +80,16 @@ pub fn accept(app: &mut Application) -> Result {
                 bail!("Couldn't move to the selected symbol's position");
             }
         },
+        Mode::Quickfix(ref mut mode) => {
+            let entry = mode.selection().ok_or("Couldn't find a selected quickfix entry")?;
+            let index = app.quickfix
+                .iter()
+                .position(|quickfix_entry| quickfix_entry == entry)
+                .ok_or("Couldn't find the selected entry in the quickfix list")?;
+            app.quickfix.select_index(index);
+
+            commands::quickfix::open_current(app)?;
+        },
         _ => bail!("Can't accept selection outside of search select mode."),
     }
 
@@ -58,7 +103,10 @@ pub fn search(app: &mut Application) -> Result {
         Mode::Command(ref mut mode) => mode.search(),
         Mode::Open(ref mut mode) => mode.search(),
         Mode::Theme(ref mut mode) => mode.search(),
+        Mode::DiffBuffers(ref mut mode) => mode.search(),
+        Mode::Syntax(ref mut mode) => mode.search(),
         Mode::SymbolJump(ref mut mode) => mode.search(),
+        Mode::Quickfix(ref mut mode) => mode.search(),
         _ => bail!("Can't search outside of search select mode."),
     };
 
@@ -70,7 +118,10 @@ pub fn select_next(app: &mut Application) -> Result {
         Mode::Command(ref mut mode) => mode.select_next(),
         Mode::Open(ref mut mode) => mode.select_next(),
         Mode::Theme(ref mut mode) => mode.select_next(),
+        Mode::DiffBuffers(ref mut mode) => mode.select_next(),
+        Mode::Syntax(ref mut mode) => mode.select_next(),
         Mode::SymbolJump(ref mut mode) => mode.select_next(),
+        Mode::Quickfix(ref mut mode) => mode.select_next(),
         _ => bail!("Can't change selection outside of search select mode."),
     }
 
@@ -82,7 +133,10 @@ pub fn select_previous(app: &mut Application) -> Result {
         Mode::Command(ref mut mode) => mode.select_previous(),
         Mode::Open(ref mut mode) => mode.select_previous(),
         Mode::Theme(ref mut mode) => mode.select_previous(),
+        Mode::DiffBuffers(ref mut mode) => mode.select_previous(),
+        Mode::Syntax(ref mut mode) => mode.select_previous(),
         Mode::SymbolJump(ref mut mode) => mode.select_previous(),
+        Mode::Quickfix(ref mut mode) => mode.select_previous(),
         _ => bail!("Can't change selection outside of search select mode."),
     }
 
@@ -94,7 +148,10 @@ pub fn enable_insert(app: &mut Application) -> Result {
         Mode::Command(ref mut mode) => mode.set_insert_mode(true),
         Mode::Open(ref mut mode) => mode.set_insert_mode(true),
         Mode::Theme(ref mut mode) => mode.set_insert_mode(true),
+        Mode::DiffBuffers(ref mut mode) => mode.set_insert_mode(true),
+        Mode::Syntax(ref mut mode) => mode.set_insert_mode(true),
         Mode::SymbolJump(ref mut mode) => mode.set_insert_mode(true),
+        Mode::Quickfix(ref mut mode) => mode.set_insert_mode(true),
         _ => bail!("Can't change search insert state outside of search select mode"),
     }
 
@@ -106,7 +163,10 @@ pub fn disable_insert(app: &mut Application) -> Result {
         Mode::Command(ref mut mode) => mode.set_insert_mode(false),
         Mode::Open(ref mut mode) => mode.set_insert_mode(false),
         Mode::Theme(ref mut mode) => mode.set_insert_mode(false),
+        Mode::DiffBuffers(ref mut mode) => mode.set_insert_mode(false),
+        Mode::Syntax(ref mut mode) => mode.set_insert_mode(false),
         Mode::SymbolJump(ref mut mode) => mode.set_insert_mode(false),
+        Mode::Quickfix(ref mut mode) => mode.set_insert_mode(false),
         _ => bail!("Can't change search insert state outside of search select mode"),
     }
 
@@ -119,7 +179,10 @@ pub fn push_search_char(app: &mut Application) -> Result {
             Mode::Command(ref mut mode) => mode.push_search_char(c),
             Mode::Open(ref mut mode) => mode.push_search_char(c),
             Mode::Theme(ref mut mode) => mode.push_search_char(c),
+            Mode::DiffBuffers(ref mut mode) => mode.push_search_char(c),
+            Mode::Syntax(ref mut mode) => mode.push_search_char(c),
             Mode::SymbolJump(ref mut mode) => mode.push_search_char(c),
+            Mode::Quickfix(ref mut mode) => mode.push_search_char(c),
             _ => bail!("Can't push search character outside of search select mode"),
         }
     }
@@ -133,7 +196,10 @@ pub fn pop_search_token(app: &mut Application) -> Result {
         Mode::Command(ref mut mode) => mode.pop_search_token(),
         Mode::Open(ref mut mode) => mode.pop_search_token(),
         Mode::Theme(ref mut mode) => mode.pop_search_token(),
+        Mode::DiffBuffers(ref mut mode) => mode.pop_search_token(),
+        Mode::Syntax(ref mut mode) => mode.pop_search_token(),
         Mode::SymbolJump(ref mut mode) => mode.pop_search_token(),
+        Mode::Quickfix(ref mut mode) => mode.pop_search_token(),
         _ => bail!("Can't pop search token outside of search select mode"),
     }
 
@@ -146,7 +212,10 @@ pub fn step_back(app: &mut Application) -> Result {
         Mode::Command(ref mut mode) => mode.results().count(),
         Mode::Open(ref mut mode) => mode.results().count(),
         Mode::Theme(ref mut mode) => mode.results().count(),
+        Mode::DiffBuffers(ref mut mode) => mode.results().count(),
+        Mode::Syntax(ref mut mode) => mode.results().count(),
         Mode::SymbolJump(ref mut mode) => mode.results().count(),
+        Mode::Quickfix(ref mut mode) => mode.results().count(),
         _ => bail!("Can't pop search token outside of search select mode"),
     };
 
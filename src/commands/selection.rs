@@ -1,18 +1,40 @@
 use models::application::{Application, ClipboardContent, Mode};
-use scribe::buffer::{LineRange, Range};
+use models::application::modes::{PathMode, SelectMode};
+use scribe::Buffer;
+use scribe::buffer::{Distance, LineRange, Position, Range};
+use std::cmp;
+use unicode_segmentation::UnicodeSegmentation;
 use super::application;
 use errors::*;
 use commands::{self, Result};
 use util;
+use util::comment::BlockComment;
+use util::{fold, movement_lexer};
 
 pub fn delete(app: &mut Application) -> Result {
     if let Some(buffer) = app.workspace.current_buffer() {
         match app.mode {
             Mode::Select(ref select_mode) => {
                 let cursor_position = *buffer.cursor.clone();
-                let delete_range = Range::new(cursor_position, select_mode.anchor);
-                buffer.delete_range(delete_range.clone());
-                buffer.cursor.move_to(delete_range.start());
+                let mut delete_ranges = app.selections.clone();
+                delete_ranges.push(Range::new(cursor_position, select_mode.anchor));
+
+                // Delete the ranges in reverse order, so that
+                // the earlier ranges' positions remain valid.
+                delete_ranges.sort_by_key(|range| {
+                    let start = range.start();
+                    cmp::Reverse((start.line, start.offset))
+                });
+                buffer.start_operation_group();
+                for delete_range in delete_ranges.iter() {
+                    buffer.delete_range(delete_range.clone());
+                }
+                util::history::end_operation_group(buffer, app.change_group);
+
+                if let Some(first_range) = delete_ranges.last() {
+                    buffer.cursor.move_to(first_range.start());
+                }
+                app.selections.clear();
             }
             Mode::SelectLine(ref mode) => {
                 let delete_range = mode.to_range(&*buffer.cursor);
@@ -68,17 +90,389 @@ pub fn select_all(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Selects the paragraph containing the cursor or, on a blank
+/// line, the run of consecutive blank lines surrounding it.
+pub fn select_paragraph(app: &mut Application) -> Result {
+    let (start, end) = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let data = buffer.data();
+        let lines: Vec<&str> = data.split('\n').collect();
+        let (start, end) = util::line_run(&lines, buffer.cursor.line)
+            .ok_or("No paragraph found at the cursor")?;
+
+        (
+            Position{ line: start, offset: 0 },
+            Position{ line: end, offset: lines[end].graphemes(true).count() }
+        )
+    };
+
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_to(start);
+    application::switch_to_select_mode(app)?;
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_to(end);
+
+    Ok(())
+}
+
+/// Selects the lines of the innermost multi-line brace/bracket region
+/// enclosing the cursor (e.g. a function), from its opening line
+/// through its closing one.
+pub fn select_scope(app: &mut Application) -> Result {
+    let (start, end) = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let data = buffer.data();
+        let tokens = movement_lexer::lex(&data);
+        let region = fold::brace_region(&tokens, &*buffer.cursor)
+            .ok_or("No enclosing scope found at the cursor")?;
+        let end_line = data.split('\n').nth(region.end()).ok_or("Couldn't find the end of the scope")?;
+
+        (
+            Position{ line: region.start(), offset: 0 },
+            Position{ line: region.end(), offset: end_line.graphemes(true).count() }
+        )
+    };
+
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_to(start);
+    application::switch_to_select_mode(app)?;
+    app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_to(end);
+
+    Ok(())
+}
+
+/// Adds the next occurrence of the selected text as an additional selection,
+/// moving the current selection to it. Subsequent deletions
+/// apply to all of the selections.
+pub fn add_next_match(app: &mut Application) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let select_mode = match app.mode {
+        Mode::Select(ref mut select_mode) => select_mode,
+        _ => bail!("Can't add selections outside of select mode"),
+    };
+    let current_range = Range::new(*buffer.cursor.clone(), select_mode.anchor);
+    let content = buffer.read(&current_range).ok_or("Couldn't read selected data from buffer")?;
+    if content.is_empty() {
+        bail!("Can't add matches for an empty selection");
+    }
+
+    // Look for the next match after the current selection, wrapping to the
+    // start of the buffer, and skipping any that overlap existing selections.
+    let distance = Distance::of_str(&content);
+    let matches: Vec<Range> = buffer.search(&content)
+        .into_iter()
+        .map(|start| Range::new(start, start + distance))
+        .collect();
+    let next_match = {
+        let selections = &app.selections;
+        let (following, preceding): (Vec<&Range>, Vec<&Range>) = matches
+            .iter()
+            .partition(|range| range.start() >= current_range.end());
+        following
+            .into_iter()
+            .chain(preceding.into_iter())
+            .find(|range| {
+                !selections.iter().chain(Some(&current_range)).any(|selection| {
+                    range.start() < selection.end() && selection.start() < range.end()
+                })
+            })
+            .cloned()
+            .ok_or("No further matches found")?
+    };
+
+    app.selections.push(current_range);
+    select_mode.anchor = next_match.start();
+    buffer.cursor.move_to(next_match.end());
+
+    Ok(())
+}
+
+/// Splits the current selection into one selection per line it spans,
+/// each covering the extent of its line within the original selection.
+pub fn split_into_lines(app: &mut Application) -> Result {
+    let selected_range = selected_range(app)?;
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let data = buffer.data();
+    let lines: Vec<&str> = data.lines().collect();
+
+    let start = selected_range.start();
+    let end = selected_range.end();
+    let mut line_ranges: Vec<Range> = (start.line..end.line + 1)
+        .filter_map(|line| {
+            let start_offset = if line == start.line { start.offset } else { 0 };
+            let end_offset = if line == end.line {
+                end.offset
+            } else {
+                lines.get(line).map(|content| content.graphemes(true).count()).unwrap_or(0)
+            };
+
+            // Skip lines with nothing selected (e.g. a trailing
+            // line that's only included up to its first column).
+            if end_offset > start_offset {
+                Some(Range::new(
+                    Position{ line, offset: start_offset },
+                    Position{ line, offset: end_offset }
+                ))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // The last line becomes the current selection; the rest are additional.
+    let last_range = line_ranges.pop().ok_or("Selection doesn't contain any text")?;
+    app.selections.extend(line_ranges);
+    app.mode = Mode::Select(SelectMode::new(last_range.start()));
+    buffer.cursor.move_to(last_range.end());
+
+    Ok(())
+}
+
+/// Marks the current selection for an exchange, or, if one has already been
+/// marked, swaps its contents with those of the current selection.
+pub fn exchange(app: &mut Application) -> Result {
+    let selected_range = selected_range(app)?;
+    let buffer_id = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.id.ok_or("Buffer has no ID")?;
+
+    match app.exchange_mark.take() {
+        Some((marked_buffer_id, ref marked_range)) if marked_buffer_id == buffer_id => {
+            exchange_ranges(app, marked_range, &selected_range)?;
+        }
+        _ => app.exchange_mark = Some((buffer_id, selected_range)),
+    }
+
+    application::switch_to_normal_mode(app)
+}
+
+/// Swaps the cursor and anchor (like Vim's `o` in visual mode), so that
+/// the selection can be extended from its other end.
+pub fn swap_selection_ends(app: &mut Application) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+
+    if let Mode::Select(ref mut mode) = app.mode {
+        let cursor = *buffer.cursor;
+        if !buffer.cursor.move_to(mode.anchor) {
+            bail!("Couldn't move the cursor to the selection's anchor");
+        }
+        mode.anchor = cursor;
+    } else {
+        bail!("Can't swap selection ends outside of select mode");
+    }
+
+    Ok(())
+}
+
+/// Restores the most recent selection in the current buffer (like Vim's
+/// `gv`), clamping it to the buffer's bounds if its content has changed.
+pub fn reselect_last(app: &mut Application) -> Result {
+    let (buffer_id, anchor, cursor) = app.last_selection.ok_or("No previous selection to restore")?;
+    let anchor = {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        if buffer.id != Some(buffer_id) {
+            bail!("The previous selection was made in another buffer");
+        }
+
+        let data = buffer.data();
+        buffer.cursor.move_to(clamp_position(&data, cursor));
+
+        clamp_position(&data, anchor)
+    };
+
+    application::switch_to_select_mode(app)?;
+    if let Mode::Select(ref mut mode) = app.mode {
+        mode.anchor = anchor;
+    }
+
+    Ok(())
+}
+
+/// Replaces the selected text with its base64-encoded equivalent.
+pub fn encode_base64(app: &mut Application) -> Result {
+    transform(app, |content| Ok(util::codec::base64_encode(content)))
+}
+
+/// Replaces the selected base64 content with its decoded equivalent.
+pub fn decode_base64(app: &mut Application) -> Result {
+    transform(app, util::codec::base64_decode)
+}
+
+/// Replaces the selected text with its percent-encoded (URL-safe) equivalent.
+pub fn url_encode(app: &mut Application) -> Result {
+    transform(app, |content| Ok(util::codec::url_encode(content)))
+}
+
+/// Replaces the selected percent-encoded content with its decoded equivalent.
+pub fn url_decode(app: &mut Application) -> Result {
+    transform(app, util::codec::url_decode)
+}
+
+/// Replaces the selected arithmetic expression (e.g. 2 + 3 * 4) with its result.
+pub fn eval_expression(app: &mut Application) -> Result {
+    transform(app, util::expression::evaluate)
+}
+
+/// Wraps the selection in a block comment using the delimiters for the
+/// buffer's file type or, if it's already enclosed in one, removes it.
+pub fn toggle_block_comment(app: &mut Application) -> Result {
+    let range = selected_range(app)?;
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let comment = buffer.path
+            .as_ref()
+            .and_then(|path| util::comment::block_comment_for(path))
+            .ok_or("No block comment syntax known for this file type")?;
+        let range = enclosing_comment_range(buffer, &range, comment).unwrap_or(range);
+        let content = buffer.read(&range).ok_or("Couldn't read selected data from buffer")?;
+
+        buffer.start_operation_group();
+        buffer.delete_range(range.clone());
+        buffer.cursor.move_to(range.start());
+        buffer.insert(util::comment::toggle(&content, comment));
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+
+    application::switch_to_normal_mode(app)
+}
+
+/// Extends the range to include block comment delimiters immediately
+/// surrounding it (e.g. when only a comment's content is selected).
+fn enclosing_comment_range(buffer: &Buffer, range: &Range, comment: BlockComment) -> Option<Range> {
+    let data = buffer.data();
+    let lines: Vec<&str> = data.split('\n').collect();
+    let start_line: Vec<&str> = lines.get(range.start().line)?.graphemes(true).collect();
+    let end_line: Vec<&str> = lines.get(range.end().line)?.graphemes(true).collect();
+    let before = start_line.get(..range.start().offset)?.concat();
+    let after = end_line.get(range.end().offset..)?.concat();
+
+    let before = before.trim_right();
+    let after_start = after.len() - after.trim_left().len();
+    if !before.ends_with(comment.start) || !after[after_start..].starts_with(comment.end) {
+        return None;
+    }
+
+    Some(Range::new(
+        Position {
+            line: range.start().line,
+            offset: before.graphemes(true).count() - comment.start.graphemes(true).count(),
+        },
+        Position {
+            line: range.end().line,
+            offset: range.end().offset +
+                after[..after_start].graphemes(true).count() +
+                comment.end.graphemes(true).count(),
+        }
+    ))
+}
+
+/// Prompts for a path (defaulting to the current buffer's directory)
+/// and moves the selection to a new file there, once accepted.
+pub fn extract_to_file(app: &mut Application) -> Result {
+    let range = selected_range(app)?;
+    let buffer_directory = app.workspace
+        .current_buffer()
+        .ok_or(BUFFER_MISSING)?
+        .path.as_ref()
+        .and_then(|path| path.parent())
+        .and_then(|parent| if parent.as_os_str().is_empty() { None } else { Some(parent.to_path_buf()) });
+    let directory = buffer_directory.unwrap_or_else(|| app.workspace.path.clone());
+
+    let mut mode = PathMode::new(format!("{}/", directory.to_string_lossy()));
+    mode.extract_range = Some(range);
+    app.mode = Mode::Path(mode);
+
+    Ok(())
+}
+
+/// Replaces the selected text with the result of the transformation, as a
+/// single operation. The buffer is left untouched if the transformation fails.
+fn transform<F>(app: &mut Application, transformation: F) -> Result
+    where F: Fn(&str) -> ::errors::Result<String> {
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let range = match app.mode {
+            Mode::Select(ref mode) => Range::new(*buffer.cursor, mode.anchor),
+            _ => bail!("Can't transform text outside of select mode"),
+        };
+        let content = buffer.read(&range).ok_or("Couldn't read selected data from buffer")?;
+        let transformed_content = transformation(&content)?;
+
+        buffer.start_operation_group();
+        buffer.delete_range(range.clone());
+        buffer.cursor.move_to(range.start());
+        buffer.insert(transformed_content);
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+
+    application::switch_to_normal_mode(app)
+}
+
+/// Moves the position onto the nearest line and offset within the data.
+fn clamp_position(data: &str, position: Position) -> Position {
+    let lines: Vec<&str> = data.split('\n').collect();
+    let line = cmp::min(position.line, lines.len() - 1);
+    let line_length = lines[line].graphemes(true).count();
+
+    Position{ line, offset: cmp::min(position.offset, line_length) }
+}
+
+fn exchange_ranges(app: &mut Application, first: &Range, second: &Range) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let (earlier, later) = if first.start() < second.start() {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    if earlier.end() > later.start() {
+        bail!("Can't exchange overlapping selections");
+    }
+
+    let earlier_data = buffer.read(earlier).ok_or("Couldn't read selected data from buffer")?;
+    let later_data = buffer.read(later).ok_or("Couldn't read selected data from buffer")?;
+
+    // Replace the later range first, so that
+    // the earlier range's positions remain valid.
+    buffer.start_operation_group();
+    buffer.delete_range(later.clone());
+    buffer.cursor.move_to(later.start());
+    buffer.insert(earlier_data);
+    buffer.delete_range(earlier.clone());
+    buffer.cursor.move_to(earlier.start());
+    buffer.insert(later_data);
+    util::history::end_operation_group(buffer, app.change_group);
+
+    Ok(())
+}
+
+fn selected_range(app: &mut Application) -> ::errors::Result<Range> {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+
+    match app.mode {
+        Mode::Select(ref select_mode) => {
+            Ok(Range::new(*buffer.cursor.clone(), select_mode.anchor))
+        }
+        Mode::SelectLine(ref mode) => Ok(mode.to_range(&*buffer.cursor)),
+        _ => bail!("Can't use selections outside of select mode"),
+    }
+}
+
 fn copy_to_clipboard(app: &mut Application) -> Result {
     let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
 
     match app.mode {
         Mode::Select(ref select_mode) => {
-            let cursor_position = *buffer.cursor.clone();
-            let selected_range = Range::new(cursor_position, select_mode.anchor);
+            // Copy every selection, in the order they appear in the buffer,
+            // placing each on its own line.
+            let mut selected_ranges = app.selections.clone();
+            selected_ranges.push(Range::new(*buffer.cursor.clone(), select_mode.anchor));
+            selected_ranges.sort_by_key(|range| {
+                let start = range.start();
+                (start.line, start.offset)
+            });
 
-            let data = buffer.read(&selected_range.clone())
-                .ok_or("Couldn't read selected data from buffer")?;
-            app.clipboard.set_content(ClipboardContent::Inline(data))?;
+            let mut selected_data = Vec::new();
+            for selected_range in selected_ranges.iter() {
+                selected_data.push(
+                    buffer.read(selected_range).ok_or("Couldn't read selected data from buffer")?
+                );
+            }
+            app.clipboard.set_content(ClipboardContent::Inline(selected_data.join("\n")))?;
         }
         Mode::SelectLine(ref mode) => {
             let selected_range = util::inclusive_range(
@@ -103,9 +497,13 @@ fn copy_to_clipboard(app: &mut Application) -> Result {
 #[cfg(test)]
 mod tests {
     use commands;
-    use models::application::{Application, Mode};
+    use models::application::{Application, ClipboardContent, Mode};
     use scribe::Buffer;
-    use scribe::buffer::Position;
+    use scribe::buffer::{Position, Range};
+    use std::fs::File;
+    use std::io::Read;
+    use std::path::PathBuf;
+    use util::test_helpers::TempDir;
 
     #[test]
     fn select_all_selects_the_entire_buffer() {
@@ -219,4 +617,541 @@ mod tests {
             String::from("amp\nitor\nbuffer")
         )
     }
+
+    #[test]
+    fn exchange_swaps_the_marked_and_current_selections() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a b");
+        app.workspace.add_buffer(buffer);
+
+        // Mark "a".
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_right(&mut app).unwrap();
+        super::exchange(&mut app).unwrap();
+
+        // Select "b" and exchange it with "a".
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 2 });
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_right(&mut app).unwrap();
+        super::exchange(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "b a");
+        assert!(app.exchange_mark.is_none());
+        if let Mode::Normal = app.mode {
+        } else {
+            panic!("Not in normal mode");
+        }
+    }
+
+    #[test]
+    fn exchange_swaps_selections_of_unequal_length() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp(first, second_argument)");
+        buffer.cursor.move_to(Position{ line: 0, offset: 4 });
+        app.workspace.add_buffer(buffer);
+
+        // Mark "first".
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 9 });
+        super::exchange(&mut app).unwrap();
+
+        // Select "second_argument" and exchange it with "first".
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 11 });
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 26 });
+        super::exchange(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "amp(second_argument, first)"
+        );
+    }
+
+    #[test]
+    fn exchange_can_be_undone_as_a_single_operation() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("a b");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_right(&mut app).unwrap();
+        super::exchange(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 2 });
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_right(&mut app).unwrap();
+        super::exchange(&mut app).unwrap();
+        commands::buffer::undo(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "a b");
+    }
+
+    #[test]
+    fn add_next_match_adds_the_current_selection_and_selects_the_next_match() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor amp editor");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 3 });
+        super::add_next_match(&mut app).unwrap();
+
+        assert_eq!(
+            app.selections,
+            vec![Range::new(Position{ line: 0, offset: 0 }, Position{ line: 0, offset: 3 })]
+        );
+        assert_eq!(
+            *app.workspace.current_buffer().unwrap().cursor,
+            Position{ line: 0, offset: 14 }
+        );
+        match app.mode {
+            Mode::Select(ref mode) => assert_eq!(mode.anchor, Position{ line: 0, offset: 11 }),
+            _ => panic!("Not in select mode"),
+        }
+    }
+
+    #[test]
+    fn add_next_match_fails_when_there_are_no_further_matches() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor amp");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 3 });
+        super::add_next_match(&mut app).unwrap();
+
+        assert!(super::add_next_match(&mut app).is_err());
+    }
+
+    #[test]
+    fn copy_copies_all_selections() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor amp editor amp");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 3 });
+        super::add_next_match(&mut app).unwrap();
+        super::copy(&mut app).unwrap();
+
+        match *app.clipboard.get_content() {
+            ClipboardContent::Inline(ref content) => assert_eq!(content, "amp\namp"),
+            _ => panic!("Clipboard doesn't contain the selections"),
+        }
+        assert!(app.selections.is_empty());
+    }
+
+    #[test]
+    fn change_removes_all_selections_and_clears_them() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor amp editor amp");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 3 });
+        super::add_next_match(&mut app).unwrap();
+        super::change(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), " editor  editor amp");
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 0, offset: 0 });
+        assert!(app.selections.is_empty());
+    }
+
+    #[test]
+    fn delete_removes_all_selections_as_a_single_operation() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor amp editor amp");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 3 });
+        super::add_next_match(&mut app).unwrap();
+        super::add_next_match(&mut app).unwrap();
+        super::delete(&mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), " editor  editor ");
+        assert!(app.selections.is_empty());
+
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp editor amp editor amp");
+    }
+
+    #[test]
+    fn split_into_lines_creates_a_selection_per_line() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\nbuffer\nsplit\nlines");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 3, offset: 5 });
+        super::split_into_lines(&mut app).unwrap();
+
+        assert_eq!(
+            app.selections,
+            vec![
+                Range::new(Position{ line: 1, offset: 0 }, Position{ line: 1, offset: 6 }),
+                Range::new(Position{ line: 2, offset: 0 }, Position{ line: 2, offset: 6 }),
+            ]
+        );
+        match app.mode {
+            Mode::Select(ref mode) => assert_eq!(mode.anchor, Position{ line: 3, offset: 0 }),
+            _ => panic!("Not in select mode"),
+        }
+        assert_eq!(
+            *app.workspace.current_buffer().unwrap().cursor,
+            Position{ line: 3, offset: 5 }
+        );
+    }
+
+    #[test]
+    fn split_into_lines_converts_line_selections() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\nbuffer\nsplit\nlines");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 3, offset: 0 });
+        super::split_into_lines(&mut app).unwrap();
+
+        assert_eq!(app.selections.len(), 2);
+        match app.mode {
+            Mode::Select(ref mode) => assert_eq!(mode.anchor, Position{ line: 3, offset: 0 }),
+            _ => panic!("Not in select mode"),
+        }
+        assert_eq!(
+            *app.workspace.current_buffer().unwrap().cursor,
+            Position{ line: 3, offset: 5 }
+        );
+    }
+
+    #[test]
+    fn reselect_last_restores_the_previous_selection() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_down(&mut app).unwrap();
+        commands::cursor::move_right(&mut app).unwrap();
+        commands::application::switch_to_normal_mode(&mut app).unwrap();
+        commands::cursor::move_to_first_line(&mut app).unwrap();
+        super::reselect_last(&mut app).unwrap();
+
+        match app.mode {
+            Mode::Select(ref mode) => assert_eq!(mode.anchor, Position{ line: 0, offset: 0 }),
+            _ => panic!("Application isn't in select mode.")
+        }
+        assert_eq!(
+            *app.workspace.current_buffer().unwrap().cursor,
+            Position{ line: 1, offset: 1 }
+        );
+    }
+
+    #[test]
+    fn reselect_last_clamps_the_selection_to_the_buffer() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_down(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+        commands::application::switch_to_normal_mode(&mut app).unwrap();
+        commands::cursor::move_to_first_line(&mut app).unwrap();
+        commands::buffer::delete_current_line(&mut app).unwrap();
+        super::reselect_last(&mut app).unwrap();
+
+        // The selection ended on the second line, which no longer exists.
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "editor");
+        assert_eq!(
+            *app.workspace.current_buffer().unwrap().cursor,
+            Position{ line: 0, offset: 6 }
+        );
+    }
+
+    #[test]
+    fn swap_selection_ends_moves_the_cursor_to_the_anchor() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor");
+        buffer.cursor.move_to(Position{ line: 0, offset: 2 });
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 6 });
+        let range = super::selected_range(&mut app).unwrap();
+        super::swap_selection_ends(&mut app).unwrap();
+
+        assert_eq!(
+            *app.workspace.current_buffer().unwrap().cursor,
+            Position{ line: 0, offset: 2 }
+        );
+        match app.mode {
+            Mode::Select(ref mode) => assert_eq!(mode.anchor, Position{ line: 0, offset: 6 }),
+            _ => panic!("Application isn't in select mode.")
+        }
+        assert_eq!(super::selected_range(&mut app).unwrap(), range);
+    }
+
+    #[test]
+    fn encode_base64_and_decode_base64_round_trip_the_selection() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("token: amp editor\n");
+        buffer.cursor.move_to(Position{ line: 0, offset: 7 });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+
+        commands::selection::encode_base64(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "token: YW1wIGVkaXRvcg==\n"
+        );
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+        commands::selection::decode_base64(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "token: amp editor\n"
+        );
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn url_encode_and_url_decode_round_trip_the_selection() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("q=a b&c");
+        buffer.cursor.move_to(Position{ line: 0, offset: 2 });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+
+        commands::selection::url_encode(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "q=a%20b%26c");
+
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+        commands::selection::url_decode(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "q=a b&c");
+    }
+
+    #[test]
+    fn eval_expression_replaces_the_selection_with_its_result() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("total = 2 + 3 * 4");
+        buffer.cursor.move_to(Position{ line: 0, offset: 8 });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+
+        commands::selection::eval_expression(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "total = 14");
+
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "total = 2 + 3 * 4");
+    }
+
+    #[test]
+    fn eval_expression_leaves_the_buffer_unchanged_when_dividing_by_zero() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("(1+2)/0");
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+
+        assert!(commands::selection::eval_expression(&mut app).is_err());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "(1+2)/0");
+        assert!(match app.mode {
+            Mode::Select(_) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn decode_base64_leaves_the_buffer_unchanged_for_invalid_input() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("not base64!");
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        commands::cursor::move_to_end_of_line(&mut app).unwrap();
+
+        assert!(commands::selection::decode_base64(&mut app).is_err());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "not base64!");
+        assert!(match app.mode {
+            Mode::Select(_) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn extract_to_file_moves_the_selection_to_a_new_file() {
+        let dir = TempDir::new("extract-to-file");
+        let path = dir.join("nested").join("b.rs");
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("fn a() {}\nfn b() {}\nfn c() {}");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_line_mode(&mut app).unwrap();
+
+        commands::selection::extract_to_file(&mut app).unwrap();
+        if let Mode::Path(ref mut mode) = app.mode {
+            mode.input = path.to_string_lossy().into_owned();
+        }
+        commands::path::accept_path(&mut app).unwrap();
+
+        let mut content = String::new();
+        File::open(&path).unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "fn b() {}\n");
+
+        // The new file is opened, and the selection removed from the original.
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "fn b() {}\n");
+        app.workspace.previous_buffer();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "fn a() {}\nfn c() {}");
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().data(),
+            "fn a() {}\nfn b() {}\nfn c() {}"
+        );
+    }
+
+    fn select_range(app: &mut Application, data: &str, path: &str, start: usize, end: usize) {
+        let mut buffer = Buffer::new();
+        buffer.insert(data);
+        buffer.path = Some(PathBuf::from(path));
+        buffer.cursor.move_to(Position{ line: 0, offset: start });
+        app.workspace.add_buffer(buffer);
+        commands::application::switch_to_select_mode(app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: end });
+    }
+
+    #[test]
+    fn toggle_block_comment_wraps_and_unwraps_rust_selections() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        select_range(&mut app, "let a = 1 + 2;", "main.rs", 8, 13);
+
+        commands::selection::toggle_block_comment(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "let a = /* 1 + 2 */;");
+
+        // Selecting the wrapped content removes the enclosing comment.
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 11 });
+        commands::application::switch_to_select_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 16 });
+        commands::selection::toggle_block_comment(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "let a = 1 + 2;");
+
+        // Each toggle is undone as a single change.
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "let a = /* 1 + 2 */;");
+    }
+
+    #[test]
+    fn toggle_block_comment_uses_html_comments_for_html_files() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        select_range(&mut app, "<p>amp</p>", "index.html", 0, 10);
+
+        commands::selection::toggle_block_comment(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "<!-- <p>amp</p> -->");
+    }
+
+    #[test]
+    fn toggle_block_comment_rejects_unknown_file_types() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        select_range(&mut app, "echo amp", "script.sh", 0, 8);
+
+        assert!(commands::selection::toggle_block_comment(&mut app).is_err());
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "echo amp");
+    }
+
+    fn selected_text(app: &mut Application) -> String {
+        let buffer = app.workspace.current_buffer().unwrap();
+        let range = match app.mode {
+            Mode::Select(ref mode) => Range::new(*buffer.cursor, mode.anchor),
+            _ => panic!("Not in select mode"),
+        };
+
+        buffer.read(&range).unwrap()
+    }
+
+    #[test]
+    fn select_paragraph_selects_the_paragraph_containing_the_cursor() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("first line\nsecond line\n\nthird line\nfourth line");
+        buffer.cursor.move_to(Position{ line: 4, offset: 2 });
+        app.workspace.add_buffer(buffer);
+
+        commands::selection::select_paragraph(&mut app).unwrap();
+        assert_eq!(selected_text(&mut app), "third line\nfourth line");
+
+        commands::application::switch_to_normal_mode(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 3 });
+        commands::selection::select_paragraph(&mut app).unwrap();
+        assert_eq!(selected_text(&mut app), "first line\nsecond line");
+    }
+
+    #[test]
+    fn select_paragraph_selects_consecutive_blank_lines_on_a_blank_line() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n\n\neditor");
+        buffer.cursor.move_to(Position{ line: 2, offset: 0 });
+        app.workspace.add_buffer(buffer);
+
+        commands::selection::select_paragraph(&mut app).unwrap();
+        assert_eq!(selected_text(&mut app), "\n");
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor, Position{ line: 2, offset: 0 });
+    }
+
+    #[test]
+    fn select_scope_selects_the_enclosing_braced_block() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("use amp;\n\nfn main() {\n    let a = (1, 2);\n    run(a);\n}\n");
+        buffer.cursor.move_to(Position{ line: 4, offset: 6 });
+        app.workspace.add_buffer(buffer);
+
+        commands::selection::select_scope(&mut app).unwrap();
+        assert_eq!(
+            selected_text(&mut app),
+            "fn main() {\n    let a = (1, 2);\n    run(a);\n}"
+        );
+    }
+
+    #[test]
+    fn select_scope_fails_outside_of_a_scope() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("use amp;\n\nfn main() {\n}\n");
+        app.workspace.add_buffer(buffer);
+
+        assert!(commands::selection::select_scope(&mut app).is_err());
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
 }
@@ -0,0 +1,123 @@
+use errors::*;
+use commands::Result;
+use input::Key;
+use models::application::{Application, Mode};
+use std::time::Duration;
+use util::{self, shell};
+
+/// How long commands can run before they're killed, so that
+/// one that never finishes doesn't leave the editor unresponsive.
+const COMMAND_TIMEOUT_SECS: u64 = 10;
+
+pub fn push_char(app: &mut Application) -> Result {
+    let last_key = app.view.last_key().as_ref().ok_or("View hasn't tracked a key press")?;
+    if let Key::Char(c) = *last_key {
+        if let Mode::ShellCommand(ref mut mode) = app.mode {
+            mode.push_char(c);
+        } else {
+            bail!("Cannot push char outside of shell command mode");
+        }
+    } else {
+        bail!("Last key press wasn't a character");
+    }
+    Ok(())
+}
+
+pub fn pop_char(app: &mut Application) -> Result {
+    if let Mode::ShellCommand(ref mut mode) = app.mode {
+        mode.pop_char();
+    } else {
+        bail!("Cannot pop char outside of shell command mode");
+    }
+    Ok(())
+}
+
+/// Runs the entered command, inserting its output at the cursor. Commands
+/// that fail leave the buffer untouched, so that they can be corrected.
+pub fn accept(app: &mut Application) -> Result {
+    let command = match app.mode {
+        Mode::ShellCommand(ref mode) => mode.input.clone(),
+        _ => bail!("Cannot accept a command outside of shell command mode"),
+    };
+    if command.trim().is_empty() {
+        bail!("Please provide a non-empty command");
+    }
+
+    // Running the command blocks until it finishes (or times out),
+    // so let the user know it's running before it gets started.
+    set_running(app, true);
+    app.render();
+    let output = shell::run(&command, Duration::from_secs(COMMAND_TIMEOUT_SECS));
+    set_running(app, false);
+    let output = output?;
+
+    {
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        buffer.start_operation_group();
+        buffer.insert(output);
+        util::history::end_operation_group(buffer, app.change_group);
+    }
+    app.mode = Mode::Normal;
+
+    Ok(())
+}
+
+fn set_running(app: &mut Application, running: bool) {
+    if let Mode::ShellCommand(ref mut mode) = app.mode {
+        mode.running = running;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commands;
+    use models::application::{Application, Mode};
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+
+    fn run_command(command: &str, app: &mut Application) -> commands::Result {
+        commands::buffer::insert_command_output(app).unwrap();
+        if let Mode::ShellCommand(ref mut mode) = app.mode {
+            mode.input = command.to_string();
+        }
+
+        super::accept(app)
+    }
+
+    #[test]
+    fn accept_inserts_command_output_at_the_cursor() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        buffer.cursor.move_to(Position{ line: 1, offset: 2 });
+        app.workspace.add_buffer(buffer);
+
+        run_command("echo text", &mut app).unwrap();
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\nedtext\nitor");
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+
+        // The insertion is undone as a single change.
+        commands::buffer::undo(&mut app).unwrap();
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\neditor");
+    }
+
+    #[test]
+    fn accept_inserts_nothing_when_the_command_fails() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+
+        assert!(run_command("echo text && exit 1", &mut app).is_err());
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\neditor");
+        assert!(match app.mode {
+            Mode::ShellCommand(ref mode) => !mode.running,
+            _ => false,
+        });
+    }
+}
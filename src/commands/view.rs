@@ -1,6 +1,10 @@
 use errors::*;
-use commands::Result;
-use models::application::Application;
+use commands::{self, Result};
+use models::application::{Application, ClipboardContent, Mode};
+use models::application::modes::Outline;
+use scribe::buffer::Position;
+use util::fold;
+use util::movement_lexer;
 
 pub fn scroll_up(app: &mut Application) -> Result {
     let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
@@ -25,3 +29,311 @@ pub fn scroll_cursor_to_center(app: &mut Application) -> Result {
     app.view.scroll_to_center(buffer)?;
     Ok(())
 }
+
+/// Cycles the gutter between absolute, relative,
+/// and hybrid line numbering for the current session.
+pub fn toggle_line_numbers(app: &mut Application) -> Result {
+    app.view.line_numbering = app.view.line_numbering.next();
+    Ok(())
+}
+
+/// Copies the rendered screen, as plain text, to the clipboard
+/// (e.g. to share exactly what's shown in a bug report).
+pub fn export_screen(app: &mut Application) -> Result {
+    let content = app.view.screen_text();
+    app.clipboard.set_content(ClipboardContent::Block(content))?;
+    app.notify("Copied the screen to the clipboard");
+
+    Ok(())
+}
+
+/// Hides (or restores) the gutter and status line, centering
+/// content at the `zen_mode_width` preference, if it's set.
+pub fn toggle_zen_mode(app: &mut Application) -> Result {
+    app.view.zen_mode = !app.view.zen_mode;
+    Ok(())
+}
+
+/// Keeps the cursor line vertically centered (or stops doing so),
+/// scrolling the content beneath it as the cursor moves.
+pub fn toggle_typewriter_mode(app: &mut Application) -> Result {
+    app.view.typewriter_mode = !app.view.typewriter_mode;
+    if app.view.typewriter_mode {
+        scroll_cursor_to_center(app)?;
+    }
+
+    Ok(())
+}
+
+/// Shows (or hides) the outline of the current buffer's symbols in a pane
+/// alongside it, which is kept up to date as the buffer changes.
+pub fn toggle_outline(app: &mut Application) -> Result {
+    if app.outline.take().is_none() {
+        app.outline = Some(Outline::new());
+        app.update_outline();
+    } else if let Mode::Outline = app.mode {
+        commands::application::switch_to_normal_mode(app)?;
+    }
+
+    Ok(())
+}
+
+/// Collapses the innermost multi-line brace/bracket region
+/// enclosing the cursor, moving the cursor to its first line.
+pub fn fold_region(app: &mut Application) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    let tokens = movement_lexer::lex(&buffer.data());
+    let region = fold::brace_region(&tokens, &*buffer.cursor)
+        .ok_or("No multi-line region found at the cursor")?;
+
+    // Folds are expanded while the cursor is within their hidden lines.
+    buffer.cursor.move_to(Position{ line: region.start(), offset: 0 });
+    app.view.fold(buffer, region)?;
+
+    Ok(())
+}
+
+/// Expands the innermost fold containing the cursor.
+pub fn unfold_region(app: &mut Application) -> Result {
+    let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+    if !app.view.unfold(buffer, buffer.cursor.line)? {
+        bail!("No folded region found at the cursor");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use commands;
+    use models::application::{Application, Mode, Preferences};
+    use presenters;
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+    use std::sync::Arc;
+    use util;
+    use view::{LineNumbering, View};
+    use view::terminal::TestTerminal;
+    use yaml::YamlLoader;
+
+    #[test]
+    fn toggle_line_numbers_cycles_through_numbering_styles() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        assert_eq!(app.view.line_numbering, LineNumbering::Absolute);
+
+        let expected = vec![
+            LineNumbering::Relative,
+            LineNumbering::Hybrid,
+            LineNumbering::Absolute,
+        ];
+        for numbering in expected {
+            super::toggle_line_numbers(&mut app).unwrap();
+            assert_eq!(app.view.line_numbering, numbering);
+        }
+    }
+
+    #[test]
+    fn toggle_line_numbers_changes_the_normal_mode_gutter() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let terminal = Arc::new(TestTerminal::new());
+        app.view = View::new(
+            terminal.clone(),
+            app.preferences.clone(),
+            app.event_channel.clone()
+        ).unwrap();
+
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc");
+        buffer.cursor.move_down();
+        util::add_buffer(buffer, &mut app).unwrap();
+
+        let mut gutters = Vec::new();
+        for _ in 0..3 {
+            presenters::modes::normal::display(
+                &mut app.workspace,
+                &mut app.view,
+                &None,
+                &mut None
+            ).unwrap();
+            let gutter: Vec<String> = terminal
+                .content()
+                .lines()
+                .take(3)
+                .map(|line| line.split_whitespace().next().unwrap_or("").to_string())
+                .collect();
+            gutters.push(gutter);
+
+            super::toggle_line_numbers(&mut app).unwrap();
+        }
+
+        assert_eq!(
+            gutters,
+            vec![
+                vec!["1", "2", "3"],
+                vec!["1", "0", "1"],
+                vec!["1", "2", "1"],
+            ]
+        );
+    }
+
+    #[test]
+    fn toggle_zen_mode_hides_and_restores_the_gutter() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let data = YamlLoader::load_from_str("zen_mode_width: 4").unwrap();
+        *app.preferences.borrow_mut() = Preferences::new(data.into_iter().nth(0));
+        let terminal = Arc::new(TestTerminal::new());
+        app.view = View::new(
+            terminal.clone(),
+            app.preferences.clone(),
+            app.event_channel.clone()
+        ).unwrap();
+
+        let mut buffer = Buffer::new();
+        buffer.insert("abcdefgh");
+        util::add_buffer(buffer, &mut app).unwrap();
+
+        let mut screens = Vec::new();
+        for _ in 0..2 {
+            super::toggle_zen_mode(&mut app).unwrap();
+            presenters::modes::normal::display(
+                &mut app.workspace,
+                &mut app.view,
+                &None,
+                &mut None
+            ).unwrap();
+            let screen: Vec<String> = terminal
+                .content()
+                .lines()
+                .map(|line| line.trim_right().to_string())
+                .collect();
+            screens.push(screen);
+        }
+
+        // Zen mode: no gutter or status line, and content
+        // wrapped at four columns and centered on the screen.
+        assert_eq!(screens[0][0], "   abcd");
+        assert_eq!(screens[0][1], "   efgh");
+        assert!(screens[0].iter().all(|line| !line.contains("NORMAL")));
+
+        // Toggled off: line numbers and the status line are back.
+        assert_eq!(screens[1][0].split_whitespace().next(), Some("1"));
+        assert!(screens[1].iter().any(|line| line.contains("NORMAL")));
+    }
+
+    #[test]
+    fn toggle_typewriter_mode_keeps_the_cursor_line_centered() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n".repeat(100));
+        app.workspace.add_buffer(buffer);
+        super::toggle_typewriter_mode(&mut app).unwrap();
+
+        for _ in 0..40 {
+            commands::cursor::move_down(&mut app).unwrap();
+            let offset = app.view.scroll_offset(app.workspace.current_buffer().unwrap()).unwrap();
+
+            // Explicitly centering the cursor line doesn't scroll any further.
+            super::scroll_cursor_to_center(&mut app).unwrap();
+            assert_eq!(
+                app.view.scroll_offset(app.workspace.current_buffer().unwrap()).unwrap(),
+                offset
+            );
+        }
+        assert!(app.view.scroll_offset(app.workspace.current_buffer().unwrap()).unwrap() > 0);
+
+        // With typewriter mode off, moving back up doesn't scroll until
+        // the cursor reaches the top of the screen.
+        super::toggle_typewriter_mode(&mut app).unwrap();
+        let offset = app.view.scroll_offset(app.workspace.current_buffer().unwrap()).unwrap();
+        commands::cursor::move_up(&mut app).unwrap();
+        assert_eq!(
+            app.view.scroll_offset(app.workspace.current_buffer().unwrap()).unwrap(),
+            offset
+        );
+    }
+
+    #[test]
+    fn fold_region_collapses_the_enclosing_region_until_unfolded() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let terminal = Arc::new(TestTerminal::new());
+        app.view = View::new(
+            terminal.clone(),
+            app.preferences.clone(),
+            app.event_channel.clone()
+        ).unwrap();
+
+        let mut buffer = Buffer::new();
+        buffer.insert("a {\n  b\n  c\n}\nd");
+        buffer.cursor.move_down();
+        buffer.cursor.move_down();
+        util::add_buffer(buffer, &mut app).unwrap();
+
+        let mut screens = Vec::new();
+        for command in &[super::fold_region, super::unfold_region] {
+            command(&mut app).unwrap();
+            presenters::modes::normal::display(
+                &mut app.workspace,
+                &mut app.view,
+                &None,
+                &mut None
+            ).unwrap();
+            let screen: Vec<String> = terminal
+                .content()
+                .lines()
+                .map(|line| line.trim_right().to_string())
+                .collect();
+            screens.push(screen);
+        }
+
+        // Folded: the region is summarized on its first line.
+        assert_eq!(screens[0][0], " 1  a { ⋯");
+        assert_eq!(screens[0][1].split_whitespace().next(), Some("5"));
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 0);
+
+        // Unfolded: every line is visible again.
+        assert_eq!(screens[1][0], " 1  a {");
+        assert_eq!(screens[1][1].split_whitespace().next(), Some("2"));
+        assert!(super::unfold_region(&mut app).is_err());
+    }
+
+    #[test]
+    fn folds_are_discarded_by_changes_at_or_above_them() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("fn amp() {\n    a();\n    b();\n}\nfn c() {}");
+        buffer.cursor.move_to(Position{ line: 2, offset: 0 });
+        util::add_buffer(buffer, &mut app).unwrap();
+
+        // Changes below the fold leave it in place.
+        super::fold_region(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 4, offset: 0 });
+        app.workspace.current_buffer().unwrap().insert("fn b() {}\n");
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 0, offset: 0 });
+        assert!(super::unfold_region(&mut app).is_ok());
+
+        // Changes above the fold shift its lines, discarding it.
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 2, offset: 0 });
+        super::fold_region(&mut app).unwrap();
+        app.workspace.current_buffer().unwrap().insert("\n");
+        assert!(super::unfold_region(&mut app).is_err());
+    }
+
+    #[test]
+    fn toggle_outline_shows_and_hides_the_outline() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+
+        super::toggle_outline(&mut app).unwrap();
+        assert!(app.outline.is_some());
+
+        app.mode = Mode::Outline;
+        super::toggle_outline(&mut app).unwrap();
+        assert!(app.outline.is_none());
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+}
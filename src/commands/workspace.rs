@@ -1,7 +1,10 @@
-use commands::Result;
+use errors::*;
+use commands::{self, Result};
 use scribe::Buffer;
-use models::application::Application;
-use util;
+use models::application::{Application, Mode, Session};
+use models::application::modes::PathMode;
+use util::{self, alternate_file};
+use util::token::Direction;
 
 pub fn next_buffer(app: &mut Application) -> Result {
     app.workspace.next_buffer();
@@ -9,6 +12,189 @@ pub fn next_buffer(app: &mut Application) -> Result {
     Ok(())
 }
 
+/// Cycles forward to the next buffer with unsaved changes.
+pub fn next_modified_buffer(app: &mut Application) -> Result {
+    select_modified_buffer(app, Direction::Forward)
+}
+
+/// Cycles backward to the previous buffer with unsaved changes.
+pub fn previous_modified_buffer(app: &mut Application) -> Result {
+    select_modified_buffer(app, Direction::Backward)
+}
+
+/// Cycles through the workspace until a modified buffer is selected,
+/// stopping (with a notice) if we wind up back where we started.
+fn select_modified_buffer(app: &mut Application, direction: Direction) -> Result {
+    let starting_id = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.id;
+
+    loop {
+        match direction {
+            Direction::Forward => app.workspace.next_buffer(),
+            Direction::Backward => app.workspace.previous_buffer(),
+        }
+
+        let buffer = app.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        if buffer.modified() {
+            return Ok(());
+        } else if buffer.id == starting_id {
+            break;
+        }
+    }
+    app.notify("No modified buffers");
+
+    Ok(())
+}
+
 pub fn new_buffer(app: &mut Application) -> Result {
     util::add_buffer(Buffer::new(), app)
 }
+
+/// Opens the current file's companion (e.g. a header for a source file),
+/// as defined by the alternate file rules in the application preferences.
+pub fn alternate_file(app: &mut Application) -> Result {
+    let path = app.workspace
+        .current_buffer()
+        .ok_or(BUFFER_MISSING)?
+        .path
+        .clone()
+        .ok_or(BUFFER_PATH_MISSING)?;
+    let rules = app.preferences.borrow().alternate_file_rules();
+
+    let alternate_path = alternate_file::candidates(&path, &rules)
+        .into_iter()
+        .find(|candidate| candidate.exists())
+        .ok_or("Couldn't find an alternate file")?;
+
+    util::open_buffer(&alternate_path, app)
+}
+
+/// Prompts for a glob (e.g. `src/**/*.rs`), relative to the
+/// workspace directory, and opens all of the files it matches.
+pub fn open_glob(app: &mut Application) -> Result {
+    let mut mode = PathMode::new(String::new());
+    mode.open_glob_on_accept = true;
+    app.mode = Mode::Path(mode);
+
+    Ok(())
+}
+
+/// Records the open buffers and their cursor positions, so
+/// that they can be reopened later using `restore_session`.
+pub fn save_session(app: &mut Application) -> Result {
+    let session = Session::capture(&mut app.workspace);
+    session.save(&app.workspace.path)?;
+    app.notify(format!("Saved session with {} buffer(s)", session.buffers.len()));
+
+    Ok(())
+}
+
+/// Reopens the buffers recorded by `save_session` for this workspace,
+/// restoring their cursor positions and making the buffer that was
+/// current when the session was saved current again.
+pub fn restore_session(app: &mut Application) -> Result {
+    let mut session = Session::load(&app.workspace.path)?;
+    let missing = session.remove_missing();
+
+    for buffer in session.buffers.iter() {
+        util::open_buffer(&buffer.path, app)?;
+        app.workspace.current_buffer().ok_or(BUFFER_MISSING)?.cursor.move_to(buffer.cursor);
+    }
+
+    // Cycle back around to the buffer that was current.
+    if let Some(first) = session.buffers.first() {
+        util::open_buffer(&first.path, app)?;
+        commands::view::scroll_to_cursor(app)?;
+    }
+
+    if !missing.is_empty() {
+        let paths: Vec<String> = missing.iter().map(|path| path.to_string_lossy().into_owned()).collect();
+        app.notify(format!("Skipped missing file(s): {}", paths.join(", ")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use models::application::Application;
+    use scribe::Buffer;
+    use std::fs::File;
+    use std::path::Path;
+    use util;
+    use util::test_helpers::TempDir;
+
+    fn add_buffer(app: &mut Application, path: &str, modified: bool) {
+        let mut buffer = Buffer::from_file(Path::new(path)).unwrap();
+        if modified {
+            buffer.insert("amp");
+        }
+        app.workspace.add_buffer(buffer);
+    }
+
+    fn current_buffer_id(app: &mut Application) -> Option<usize> {
+        app.workspace.current_buffer().and_then(|buffer| buffer.id)
+    }
+
+    #[test]
+    fn next_and_previous_modified_buffer_cycle_between_modified_buffers() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        add_buffer(&mut app, "LICENSE", true);
+        let first_id = current_buffer_id(&mut app);
+        add_buffer(&mut app, "Cargo.toml", false);
+        add_buffer(&mut app, "README.md", true);
+        let second_id = current_buffer_id(&mut app);
+
+        super::next_modified_buffer(&mut app).unwrap();
+        assert_eq!(current_buffer_id(&mut app), first_id);
+        super::next_modified_buffer(&mut app).unwrap();
+        assert_eq!(current_buffer_id(&mut app), second_id);
+        super::previous_modified_buffer(&mut app).unwrap();
+        assert_eq!(current_buffer_id(&mut app), first_id);
+        super::previous_modified_buffer(&mut app).unwrap();
+        assert_eq!(current_buffer_id(&mut app), second_id);
+        assert!(app.message_history.iter().last().is_none());
+    }
+
+    #[test]
+    fn next_modified_buffer_reports_when_no_buffers_are_modified() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        add_buffer(&mut app, "LICENSE", false);
+        add_buffer(&mut app, "Cargo.toml", false);
+        let id = current_buffer_id(&mut app);
+
+        super::next_modified_buffer(&mut app).unwrap();
+
+        assert_eq!(current_buffer_id(&mut app), id);
+        assert_eq!(
+            app.message_history.iter().last().map(|message| message.content.as_str()),
+            Some("No modified buffers")
+        );
+    }
+
+    #[test]
+    fn alternate_file_opens_the_companion_file() {
+        let dir = TempDir::new("alternate-file");
+        File::create(dir.join("amp.h")).unwrap();
+        File::create(dir.join("amp.c")).unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        util::load_buffer(&dir.join("amp.h"), &mut app).unwrap();
+        super::alternate_file(&mut app).unwrap();
+
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().path,
+            Some(dir.join("amp.c"))
+        );
+    }
+
+    #[test]
+    fn alternate_file_fails_when_there_is_no_companion_file() {
+        let dir = TempDir::new("alternate-file-missing");
+        File::create(dir.join("amp.h")).unwrap();
+
+        let mut app = Application::new(&Vec::new()).unwrap();
+        util::load_buffer(&dir.join("amp.h"), &mut app).unwrap();
+
+        assert!(super::alternate_file(&mut app).is_err());
+    }
+}
@@ -0,0 +1,283 @@
+use commands::RegisteredCommand;
+use input::{Key, KeyMap};
+use smallvec::SmallVec;
+use std::time::{Duration, Instant};
+
+/// How long to wait for the next key in a sequence before giving up on it.
+const SEQUENCE_TIMEOUT_MS: u64 = 1000;
+
+/// Tracks keys that form a partial multi-key sequence, resolving them to
+/// commands once the sequence is complete. Keys that don't continue a pending
+/// sequence cause its keys to be resolved individually, after which the new
+/// key is treated as a fresh start. Pending keys only apply to the mode in
+/// which they were pressed, and are discarded if it changes.
+pub struct KeyBuffer {
+    keys: Vec<Key>,
+    mode: Option<String>,
+    last_key_at: Option<Instant>,
+    timeout: Duration,
+    held: bool,
+}
+
+impl KeyBuffer {
+    pub fn new() -> KeyBuffer {
+        KeyBuffer::with_timeout(Duration::from_millis(SEQUENCE_TIMEOUT_MS))
+    }
+
+    pub fn with_timeout(timeout: Duration) -> KeyBuffer {
+        KeyBuffer {
+            keys: Vec::new(),
+            mode: None,
+            last_key_at: None,
+            timeout,
+            held: false,
+        }
+    }
+
+    /// Adds a key to the buffer, returning any keys that have been resolved
+    /// along with their commands, in the order they should be run.
+    pub fn push(&mut self, keymap: &KeyMap, mode: &str, key: Key) -> Vec<(Key, SmallVec<[RegisteredCommand; 4]>)> {
+        let mut resolved = self.expire(keymap, mode);
+
+        self.keys.push(key);
+        self.last_key_at = Some(Instant::now());
+
+        loop {
+            if self.keys.len() > 1 {
+                if let Some(commands) = keymap.commands_for_sequence(mode, &self.keys) {
+                    // We've matched a complete sequence.
+                    let key = self.keys.pop().unwrap();
+                    resolved.push((key, commands));
+                    self.clear();
+                    break;
+                }
+            }
+
+            if keymap.is_sequence_prefix(mode, &self.keys) {
+                // Wait for more keys.
+                break;
+            }
+
+            if self.keys.len() == 1 {
+                self.flush(keymap, mode, &mut resolved);
+                break;
+            }
+
+            // The last key doesn't continue the pending sequence; resolve
+            // the pending keys on their own and start over with the last.
+            let key = self.keys.pop().unwrap();
+            self.flush(keymap, mode, &mut resolved);
+            self.keys.push(key);
+            self.last_key_at = Some(Instant::now());
+        }
+        if !self.keys.is_empty() {
+            self.mode = Some(mode.to_string());
+        }
+
+        resolved
+    }
+
+    /// Gives up on the pending sequence if it's been too long since its last
+    /// key, returning its keys, resolved individually, along with their
+    /// commands. Keys pending in a mode other than the one specified are
+    /// discarded instead.
+    pub fn expire(&mut self, keymap: &KeyMap, mode: &str) -> Vec<(Key, SmallVec<[RegisteredCommand; 4]>)> {
+        let mut resolved = Vec::new();
+
+        if self.mode.as_ref().map(|pending_mode| pending_mode != mode).unwrap_or(false) {
+            self.clear();
+        } else if self.remaining() == Some(Duration::from_millis(0)) {
+            self.flush(keymap, mode, &mut resolved);
+        }
+
+        resolved
+    }
+
+    /// The time remaining before the pending sequence times out, or None
+    /// if there isn't one (or if it's being held).
+    pub fn remaining(&self) -> Option<Duration> {
+        if self.held {
+            return None;
+        }
+
+        self.last_key_at.map(|time| {
+            self.timeout.checked_sub(time.elapsed()).unwrap_or_default()
+        })
+    }
+
+    /// Keys that form part of an incomplete sequence.
+    pub fn pending(&self) -> &[Key] {
+        &self.keys
+    }
+
+    /// How long the current sequence has been waiting for its next key.
+    pub fn pending_duration(&self) -> Option<Duration> {
+        self.last_key_at.map(|time| time.elapsed())
+    }
+
+    /// Prevents the pending sequence from timing out, which
+    /// is useful while its completions are being displayed.
+    pub fn hold(&mut self) {
+        self.held = true;
+    }
+
+    /// Resolves each of the buffered keys individually, emptying the buffer.
+    fn flush(&mut self, keymap: &KeyMap, mode: &str, resolved: &mut Vec<(Key, SmallVec<[RegisteredCommand; 4]>)>) {
+        for key in self.keys.drain(..) {
+            if let Some(commands) = keymap.commands_for(mode, &key) {
+                resolved.push((key, commands));
+            }
+        }
+        self.mode = None;
+        self.last_key_at = None;
+        self.held = false;
+    }
+
+    /// Discards any pending keys.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.mode = None;
+        self.last_key_at = None;
+        self.held = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commands;
+    use input::{Key, KeyMap};
+    use std::time::Duration;
+    use super::KeyBuffer;
+    use yaml::YamlLoader;
+
+    fn keymap() -> KeyMap {
+        let yaml_data = "normal:\n  g: cursor::move_down\n  g g: cursor::move_to_first_line\n  x: buffer::delete";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+
+        KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn push_resolves_a_complete_sequence() {
+        let keymap = keymap();
+        let mut buffer = KeyBuffer::new();
+
+        assert!(buffer.push(&keymap, "normal", Key::Char('g')).is_empty());
+        let resolved = buffer.push(&keymap, "normal", Key::Char('g'));
+
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].0, Key::Char('g'));
+        assert_eq!(
+            (resolved[0].1[0].command as *const usize),
+            (commands::cursor::move_to_first_line as *const usize)
+        );
+    }
+
+    #[test]
+    fn push_resolves_pending_keys_individually_when_sequence_is_broken() {
+        let keymap = keymap();
+        let mut buffer = KeyBuffer::new();
+
+        assert!(buffer.push(&keymap, "normal", Key::Char('g')).is_empty());
+        let resolved = buffer.push(&keymap, "normal", Key::Char('x'));
+
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].0, Key::Char('g'));
+        assert_eq!(
+            (resolved[0].1[0].command as *const usize),
+            (commands::cursor::move_down as *const usize)
+        );
+        assert_eq!(resolved[1].0, Key::Char('x'));
+        assert_eq!(
+            (resolved[1].1[0].command as *const usize),
+            (commands::buffer::delete as *const usize)
+        );
+    }
+
+    #[test]
+    fn push_skips_unbound_pending_keys_when_sequence_is_broken() {
+        let yaml_data = "normal:\n  g g: cursor::move_to_first_line\n  x: buffer::delete";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+        let mut buffer = KeyBuffer::new();
+
+        assert!(buffer.push(&keymap, "normal", Key::Char('g')).is_empty());
+        let resolved = buffer.push(&keymap, "normal", Key::Char('x'));
+
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].0, Key::Char('x'));
+    }
+
+    #[test]
+    fn push_keeps_held_sequence_beyond_timeout() {
+        let keymap = keymap();
+        let mut buffer = KeyBuffer::with_timeout(Duration::from_millis(0));
+
+        assert!(buffer.push(&keymap, "normal", Key::Char('g')).is_empty());
+        buffer.hold();
+        ::std::thread::sleep(Duration::from_millis(5));
+        let resolved = buffer.push(&keymap, "normal", Key::Char('g'));
+
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(
+            (resolved[0].1[0].command as *const usize),
+            (commands::cursor::move_to_first_line as *const usize)
+        );
+    }
+
+    #[test]
+    fn push_abandons_pending_sequence_after_timeout() {
+        let keymap = keymap();
+        let mut buffer = KeyBuffer::with_timeout(Duration::from_millis(0));
+
+        assert!(buffer.push(&keymap, "normal", Key::Char('g')).is_empty());
+        ::std::thread::sleep(Duration::from_millis(5));
+        let resolved = buffer.push(&keymap, "normal", Key::Char('g'));
+
+        // The first key is resolved on its own, and the second is pending.
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(
+            (resolved[0].1[0].command as *const usize),
+            (commands::cursor::move_down as *const usize)
+        );
+    }
+
+    #[test]
+    fn expire_resolves_pending_keys_once_the_sequence_times_out() {
+        let keymap = keymap();
+        let mut buffer = KeyBuffer::with_timeout(Duration::from_millis(0));
+
+        assert!(buffer.push(&keymap, "normal", Key::Char('g')).is_empty());
+        ::std::thread::sleep(Duration::from_millis(5));
+        let resolved = buffer.expire(&keymap, "normal");
+
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(
+            (resolved[0].1[0].command as *const usize),
+            (commands::cursor::move_down as *const usize)
+        );
+        assert!(buffer.pending().is_empty());
+    }
+
+    #[test]
+    fn expire_keeps_pending_keys_before_the_sequence_times_out() {
+        let keymap = keymap();
+        let mut buffer = KeyBuffer::new();
+
+        assert!(buffer.push(&keymap, "normal", Key::Char('g')).is_empty());
+
+        assert!(buffer.expire(&keymap, "normal").is_empty());
+        assert_eq!(buffer.pending(), &[Key::Char('g')]);
+    }
+
+    #[test]
+    fn expire_discards_keys_pending_in_another_mode() {
+        let keymap = keymap();
+        let mut buffer = KeyBuffer::new();
+
+        assert!(buffer.push(&keymap, "normal", Key::Char('g')).is_empty());
+
+        assert!(buffer.expire(&keymap, "insert").is_empty());
+        assert!(buffer.pending().is_empty());
+    }
+}
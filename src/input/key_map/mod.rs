@@ -1,4 +1,4 @@
-use commands::{self, Command};
+use commands::{self, RegisteredCommand};
 use errors::*;
 use input::Key;
 use smallvec::SmallVec;
@@ -7,8 +7,25 @@ use std::ops::{Deref, DerefMut};
 use std::convert::Into;
 use yaml::yaml::{Hash, Yaml, YamlLoader};
 
-/// Nested HashMap newtype that provides a more ergonomic interface.
-pub struct KeyMap(HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>>);
+/// Bindings in this mode apply to all others, unless overridden by them.
+const GLOBAL_MODE: &str = "global";
+
+/// The leader key used when one isn't specified.
+const DEFAULT_LEADER: &str = "space";
+
+/// A placeholder in key definitions, replaced with the leader key.
+const LEADER_TOKEN: &str = "<leader>";
+
+/// Commands that, when merged, remove a key's existing binding.
+const UNBIND_COMMANDS: [&str; 2] = ["none", "noop"];
+
+/// Nested HashMap wrapper that provides a more ergonomic interface.
+/// Multi-key sequences (e.g. "g g") are tracked separately from
+/// single-key bindings, which the keymap dereferences to.
+pub struct KeyMap {
+    bindings: HashMap<String, HashMap<Key, SmallVec<[RegisteredCommand; 4]>>>,
+    sequences: HashMap<String, HashMap<Vec<Key>, SmallVec<[RegisteredCommand; 4]>>>,
+}
 
 impl KeyMap {
     /// Parses a Yaml tree of modes and their keybindings into a complete keymap.
@@ -23,28 +40,56 @@ impl KeyMap {
     ///   "normal" => { Key::Char('k') => commands::cursor::move_up }
     ///
     pub fn from(keymap_data: &Hash) -> Result<KeyMap> {
-        let mut keymap = HashMap::new();
-        let commands = commands::hash_map();
+        KeyMap::from_with_leader(keymap_data, DEFAULT_LEADER)
+    }
+
+    /// Parses a keymap, replacing `<leader>` tokens with the specified key.
+    ///
+    /// e.g. with a leader of ",":
+    ///
+    ///  normal:
+    ///     <leader> w: "buffer::save"
+    ///
+    /// becomes this sequence entry:
+    ///
+    ///   "normal" => { [Key::Char(','), Key::Char('w')] => commands::buffer::save }
+    ///
+    pub fn from_with_leader(keymap_data: &Hash, leader: &str) -> Result<KeyMap> {
+        let leader = parse_key(leader)
+            .chain_err(|| format!("Leader key \"{}\" is invalid", leader))?;
+        let mut bindings = HashMap::new();
+        let mut sequences = HashMap::new();
+        let commands: HashMap<&str, RegisteredCommand> = commands::registry()
+            .into_iter()
+            .map(|command| (command.name, command))
+            .collect();
 
         for (yaml_mode, yaml_key_bindings) in keymap_data {
             let mode = yaml_mode.as_str().ok_or_else(||
                 "A mode key couldn't be parsed as a string".to_string()
             )?;
-            let key_bindings = parse_mode_key_bindings(yaml_key_bindings, &commands).
+            let (key_bindings, key_sequences) = parse_mode_key_bindings(yaml_key_bindings, &commands, &leader).
                 chain_err(|| format!("Failed to parse keymaps for \"{}\" mode", mode))?;
 
-            keymap.insert(mode.to_string(), key_bindings);
+            bindings.insert(mode.to_string(), key_bindings);
+            sequences.insert(mode.to_string(), key_sequences);
         }
 
-        Ok(KeyMap(keymap))
+        Ok(KeyMap { bindings, sequences })
     }
 
     /// Searches the keymap for the specified key.
     /// Character keys will fall back to wildcard character bindings
-    /// if the specific character binding cannot be found.
+    /// if the specific character binding cannot be found. Keys that
+    /// aren't bound in the specified mode fall back to global bindings.
     ///
-    pub fn commands_for(&self, mode: &str, key: &Key) -> Option<SmallVec<[Command; 4]>> {
-        self.0.get(mode).and_then(|mode_keymap| {
+    pub fn commands_for(&self, mode: &str, key: &Key) -> Option<SmallVec<[RegisteredCommand; 4]>> {
+        self.mode_commands_for(mode, key)
+            .or_else(|| self.mode_commands_for(GLOBAL_MODE, key))
+    }
+
+    fn mode_commands_for(&self, mode: &str, key: &Key) -> Option<SmallVec<[RegisteredCommand; 4]>> {
+        self.bindings.get(mode).and_then(|mode_keymap| {
             if let Key::Char(_) = *key {
                 // Look for a command for this specific character, falling
                 // back to another search for a wildcard character binding.
@@ -55,6 +100,87 @@ impl KeyMap {
         }).map(|commands| (*commands).clone())
     }
 
+    /// Searches the keymap for a multi-key sequence. Global sequences
+    /// only apply if the mode doesn't bind the sequence's first key.
+    pub fn commands_for_sequence(&self, mode: &str, keys: &[Key]) -> Option<SmallVec<[RegisteredCommand; 4]>> {
+        self.sequence_modes(mode, keys)
+            .into_iter()
+            .filter_map(|mode| self.sequences.get(mode).and_then(|sequences| sequences.get(keys)))
+            .next()
+            .cloned()
+    }
+
+    /// Whether or not the keys are the start of a longer sequence,
+    /// in which case more keys are needed to resolve a command.
+    pub fn is_sequence_prefix(&self, mode: &str, keys: &[Key]) -> bool {
+        self.sequence_modes(mode, keys).into_iter().any(|mode| {
+            self.sequences.get(mode).map(|sequences| {
+                sequences.keys().any(|sequence| {
+                    sequence.len() > keys.len() && sequence.starts_with(keys)
+                })
+            }).unwrap_or(false)
+        })
+    }
+
+    /// Lists the keys that can follow the specified prefix, along with the
+    /// names of the commands they'd run, sorted by key. Keys that only lead
+    /// to longer sequences are described with an ellipsis.
+    pub fn sequence_completions(&self, mode: &str, prefix: &[Key]) -> Vec<(Key, String)> {
+        let mut completions: Vec<(Key, String)> = Vec::new();
+
+        for mode in self.sequence_modes(mode, prefix) {
+            let sequences = match self.sequences.get(mode) {
+                Some(sequences) => sequences,
+                None => continue,
+            };
+
+            for (sequence, sequence_commands) in sequences {
+                if sequence.len() <= prefix.len() || !sequence.starts_with(prefix) {
+                    continue;
+                }
+
+                let key = sequence[prefix.len()].clone();
+                let complete = sequence.len() == prefix.len() + 1;
+                let description = if complete {
+                    sequence_commands
+                        .iter()
+                        .map(|command| command.name)
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                } else {
+                    String::from("...")
+                };
+
+                // Earlier (mode-specific) entries take precedence, but
+                // complete sequences replace those leading to longer ones.
+                let existing = completions.iter().position(|&(ref other_key, _)| *other_key == key);
+                match existing {
+                    Some(index) => if complete && completions[index].1 == "..." {
+                        completions[index].1 = description;
+                    },
+                    None => completions.push((key, description)),
+                }
+            }
+        }
+
+        completions.sort_by_key(|&(ref key, _)| key.to_string());
+        completions
+    }
+
+    /// The modes whose sequences are considered for the specified keys.
+    fn sequence_modes<'a>(&self, mode: &'a str, keys: &[Key]) -> Vec<&'a str> {
+        let shadowed = keys
+            .first()
+            .map(|key| self.mode_commands_for(mode, key).is_some())
+            .unwrap_or(false);
+
+        if shadowed {
+            vec![mode]
+        } else {
+            vec![mode, GLOBAL_MODE]
+        }
+    }
+
     /// Loads the default keymap from a static
     /// YAML document injected during the build.
     pub fn default() -> Result<KeyMap> {
@@ -94,45 +220,76 @@ impl KeyMap {
     ///       Key::Char('j') => commands::cursor::move_down
     ///   }
     ///
+    /// Keys mapped to `none` (or `noop`) have their existing binding removed.
+    ///
     pub fn merge(&mut self, mut key_map: KeyMap) {
         // Step through the specified key map's modes.
-        for (mode, other_key_bindings) in key_map.iter_mut() {
+        for (mode, other_key_bindings) in key_map.bindings.iter_mut() {
             // Fetch the current key bindings for the specified mode.
-            if let Some(key_bindings) = self.get_mut(mode) {
+            if let Some(key_bindings) = self.bindings.get_mut(mode) {
                 for (key, command) in other_key_bindings.drain() {
-                    key_bindings.insert(key, command);
+                    if command.is_empty() {
+                        key_bindings.remove(&key);
+                    } else {
+                        key_bindings.insert(key, command);
+                    }
+                }
+            }
+        }
+
+        // Do the same for key sequences.
+        for (mode, other_key_sequences) in key_map.sequences.iter_mut() {
+            if let Some(key_sequences) = self.sequences.get_mut(mode) {
+                for (keys, command) in other_key_sequences.drain() {
+                    if command.is_empty() {
+                        key_sequences.remove(&keys);
+                    } else {
+                        key_sequences.insert(keys, command);
+                    }
                 }
             }
         }
     }
 }
 
-/// Parses the key bindings for a particular mode.
+/// Parses the key bindings for a particular mode, separating
+/// whitespace-delimited key sequences from single-key bindings.
 ///
 /// e.g.
 ///
 ///   k: "cursor::move_up"
+///   g g: "cursor::move_to_first_line"
 ///
-/// becomes this HashMap entry:
+/// becomes these HashMap entries:
 ///
 ///   Key::Char('k') => [commands::cursor::move_up]
+///   [Key::Char('g'), Key::Char('g')] => [commands::cursor::move_to_first_line]
 ///
-fn parse_mode_key_bindings(mode: &Yaml, commands: &HashMap<&str, Command>) -> Result<HashMap<Key, SmallVec<[Command; 4]>>> {
+fn parse_mode_key_bindings(mode: &Yaml, commands: &HashMap<&str, RegisteredCommand>, leader: &Key) -> Result<(HashMap<Key, SmallVec<[RegisteredCommand; 4]>>, HashMap<Vec<Key>, SmallVec<[RegisteredCommand; 4]>>)> {
     let mode_key_bindings = mode.as_hash().ok_or(
         "Keymap mode config didn't return a hash of key bindings",
     )?;
 
     let mut key_bindings = HashMap::new();
+    let mut key_sequences = HashMap::new();
     for (yaml_key, yaml_command) in mode_key_bindings {
-        // Parse modifier/character from key component.
-        let key = parse_key(yaml_key.as_str().ok_or_else(||
+        // Parse modifier/character from each key component.
+        let mut keys = yaml_key.as_str().ok_or_else(||
             "A keymap key couldn't be parsed as a string".to_string()
-        )?)?;
+        )?.split_whitespace().map(|component| {
+            if component == LEADER_TOKEN {
+                Ok(leader.clone())
+            } else {
+                parse_key(component)
+            }
+        }).collect::<Result<Vec<Key>>>()?;
 
         let mut key_commands = SmallVec::new();
 
         // Parse and find command reference from command component.
         match *yaml_command {
+            // Unbinding entries have no commands; they're removed during merges.
+            Yaml::String(ref command) if UNBIND_COMMANDS.contains(&command.as_str()) => (),
             Yaml::String(ref command) => {
                 let command_string = command.as_str();
 
@@ -159,83 +316,139 @@ fn parse_mode_key_bindings(mode: &Yaml, commands: &HashMap<&str, Command>) -> Re
             _ => bail!(format!("Keymap command \"{:?}\" couldn't be parsed", yaml_command))
         }
 
-        // Add a key/command entry to the mapping.
-        key_bindings.insert(key, key_commands);
+        // Add a key/command entry to the appropriate mapping.
+        if keys.len() > 1 {
+            key_sequences.insert(keys, key_commands);
+        } else {
+            let key = keys.pop().ok_or("A keymap key is an empty string")?;
+            key_bindings.insert(key, key_commands);
+        }
     }
 
-    Ok(key_bindings)
+    Ok((key_bindings, key_sequences))
 }
 
-/// Parses a str-based key into its Key equivalent.
+/// Parses a str-based key into its Key equivalent. Modifiers can be
+/// separated from the key using either a dash or a plus sign, and shift
+/// is expressed using the uppercase character, which it produces.
 ///
 /// e.g.
 ///
 ///   ctrl-r becomes Key::Ctrl('r')
+///   alt+shift+p becomes Key::Alt('P')
+///   alt+x becomes Key::Alt('x')
+///   f5 becomes Key::F(5)
 ///
 fn parse_key(data: &str) -> Result<Key> {
-    let mut key_components = data.split('-');
-    let component = key_components.next().ok_or(
-        "A keymap key is an empty string",
-    )?;
+    let (mut ctrl, mut alt, mut shift) = (false, false, false);
+    let mut name = data;
+
+    // Strip modifiers from the front of the key, leaving its name.
+    // Single characters are never modifiers, which lets the dash
+    // and plus keys be bound using their own characters.
+    while name.chars().count() > 1 {
+        let (modifier, rest) = match name.find(|c| c == '-' || c == '+') {
+            Some(index) if index > 0 => (&name[..index], &name[index + 1..]),
+            _ => break,
+        };
+        match modifier {
+            "ctrl" => ctrl = true,
+            "alt" => alt = true,
+            "shift" => shift = true,
+            _ => bail!(format!("Keymap modifier \"{}\" is invalid", modifier)),
+        }
+        name = rest;
+    }
+    if name.is_empty() {
+        bail!(format!("Keymap key \"{}\" is missing a key after its modifiers", data));
+    }
+    if shift && name != "tab" && name.chars().count() > 1 {
+        bail!(format!("Keymap key \"{}\" can't be modified using shift", data));
+    }
 
-    if let Some(key) = key_components.next() {
-        // We have a modifier-qualified key; get the key.
-        let key_char = key.chars().nth(0).ok_or_else(|| format!(
-            "Keymap key \"{}\" is invalid",
-            key
-        ))?;
-
-        // Find the variant for the specified modifier.
-        match component {
-            "ctrl" => Ok(Key::Ctrl(key_char)),
-            _ => bail!(format!("Keymap modifier \"{}\" is invalid", component)),
+    let key = match name {
+        "space"     => Key::Char(' '),
+        "backspace" => Key::Backspace,
+        "left"      => Key::Left,
+        "right"     => Key::Right,
+        "up"        => Key::Up,
+        "down"      => Key::Down,
+        "home"      => Key::Home,
+        "end"       => Key::End,
+        "page_up"   => Key::PageUp,
+        "page_down" => Key::PageDown,
+        "delete"    => Key::Delete,
+        "insert"    => Key::Insert,
+        "escape"    => Key::Esc,
+        "tab"       => if shift { Key::BackTab } else { Key::Tab },
+        "enter"     => Key::Enter,
+        "_"         => Key::AnyChar,
+        _           => {
+            match function_key_number(name) {
+                Some(number) => Key::F(number),
+                None => {
+                    let mut characters = name.chars();
+                    match (characters.next(), characters.next()) {
+                        (Some(character), None) if shift => {
+                            Key::Char(character.to_uppercase().next().unwrap_or(character))
+                        }
+                        (Some(character), None) => Key::Char(character),
+                        // Anything longer than a character must be a keyword.
+                        _ => bail!(format!("Keymap key \"{}\" is invalid", name)),
+                    }
+                }
+            }
         }
-    } else {
-        // No modifier; just get the key.
-        Ok(match component {
-            "space"     => Key::Char(' '),
-            "backspace" => Key::Backspace,
-            "left"      => Key::Left,
-            "right"     => Key::Right,
-            "up"        => Key::Up,
-            "down"      => Key::Down,
-            "home"      => Key::Home,
-            "end"       => Key::End,
-            "page_up"   => Key::PageUp,
-            "page_down" => Key::PageDown,
-            "delete"    => Key::Delete,
-            "insert"    => Key::Insert,
-            "escape"    => Key::Esc,
-            "tab"       => Key::Tab,
-            "enter"     => Key::Enter,
-            "_"         => Key::AnyChar,
-            _           => Key::Char(
-                // It's not a keyword; take its first character, if available.
-                component.chars().nth(0).ok_or_else(||
-                    format!("Keymap key \"{}\" is invalid", component)
-                )?
-            ),
-        })
+    };
+
+    // Apply modifiers to characters; special keys only support shift.
+    match (key, ctrl, alt) {
+        (_, true, true) => bail!(format!(
+            "Keymap key \"{}\" combines ctrl and alt, which terminals can't report",
+            data
+        )),
+        (Key::Char(character), true, false) if shift || character.is_uppercase() => bail!(format!(
+            "Keymap key \"{}\" combines ctrl and shift, which terminals can't report",
+            data
+        )),
+        (Key::Char(character), true, false) => Ok(Key::Ctrl(character)),
+        (Key::Char(character), false, true) => Ok(Key::Alt(character)),
+        (_, true, false) | (_, false, true) => bail!(format!(
+            "Keymap key \"{}\" can only be modified using shift",
+            data
+        )),
+        (key, false, false) => Ok(key),
     }
 }
 
+/// Parses function key names (e.g. "f5"), limited to F1 through F12.
+fn function_key_number(name: &str) -> Option<u8> {
+    if !name.starts_with('f') {
+        return None;
+    }
+
+    name[1..].parse().ok().and_then(|number| {
+        if number >= 1 && number <= 12 { Some(number) } else { None }
+    })
+}
+
 impl Deref for KeyMap {
-    type Target = HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>>;
+    type Target = HashMap<String, HashMap<Key, SmallVec<[RegisteredCommand; 4]>>>;
 
-    fn deref(&self) -> &HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>> {
-        &self.0
+    fn deref(&self) -> &HashMap<String, HashMap<Key, SmallVec<[RegisteredCommand; 4]>>> {
+        &self.bindings
     }
 }
 
 impl DerefMut for KeyMap {
-    fn deref_mut(&mut self) -> &mut HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>> {
-        &mut self.0
+    fn deref_mut(&mut self) -> &mut HashMap<String, HashMap<Key, SmallVec<[RegisteredCommand; 4]>>> {
+        &mut self.bindings
     }
 }
 
-impl Into<HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>>> for KeyMap {
-    fn into(self) -> HashMap<String, HashMap<Key, SmallVec<[Command; 4]>>> {
-        self.0
+impl Into<HashMap<String, HashMap<Key, SmallVec<[RegisteredCommand; 4]>>>> for KeyMap {
+    fn into(self) -> HashMap<String, HashMap<Key, SmallVec<[RegisteredCommand; 4]>>> {
+        self.bindings
     }
 }
 
@@ -257,7 +470,7 @@ mod tests {
             "Keymap doesn't contain command",
         );
         assert_eq!(
-            (command[0] as *const usize),
+            (command[0].command as *const usize),
             (commands::cursor::move_up as *const usize)
         );
     }
@@ -275,7 +488,7 @@ mod tests {
                 "Keymap doesn't contain command",
             );
             assert_eq!(
-                (command[0] as *const usize),
+                (command[0].command as *const usize),
                 (commands::cursor::move_up as *const usize)
             );
         }
@@ -292,14 +505,14 @@ mod tests {
             "Keymap doesn't contain command",
         );
         assert_eq!(
-            (char_command[0] as *const usize),
+            (char_command[0].command as *const usize),
             (commands::cursor::move_down as *const usize)
         );
         let wildcard_command = keymap.commands_for("normal", &Key::Char('a')).expect(
             "Keymap doesn't contain command",
         );
         assert_eq!(
-            (wildcard_command[0] as *const usize),
+            (wildcard_command[0].command as *const usize),
             (commands::cursor::move_up as *const usize)
         );
     }
@@ -315,11 +528,57 @@ mod tests {
             "Keymap doesn't contain command",
         );
         assert_eq!(
-            (command[0] as *const usize),
+            (command[0].command as *const usize),
+            (commands::cursor::move_up as *const usize)
+        );
+    }
+
+    #[test]
+    fn keymap_correctly_parses_plus_separated_control_keybindings() {
+        let yaml_data = "normal:\n  ctrl+p: cursor::move_up";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+
+        let command = keymap.commands_for("normal", &Key::Ctrl('p')).expect(
+            "Keymap doesn't contain command",
+        );
+        assert_eq!(
+            (command[0].command as *const usize),
             (commands::cursor::move_up as *const usize)
         );
     }
 
+    #[test]
+    fn parse_key_supports_modifiers_function_keys_and_special_keys() {
+        let keys = vec![
+            ("ctrl+p", Key::Ctrl('p')),
+            ("shift-a", Key::Char('A')),
+            ("alt+x", Key::Alt('x')),
+            ("alt-.", Key::Alt('.')),
+            ("f1", Key::F(1)),
+            ("f12", Key::F(12)),
+            ("f", Key::Char('f')),
+            ("shift+tab", Key::BackTab),
+            ("page_down", Key::PageDown),
+            ("delete", Key::Delete),
+            ("home", Key::Home),
+            ("-", Key::Char('-')),
+            ("+", Key::Char('+')),
+            ("ctrl+-", Key::Ctrl('-')),
+        ];
+
+        for (data, key) in keys {
+            assert_eq!(super::parse_key(data).unwrap(), key, "couldn't parse {}", data);
+        }
+    }
+
+    #[test]
+    fn parse_key_rejects_unsupported_keys() {
+        for data in &["f13", "ctrl+alt+p", "ctrl+shift+p", "ctrl+P", "ctrl+f5", "shift+up", "super+p", "ctrl+", "foo"] {
+            assert!(super::parse_key(data).is_err(), "parsed {}", data);
+        }
+    }
+
     #[test]
     fn keymap_correctly_parses_yaml_keyword_keybindings() {
         let mappings = vec![
@@ -346,7 +605,7 @@ mod tests {
             let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
 
             let parsed_command = keymap.commands_for("normal", &key).expect("Keymap doesn't contain command");
-            assert_eq!((parsed_command[0] as *const usize), (command as *const usize));
+            assert_eq!((parsed_command[0].command as *const usize), (command as *const usize));
         }
     }
 
@@ -359,11 +618,46 @@ mod tests {
             "Keymap doesn't contain command",
         );
         assert_eq!(
-            (command[0] as *const usize),
+            (command[0].command as *const usize),
             (commands::cursor::move_up as *const usize)
         );
     }
 
+    #[test]
+    fn keymap_binds_operators_by_default() {
+        let keymap = KeyMap::default().unwrap();
+
+        for &(key, operator) in &[
+            ('d', commands::operator::delete as *const usize),
+            ('c', commands::operator::change as *const usize),
+            ('y', commands::operator::yank as *const usize),
+        ] {
+            let command = keymap.commands_for("normal", &Key::Alt(key)).expect(
+                "Keymap doesn't contain command",
+            );
+            assert_eq!((command[0].command as *const usize), operator);
+        }
+    }
+
+    #[test]
+    fn keymap_binds_character_searches_by_default() {
+        let keymap = KeyMap::default().unwrap();
+
+        for &(key, search) in &[
+            ('n', commands::find_char::find_char_forward as *const usize),
+            ('N', commands::find_char::find_char_backward as *const usize),
+            ('t', commands::find_char::till_char_forward as *const usize),
+            ('T', commands::find_char::till_char_backward as *const usize),
+            (';', commands::find_char::repeat_find as *const usize),
+            (',', commands::find_char::repeat_find_reverse as *const usize),
+        ] {
+            let command = keymap.commands_for("normal", &Key::Alt(key)).expect(
+                "Keymap doesn't contain command",
+            );
+            assert_eq!((command[0].command as *const usize), search);
+        }
+    }
+
     #[test]
     fn keymap_correctly_merges_keybindings() {
         let yaml_data = "normal:\n  k: cursor::move_up\n  j: cursor::move_down";
@@ -380,7 +674,7 @@ mod tests {
             "Keymap doesn't contain original command",
         );
         assert_eq!(
-            (command[0] as *const usize),
+            (command[0].command as *const usize),
             (commands::cursor::move_down as *const usize)
         );
 
@@ -388,7 +682,7 @@ mod tests {
             "Keymap doesn't contain overlapping command",
         );
         assert_eq!(
-            (command[0] as *const usize),
+            (command[0].command as *const usize),
             (commands::cursor::move_left as *const usize)
         );
 
@@ -396,11 +690,158 @@ mod tests {
             "Keymap doesn't contain other command",
         );
         assert_eq!(
-            (command[0] as *const usize),
+            (command[0].command as *const usize),
             (commands::cursor::move_right as *const usize)
         );
     }
 
+    #[test]
+    fn keymap_merge_removes_keybindings_mapped_to_none() {
+        let yaml_data = "normal:\n  k: cursor::move_up\n  j: cursor::move_down\n  g g: cursor::move_to_first_line";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let mut keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+
+        let other_yaml_data = "normal:\n  k: none\n  g g: noop";
+        let other_yaml = YamlLoader::load_from_str(other_yaml_data).unwrap();
+        let other_keymap = KeyMap::from(&other_yaml[0].as_hash().unwrap()).unwrap();
+
+        keymap.merge(other_keymap);
+
+        assert!(keymap.commands_for("normal", &Key::Char('k')).is_none());
+        assert!(keymap.commands_for("normal", &Key::Char('j')).is_some());
+        assert!(keymap.commands_for_sequence("normal", &[Key::Char('g'), Key::Char('g')]).is_none());
+    }
+
+    #[test]
+    fn keymap_prioritizes_mode_keybindings_over_global_keybindings() {
+        let yaml_data = "global:\n  ctrl-r: cursor::move_down\nnormal:\n  ctrl-r: cursor::move_up";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+
+        let command = keymap.commands_for("normal", &Key::Ctrl('r')).expect(
+            "Keymap doesn't contain command",
+        );
+        assert_eq!(
+            (command[0].command as *const usize),
+            (commands::cursor::move_up as *const usize)
+        );
+    }
+
+    #[test]
+    fn keymap_falls_back_to_global_keybindings() {
+        let yaml_data = "global:\n  ctrl-r: cursor::move_down\nnormal:\n  k: cursor::move_up";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+
+        let command = keymap.commands_for("normal", &Key::Ctrl('r')).expect(
+            "Keymap doesn't contain command",
+        );
+        assert_eq!(
+            (command[0].command as *const usize),
+            (commands::cursor::move_down as *const usize)
+        );
+
+        // Modes without any bindings of their own should also fall back.
+        let command = keymap.commands_for("insert", &Key::Ctrl('r')).expect(
+            "Keymap doesn't contain command",
+        );
+        assert_eq!(
+            (command[0].command as *const usize),
+            (commands::cursor::move_down as *const usize)
+        );
+    }
+
+    #[test]
+    fn keymap_correctly_parses_yaml_key_sequences() {
+        let yaml_data = "normal:\n  g g: cursor::move_to_first_line";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+
+        let command = keymap.commands_for_sequence(
+            "normal",
+            &[Key::Char('g'), Key::Char('g')]
+        ).expect("Keymap doesn't contain command");
+        assert_eq!(
+            (command[0].command as *const usize),
+            (commands::cursor::move_to_first_line as *const usize)
+        );
+        assert!(keymap.is_sequence_prefix("normal", &[Key::Char('g')]));
+        assert!(keymap.commands_for("normal", &Key::Char('g')).is_none());
+    }
+
+    #[test]
+    fn keymap_expands_leader_in_key_sequences() {
+        let yaml_data = "normal:\n  <leader> w: buffer::save";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from_with_leader(&yaml[0].as_hash().unwrap(), ",").unwrap();
+
+        let command = keymap.commands_for_sequence(
+            "normal",
+            &[Key::Char(','), Key::Char('w')]
+        ).expect("Keymap doesn't contain command");
+        assert_eq!(
+            (command[0].command as *const usize),
+            (commands::buffer::save as *const usize)
+        );
+    }
+
+    #[test]
+    fn keymap_retargets_leader_bindings_when_leader_changes() {
+        let yaml_data = "normal:\n  <leader> w: buffer::save";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from_with_leader(&yaml[0].as_hash().unwrap(), ";").unwrap();
+
+        assert!(keymap.commands_for_sequence(
+            "normal",
+            &[Key::Char(','), Key::Char('w')]
+        ).is_none());
+        assert!(keymap.commands_for_sequence(
+            "normal",
+            &[Key::Char(';'), Key::Char('w')]
+        ).is_some());
+    }
+
+    #[test]
+    fn keymap_uses_space_as_the_default_leader() {
+        let yaml_data = "normal:\n  <leader> w: buffer::save";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+
+        assert!(keymap.commands_for_sequence(
+            "normal",
+            &[Key::Char(' '), Key::Char('w')]
+        ).is_some());
+    }
+
+    #[test]
+    fn sequence_completions_lists_next_keys_and_command_names() {
+        let yaml_data = "normal:\n  g g: cursor::move_to_first_line\n  g s: buffer::save\n  g t x: buffer::delete\n  x: buffer::delete";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+
+        assert_eq!(
+            keymap.sequence_completions("normal", &[Key::Char('g')]),
+            vec![
+                (Key::Char('g'), String::from("cursor::move_to_first_line")),
+                (Key::Char('s'), String::from("buffer::save")),
+                (Key::Char('t'), String::from("...")),
+            ]
+        );
+        assert_eq!(
+            keymap.sequence_completions("normal", &[Key::Char('g'), Key::Char('t')]),
+            vec![(Key::Char('x'), String::from("buffer::delete"))]
+        );
+    }
+
+    #[test]
+    fn sequence_completions_is_empty_without_matching_sequences() {
+        let yaml_data = "normal:\n  g g: cursor::move_to_first_line";
+        let yaml = YamlLoader::load_from_str(yaml_data).unwrap();
+        let keymap = KeyMap::from(&yaml[0].as_hash().unwrap()).unwrap();
+
+        assert!(keymap.sequence_completions("normal", &[Key::Char('x')]).is_empty());
+    }
+
     #[test]
     fn keymap_correctly_parses_multiple_yaml_keybindings() {
         // Build the keymap
@@ -412,11 +853,11 @@ mod tests {
             "Keymap doesn't contain command",
         );
         assert_eq!(
-            (command[0] as *const usize),
+            (command[0].command as *const usize),
             (commands::cursor::move_up as *const usize)
         );
         assert_eq!(
-            (command[1] as *const usize),
+            (command[1].command as *const usize),
             (commands::cursor::move_down as *const usize)
         );
     }
@@ -1,7 +1,11 @@
+pub use self::key_buffer::KeyBuffer;
 pub use self::key_map::KeyMap;
 
+mod key_buffer;
 mod key_map;
 
+use std::fmt;
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Key {
     Backspace,
@@ -17,8 +21,40 @@ pub enum Key {
     Insert,
     Esc,
     Tab,
+    BackTab,
     Enter,
     AnyChar,
     Char(char),
     Ctrl(char),
+    Alt(char),
+    F(u8),
+}
+
+impl fmt::Display for Key {
+    /// Formats the key as it would be written in a keymap (e.g. "ctrl-r").
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Key::Backspace => write!(f, "backspace"),
+            Key::Left      => write!(f, "left"),
+            Key::Right     => write!(f, "right"),
+            Key::Up        => write!(f, "up"),
+            Key::Down      => write!(f, "down"),
+            Key::Home      => write!(f, "home"),
+            Key::End       => write!(f, "end"),
+            Key::PageUp    => write!(f, "page_up"),
+            Key::PageDown  => write!(f, "page_down"),
+            Key::Delete    => write!(f, "delete"),
+            Key::Insert    => write!(f, "insert"),
+            Key::Esc       => write!(f, "escape"),
+            Key::Tab       => write!(f, "tab"),
+            Key::BackTab   => write!(f, "shift-tab"),
+            Key::Enter     => write!(f, "enter"),
+            Key::AnyChar   => write!(f, "_"),
+            Key::Char(' ') => write!(f, "space"),
+            Key::Char(c)   => write!(f, "{}", c),
+            Key::Ctrl(c)   => write!(f, "ctrl-{}", c),
+            Key::Alt(c)    => write!(f, "alt-{}", c),
+            Key::F(n)      => write!(f, "f{}", n),
+        }
+    }
 }
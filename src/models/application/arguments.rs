@@ -0,0 +1,79 @@
+use errors::*;
+
+/// Command-line arguments, separated into paths to open,
+/// a line to which the cursor should initially be moved (`+N`),
+/// and commands to run once the application has started (`-c command`).
+#[derive(Debug, Default, PartialEq)]
+pub struct Arguments {
+    pub paths: Vec<String>,
+    pub line: Option<usize>,
+    pub commands: Vec<String>,
+}
+
+impl Arguments {
+    /// Parses the full argument list, including the executable portion.
+    pub fn parse(args: &[String]) -> Result<Arguments> {
+        let mut arguments = Arguments::default();
+        let mut args = args.iter().skip(1);
+
+        while let Some(arg) = args.next() {
+            if arg == "-c" {
+                let command = args.next().ok_or("Missing command name after -c")?;
+                arguments.commands.push(command.clone());
+            } else if arg.starts_with('+') && arg.len() > 1 {
+                let line = arg[1..]
+                    .parse::<usize>()
+                    .chain_err(|| format!("Couldn't parse a line number from \"{}\"", arg))?;
+                arguments.line = Some(line);
+            } else {
+                arguments.paths.push(arg.clone());
+            }
+        }
+
+        Ok(arguments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arguments;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_skips_the_executable_and_treats_plain_arguments_as_paths() {
+        let arguments = Arguments::parse(&args(&["amp", "src", "Cargo.toml"])).unwrap();
+
+        assert_eq!(arguments.paths, args(&["src", "Cargo.toml"]));
+        assert_eq!(arguments.line, None);
+        assert!(arguments.commands.is_empty());
+    }
+
+    #[test]
+    fn parse_separates_line_and_command_flags_from_paths() {
+        let arguments = Arguments::parse(
+            &args(&["amp", "+42", "file.rs", "-c", "application::switch_to_command_mode"])
+        ).unwrap();
+
+        assert_eq!(
+            arguments,
+            Arguments {
+                paths: args(&["file.rs"]),
+                line: Some(42),
+                commands: args(&["application::switch_to_command_mode"]),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_fails_for_invalid_line_numbers() {
+        assert!(Arguments::parse(&args(&["amp", "+amp"])).is_err());
+    }
+
+    #[test]
+    fn parse_fails_when_command_flag_is_missing_its_value() {
+        assert!(Arguments::parse(&args(&["amp", "-c"])).is_err());
+    }
+}
@@ -1,12 +1,17 @@
 use errors::*;
 use clipboard::{ClipboardContext, ClipboardProvider};
+use scribe::buffer::Range;
+use std::collections::VecDeque;
+
+/// The number of previously copied entries retained for cycling after a paste.
+const RING_SIZE: usize = 10;
 
 /// In-app content can be captured in both regular and full-line selection
 /// modes. This type describes the structure of said content, based on the
 /// context in which it was captured. When OS-level clipboard contents are
 /// used, they are always represented as inline, as we cannot infer block
 /// style without the copy context.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ClipboardContent {
     Inline(String),
     Block(String),
@@ -18,9 +23,22 @@ pub enum ClipboardContent {
 /// in scenarios where it differs from the in-app equivalent).
 pub struct Clipboard {
     content: ClipboardContent,
+    ring: VecDeque<ClipboardContent>,
     system_clipboard: Option<ClipboardContext>,
 }
 
+/// The content inserted by the most recent paste, tracked so that it can be
+/// replaced with older clipboard entries, provided nothing has happened since.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Paste {
+    pub buffer_id: usize,
+    pub range: Range,
+    pub ring_index: usize,
+    /// Whether the paste happened during the current input, as opposed to
+    /// the previous one; stale pastes are discarded after each input.
+    pub fresh: bool,
+}
+
 impl Default for Clipboard {
     fn default() -> Self {
         Self::new()
@@ -37,6 +55,7 @@ impl Clipboard {
 
         Clipboard {
             content: ClipboardContent::None,
+            ring: VecDeque::new(),
             system_clipboard,
         }
     }
@@ -77,8 +96,9 @@ impl Clipboard {
         };
 
         // Update the in-app clipboard if we've found newer content.
-        if new_content.is_some() {
-            self.content = new_content.unwrap();
+        if let Some(content) = new_content {
+            self.push_ring_entry(content.clone());
+            self.content = content;
         }
 
         &self.content
@@ -87,6 +107,7 @@ impl Clipboard {
     // Updates the in-app and system clipboards with the specified content.
     pub fn set_content(&mut self, content: ClipboardContent) -> Result<()> {
         // Update the in-app clipboard.
+        self.push_ring_entry(content.clone());
         self.content = content;
 
         // Update the system clipboard.
@@ -104,4 +125,50 @@ impl Clipboard {
 
         Ok(())
     }
+
+    /// Returns previously copied content, where zero is the most recent entry.
+    pub fn ring_entry(&self, index: usize) -> Option<&ClipboardContent> {
+        self.ring.get(index)
+    }
+
+    pub fn ring_len(&self) -> usize {
+        self.ring.len()
+    }
+
+    fn push_ring_entry(&mut self, content: ClipboardContent) {
+        if content == ClipboardContent::None || self.ring.front() == Some(&content) {
+            return;
+        }
+
+        self.ring.push_front(content);
+        self.ring.truncate(RING_SIZE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clipboard, ClipboardContent, RING_SIZE};
+
+    #[test]
+    fn set_content_adds_entries_to_the_ring_most_recent_first() {
+        let mut clipboard = Clipboard::new();
+        for content in &["amp", "editor", "editor"] {
+            clipboard.set_content(ClipboardContent::Inline(content.to_string())).unwrap();
+        }
+
+        assert_eq!(clipboard.ring_len(), 2);
+        assert_eq!(clipboard.ring_entry(0), Some(&ClipboardContent::Inline("editor".to_string())));
+        assert_eq!(clipboard.ring_entry(1), Some(&ClipboardContent::Inline("amp".to_string())));
+    }
+
+    #[test]
+    fn set_content_limits_the_ring_size() {
+        let mut clipboard = Clipboard::new();
+        for index in 0..RING_SIZE + 1 {
+            clipboard.set_content(ClipboardContent::Block(index.to_string())).unwrap();
+        }
+
+        assert_eq!(clipboard.ring_len(), RING_SIZE);
+        assert_eq!(clipboard.ring_entry(RING_SIZE - 1), Some(&ClipboardContent::Block("1".to_string())));
+    }
 }
@@ -1,9 +1,80 @@
+use errors::*;
 use input::Key;
 use models::application::modes::open::Index;
+use models::application::QuickfixEntry;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// The maximum number of queued events handled between renders. This keeps
+/// the screen updating even if events arrive faster than they're handled.
+const MAX_BATCH_SIZE: usize = 100;
 
 #[derive(Debug, PartialEq)]
 pub enum Event {
     Key(Key),
     Resize,
-    OpenModeIndexComplete(Index)
+    OpenModeIndexComplete(Index),
+    WorkspaceSearchComplete(String, ::std::result::Result<Vec<QuickfixEntry>, String>)
+}
+
+/// Blocks until an event is available, and then collects any others that
+/// have already been queued, so that they can all be handled before the
+/// next render. A lone event is returned as soon as it's received. If a
+/// timeout is provided and elapses first, an empty batch is returned.
+pub fn next_batch(events: &Receiver<Event>, timeout: Option<Duration>) -> Result<Vec<Event>> {
+    let first_event = match timeout {
+        Some(timeout) => match events.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => return Ok(Vec::new()),
+            Err(RecvTimeoutError::Disconnected) => bail!("Error receiving application event"),
+        },
+        None => events.recv().chain_err(|| "Error receiving application event")?,
+    };
+    let mut batch = vec![first_event];
+
+    while batch.len() < MAX_BATCH_SIZE {
+        match events.try_recv() {
+            Ok(event) => batch.push(event),
+            Err(_) => break,
+        }
+    }
+
+    Ok(batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use input::Key;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use super::{Event, next_batch};
+
+    #[test]
+    fn next_batch_collects_queued_events() {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..5 {
+            tx.send(Event::Key(Key::Char('j'))).unwrap();
+        }
+
+        let batch = next_batch(&rx, None).unwrap();
+
+        assert_eq!(batch.len(), 5);
+        assert!(batch.iter().all(|event| *event == Event::Key(Key::Char('j'))));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn next_batch_returns_a_lone_event_immediately() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(Event::Resize).unwrap();
+
+        assert_eq!(next_batch(&rx, None).unwrap(), vec![Event::Resize]);
+    }
+
+    #[test]
+    fn next_batch_returns_an_empty_batch_after_timeout() {
+        let (_tx, rx) = mpsc::channel::<Event>();
+
+        assert!(next_batch(&rx, Some(Duration::from_millis(1))).unwrap().is_empty());
+    }
 }
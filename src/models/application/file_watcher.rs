@@ -0,0 +1,132 @@
+use std::mem;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the current buffer's file is checked for changes.
+const CHECK_INTERVAL_MS: u64 = 1000;
+
+/// The response to a buffer's file being changed outside of the editor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExternalChange {
+    /// The buffer has no local edits, so it can be reloaded automatically.
+    Reload,
+    /// Reloading would discard local edits, so ask first.
+    Confirm,
+}
+
+/// Polls the current buffer's file for changes made outside of the editor
+/// (e.g. by a build), by comparing its modification time against the one
+/// observed during the previous check.
+pub struct FileWatcher {
+    watched: Option<(usize, SystemTime)>,
+    last_check: Instant,
+}
+
+impl FileWatcher {
+    pub fn new(now: Instant) -> FileWatcher {
+        FileWatcher {
+            watched: None,
+            last_check: now,
+        }
+    }
+
+    /// The time remaining before the next check is due.
+    pub fn remaining(&self, now: Instant) -> Duration {
+        let interval = Duration::from_millis(CHECK_INTERVAL_MS);
+        let elapsed = now.duration_since(self.last_check);
+        if elapsed >= interval {
+            Duration::from_millis(0)
+        } else {
+            interval - elapsed
+        }
+    }
+
+    /// Returns true, starting a new interval, if a check is due.
+    pub fn check_due(&mut self, now: Instant) -> bool {
+        if self.remaining(now) > Duration::from_millis(0) {
+            return false;
+        }
+        self.last_check = now;
+
+        true
+    }
+
+    /// Records the modification time of the buffer's file, returning the
+    /// response required if it's changed since the previous observation.
+    /// Observing a different buffer starts watching it anew.
+    pub fn observe(&mut self, buffer_id: usize, modified_at: SystemTime,
+                   buffer_modified: bool) -> Option<ExternalChange> {
+        let previous = mem::replace(&mut self.watched, Some((buffer_id, modified_at)));
+
+        match previous {
+            Some((id, previous_modified_at)) if id == buffer_id && previous_modified_at != modified_at => {
+                if buffer_modified {
+                    Some(ExternalChange::Confirm)
+                } else {
+                    Some(ExternalChange::Reload)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Forgets the observed modification time (e.g. after saving), so that
+    /// changes made by the editor itself aren't mistaken for external ones.
+    pub fn reset(&mut self) {
+        self.watched = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExternalChange, FileWatcher};
+    use std::time::{Duration, Instant, SystemTime};
+
+    #[test]
+    fn observe_reloads_unmodified_buffers_when_their_file_changes() {
+        let mut watcher = FileWatcher::new(Instant::now());
+        let modified_at = SystemTime::now();
+
+        assert_eq!(watcher.observe(1, modified_at, false), None);
+        assert_eq!(watcher.observe(1, modified_at, false), None);
+        assert_eq!(
+            watcher.observe(1, modified_at + Duration::from_secs(1), false),
+            Some(ExternalChange::Reload)
+        );
+    }
+
+    #[test]
+    fn observe_confirms_before_discarding_local_edits() {
+        let mut watcher = FileWatcher::new(Instant::now());
+        let modified_at = SystemTime::now();
+
+        assert_eq!(watcher.observe(1, modified_at, true), None);
+        assert_eq!(
+            watcher.observe(1, modified_at + Duration::from_secs(1), true),
+            Some(ExternalChange::Confirm)
+        );
+    }
+
+    #[test]
+    fn observe_ignores_buffer_switches_and_resets() {
+        let mut watcher = FileWatcher::new(Instant::now());
+        let modified_at = SystemTime::now();
+        let later = modified_at + Duration::from_secs(1);
+
+        watcher.observe(1, modified_at, false);
+        assert_eq!(watcher.observe(2, later, false), None);
+
+        watcher.reset();
+        assert_eq!(watcher.observe(2, modified_at, false), None);
+    }
+
+    #[test]
+    fn check_due_is_true_once_per_interval() {
+        let start = Instant::now();
+        let mut watcher = FileWatcher::new(start);
+
+        assert!(!watcher.check_due(start + Duration::from_millis(500)));
+        assert!(watcher.check_due(start + Duration::from_millis(1000)));
+        assert!(!watcher.check_due(start + Duration::from_millis(1500)));
+        assert!(watcher.check_due(start + Duration::from_millis(2000)));
+    }
+}
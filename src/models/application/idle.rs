@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+
+/// The action taken once the application has gone without input for
+/// longer than the configured idle timeout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IdleAction {
+    Save,
+    Lock,
+}
+
+/// Tracks the time of the most recent input, so that an idle action can
+/// be triggered once (and only once) per period of inactivity.
+pub struct IdleTimer {
+    last_input: Instant,
+    triggered: bool,
+}
+
+impl IdleTimer {
+    pub fn new(now: Instant) -> IdleTimer {
+        IdleTimer {
+            last_input: now,
+            triggered: false,
+        }
+    }
+
+    /// Starts a new period of activity.
+    pub fn record_input(&mut self, at: Instant) {
+        self.last_input = at;
+        self.triggered = false;
+    }
+
+    /// The time remaining before the current period of inactivity exceeds
+    /// the timeout, or None if its idle action has already been triggered.
+    pub fn remaining(&self, timeout: Duration, now: Instant) -> Option<Duration> {
+        if self.triggered {
+            return None;
+        }
+
+        let elapsed = now.duration_since(self.last_input);
+        if elapsed >= timeout {
+            Some(Duration::from_millis(0))
+        } else {
+            Some(timeout - elapsed)
+        }
+    }
+
+    /// Returns true if the timeout has elapsed since the last input, unless
+    /// that's already been reported for the current period of inactivity.
+    pub fn check(&mut self, timeout: Duration, now: Instant) -> bool {
+        if self.remaining(timeout, now) == Some(Duration::from_millis(0)) {
+            self.triggered = true;
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdleTimer;
+    use std::time::{Duration, Instant};
+
+    fn timer_with_inputs(start: Instant, inputs: &[u64]) -> IdleTimer {
+        let mut timer = IdleTimer::new(start);
+        for &offset in inputs {
+            timer.record_input(start + Duration::from_secs(offset));
+        }
+
+        timer
+    }
+
+    #[test]
+    fn check_is_false_until_the_timeout_elapses_after_the_last_input() {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(60);
+        let mut timer = timer_with_inputs(start, &[10, 50, 100]);
+
+        assert!(!timer.check(timeout, start + Duration::from_secs(120)));
+        assert!(!timer.check(timeout, start + Duration::from_secs(159)));
+        assert!(timer.check(timeout, start + Duration::from_secs(160)));
+    }
+
+    #[test]
+    fn check_only_triggers_once_per_period_of_inactivity() {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(60);
+        let mut timer = timer_with_inputs(start, &[]);
+
+        assert!(timer.check(timeout, start + Duration::from_secs(60)));
+        assert!(!timer.check(timeout, start + Duration::from_secs(120)));
+
+        timer.record_input(start + Duration::from_secs(130));
+        assert!(!timer.check(timeout, start + Duration::from_secs(180)));
+        assert!(timer.check(timeout, start + Duration::from_secs(190)));
+    }
+
+    #[test]
+    fn remaining_counts_down_from_the_last_input() {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(60);
+        let mut timer = timer_with_inputs(start, &[30]);
+
+        assert_eq!(
+            timer.remaining(timeout, start + Duration::from_secs(40)),
+            Some(Duration::from_secs(50))
+        );
+        assert_eq!(
+            timer.remaining(timeout, start + Duration::from_secs(100)),
+            Some(Duration::from_millis(0))
+        );
+
+        timer.check(timeout, start + Duration::from_secs(100));
+        assert_eq!(timer.remaining(timeout, start + Duration::from_secs(100)), None);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+/// The number of messages retained before the oldest are discarded.
+const DEFAULT_CAPACITY: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MessageKind {
+    Error,
+    Notice,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub kind: MessageKind,
+    pub content: String,
+    pub time: SystemTime,
+}
+
+/// A fixed-capacity record of recent errors and notices, kept so that
+/// messages that flash by in the status line can be reviewed later.
+pub struct MessageHistory {
+    capacity: usize,
+    messages: VecDeque<Message>,
+}
+
+impl MessageHistory {
+    pub fn new() -> MessageHistory {
+        MessageHistory::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> MessageHistory {
+        MessageHistory {
+            capacity,
+            messages: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Adds a message to the history, discarding the oldest
+    /// message if the history has reached its capacity.
+    pub fn push<T: Into<String>>(&mut self, kind: MessageKind, content: T) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+
+        self.messages.push_back(Message {
+            kind,
+            content: content.into(),
+            time: SystemTime::now(),
+        });
+    }
+
+    /// Iterates over messages in the order they were added (newest last).
+    pub fn iter(&self) -> ::std::collections::vec_deque::Iter<Message> {
+        self.messages.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MessageHistory, MessageKind};
+
+    #[test]
+    fn push_adds_messages_newest_last() {
+        let mut history = MessageHistory::new();
+        history.push(MessageKind::Error, "first");
+        history.push(MessageKind::Notice, "second");
+
+        let messages: Vec<(MessageKind, &str)> = history
+            .iter()
+            .map(|message| (message.kind, message.content.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![(MessageKind::Error, "first"), (MessageKind::Notice, "second")]
+        );
+    }
+
+    #[test]
+    fn push_discards_oldest_messages_beyond_capacity() {
+        let mut history = MessageHistory::with_capacity(3);
+        for content in &["one", "two", "three", "four", "five"] {
+            history.push(MessageKind::Notice, *content);
+        }
+
+        let contents: Vec<&str> = history
+            .iter()
+            .map(|message| message.content.as_str())
+            .collect();
+        assert_eq!(contents, vec!["three", "four", "five"]);
+    }
+}
@@ -1,44 +1,84 @@
+mod arguments;
 mod clipboard;
 mod event;
+mod file_watcher;
+mod idle;
+mod message_history;
 pub mod modes;
+mod operator;
 mod preferences;
+pub mod quickfix;
+pub mod session;
+mod start_screen;
+mod undo_history;
 
 // Published API
-pub use self::clipboard::ClipboardContent;
+pub use self::clipboard::{ClipboardContent, Paste};
 pub use self::event::Event;
+pub use self::idle::IdleAction;
+pub use self::message_history::{MessageHistory, MessageKind};
+pub use self::operator::{Operator, PendingOperator};
 pub use self::preferences::Preferences;
+pub use self::quickfix::QuickfixEntry;
+pub use self::session::Session;
+pub use self::start_screen::StartScreen;
+pub use self::undo_history::{PendingChange, UndoHistory};
 
+use self::arguments::Arguments;
 use self::clipboard::Clipboard;
+use self::file_watcher::{ExternalChange, FileWatcher};
+use self::idle::IdleTimer;
 use self::modes::*;
 use commands;
 use errors::*;
 use git2::Repository;
+use input::{Key, KeyBuffer};
 use presenters;
 use scribe::{Buffer, Workspace};
+use scribe::buffer::{Position, Range};
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::fs;
 use std::ops::Drop;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
+use std::mem;
+use std::time::{Duration, Instant, SystemTime};
+use util::{self, error_log, line_ending, FileFormat, SelectableVec};
+use util::blame::BlameCache;
 use view::terminal::*;
 use view::{self, StatusLineData, View};
 
+/// How long a key sequence must be pending before its completions are shown.
+const KEY_SEQUENCE_HELP_DELAY_MS: u64 = 500;
+
 pub enum Mode {
     Confirm(ConfirmMode),
     Command(CommandMode),
+    DiffBuffers(SelectableListMode),
     Exit,
+    FindChar(FindCharMode),
     Insert,
     Jump(JumpMode),
+    LineFilter(LineFilterMode),
     LineJump(LineJumpMode),
+    Lock(Box<Mode>),
     Path(PathMode),
+    Quickfix(QuickfixMode),
+    ReplaceChar,
     Normal,
     Open(OpenMode),
+    Outline,
     Select(SelectMode),
     SelectLine(SelectLineMode),
     Search(SearchMode),
+    Separator,
+    ShellCommand(ShellCommandMode),
     SymbolJump(SymbolJumpMode),
+    Syntax(SelectableListMode),
     Theme(ThemeMode),
 }
 
@@ -52,11 +92,34 @@ pub struct Application {
     pub error: Option<Error>,
     pub preferences: Rc<RefCell<Preferences>>,
     pub event_channel: Sender<Event>,
+    pub file_formats: HashMap<usize, FileFormat>,
+    pub message_history: MessageHistory,
+    pub key_buffer: KeyBuffer,
+    pub blame: Option<BlameCache>,
+    pub exchange_mark: Option<(usize, Range)>,
+    pub last_char_search: Option<(CharSearch, char)>,
+    pub pending_count: Option<usize>,
+    pub pending_operator: Option<PendingOperator>,
+    pub selections: Vec<Range>,
+    pub last_selection: Option<(usize, Position, Position)>,
+    pub quickfix: SelectableVec<QuickfixEntry>,
+    pub last_palette_command: Option<&'static str>,
+    pub last_paste: Option<Paste>,
+    pub undo_histories: HashMap<usize, UndoHistory>,
+    pub read_only_buffers: HashSet<usize>,
+    pub pending_change: Option<PendingChange>,
+    pub change_group: Option<usize>,
+    pub start_screen: Option<StartScreen>,
+    pub closed_buffers: Vec<(PathBuf, Position)>,
+    pub outline: Option<Outline>,
+    idle_timer: IdleTimer,
+    pub file_watcher: FileWatcher,
     events: Receiver<Event>,
 }
 
 impl Application {
     pub fn new(args: &Vec<String>) -> Result<Application> {
+        let arguments = Arguments::parse(args)?;
         let preferences = initialize_preferences();
 
         let (event_channel, events) = mpsc::channel();
@@ -64,9 +127,10 @@ impl Application {
         let clipboard = Clipboard::new();
 
         // Set up a workspace in the current directory.
-        let workspace = create_workspace(&mut view, args)?;
+        let mut file_formats = HashMap::new();
+        let workspace = create_workspace(&mut view, &mut file_formats, &arguments.paths)?;
 
-        Ok(Application {
+        let mut app = Application {
             mode: Mode::Normal,
             workspace,
             search_query: None,
@@ -76,14 +140,70 @@ impl Application {
             error: None,
             preferences,
             event_channel,
+            file_formats,
+            message_history: MessageHistory::new(),
+            key_buffer: KeyBuffer::new(),
+            blame: None,
+            exchange_mark: None,
+            last_char_search: None,
+            pending_count: None,
+            pending_operator: None,
+            selections: Vec::new(),
+            last_selection: None,
+            quickfix: SelectableVec::new(Vec::new()),
+            last_palette_command: None,
+            last_paste: None,
+            undo_histories: HashMap::new(),
+            read_only_buffers: HashSet::new(),
+            pending_change: None,
+            change_group: None,
+            start_screen: None,
+            closed_buffers: Vec::new(),
+            outline: None,
+            idle_timer: IdleTimer::new(Instant::now()),
+            file_watcher: FileWatcher::new(Instant::now()),
             events,
-        })
+        };
+
+        // Greet launches without any files using a start screen.
+        if app.workspace.current_buffer().is_none() && app.preferences.borrow().start_screen() {
+            app.start_screen = Some(StartScreen::new(&app.workspace.path));
+        }
+        app.apply_arguments(&arguments)?;
+
+        Ok(app)
+    }
+
+    /// Moves to the argument-specified line and runs any
+    /// argument-specified commands, in the order they were provided.
+    fn apply_arguments(&mut self, arguments: &Arguments) -> Result<()> {
+        if let Some(line) = arguments.line {
+            if let Some(buffer) = self.workspace.current_buffer() {
+                // Line arguments aren't zero-indexed; fall
+                // back to the last line if it's out of range.
+                let target_line = line.saturating_sub(1);
+                if !buffer.cursor.move_to(Position{ line: target_line, offset: 0 }) {
+                    buffer.cursor.move_to_last_line();
+                }
+            }
+            let _ = commands::view::scroll_cursor_to_center(self);
+        }
+
+        let command_map = commands::hash_map();
+        for name in arguments.commands.iter() {
+            let command = command_map
+                .get(name.as_str())
+                .ok_or_else(|| format!("Couldn't find the \"{}\" command", name))?;
+            command(self).chain_err(|| format!("Failed to run the \"{}\" command", name))?;
+        }
+
+        Ok(())
     }
 
     pub fn run(&mut self) -> Result<()> {
         loop {
             self.render();
-            self.wait_for_event()?;
+            self.wait_for_events()?;
 
             if let Mode::Exit = self.mode {
                 break;
@@ -93,44 +213,122 @@ impl Application {
         Ok(())
     }
 
-    fn render(&mut self) {
+    /// Draws the current state of the application to the screen. This is done
+    /// after handling each event, but commands that block (e.g. running shell
+    /// commands) can use this to show their progress beforehand.
+    pub fn render(&mut self) {
         if let Err(error) = self.present() {
+            self.record_error(&error);
             render_error(&mut self.view, &error);
         } else if let Some(ref error) = self.error {
             // Display an error from previous command invocation, if one exists.
             render_error(&mut self.view, error);
         }
+
+        self.render_key_sequence_help();
+    }
+
+    /// Lists the ways a pending key sequence can be completed, once it's been
+    /// pending long enough to suggest that the user could use a reminder.
+    fn render_key_sequence_help(&mut self) {
+        if self.key_sequence_help_delay() != Some(Duration::from_millis(0)) {
+            return;
+        }
+
+        let completions = match self.mode_str() {
+            Some(mode) => self.preferences.borrow().keymap().sequence_completions(
+                mode,
+                self.key_buffer.pending()
+            ),
+            None => return,
+        };
+
+        if !completions.is_empty() {
+            // Don't abandon the sequence while its completions are being read.
+            self.key_buffer.hold();
+            presenters::key_sequence::display(&mut self.view, &completions);
+        }
+    }
+
+    /// The time remaining before a pending key sequence's completions
+    /// should be shown, or None if there isn't a sequence pending.
+    fn key_sequence_help_delay(&self) -> Option<Duration> {
+        let delay = Duration::from_millis(KEY_SEQUENCE_HELP_DELAY_MS);
+
+        self.key_buffer.pending_duration().map(|elapsed| {
+            if elapsed >= delay {
+                Duration::from_millis(0)
+            } else {
+                delay - elapsed
+            }
+        })
     }
 
     fn present(&mut self) -> Result<()> {
+        // Keep the outline pane, if it's shown, in step with the buffer.
+        self.update_outline();
+        let outline_focused = if let Mode::Outline = self.mode { true } else { false };
+        let sidebar = self.outline
+            .as_ref()
+            .map(|outline| presenters::outline::sidebar(outline, outline_focused, &self.view));
+        self.view.set_sidebar(sidebar);
+
+        if let Mode::Normal = self.mode {
+            if self.workspace.current_buffer().is_none() {
+                if let Some(ref start_screen) = self.start_screen {
+                    return presenters::start_screen::display(start_screen, &self.workspace.path, &mut self.view);
+                }
+            }
+        }
+
         match self.mode {
-            Mode::Confirm(_) => {
-                presenters::modes::confirm::display(&mut self.workspace, &mut self.view)
+            Mode::Confirm(ref mode) => {
+                presenters::modes::confirm::display(&mut self.workspace, mode, &mut self.view)
             }
             Mode::Command(ref mut mode) => {
                 presenters::modes::search_select::display(&mut self.workspace, mode, &mut self.view)
             }
+            Mode::FindChar(ref mode) => {
+                presenters::modes::find_char::display(&mut self.workspace, mode, &mut self.view)
+            }
             Mode::Insert => presenters::modes::insert::display(&mut self.workspace, &mut self.view),
+            Mode::ReplaceChar => {
+                presenters::modes::replace_char::display(&mut self.workspace, &mut self.view)
+            }
             Mode::Open(ref mut mode) => {
                 presenters::modes::search_select::display(&mut self.workspace, mode, &mut self.view)
             }
+            Mode::Outline => presenters::modes::outline::display(&mut self.workspace, &mut self.view),
             Mode::Search(ref mode) => {
                 presenters::modes::search::display(&mut self.workspace, mode, &mut self.view)
             }
+            Mode::Separator => {
+                presenters::modes::separator::display(&mut self.workspace, &mut self.view)
+            }
             Mode::Jump(ref mut mode) => {
                 presenters::modes::jump::display(&mut self.workspace, mode, &mut self.view)
             }
             Mode::LineJump(ref mode) => {
                 presenters::modes::line_jump::display(&mut self.workspace, mode, &mut self.view)
             }
+            Mode::Lock(_) => presenters::modes::lock::display(&mut self.view),
             Mode::Path(ref mode) => {
                 presenters::modes::path::display(&mut self.workspace, mode, &mut self.view)
             }
+            Mode::ShellCommand(ref mode) => {
+                presenters::modes::shell_command::display(&mut self.workspace, mode, &mut self.view)
+            }
+            Mode::LineFilter(ref mode) => {
+                presenters::modes::line_filter::display(&mut self.workspace, mode, &mut self.view)
+            }
             Mode::SymbolJump(ref mut mode) => {
                 presenters::modes::search_select::display(&mut self.workspace, mode, &mut self.view)
             }
+            Mode::Quickfix(ref mut mode) => {
+                presenters::modes::search_select::display(&mut self.workspace, mode, &mut self.view)
+            }
             Mode::Select(ref mode) => {
-                presenters::modes::select::display(&mut self.workspace, mode, &mut self.view)
+                presenters::modes::select::display(&mut self.workspace, mode, &self.selections, &mut self.view)
             }
             Mode::SelectLine(ref mode) => {
                 presenters::modes::select_line::display(&mut self.workspace, mode, &mut self.view)
@@ -139,25 +337,196 @@ impl Application {
                 &mut self.workspace,
                 &mut self.view,
                 &self.repository,
+                &mut self.blame,
             ),
             Mode::Theme(ref mut mode) => {
                 presenters::modes::search_select::display(&mut self.workspace, mode, &mut self.view)
             }
+            Mode::DiffBuffers(ref mut mode) => {
+                presenters::modes::search_select::display(&mut self.workspace, mode, &mut self.view)
+            }
+            Mode::Syntax(ref mut mode) => {
+                presenters::modes::search_select::display(&mut self.workspace, mode, &mut self.view)
+            }
             Mode::Exit => Ok(()),
         }
     }
 
-    fn wait_for_event(&mut self) -> Result<()> {
-        let event = self
-            .events
-            .recv()
-            .chain_err(|| "Error receiving application event")?;
+    /// Handles all of the events queued since the last render, so that a
+    /// flood of input (e.g. a held key) only triggers a single render.
+    fn wait_for_events(&mut self) -> Result<()> {
+        // Stop waiting when it's time to show a pending key sequence's
+        // completions, to give up on the sequence, or to trigger the idle
+        // action; an empty batch will result in a re-render.
+        let help_delay = self.key_sequence_help_delay().and_then(|delay| {
+            if delay > Duration::from_millis(0) {
+                Some(delay)
+            } else {
+                None
+            }
+        });
+        let idle_delay = self.preferences.borrow().idle_timeout().and_then(|timeout| {
+            self.idle_timer.remaining(timeout, Instant::now())
+        });
+        let watch_delay = if self.preferences.borrow().auto_reload() {
+            Some(self.file_watcher.remaining(Instant::now()))
+        } else {
+            None
+        };
+        let sequence_delay = self.key_buffer.remaining();
+        let timeout = vec![help_delay, sequence_delay, idle_delay, watch_delay]
+            .into_iter()
+            .filter_map(|delay| delay)
+            .min();
+
+        let events = event::next_batch(&self.events, timeout)?;
+        if events.is_empty() {
+            self.handle_idle_timeout();
+        }
+        if let Err(error) = commands::application::handle_key_sequence_timeout(self) {
+            self.record_error(&error);
+            self.error = Some(error);
+        }
+        self.check_for_external_changes();
+
+        for event in events {
+            self.handle_event(event);
+
+            // Don't handle anything else once we're exiting.
+            if let Mode::Exit = self.mode {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Triggers the configured idle action, if the application has
+    /// gone without input for longer than the idle timeout.
+    fn handle_idle_timeout(&mut self) {
+        let (timeout, action) = {
+            let preferences = self.preferences.borrow();
+            match preferences.idle_timeout() {
+                Some(timeout) => (timeout, preferences.idle_action()),
+                None => return,
+            }
+        };
+        if !self.idle_timer.check(timeout, Instant::now()) {
+            return;
+        }
+
+        match action {
+            IdleAction::Save => {
+                if self.workspace.current_buffer().is_some() {
+                    self.error = commands::buffer::save_all(self).err();
+                }
+            }
+            IdleAction::Lock => {
+                if let Mode::Lock(_) = self.mode {
+                    return;
+                }
+                let mode = mem::replace(&mut self.mode, Mode::Normal);
+                self.mode = Mode::Lock(Box::new(mode));
+            }
+        }
+    }
+
+    /// Reloads the current buffer if its file has been changed on disk, when
+    /// the `auto_reload` preference is enabled. Buffers with local edits
+    /// aren't reloaded without confirmation.
+    fn check_for_external_changes(&mut self) {
+        // Only check between commands, rather than in the middle of an
+        // insert session or selection, where a reload would be disruptive.
+        match self.mode {
+            Mode::Normal => (),
+            _ => return,
+        }
+        if !self.preferences.borrow().auto_reload() || !self.file_watcher.check_due(Instant::now()) {
+            return;
+        }
+
+        let change = match self.workspace.current_buffer() {
+            Some(buffer) => {
+                let modified_at = buffer.path
+                    .as_ref()
+                    .and_then(|path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok());
+                match (buffer.id, modified_at) {
+                    (Some(id), Some(modified_at)) => {
+                        self.file_watcher.observe(id, modified_at, buffer.modified())
+                    }
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+
+        match change {
+            Some(ExternalChange::Reload) => {
+                if let Err(error) = commands::buffer::reload(self) {
+                    self.record_error(&error);
+                    self.error = Some(error);
+                }
+            }
+            Some(ExternalChange::Confirm) => {
+                self.mode = Mode::Confirm(ConfirmMode::with_choices("File changed on disk", vec![
+                    Choice::new('r', "reload", commands::buffer::reload),
+                    Choice::new('k', "keep changes", commands::application::switch_to_normal_mode),
+                ]));
+            }
+            None => (),
+        }
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        if let Event::Key(_) = event {
+            self.idle_timer.record_input(Instant::now());
+
+            // Any key unlocks the screen, restoring the mode that
+            // was active beforehand; the key itself is discarded.
+            if let Mode::Lock(_) = self.mode {
+                if let Mode::Lock(mode) = mem::replace(&mut self.mode, Mode::Normal) {
+                    self.mode = *mode;
+                }
+                return;
+            }
+
+            // Any key dismisses the start screen, and is then handled as
+            // usual, unless it's the number of a listed recent file.
+            if let Some(start_screen) = self.start_screen.take() {
+                if let Event::Key(Key::Char(c)) = event {
+                    let recent_file = c.to_digit(10)
+                        .and_then(|number| start_screen.recent_file(number as usize))
+                        .cloned();
+                    if let Some(path) = recent_file {
+                        if let Err(error) = util::open_buffer(&path, self) {
+                            self.record_error(&error);
+                            self.error = Some(error);
+                        }
+                        return;
+                    }
+                }
+            }
+        }
+
         match event {
             Event::Key(key) => {
                 self.view.last_key = Some(key);
                 self.error = commands::application::handle_input(self).err();
+                if let Some(error) = self.error.take() {
+                    self.record_error(&error);
+                    self.error = Some(error);
+                }
+            }
+            Event::Resize => {
+                let result = {
+                    let buffer = self.workspace.current_buffer().map(|buffer| &*buffer);
+                    self.view.resize(buffer)
+                };
+                if let Err(error) = result {
+                    self.record_error(&error);
+                    self.error = Some(error);
+                }
             }
-            Event::Resize => {}
             Event::OpenModeIndexComplete(index) => {
                 if let Mode::Open(ref mut open_mode) = self.mode {
                     open_mode.set_index(index);
@@ -167,11 +536,99 @@ impl Application {
                     open_mode.search();
                 }
             }
+            Event::WorkspaceSearchComplete(query, results) => {
+                if let Err(error) = self.load_workspace_search_results(&query, results) {
+                    self.record_error(&error);
+                    self.error = Some(error);
+                }
+            }
+        }
+    }
+
+    /// Populates the quickfix list with the results of a workspace search
+    /// started by `quickfix::search_workspace`, and moves to the first.
+    fn load_workspace_search_results(&mut self, query: &str, results: ::std::result::Result<Vec<QuickfixEntry>, String>) -> Result<()> {
+        let entries = results.map_err(Error::from)
+            .chain_err(|| format!("Couldn't search the workspace for \"{}\"", query))?;
+        if entries.is_empty() {
+            bail!("No matches found for \"{}\" in the workspace", query);
         }
 
+        let entry_count = entries.len();
+        self.quickfix = SelectableVec::new(entries);
+        commands::quickfix::open_current(self)?;
+        self.notify(format!("Found {} matches for \"{}\"", entry_count, query));
+
         Ok(())
     }
 
+    /// Rebuilds the outline pane, if it's shown, when the current buffer or
+    /// its content changes. Outside of outline mode, its selection follows
+    /// the cursor.
+    pub fn update_outline(&mut self) {
+        let outline = match self.outline {
+            Some(ref mut outline) => outline,
+            None => return,
+        };
+        let buffer = match self.workspace.current_buffer() {
+            Some(buffer) => buffer,
+            None => {
+                *outline = Outline::new();
+                return;
+            }
+        };
+
+        outline.update(buffer, self.view.buffer_revision(buffer).ok());
+        match self.mode {
+            Mode::Outline => (),
+            _ => outline.select_at(&*buffer.cursor),
+        }
+    }
+
+    /// Consumes the numeric count typed ahead of a command (e.g. the
+    /// "5" in "5j"), defaulting to one when no count is pending.
+    pub fn take_count(&mut self) -> usize {
+        self.pending_count.take().unwrap_or(1)
+    }
+
+    /// The current buffer's revision, which increases with each change
+    /// to its content, but not with cursor movement or other actions.
+    pub fn current_buffer_revision(&mut self) -> Result<usize> {
+        let buffer = self.workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+
+        self.view.buffer_revision(buffer)
+    }
+
+    /// Records a notice in the message history.
+    pub fn notify<T: Into<String>>(&mut self, content: T) {
+        self.message_history.push(MessageKind::Notice, content);
+    }
+
+    /// Adds the error to the message history, as well as the error log.
+    fn record_error(&mut self, error: &Error) {
+        let mut content = error.to_string();
+        for cause in error.iter().skip(1) {
+            content.push_str(&format!("\ncaused by: {}", cause));
+        }
+        self.message_history.push(MessageKind::Error, content);
+
+        self.log_error(error);
+    }
+
+    /// Appends the error to the error log, if it's been enabled. Failing to
+    /// log shouldn't interrupt the user, so those errors are discarded.
+    fn log_error(&self, error: &Error) {
+        let entry = error_log::format_entry(
+            error,
+            self.mode_str().unwrap_or("unknown"),
+            SystemTime::now()
+        );
+
+        if let Ok(path) = self.preferences.borrow().error_log_path() {
+            let _ = error_log::append(path.as_ref().map(|p| p.as_path()), &entry);
+        }
+    }
+
     pub fn mode_str(&self) -> Option<&'static str> {
         match self.mode {
             Mode::Command(ref mode) => if mode.insert_mode() {
@@ -184,6 +641,11 @@ impl Application {
             } else {
                 Some("search_select")
             },
+            Mode::Quickfix(ref mode) => if mode.insert_mode() {
+                Some("search_select_insert")
+            } else {
+                Some("search_select")
+            },
             Mode::Open(ref mode) => if mode.insert_mode() {
                 Some("search_select_insert")
             } else {
@@ -194,14 +656,31 @@ impl Application {
             } else {
                 Some("search_select")
             },
+            Mode::DiffBuffers(ref mode) => if mode.insert_mode() {
+                Some("search_select_insert")
+            } else {
+                Some("search_select")
+            },
+            Mode::Syntax(ref mode) => if mode.insert_mode() {
+                Some("search_select_insert")
+            } else {
+                Some("search_select")
+            },
             Mode::Normal => Some("normal"),
             Mode::Path(_) => Some("path"),
             Mode::Confirm(_) => Some("confirm"),
+            Mode::FindChar(_) => Some("find_char"),
             Mode::Insert => Some("insert"),
             Mode::Jump(_) => Some("jump"),
+            Mode::LineFilter(_) => Some("line_filter"),
             Mode::LineJump(_) => Some("line_jump"),
+            Mode::Lock(_) => None,
+            Mode::Outline => Some("outline"),
+            Mode::ReplaceChar => Some("replace_char"),
             Mode::Select(_) => Some("select"),
             Mode::SelectLine(_) => Some("select_line"),
+            Mode::Separator => Some("separator"),
+            Mode::ShellCommand(_) => Some("shell_command"),
             Mode::Search(ref mode) => if mode.insert_mode() {
                 Some("search_insert")
             } else {
@@ -233,9 +712,8 @@ fn initialize_preferences() -> Rc<RefCell<Preferences>> {
     ))
 }
 
-fn create_workspace(view: &mut View, args: &Vec<String>) -> Result<Workspace> {
-    // Discard the executable portion of the argument list.
-    let mut path_args = args.iter().skip(1).peekable();
+fn create_workspace(view: &mut View, file_formats: &mut HashMap<usize, FileFormat>, paths: &[String]) -> Result<Workspace> {
+    let mut path_args = paths.iter().peekable();
 
     // Move into an argument-specified directory, if present.
     let initial_dir = env::current_dir()?;
@@ -263,8 +741,8 @@ fn create_workspace(view: &mut View, args: &Vec<String>) -> Result<Workspace> {
 
         // Open the specified path if it exists, or
         // create a new buffer pointing to it if it doesn't.
-        let mut argument_buffer = if path.exists() {
-            Buffer::from_file(path)?
+        let (argument_buffer, format) = if path.exists() {
+            line_ending::load_buffer(path)?
         } else {
             let mut buffer = Buffer::new();
 
@@ -275,10 +753,14 @@ fn create_workspace(view: &mut View, args: &Vec<String>) -> Result<Workspace> {
                 buffer.path = Some(workspace.path.join(path));
             }
 
-            buffer
+            (buffer, FileFormat::default())
         };
         workspace.add_buffer(argument_buffer);
-        view.initialize_buffer(workspace.current_buffer().unwrap())?;
+        let buffer = workspace.current_buffer().unwrap();
+        if let Some(id) = buffer.id {
+            file_formats.insert(id, format);
+        }
+        view.initialize_buffer(buffer)?;
     }
 
     // Add user syntax definitions.
@@ -304,10 +786,16 @@ fn build_terminal() -> Arc<Terminal + Sync + Send> {
 
 #[cfg(test)]
 mod tests {
-    use super::Application;
+    use super::{Application, Event, Mode};
+    use commands;
     use scribe::Buffer;
+    use scribe::buffer::Position;
     use std::env;
+    use std::fs;
     use std::path::Path;
+    use std::time::{Duration, Instant};
+    use util;
+    use util::test_helpers::TempDir;
 
     #[test]
     fn application_uses_file_arguments_to_load_contents_into_buffers_when_files_exist() {
@@ -325,6 +813,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn application_shows_a_start_screen_when_launched_without_file_arguments() {
+        let mut application = Application::new(&vec![String::new()]).unwrap();
+
+        assert!(application.start_screen.is_some());
+        assert!(application.workspace.current_buffer().is_none());
+    }
+
+    #[test]
+    fn application_skips_the_start_screen_when_launched_with_file_arguments() {
+        let application =
+            Application::new(&vec![String::new(), String::from("Cargo.lock")]).unwrap();
+
+        assert!(application.start_screen.is_none());
+    }
+
     #[test]
     fn application_uses_file_arguments_to_create_new_buffers_when_files_do_not_exist() {
         let mut application =
@@ -336,4 +840,106 @@ mod tests {
         );
         assert_eq!(application.workspace.current_buffer().unwrap().data(), "");
     }
+
+    #[test]
+    fn application_moves_to_line_argument_after_loading_files() {
+        let mut application = Application::new(&vec![
+            String::new(),
+            String::from("+3"),
+            String::from("Cargo.lock")
+        ]).unwrap();
+
+        assert_eq!(
+            *application.workspace.current_buffer().unwrap().cursor,
+            Position{ line: 2, offset: 0 }
+        );
+    }
+
+    #[test]
+    fn application_runs_command_arguments() {
+        let application = Application::new(&vec![
+            String::new(),
+            String::from("Cargo.lock"),
+            String::from("-c"),
+            String::from("application::switch_to_insert_mode")
+        ]).unwrap();
+
+        assert!(match application.mode {
+            Mode::Insert => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn application_fails_for_unknown_command_arguments() {
+        let result = Application::new(&vec![
+            String::new(),
+            String::from("-c"),
+            String::from("application::non_existent_command")
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn current_buffer_revision_increments_after_an_edit() {
+        let mut application = Application::new(&Vec::new()).unwrap();
+        util::add_buffer(Buffer::new(), &mut application).unwrap();
+        let revision = application.current_buffer_revision().unwrap();
+
+        application.workspace.current_buffer().unwrap().insert("amp");
+
+        assert!(application.current_buffer_revision().unwrap() > revision);
+    }
+
+    #[test]
+    fn current_buffer_revision_is_unchanged_by_cursor_movement() {
+        let mut application = Application::new(&Vec::new()).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        util::add_buffer(buffer, &mut application).unwrap();
+        let revision = application.current_buffer_revision().unwrap();
+
+        application.workspace.current_buffer().unwrap().cursor.move_to(Position{ line: 1, offset: 3 });
+
+        assert_eq!(application.current_buffer_revision().unwrap(), revision);
+    }
+
+    #[test]
+    fn workspace_search_results_populate_the_quickfix_list() {
+        let root = TempDir::new("workspace-search");
+        fs::write(root.join("first.txt"), "amp\neditor\namp\n").unwrap();
+        fs::write(root.join("second.txt"), "buffer\namp\n").unwrap();
+        let mut app = Application::new(&Vec::new()).unwrap();
+        app.workspace.path = root.path().to_path_buf();
+        app.search_query = Some(String::from("amp"));
+
+        // The search runs in the background, reporting back using an event
+        // (which may follow one queued by the test terminal's input listener).
+        commands::quickfix::search_workspace(&mut app).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let event = loop {
+            let now = Instant::now();
+            assert!(now < deadline, "Timed out waiting for the workspace search to complete");
+            match app.events.recv_timeout(deadline - now) {
+                Ok(event @ Event::WorkspaceSearchComplete(..)) => break event,
+                Ok(_) => continue,
+                Err(error) => panic!("Workspace search didn't complete: {}", error),
+            }
+        };
+        app.handle_event(event);
+
+        assert_eq!(
+            app.quickfix.iter().map(|entry| (entry.path.clone(), entry.line)).collect::<Vec<_>>(),
+            vec![
+                (root.join("first.txt"), 0),
+                (root.join("first.txt"), 2),
+                (root.join("second.txt"), 1),
+            ]
+        );
+        assert_eq!(
+            app.workspace.current_buffer().unwrap().path,
+            Some(root.join("first.txt"))
+        );
+    }
 }
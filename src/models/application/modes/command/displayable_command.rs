@@ -4,12 +4,17 @@ use commands::Command;
 // Utility type to make an Amp command function presentable (via the
 // Display trait), which is required for any type used in search/select mode.
 pub struct DisplayableCommand {
+    pub name: &'static str,
     pub description: &'static str,
     pub command: Command,
 }
 
 impl fmt::Display for DisplayableCommand {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.description)
+        if self.description.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}: {}", self.name, self.description)
+        }
     }
 }
@@ -1,18 +1,16 @@
 mod displayable_command;
 
-use fragment;
-use util::SelectableVec;
-use std::collections::HashMap;
+use util::{fuzzy_match, SelectableVec};
 use std::fmt;
 use std::slice::Iter;
 use models::application::modes::{SearchSelectMode, SearchSelectConfig};
-use commands::{self, Command};
+use commands::{self, RegisteredCommand};
 pub use self::displayable_command::DisplayableCommand;
 
 pub struct CommandMode {
     insert: bool,
     input: String,
-    commands: HashMap<&'static str, Command>,
+    commands: Vec<RegisteredCommand>,
     results: SelectableVec<DisplayableCommand>,
     config: SearchSelectConfig,
 }
@@ -22,7 +20,7 @@ impl CommandMode {
         CommandMode {
             insert: true,
             input: String::new(),
-            commands: commands::hash_map(),
+            commands: commands::registry(),
             results: SelectableVec::new(Vec::new()),
             config,
         }
@@ -37,10 +35,11 @@ impl fmt::Display for CommandMode {
 
 impl SearchSelectMode<DisplayableCommand> for CommandMode {
     fn search(&mut self) {
-        let commands: Vec<&'static str> = self.commands.keys().map(|k| *k).collect();
+        let commands: Vec<&'static str> =
+            self.commands.iter().map(|command| command.name).collect();
 
         // Find the commands we're looking for using the query.
-        let results = fragment::matching::find(
+        let results = fuzzy_match::find(
             &self.input,
             &commands,
             self.config.max_results
@@ -52,12 +51,16 @@ impl SearchSelectMode<DisplayableCommand> for CommandMode {
             results
             .into_iter()
             .filter_map(|result| {
-                self.commands.get(*result).map(|command| {
-                    DisplayableCommand{
-                      description: *result,
-                      command: *command
-                    }
-                })
+                self.commands
+                    .iter()
+                    .find(|command| command.name == *result)
+                    .map(|command| {
+                        DisplayableCommand{
+                          name: command.name,
+                          description: command.description,
+                          command: command.command
+                        }
+                    })
             })
             .collect()
         );
@@ -99,3 +102,26 @@ impl SearchSelectMode<DisplayableCommand> for CommandMode {
         &self.config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use commands::{self, Command};
+    use models::application::modes::{SearchSelectMode, SearchSelectConfig};
+    use super::CommandMode;
+
+    #[test]
+    fn search_includes_registered_command_descriptions() {
+        let mut mode = CommandMode::new(SearchSelectConfig::default());
+        mode.query().push_str("application::display_available_commands");
+        mode.search();
+
+        let result = mode
+            .results()
+            .find(|result| result.name == "application::display_available_commands")
+            .unwrap();
+        assert_eq!(result.description, "Opens a new buffer listing all available commands.");
+
+        let expected: Command = commands::application::display_available_commands;
+        assert_eq!(result.command as usize, expected as usize);
+    }
+}
@@ -1,11 +1,108 @@
-use commands::Command;
+use commands::{self, Command};
+use input::Key;
 
-pub struct ConfirmMode {
+/// A labelled option in a confirmation prompt, run when its key is pressed.
+pub struct Choice {
+    pub key: char,
+    pub label: String,
     pub command: Command,
 }
 
+impl Choice {
+    pub fn new<T: Into<String>>(key: char, label: T, command: Command) -> Choice {
+        Choice { key, label: label.into(), command }
+    }
+}
+
+pub struct ConfirmMode {
+    pub prompt: String,
+    pub choices: Vec<Choice>,
+}
+
 impl ConfirmMode {
+    /// Builds a yes/no prompt that runs the specified command when confirmed.
     pub fn new(command: Command) -> ConfirmMode {
-        ConfirmMode { command }
+        ConfirmMode::with_choices("Are you sure?", vec![
+            Choice::new('y', "yes", command),
+            Choice::new('n', "no", commands::application::switch_to_normal_mode),
+        ])
+    }
+
+    pub fn with_choices<T: Into<String>>(prompt: T, choices: Vec<Choice>) -> ConfirmMode {
+        ConfirmMode { prompt: prompt.into(), choices }
+    }
+
+    /// Finds the command for the choice bound to the specified key, if any.
+    pub fn command_for(&self, key: &Key) -> Option<Command> {
+        match *key {
+            Key::Char(c) => self.choices
+                .iter()
+                .find(|choice| choice.key == c)
+                .map(|choice| choice.command),
+            _ => None,
+        }
+    }
+
+    /// The prompt, followed by a summary of the available choices.
+    ///
+    /// e.g. "Are you sure? (y: yes, n: no)"
+    ///
+    pub fn status(&self) -> String {
+        let choices = self.choices
+            .iter()
+            .map(|choice| format!("{}: {}", choice.key, choice.label))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!("{} ({})", self.prompt, choices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commands::{self, Command};
+    use input::Key;
+    use super::{Choice, ConfirmMode};
+
+    fn unsaved_changes_mode() -> ConfirmMode {
+        ConfirmMode::with_choices("Unsaved changes", vec![
+            Choice::new('s', "save", commands::buffer::save),
+            Choice::new('d', "discard", commands::buffer::close),
+            Choice::new('c', "cancel", commands::application::switch_to_normal_mode),
+        ])
+    }
+
+    #[test]
+    fn command_for_resolves_each_bound_key_to_its_command() {
+        let mode = unsaved_changes_mode();
+
+        assert_eq!(
+            mode.command_for(&Key::Char('s')).map(|command| command as usize),
+            Some(commands::buffer::save as Command as usize)
+        );
+        assert_eq!(
+            mode.command_for(&Key::Char('d')).map(|command| command as usize),
+            Some(commands::buffer::close as Command as usize)
+        );
+        assert_eq!(
+            mode.command_for(&Key::Char('c')).map(|command| command as usize),
+            Some(commands::application::switch_to_normal_mode as Command as usize)
+        );
+    }
+
+    #[test]
+    fn command_for_ignores_unbound_keys() {
+        let mode = unsaved_changes_mode();
+
+        assert!(mode.command_for(&Key::Char('x')).is_none());
+        assert!(mode.command_for(&Key::Enter).is_none());
+    }
+
+    #[test]
+    fn status_lists_choices_after_the_prompt() {
+        assert_eq!(
+            unsaved_changes_mode().status(),
+            "Unsaved changes (s: save, d: discard, c: cancel)"
+        );
     }
 }
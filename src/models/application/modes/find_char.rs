@@ -0,0 +1,33 @@
+use util::token::Direction;
+
+/// An intra-line search for a character, which either lands on
+/// the character (find) or stops one short of it (till).
+#[derive(Clone, Copy)]
+pub struct CharSearch {
+    pub direction: Direction,
+    pub till: bool,
+}
+
+impl CharSearch {
+    /// The same search, performed in the opposite direction.
+    pub fn reversed(&self) -> CharSearch {
+        CharSearch {
+            direction: match self.direction {
+                Direction::Forward => Direction::Backward,
+                Direction::Backward => Direction::Forward,
+            },
+            till: self.till,
+        }
+    }
+}
+
+/// Captures the character targeted by a pending search.
+pub struct FindCharMode {
+    pub search: CharSearch,
+}
+
+impl FindCharMode {
+    pub fn new(search: CharSearch) -> FindCharMode {
+        FindCharMode { search }
+    }
+}
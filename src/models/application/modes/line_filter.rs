@@ -0,0 +1,36 @@
+use scribe::buffer::LineRange;
+use std::fmt;
+
+/// Collects a pattern used to keep (or delete) the buffer's matching lines,
+/// limited to a range of lines when filtering a line selection.
+pub struct LineFilterMode {
+    pub input: String,
+    pub keep: bool,
+    pub lines: Option<LineRange>,
+}
+
+impl LineFilterMode {
+    pub fn new(keep: bool, lines: Option<LineRange>) -> LineFilterMode {
+        LineFilterMode {
+            input: String::new(),
+            keep,
+            lines,
+        }
+    }
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+    }
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+    }
+}
+
+impl fmt::Display for LineFilterMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.keep {
+            write!(f, "KEEP LINES")
+        } else {
+            write!(f, "DELETE LINES")
+        }
+    }
+}
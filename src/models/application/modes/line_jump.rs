@@ -1,6 +1,16 @@
+use scribe::buffer::Position;
+
 #[derive(Default)]
 pub struct LineJumpMode {
     pub input: String,
+
+    /// The (zero-based) line currently being previewed, if any.
+    pub preview_line: Option<usize>,
+
+    /// Where the cursor and view were before previewing, so that
+    /// they can be restored if the jump is cancelled.
+    pub original_cursor: Option<Position>,
+    pub original_scroll_offset: Option<usize>,
 }
 
 impl LineJumpMode {
@@ -1,25 +1,37 @@
 mod confirm;
 mod command;
+mod find_char;
 pub mod jump;
+mod line_filter;
 mod line_jump;
 pub mod open;
+mod outline;
 mod path;
+mod quickfix;
 mod search;
 mod search_select;
 mod select;
 mod select_line;
+mod selectable_list;
+mod shell_command;
 mod symbol_jump;
 mod theme;
 
-pub use self::confirm::ConfirmMode;
+pub use self::confirm::{Choice, ConfirmMode};
 pub use self::command::CommandMode;
+pub use self::find_char::{CharSearch, FindCharMode};
 pub use self::jump::JumpMode;
+pub use self::line_filter::LineFilterMode;
 pub use self::line_jump::LineJumpMode;
 pub use self::path::PathMode;
+pub use self::quickfix::QuickfixMode;
 pub use self::open::OpenMode;
-pub use self::search::SearchMode;
+pub use self::outline::{Outline, OutlineEntry};
+pub use self::search::{SearchMode, SearchScope};
 pub use self::search_select::{SearchSelectMode, SearchSelectConfig};
 pub use self::select::SelectMode;
 pub use self::select_line::SelectLineMode;
-pub use self::symbol_jump::SymbolJumpMode;
+pub use self::selectable_list::SelectableListMode;
+pub use self::shell_command::ShellCommandMode;
+pub use self::symbol_jump::{Symbol, SymbolJumpMode};
 pub use self::theme::ThemeMode;
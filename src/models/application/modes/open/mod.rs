@@ -5,7 +5,7 @@ use std::fmt;
 use std::path::PathBuf;
 use std::slice::Iter;
 use bloodhound::ExclusionPattern;
-use util::SelectableVec;
+use util::{fuzzy_match, SelectableVec};
 use models::application::modes::{SearchSelectMode, SearchSelectConfig};
 use models::application::Event;
 use std::sync::mpsc::Sender;
@@ -61,14 +61,21 @@ impl fmt::Display for OpenMode {
 
 impl SearchSelectMode<DisplayablePath> for OpenMode {
     fn search(&mut self) {
+        // Rank all of the index's matches using our own scoring, which
+        // favours contiguous runs and path component boundaries, rather
+        // than re-ordering a list the index has already cut short.
         let results =
             if let OpenModeIndex::Complete(ref index) = self.index {
-                index.find(
-                    &self.input.to_lowercase(),
+                let paths = index.find(&self.input.to_lowercase(), usize::max_value());
+
+                fuzzy_match::rank(
+                    &self.input,
+                    paths.into_iter().map(|path| {
+                        let text = path.to_string_lossy().into_owned();
+                        (DisplayablePath(path.to_path_buf()), text)
+                    }),
                     self.config.max_results
-                ).into_iter()
-                .map(|path| DisplayablePath(path.to_path_buf()))
-                .collect()
+                )
             } else {
                 vec![]
             };
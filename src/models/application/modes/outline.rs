@@ -0,0 +1,203 @@
+use scribe::Buffer;
+use scribe::buffer::{Position, Token};
+use std::cmp;
+use std::fmt;
+use std::iter::Iterator;
+use util::SelectableVec;
+use super::symbol_jump::{symbols, Symbol};
+
+/// A symbol in the outline, along with its nesting depth.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutlineEntry {
+    pub symbol: Symbol,
+    pub depth: usize,
+}
+
+impl fmt::Display for OutlineEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", "  ".repeat(self.depth), self.symbol)
+    }
+}
+
+/// An outline of the current buffer's symbols, shown in a pane alongside
+/// it. The outline is rebuilt as the buffer changes, and outside of outline
+/// mode (in which entries are selected by hand), its selection follows the
+/// cursor.
+pub struct Outline {
+    pub entries: SelectableVec<OutlineEntry>,
+    source: Option<(usize, usize)>,
+}
+
+impl Outline {
+    pub fn new() -> Outline {
+        Outline {
+            entries: SelectableVec::new(Vec::new()),
+            source: None,
+        }
+    }
+
+    /// Rebuilds the outline from the buffer's symbols, unless it's already
+    /// been built from the same revision of the same buffer. Buffers whose
+    /// revisions aren't tracked are rebuilt every time.
+    pub fn update(&mut self, buffer: &Buffer, revision: Option<usize>) {
+        let source = match (buffer.id, revision) {
+            (Some(id), Some(revision)) => Some((id, revision)),
+            _ => None,
+        };
+        if source.is_some() && source == self.source {
+            return;
+        }
+
+        let entries = match buffer.tokens() {
+            Ok(tokens) => outline(tokens.iter(), &buffer.data()),
+            Err(_) => Vec::new(),
+        };
+        let selected_index = self.entries.selected_index();
+        self.entries = SelectableVec::new(entries);
+        self.entries.select_index(cmp::min(selected_index, self.entries.len().saturating_sub(1)));
+        self.source = source;
+    }
+
+    /// Selects the closest entry at or preceding the position.
+    pub fn select_at(&mut self, position: &Position) {
+        let index = self.entries
+            .iter()
+            .rposition(|entry| entry.symbol.position <= *position)
+            .unwrap_or(0);
+        self.entries.select_index(index);
+    }
+}
+
+/// Builds an outline from the symbols in the token stream, nesting them
+/// based on the indentation of the lines on which they're defined.
+pub fn outline<'a, T>(tokens: T, data: &str) -> Vec<OutlineEntry> where T: Iterator<Item=Token<'a>> {
+    let lines: Vec<&str> = data.lines().collect();
+    let mut enclosing_indents: Vec<usize> = Vec::new();
+
+    symbols(tokens).into_iter().map(|symbol| {
+        let indent = lines
+            .get(symbol.position.line)
+            .map(|line| line.len() - line.trim_left().len())
+            .unwrap_or(0);
+
+        // Discard enclosing symbols that this one isn't nested within.
+        while enclosing_indents.last().map(|&enclosing| enclosing >= indent).unwrap_or(false) {
+            enclosing_indents.pop();
+        }
+        let depth = enclosing_indents.len();
+        enclosing_indents.push(indent);
+
+        OutlineEntry { symbol, depth }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use scribe::{Buffer, Workspace};
+    use scribe::buffer::{Lexeme, Position, ScopeStack, Token};
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+    use super::{outline, Outline};
+    use util::SelectableVec;
+
+    fn lexeme(value: &'static str, line: usize, scope: &str) -> Token<'static> {
+        Token::Lexeme(
+            Lexeme{
+                value,
+                position: Position{ line, offset: 0 },
+                scope: ScopeStack::from_str(scope).unwrap()
+            }
+        )
+    }
+
+    #[test]
+    fn outline_nests_symbols_by_indentation() {
+        let data = "struct Amp {}\n\
+                    fn outer() {\n    \
+                        fn inner() {\n        \
+                            fn innermost() {}\n    \
+                        }\n    \
+                        fn sibling() {}\n\
+                    }\n\
+                    fn last() {}\n";
+        let tokens = vec![
+            lexeme("Amp", 0, "entity.name.struct"),
+            lexeme("outer", 1, "entity.name.function"),
+            lexeme("{", 1, "meta.block.rust"),
+            lexeme("inner", 2, "entity.name.function"),
+            lexeme("innermost", 3, "entity.name.function"),
+            lexeme("sibling", 5, "entity.name.function"),
+            lexeme("last", 7, "entity.name.function"),
+        ];
+
+        let entries: Vec<(String, usize)> = outline(tokens.into_iter(), data)
+            .into_iter()
+            .map(|entry| (entry.symbol.token, entry.depth))
+            .collect();
+
+        assert_eq!(
+            entries,
+            vec![
+                (String::from("Amp"), 0),
+                (String::from("outer"), 0),
+                (String::from("inner"), 1),
+                (String::from("innermost"), 2),
+                (String::from("sibling"), 1),
+                (String::from("last"), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn outline_entries_display_their_nesting() {
+        let data = "fn outer() {\n  fn inner() {}\n}\n";
+        let tokens = vec![
+            lexeme("outer", 0, "entity.name.function"),
+            lexeme("inner", 1, "entity.name.function"),
+        ];
+
+        let entries = outline(tokens.into_iter(), data);
+        assert_eq!(entries[1].to_string(), "  inner");
+    }
+
+    fn outline_tokens(outline: &Outline) -> Vec<String> {
+        outline.entries.iter().map(|entry| entry.symbol.token.clone()).collect()
+    }
+
+    #[test]
+    fn update_rebuilds_the_outline_when_the_buffer_changes() {
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.path = Some(PathBuf::from("outline.rs"));
+        buffer.insert("fn amp() {}\n");
+        workspace.add_buffer(buffer);
+        let buffer = workspace.current_buffer().unwrap();
+        let mut outline = Outline::new();
+
+        outline.update(buffer, Some(0));
+        assert_eq!(outline_tokens(&outline), vec!["amp"]);
+
+        // The outline is only rebuilt once the revision changes.
+        buffer.insert("fn editor() {}\n");
+        outline.update(buffer, Some(0));
+        assert_eq!(outline_tokens(&outline), vec!["amp"]);
+        outline.update(buffer, Some(1));
+        assert_eq!(outline_tokens(&outline), vec!["editor", "amp"]);
+    }
+
+    #[test]
+    fn select_at_selects_the_closest_preceding_entry() {
+        let data = "fn amp() {}\n\nfn editor() {}\n";
+        let tokens = vec![
+            lexeme("amp", 0, "entity.name.function"),
+            lexeme("editor", 2, "entity.name.function"),
+        ];
+        let mut outline = Outline::new();
+        outline.entries = SelectableVec::new(super::outline(tokens.into_iter(), data));
+
+        outline.select_at(&Position{ line: 1, offset: 0 });
+        assert_eq!(outline.entries.selected_index(), 0);
+        outline.select_at(&Position{ line: 2, offset: 5 });
+        assert_eq!(outline.entries.selected_index(), 1);
+    }
+}
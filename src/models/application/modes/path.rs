@@ -1,15 +1,23 @@
+use scribe::buffer::Range;
 use std::fmt;
 
 pub struct PathMode {
     pub input: String,
     pub save_on_accept: bool,
+    pub rename_on_accept: bool,
+    pub open_glob_on_accept: bool,
+    /// The selection moved to the accepted path, if extracting.
+    pub extract_range: Option<Range>,
 }
 
 impl PathMode {
     pub fn new(initial_path: String) -> PathMode {
         PathMode {
             input: initial_path,
-            save_on_accept: false
+            save_on_accept: false,
+            rename_on_accept: false,
+            open_glob_on_accept: false,
+            extract_range: None,
         }
     }
     pub fn push_char(&mut self, c: char) {
@@ -22,6 +30,14 @@ impl PathMode {
 
 impl fmt::Display for PathMode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "PATH")
+        if self.rename_on_accept {
+            write!(f, "RENAME")
+        } else if self.open_glob_on_accept {
+            write!(f, "OPEN GLOB")
+        } else if self.extract_range.is_some() {
+            write!(f, "EXTRACT")
+        } else {
+            write!(f, "PATH")
+        }
     }
 }
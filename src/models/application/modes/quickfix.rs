@@ -0,0 +1,135 @@
+use models::application::QuickfixEntry;
+use models::application::modes::{SearchSelectMode, SearchSelectConfig};
+use std::fmt;
+use std::slice::Iter;
+use util::{fuzzy_match, SelectableVec};
+
+pub struct QuickfixMode {
+    insert: bool,
+    input: String,
+    entries: Vec<QuickfixEntry>,
+    results: SelectableVec<QuickfixEntry>,
+    config: SearchSelectConfig,
+}
+
+impl QuickfixMode {
+    pub fn new(entries: Vec<QuickfixEntry>, config: SearchSelectConfig) -> QuickfixMode {
+        QuickfixMode {
+            insert: true,
+            input: String::new(),
+            entries,
+            results: SelectableVec::new(Vec::new()),
+            config,
+        }
+    }
+}
+
+impl fmt::Display for QuickfixMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QUICKFIX")
+    }
+}
+
+impl SearchSelectMode<QuickfixEntry> for QuickfixMode {
+    fn search(&mut self) {
+        let mut results: Vec<(usize, &QuickfixEntry)> = self.entries
+            .iter()
+            .filter_map(|entry| {
+                fuzzy_match::score(&self.input, &entry.to_string()).map(|score| (score, entry))
+            })
+            .collect();
+
+        // Without a query, every entry has the same score; the
+        // stable sort keeps those in their original order.
+        results.sort_by(|&(score, _), &(other_score, _)| other_score.cmp(&score));
+
+        self.results = SelectableVec::new(
+            results
+                .into_iter()
+                .take(self.config.max_results)
+                .map(|(_, entry)| entry.clone())
+                .collect()
+        );
+    }
+
+    fn query(&mut self) -> &mut String {
+        &mut self.input
+    }
+
+    fn insert_mode(&self) -> bool {
+        self.insert
+    }
+
+    fn set_insert_mode(&mut self, insert_mode: bool) {
+        self.insert = insert_mode;
+    }
+
+    fn results(&self) -> Iter<QuickfixEntry> {
+        self.results.iter()
+    }
+
+    fn selection(&self) -> Option<&QuickfixEntry> {
+        self.results.selection()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.results.selected_index()
+    }
+
+    fn select_previous(&mut self) {
+        self.results.select_previous();
+    }
+
+    fn select_next(&mut self) {
+        self.results.select_next();
+    }
+
+    fn config(&self) -> &SearchSelectConfig {
+        &self.config
+    }
+
+    // Entries are listed without a query, so only report a lack of results.
+    fn message(&mut self) -> Option<String> {
+        if self.results.is_empty() {
+            Some(String::from("No matching entries found."))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use models::application::QuickfixEntry;
+    use models::application::modes::{SearchSelectMode, SearchSelectConfig};
+    use std::path::PathBuf;
+    use super::QuickfixMode;
+
+    fn entry(path: &str, message: &str) -> QuickfixEntry {
+        QuickfixEntry { path: PathBuf::from(path), line: 0, message: String::from(message) }
+    }
+
+    #[test]
+    fn search_lists_all_entries_in_order_without_a_query() {
+        let entries = vec![entry("b.rs", "amp"), entry("a.rs", "editor")];
+        let mut mode = QuickfixMode::new(entries.clone(), SearchSelectConfig::default());
+        mode.search();
+
+        assert_eq!(mode.results().cloned().collect::<Vec<QuickfixEntry>>(), entries);
+        assert_eq!(mode.message(), None);
+    }
+
+    #[test]
+    fn search_filters_entries_using_the_query() {
+        let entries = vec![entry("b.rs", "amp"), entry("a.rs", "editor")];
+        let mut mode = QuickfixMode::new(entries, SearchSelectConfig::default());
+        mode.push_search_char('e');
+        mode.push_search_char('d');
+        mode.search();
+
+        assert_eq!(
+            mode.results().cloned().collect::<Vec<QuickfixEntry>>(),
+            vec![entry("a.rs", "editor")]
+        );
+    }
+}
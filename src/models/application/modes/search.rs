@@ -1,12 +1,56 @@
 use errors::*;
 use util::SelectableVec;
 use std::fmt;
-use scribe::buffer::{Buffer, Distance, Range};
+use scribe::buffer::{Buffer, Distance, Position, Range};
+use unicode_segmentation::UnicodeSegmentation;
 
 pub struct SearchMode {
     pub insert: bool,
     pub input: Option<String>,
     pub results: Option<SelectableVec<Range>>,
+    pub scope: Option<SearchScope>,
+}
+
+/// The region of a buffer to which search results are limited. Rather than
+/// its end position, the amount of content following it is tracked, so that
+/// edits within the scope (e.g. deleting or pasting over results) move its
+/// end along with the content that follows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchScope {
+    start: Position,
+    lines_after: usize,
+    offset_from_line_end: usize,
+}
+
+impl SearchScope {
+    pub fn new(range: &Range, data: &str) -> SearchScope {
+        let end = range.end();
+
+        SearchScope {
+            start: range.start(),
+            lines_after: data.matches('\n').count().saturating_sub(end.line),
+            offset_from_line_end: line_length(data, end.line).saturating_sub(end.offset),
+        }
+    }
+
+    /// The range spanned by the scope within the data.
+    pub fn range(&self, data: &str) -> Range {
+        let line = data.matches('\n').count().saturating_sub(self.lines_after);
+        let end = Position {
+            line,
+            offset: line_length(data, line).saturating_sub(self.offset_from_line_end),
+        };
+
+        Range::new(self.start, end)
+    }
+}
+
+/// The number of graphemes on the specified line.
+fn line_length(data: &str, line: usize) -> usize {
+    data.split('\n')
+        .nth(line)
+        .map(|content| content.graphemes(true).count())
+        .unwrap_or(0)
 }
 
 impl SearchMode {
@@ -15,6 +59,16 @@ impl SearchMode {
             insert: true,
             input: query,
             results: None,
+            scope: None,
+        }
+    }
+
+    /// Builds a search mode whose results are limited to
+    /// those that fall entirely within the specified scope.
+    pub fn scoped(query: Option<String>, scope: SearchScope) -> SearchMode {
+        SearchMode {
+            scope: Some(scope),
+            ..SearchMode::new(query)
         }
     }
 
@@ -22,6 +76,11 @@ impl SearchMode {
         self.insert
     }
 
+    /// The result ranges to be highlighted when presenting the mode.
+    pub fn highlights(&self) -> Option<&[Range]> {
+        self.results.as_ref().map(|results| results.as_slice())
+    }
+
     // Searches the specified buffer for the input string
     // and stores the result as a collection of ranges.
     pub fn search(&mut self, buffer: &Buffer) -> Result<()> {
@@ -30,12 +89,18 @@ impl SearchMode {
 
         // Buffer search returns match starting positions, but we'd like ranges.
         // This maps the positions to ranges using the search query distance
-        // before storing them.
+        // before storing them, discarding any that fall outside of the scope.
+        let scope = self.scope.as_ref().map(|scope| scope.range(&buffer.data()));
         self.results = Some(
             SelectableVec::new(
                 buffer.search(&query)
                     .into_iter()
                     .map(|start| Range::new(start, start + distance))
+                    .filter(|range| {
+                        scope.as_ref().map(|scope| {
+                            range.start() >= scope.start() && range.end() <= scope.end()
+                        }).unwrap_or(true)
+                    })
                     .collect()
             )
         );
@@ -53,7 +118,7 @@ impl fmt::Display for SearchMode {
 #[cfg(test)]
 mod tests {
     use scribe::buffer::{Buffer, Position, Range};
-    use super::SearchMode;
+    use super::{SearchMode, SearchScope};
 
     #[test]
     fn search_populates_results_with_correct_ranges() {
@@ -77,4 +142,64 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn search_excludes_results_outside_of_its_scope() {
+        let mut buffer = Buffer::new();
+        buffer.insert("test\ntest test\ntest");
+
+        let scope = Range::new(
+            Position{ line: 1, offset: 0 },
+            Position{ line: 1, offset: 7 },
+        );
+        let mut mode = SearchMode::scoped(
+            Some(String::from("test")),
+            SearchScope::new(&scope, &buffer.data())
+        );
+        mode.search(&buffer).unwrap();
+
+        assert_eq!(
+            *mode.results.unwrap(),
+            vec![
+                Range::new(
+                    Position{ line: 1, offset: 0 },
+                    Position{ line: 1, offset: 4 },
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn search_keeps_its_scope_in_step_with_edits_within_it() {
+        let mut buffer = Buffer::new();
+        buffer.insert("test\ntest test\ntest");
+
+        let scope = Range::new(
+            Position{ line: 1, offset: 0 },
+            Position{ line: 1, offset: 9 },
+        );
+        let mut mode = SearchMode::scoped(
+            Some(String::from("test")),
+            SearchScope::new(&scope, &buffer.data())
+        );
+
+        // Push the scoped content down a line, and shorten it.
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+        buffer.insert("\n");
+        buffer.delete_range(Range::new(
+            Position{ line: 2, offset: 0 },
+            Position{ line: 2, offset: 1 },
+        ));
+        mode.search(&buffer).unwrap();
+
+        assert_eq!(
+            *mode.results.unwrap(),
+            vec![
+                Range::new(
+                    Position{ line: 2, offset: 4 },
+                    Position{ line: 2, offset: 8 },
+                ),
+            ]
+        );
+    }
 }
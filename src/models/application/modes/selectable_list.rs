@@ -0,0 +1,86 @@
+use util::{fuzzy_match, SelectableVec};
+use std::fmt;
+use std::slice::Iter;
+use models::application::modes::{SearchSelectMode, SearchSelectConfig};
+
+/// Selects one of a fixed list of entries (e.g. buffer paths or syntax
+/// names), for modes that don't need anything more than their names.
+pub struct SelectableListMode {
+    name: &'static str,
+    insert: bool,
+    input: String,
+    entries: Vec<String>,
+    results: SelectableVec<String>,
+    config: SearchSelectConfig,
+}
+
+impl SelectableListMode {
+    pub fn new(name: &'static str, entries: Vec<String>, config: SearchSelectConfig) -> SelectableListMode {
+        SelectableListMode {
+            name,
+            insert: true,
+            input: String::new(),
+            entries,
+            results: SelectableVec::new(Vec::new()),
+            config,
+        }
+    }
+}
+
+impl fmt::Display for SelectableListMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl SearchSelectMode<String> for SelectableListMode {
+    fn search(&mut self) {
+        // Find the entries we're looking for using the query.
+        let results = fuzzy_match::find(&self.input, &self.entries, self.config.max_results);
+
+        // We don't care about the result objects; we just want
+        // the underlying entries. Map the collection to get these.
+        self.results = SelectableVec::new(
+            results
+            .into_iter()
+            .cloned()
+            .collect()
+        );
+    }
+
+    fn query(&mut self) -> &mut String {
+        &mut self.input
+    }
+
+    fn insert_mode(&self) -> bool {
+        self.insert
+    }
+
+    fn set_insert_mode(&mut self, insert_mode: bool) {
+        self.insert = insert_mode;
+    }
+
+    fn results(&self) -> Iter<String> {
+        self.results.iter()
+    }
+
+    fn selection(&self) -> Option<&String> {
+        self.results.selection()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.results.selected_index()
+    }
+
+    fn select_previous(&mut self) {
+        self.results.select_previous();
+    }
+
+    fn select_next(&mut self) {
+        self.results.select_next();
+    }
+
+    fn config(&self) -> &SearchSelectConfig {
+        &self.config
+    }
+}
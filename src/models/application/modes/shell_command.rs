@@ -0,0 +1,25 @@
+use std::fmt;
+
+/// Collects a shell command whose output will be inserted into the buffer.
+pub struct ShellCommandMode {
+    pub input: String,
+    pub running: bool,
+}
+
+impl ShellCommandMode {
+    pub fn new() -> ShellCommandMode {
+        ShellCommandMode { input: String::new(), running: false }
+    }
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+    }
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+    }
+}
+
+impl fmt::Display for ShellCommandMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SHELL")
+    }
+}
@@ -1,8 +1,7 @@
-use fragment;
 use fragment::matching::AsStr;
 use scribe::buffer::{Position, Token, TokenSet};
 use syntect::highlighting::ScopeSelectors;
-use util::SelectableVec;
+use util::{fuzzy_match, SelectableVec};
 use std::fmt;
 use std::iter::Iterator;
 use std::clone::Clone;
@@ -70,11 +69,11 @@ impl fmt::Display for SymbolJumpMode {
 impl SearchSelectMode<Symbol> for SymbolJumpMode {
     fn search(&mut self) {
         // Find the symbols we're looking for using the query.
-        let results = fragment::matching::find(&self.input, &self.symbols, self.config.max_results);
+        let results = fuzzy_match::find(&self.input, &self.symbols, self.config.max_results);
 
         // We don't care about the result objects; we just want
         // the underlying symbols. Map the collection to get these.
-        self.results = SelectableVec::new(results.into_iter().map(|r| r.clone()).collect());
+        self.results = SelectableVec::new(results.into_iter().cloned().collect());
     }
 
     fn query(&mut self) -> &mut String {
@@ -114,7 +113,7 @@ impl SearchSelectMode<Symbol> for SymbolJumpMode {
     }
 }
 
-fn symbols<'a, T>(tokens: T) -> Vec<Symbol> where T: Iterator<Item=Token<'a>> {
+pub fn symbols<'a, T>(tokens: T) -> Vec<Symbol> where T: Iterator<Item=Token<'a>> {
     let eligible_scopes = ScopeSelectors::from_str(
         "entity.name.function, entity.name.class, entity.name.struct"
     ).unwrap();
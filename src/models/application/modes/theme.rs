@@ -1,5 +1,4 @@
-use fragment;
-use util::SelectableVec;
+use util::{fuzzy_match, SelectableVec};
 use std::fmt;
 use std::slice::Iter;
 use models::application::modes::{SearchSelectMode, SearchSelectConfig};
@@ -33,14 +32,14 @@ impl fmt::Display for ThemeMode {
 impl SearchSelectMode<String> for ThemeMode {
     fn search(&mut self) {
         // Find the themes we're looking for using the query.
-        let results = fragment::matching::find(&self.input, &self.themes, self.config.max_results);
+        let results = fuzzy_match::find(&self.input, &self.themes, self.config.max_results);
 
         // We don't care about the result objects; we just want
         // the underlying symbols. Map the collection to get these.
         self.results = SelectableVec::new(
             results
             .into_iter()
-            .map(|r| r.clone())
+            .cloned()
             .collect()
         );
     }
@@ -0,0 +1,17 @@
+use scribe::buffer::Position;
+
+/// An edit that is applied to the range traversed by a subsequent motion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    Change,
+    Delete,
+    Yank,
+}
+
+/// An operator awaiting a motion, along with the
+/// cursor position from which that motion began.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PendingOperator {
+    pub operator: Operator,
+    pub start: Position,
+}
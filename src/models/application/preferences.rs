@@ -2,11 +2,21 @@ use app_dirs::{app_dir, app_root, get_app_root, AppDataType, AppInfo};
 use bloodhound::ExclusionPattern;
 use errors::*;
 use input::KeyMap;
+use models::application::IdleAction;
 use models::application::modes::open;
 use scribe::Buffer;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env;
 use std::fs::OpenOptions;
 use std::io::Read;
 use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+use util::LineEnding;
+use util::editor_config::EditorConfig;
+use util::spell_check::Dictionary;
+use util::uuid::UuidFormat;
 use yaml::yaml::{Hash, Yaml, YamlLoader};
 use models::application::modes::SearchSelectConfig;
 
@@ -14,29 +24,94 @@ const APP_INFO: AppInfo = AppInfo {
     name: "amp",
     author: "Jordan MacDonald",
 };
+const ALTERNATE_FILES_KEY: &str = "alternate_files";
+const ALTERNATE_FILES_DEFAULT: [(&str, &str); 7] = [
+    (".h", ".c"),
+    (".h", ".cpp"),
+    (".h", ".cc"),
+    (".hpp", ".cpp"),
+    ("mod.rs", "lib.rs"),
+    ("_test.go", ".go"),
+    ("_spec.rb", ".rb"),
+];
+const ANNOTATION_PATTERNS_DEFAULT: [&str; 3] = ["TODO", "FIXME", "XXX"];
+const ANNOTATION_PATTERNS_KEY: &str = "annotation_patterns";
+const AUTO_PAIRS_DEFAULT: bool = false;
+const AUTO_PAIRS_KEY: &str = "auto_pairs";
+const AUTO_RELOAD_DEFAULT: bool = false;
+const AUTO_RELOAD_KEY: &str = "auto_reload";
+#[cfg(target_os = "macos")]
+const BROWSER_COMMAND_DEFAULT: &str = "open";
+#[cfg(windows)]
+const BROWSER_COMMAND_DEFAULT: &str = "explorer";
+#[cfg(not(any(target_os = "macos", windows)))]
+const BROWSER_COMMAND_DEFAULT: &str = "xdg-open";
+const BROWSER_COMMAND_KEY: &str = "browser_command";
+const CENTER_ON_SEARCH_DEFAULT: bool = true;
+const CENTER_ON_SEARCH_KEY: &str = "center_on_search";
+const CONFIG_PATH_ENV_VAR: &str = "AMP_CONFIG";
+const DATA_DIRECTORY_ENV_VAR: &str = "AMP_DATA_DIR";
+const ERROR_LOG_FILE_NAME: &str = "error.log";
+const ERROR_LOG_KEY: &str = "error_log";
 const FILE_NAME: &str = "config.yml";
+const HIGHLIGHT_CURRENT_LINE_DEFAULT: bool = true;
+const HIGHLIGHT_CURRENT_LINE_KEY: &str = "highlight_current_line";
+const HORIZONTAL_SCROLL_MARGIN_DEFAULT: usize = 5;
+const HORIZONTAL_SCROLL_MARGIN_KEY: &str = "horizontal_scroll_margin";
+const IDLE_ACTION_KEY: &str = "idle_action";
+const IDLE_TIMEOUT_KEY: &str = "idle_timeout";
+const LEADER_DEFAULT: &str = "space";
+const LEADER_KEY: &str = "leader";
 const LINE_LENGTH_GUIDE_DEFAULT: usize = 80;
 const LINE_LENGTH_GUIDE_KEY: &str = "line_length_guide";
+const LINE_ENDINGS_KEY: &str = "line_endings";
 const LINE_WRAPPING_DEFAULT: bool = true;
 const LINE_WRAPPING_KEY: &str = "line_wrapping";
+const LINT_WHITESPACE_ON_OPEN_DEFAULT: bool = false;
+const LINT_WHITESPACE_ON_OPEN_KEY: &str = "lint_whitespace_on_open";
+const NORMALIZE_INDENTATION_DEFAULT: bool = false;
+const NORMALIZE_INDENTATION_KEY: &str = "normalize_indentation";
+const OPEN_EXTRACTED_FILE_DEFAULT: bool = true;
+const OPEN_EXTRACTED_FILE_KEY: &str = "open_extracted_file";
+const OPEN_GLOB_LIMIT_DEFAULT: usize = 50;
+const OPEN_GLOB_LIMIT_KEY: &str = "open_glob_limit";
+const SCROLLBAR_DEFAULT: bool = false;
+const SCROLLBAR_KEY: &str = "scrollbar";
 const SEARCH_SELECT_KEY: &str = "search_select";
+const SMART_HOME_DEFAULT: bool = false;
+const SMART_HOME_KEY: &str = "smart_home";
 const SOFT_TABS_DEFAULT: bool = true;
 const SOFT_TABS_KEY: &str = "soft_tabs";
+const SPELL_CHECK_DEFAULT: bool = false;
+const SPELL_CHECK_DICTIONARY_DEFAULT: &str = "/usr/share/dict/words";
+const SPELL_CHECK_DICTIONARY_KEY: &str = "spell_check_dictionary";
+const SPELL_CHECK_KEY: &str = "spell_check";
+const START_SCREEN_DEFAULT: bool = true;
+const START_SCREEN_KEY: &str = "start_screen";
 const SYNTAX_PATH: &str = "syntaxes";
 const TAB_WIDTH_DEFAULT: usize = 2;
 const TAB_WIDTH_KEY: &str = "tab_width";
+const TEXT_WIDTH_DEFAULT: usize = 80;
+const TEXT_WIDTH_KEY: &str = "text_width";
 const THEME_DEFAULT: &str = "solarized_dark";
 const THEME_KEY: &str = "theme";
 const THEME_PATH: &str = "themes";
 const TYPES_KEY: &str = "types";
+const UUID_FORMAT_KEY: &str = "uuid_format";
+const WRAP_CURSOR_DEFAULT: bool = false;
+const WRAP_CURSOR_KEY: &str = "wrap_cursor";
+const ZEN_MODE_WIDTH_KEY: &str = "zen_mode_width";
 
 /// Loads, creates, and provides default values for application preferences.
 /// Values are immutable once loaded, with the exception of those that provide
-/// expicit setter methods (e.g. `theme`).
+/// expicit setter methods (e.g. `theme` and `wrap_cursor`).
 pub struct Preferences {
     data: Option<Yaml>,
     keymap: KeyMap,
     theme: Option<String>,
+    wrap_cursor: Option<bool>,
+    editor_configs: RefCell<HashMap<PathBuf, EditorConfig>>,
+    dictionary: RefCell<Option<::std::result::Result<Rc<Dictionary>, String>>>,
 }
 
 impl Preferences {
@@ -45,7 +120,10 @@ impl Preferences {
         Preferences {
             data,
             keymap: KeyMap::default().expect("Failed to load default keymap!"),
-            theme: None
+            theme: None,
+            wrap_cursor: None,
+            editor_configs: RefCell::new(HashMap::new()),
+            dictionary: RefCell::new(None),
         }
     }
 
@@ -53,22 +131,34 @@ impl Preferences {
     pub fn load() -> Result<Preferences> {
         let data = load_document()?;
         let keymap = load_keymap(
-            data.as_ref().and_then(|data| data["keymap"].as_hash())
+            data.as_ref().and_then(|data| data["keymap"].as_hash()),
+            leader(data.as_ref())
         )?;
 
-        Ok(Preferences { data, keymap, theme: None })
+        Ok(Preferences {
+            data,
+            keymap,
+            theme: None,
+            wrap_cursor: None,
+            editor_configs: RefCell::new(HashMap::new()),
+            dictionary: RefCell::new(None),
+        })
     }
 
     /// Reloads all user preferences from disk and merges them with defaults.
     pub fn reload(&mut self) -> Result<()> {
         let data = load_document()?;
         let keymap = load_keymap(
-            data.as_ref().and_then(|data| data["keymap"].as_hash())
+            data.as_ref().and_then(|data| data["keymap"].as_hash()),
+            leader(data.as_ref())
         )?;
 
         self.data = data;
         self.keymap = keymap;
         self.theme = None;
+        self.wrap_cursor = None;
+        self.editor_configs.borrow_mut().clear();
+        self.dictionary.borrow_mut().take();
 
         Ok(())
     }
@@ -84,6 +174,12 @@ impl Preferences {
             .chain_err(|| "Couldn't create preferences directory or build a path to it.")
     }
 
+    /// A path pointing to the directory used for application state (e.g. the
+    /// error log), overridable using the `AMP_DATA_DIR` environment variable.
+    pub fn data_directory() -> Result<PathBuf> {
+        data_directory_from(env::var_os(DATA_DIRECTORY_ENV_VAR).map(PathBuf::from))
+    }
+
     /// A path pointing to the user syntax definition directory.
     pub fn syntax_path() -> Result<PathBuf> {
         app_dir(AppDataType::UserConfig, &APP_INFO, SYNTAX_PATH)
@@ -96,10 +192,14 @@ impl Preferences {
     /// if they don't already exist.
     pub fn edit() -> Result<Buffer> {
         // Build the path, creating parent directories, if required.
-        let mut config_path =
-            app_root(AppDataType::UserConfig, &APP_INFO)
-                .chain_err(|| "Couldn't create or open application config directory")?;
-        config_path.push(FILE_NAME);
+        let config_path = match env::var_os(CONFIG_PATH_ENV_VAR) {
+            Some(path) => PathBuf::from(path),
+            None => {
+                app_root(AppDataType::UserConfig, &APP_INFO)
+                    .chain_err(|| "Couldn't create or open application config directory")?
+                    .join(FILE_NAME)
+            }
+        };
 
         // Load the buffer, falling back to a
         // new/empty buffer if it doesn't exist.
@@ -137,7 +237,32 @@ impl Preferences {
         self.theme = Some(theme.into());
     }
 
+    /// If set, returns the in-memory cursor wrapping value, falling back to
+    /// the value set via the configuration file, and then the default value.
+    pub fn wrap_cursor(&self) -> bool {
+        // Return the mutable in-memory value, if set.
+        if let Some(wrap_cursor) = self.wrap_cursor { return wrap_cursor; }
+
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(wrap_cursor) = data[WRAP_CURSOR_KEY] {
+                          Some(wrap_cursor)
+                      } else {
+                          None
+                      })
+            .unwrap_or(WRAP_CURSOR_DEFAULT)
+    }
+
+    /// Updates the in-memory cursor wrapping value.
+    pub fn set_wrap_cursor(&mut self, wrap_cursor: bool) {
+        self.wrap_cursor = Some(wrap_cursor);
+    }
+
     pub fn tab_width(&self, path: Option<&PathBuf>) -> usize {
+        if let Some(tab_width) = self.editor_config(path).tab_width {
+            return tab_width;
+        }
+
         self.data
             .as_ref()
             .and_then(|data| {
@@ -167,6 +292,10 @@ impl Preferences {
     }
 
     pub fn soft_tabs(&self, path: Option<&PathBuf>) -> bool {
+        if let Some(soft_tabs) = self.editor_config(path).soft_tabs {
+            return soft_tabs;
+        }
+
         self.data
             .as_ref()
             .and_then(|data| {
@@ -212,6 +341,338 @@ impl Preferences {
             .unwrap_or(LINE_WRAPPING_DEFAULT)
     }
 
+    /// Whether files opened using open mode are checked for trailing
+    /// whitespace and mixed indentation, populating the quickfix list.
+    pub fn lint_whitespace_on_open(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(lint) = data[LINT_WHITESPACE_ON_OPEN_KEY] {
+                          Some(lint)
+                      } else {
+                          None
+                      })
+            .unwrap_or(LINT_WHITESPACE_ON_OPEN_DEFAULT)
+    }
+
+    /// Whether `buffer::normalize_buffer` converts indentation
+    /// to match the soft tab and tab width preferences.
+    pub fn normalize_indentation(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(normalize) = data[NORMALIZE_INDENTATION_KEY] {
+                          Some(normalize)
+                      } else {
+                          None
+                      })
+            .unwrap_or(NORMALIZE_INDENTATION_DEFAULT)
+    }
+
+    /// Whether the cursor's line is rendered with a distinct background.
+    pub fn highlight_current_line(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(highlight) = data[HIGHLIGHT_CURRENT_LINE_KEY] {
+                          Some(highlight)
+                      } else {
+                          None
+                      })
+            .unwrap_or(HIGHLIGHT_CURRENT_LINE_DEFAULT)
+    }
+
+    /// The line length that `buffer::hard_wrap` and
+    /// `buffer::reflow_comment` reflow text to fit within.
+    pub fn text_width(&self) -> usize {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Integer(width) = data[TEXT_WIDTH_KEY] {
+                          Some(width as usize)
+                      } else {
+                          None
+                      })
+            .unwrap_or(TEXT_WIDTH_DEFAULT)
+    }
+
+    /// The number of columns kept visible on either side of the cursor
+    /// when scrolling horizontally (i.e. when line wrapping is disabled).
+    pub fn horizontal_scroll_margin(&self) -> usize {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Integer(margin) = data[HORIZONTAL_SCROLL_MARGIN_KEY] {
+                          Some(margin as usize)
+                      } else {
+                          None
+                      })
+            .unwrap_or(HORIZONTAL_SCROLL_MARGIN_DEFAULT)
+    }
+
+    /// The line ending style to use when saving buffers. A value of `None`
+    /// (the "auto" setting) preserves each buffer's original line endings.
+    pub fn line_endings(&self, path: Option<&PathBuf>) -> Option<LineEnding> {
+        self.editor_config(path).line_ending.or_else(|| {
+            self.data
+                .as_ref()
+                .and_then(|data| match data[LINE_ENDINGS_KEY].as_str() {
+                              Some("lf") => Some(LineEnding::Lf),
+                              Some("crlf") => Some(LineEnding::Crlf),
+                              _ => None,
+                          })
+        })
+    }
+
+    /// Whether trailing whitespace is removed when saving the file.
+    /// Only configurable via `.editorconfig` files.
+    pub fn trim_trailing_whitespace(&self, path: Option<&PathBuf>) -> bool {
+        self.editor_config(path).trim_trailing_whitespace.unwrap_or(true)
+    }
+
+    /// Whether a trailing newline is added when saving the file.
+    /// Only configurable via `.editorconfig` files.
+    pub fn insert_final_newline(&self, path: Option<&PathBuf>) -> bool {
+        self.editor_config(path).insert_final_newline.unwrap_or(true)
+    }
+
+    /// Settings from `.editorconfig` files applicable to the path, which take
+    /// precedence over user preferences (there's no per-project preferences
+    /// layer above them). These are cached until reloaded.
+    fn editor_config(&self, path: Option<&PathBuf>) -> EditorConfig {
+        let path = match path {
+            Some(path) => path,
+            None => return EditorConfig::default(),
+        };
+
+        self.editor_configs
+            .borrow_mut()
+            .entry(path.clone())
+            .or_insert_with(|| EditorConfig::for_path(path).unwrap_or_default())
+            .clone()
+    }
+
+    /// The path to which errors are logged, if enabled. A value of `true` logs
+    /// to the preferences directory, whereas a string specifies the path.
+    pub fn error_log_path(&self) -> Result<Option<PathBuf>> {
+        match self.data.as_ref().map(|data| &data[ERROR_LOG_KEY]) {
+            Some(&Yaml::Boolean(true)) => {
+                Ok(Some(Preferences::data_directory()?.join(ERROR_LOG_FILE_NAME)))
+            }
+            Some(&Yaml::String(ref path)) => Ok(Some(PathBuf::from(path))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Pairs of file name suffixes used to find a file's companion (e.g. a
+    /// header for a source file). User-defined pairs take precedence.
+    pub fn alternate_file_rules(&self) -> Vec<(String, String)> {
+        let mut rules = Vec::new();
+        if let Some(&Yaml::Array(ref pairs)) = self.data.as_ref().map(|data| &data[ALTERNATE_FILES_KEY]) {
+            for pair in pairs {
+                if let (Some(first), Some(second)) = (pair[0].as_str(), pair[1].as_str()) {
+                    rules.push((first.to_string(), second.to_string()));
+                }
+            }
+        }
+
+        rules.extend(
+            ALTERNATE_FILES_DEFAULT
+                .iter()
+                .map(|&(first, second)| (first.to_string(), second.to_string()))
+        );
+
+        rules
+    }
+
+    /// Literal patterns (e.g. TODO) navigated by the annotation commands.
+    /// User-defined patterns replace the defaults.
+    pub fn annotation_patterns(&self) -> Vec<String> {
+        if let Some(&Yaml::Array(ref patterns)) = self.data.as_ref().map(|data| &data[ANNOTATION_PATTERNS_KEY]) {
+            return patterns
+                .iter()
+                .filter_map(|pattern| pattern.as_str())
+                .map(|pattern| pattern.to_string())
+                .collect();
+        }
+
+        ANNOTATION_PATTERNS_DEFAULT
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .collect()
+    }
+
+    /// The key substituted for `<leader>` tokens in keymap definitions.
+    pub fn leader(&self) -> &str {
+        leader(self.data.as_ref())
+    }
+
+    /// Whether brackets and quotes typed in insert mode
+    /// are automatically paired with their closing counterpart.
+    pub fn auto_pairs(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(auto_pairs) = data[AUTO_PAIRS_KEY] {
+                          Some(auto_pairs)
+                      } else {
+                          None
+                      })
+            .unwrap_or(AUTO_PAIRS_DEFAULT)
+    }
+
+    /// Whether the current buffer is reloaded when its file changes on disk.
+    pub fn auto_reload(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(auto_reload) = data[AUTO_RELOAD_KEY] {
+                          Some(auto_reload)
+                      } else {
+                          None
+                      })
+            .unwrap_or(AUTO_RELOAD_DEFAULT)
+    }
+
+    /// The program (and any leading arguments) used to open URLs,
+    /// which is passed the URL as its final argument.
+    pub fn browser_command(&self) -> Vec<String> {
+        self.data
+            .as_ref()
+            .and_then(|data| data[BROWSER_COMMAND_KEY].as_str())
+            .unwrap_or(BROWSER_COMMAND_DEFAULT)
+            .split_whitespace()
+            .map(|argument| argument.to_string())
+            .collect()
+    }
+
+    /// Whether UUIDs inserted by `buffer::insert_uuid` are hyphenated.
+    pub fn uuid_format(&self) -> UuidFormat {
+        self.data
+            .as_ref()
+            .and_then(|data| match data[UUID_FORMAT_KEY].as_str() {
+                          Some("plain") => Some(UuidFormat::Plain),
+                          Some("hyphenated") => Some(UuidFormat::Hyphenated),
+                          _ => None,
+                      })
+            .unwrap_or(UuidFormat::Hyphenated)
+    }
+
+    /// The width of the centered text column used in zen mode,
+    /// or None if content should span the full screen width.
+    pub fn zen_mode_width(&self) -> Option<usize> {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Integer(width) = data[ZEN_MODE_WIDTH_KEY] {
+                          Some(width as usize)
+                      } else {
+                          None
+                      })
+    }
+
+    /// Whether moving to the start of a line goes to its first non-whitespace
+    /// character, and then alternates between that and its first column.
+    pub fn smart_home(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(smart_home) = data[SMART_HOME_KEY] {
+                          Some(smart_home)
+                      } else {
+                          None
+                      })
+            .unwrap_or(SMART_HOME_DEFAULT)
+    }
+
+    /// Whether a scrollbar is drawn in the rightmost column.
+    pub fn scrollbar(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(scrollbar) = data[SCROLLBAR_KEY] {
+                          Some(scrollbar)
+                      } else {
+                          None
+                      })
+            .unwrap_or(SCROLLBAR_DEFAULT)
+    }
+
+    /// Whether a start screen is shown when launching without any files.
+    pub fn start_screen(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(start_screen) = data[START_SCREEN_KEY] {
+                          Some(start_screen)
+                      } else {
+                          None
+                      })
+            .unwrap_or(START_SCREEN_DEFAULT)
+    }
+
+    /// Whether misspelled words in comments and strings are underlined.
+    pub fn spell_check(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(spell_check) = data[SPELL_CHECK_KEY] {
+                          Some(spell_check)
+                      } else {
+                          None
+                      })
+            .unwrap_or(SPELL_CHECK_DEFAULT)
+    }
+
+    /// The word list used to spell check comments and strings, loaded
+    /// on first use and cached until preferences are reloaded. Failing
+    /// to load it is cached, too, so that it isn't retried on every render.
+    pub fn spell_check_dictionary(&self) -> Result<Rc<Dictionary>> {
+        if let Some(ref dictionary) = *self.dictionary.borrow() {
+            return dictionary.clone().map_err(Error::from);
+        }
+
+        let path = self.data
+            .as_ref()
+            .and_then(|data| data[SPELL_CHECK_DICTIONARY_KEY].as_str())
+            .unwrap_or(SPELL_CHECK_DICTIONARY_DEFAULT);
+        let dictionary = Dictionary::load(&PathBuf::from(path))
+            .map(Rc::new)
+            .map_err(|error| {
+                error.iter().map(|cause| cause.to_string()).collect::<Vec<String>>().join(": ")
+            });
+        *self.dictionary.borrow_mut() = Some(dictionary.clone());
+
+        dictionary.map_err(Error::from)
+    }
+
+    /// Whether the cursor line is centered in the viewport after
+    /// jumping to a search result, rather than just scrolled into view.
+    pub fn center_on_search(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(center_on_search) = data[CENTER_ON_SEARCH_KEY] {
+                          Some(center_on_search)
+                      } else {
+                          None
+                      })
+            .unwrap_or(CENTER_ON_SEARCH_DEFAULT)
+    }
+
+    /// The duration of inactivity after which the idle action is
+    /// triggered, or None if the idle timeout hasn't been configured.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.data
+            .as_ref()
+            .and_then(|data| match data[IDLE_TIMEOUT_KEY] {
+                          Yaml::Integer(seconds) if seconds > 0 => {
+                              Some(Duration::from_secs(seconds as u64))
+                          }
+                          _ => None,
+                      })
+    }
+
+    /// The action taken once the idle timeout elapses; either saving
+    /// all modified buffers (the default) or locking the screen.
+    pub fn idle_action(&self) -> IdleAction {
+        self.data
+            .as_ref()
+            .and_then(|data| match data[IDLE_ACTION_KEY].as_str() {
+                          Some("lock") => Some(IdleAction::Lock),
+                          Some("save") => Some(IdleAction::Save),
+                          _ => None,
+                      })
+            .unwrap_or(IdleAction::Save)
+    }
+
     pub fn tab_content(&self, path: Option<&PathBuf>) -> String {
         if self.soft_tabs(path) {
             format!("{:1$}", "", self.tab_width(path))
@@ -220,6 +681,30 @@ impl Preferences {
         }
     }
 
+    /// Whether files created by `selection::extract_to_file` are opened.
+    pub fn open_extracted_file(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Boolean(open) = data[OPEN_EXTRACTED_FILE_KEY] {
+                          Some(open)
+                      } else {
+                          None
+                      })
+            .unwrap_or(OPEN_EXTRACTED_FILE_DEFAULT)
+    }
+
+    /// The maximum number of files opened by `workspace::open_glob`.
+    pub fn open_glob_limit(&self) -> usize {
+        self.data
+            .as_ref()
+            .and_then(|data| if let Yaml::Integer(limit) = data[OPEN_GLOB_LIMIT_KEY] {
+                          Some(limit as usize)
+                      } else {
+                          None
+                      })
+            .unwrap_or(OPEN_GLOB_LIMIT_DEFAULT)
+    }
+
     pub fn open_mode_exclusions(&self) -> Result<Option<Vec<ExclusionPattern>>> {
         if let Some(exclusion_data) = self.data.as_ref().map(|data| &data["open_mode"]["exclusions"]) {
             match *exclusion_data {
@@ -238,13 +723,38 @@ impl Preferences {
     }
 }
 
-/// Loads the first YAML document in the user's config file.
+/// Uses the specified data directory, falling back to the preferences directory.
+fn data_directory_from(path: Option<PathBuf>) -> Result<PathBuf> {
+    match path {
+        Some(path) => Ok(path),
+        None => Preferences::directory(),
+    }
+}
+
+/// Loads the first YAML document in the user's config file, or the file
+/// specified by the `AMP_CONFIG` environment variable.
 fn load_document() -> Result<Option<Yaml>> {
+    load_document_from(env::var_os(CONFIG_PATH_ENV_VAR).map(PathBuf::from))
+}
+
+/// Loads the first YAML document in the specified config file, falling back
+/// to default preferences if it doesn't exist, or in the user's config file.
+fn load_document_from(path: Option<PathBuf>) -> Result<Option<Yaml>> {
     // Build a path to the config file.
-    let mut config_path =
-        get_app_root(AppDataType::UserConfig, &APP_INFO)
-            .chain_err(|| "Couldn't open application config directory")?;
-    config_path.push(FILE_NAME);
+    let config_path = match path {
+        Some(path) => {
+            if !path.exists() {
+                return Ok(None);
+            }
+
+            path
+        }
+        None => {
+            get_app_root(AppDataType::UserConfig, &APP_INFO)
+                .chain_err(|| "Couldn't open application config directory")?
+                .join(FILE_NAME)
+        }
+    };
 
     // Open (or create) the config file.
     let mut config_file = OpenOptions::new()
@@ -265,17 +775,25 @@ fn load_document() -> Result<Option<Yaml>> {
 }
 
 /// Loads default keymaps, merging in the provided overrides.
-fn load_keymap(keymap_overrides: Option<&Hash>) -> Result<KeyMap> {
+fn load_keymap(keymap_overrides: Option<&Hash>, leader: &str) -> Result<KeyMap> {
     let mut keymap = KeyMap::default()?;
 
     // Merge user-defined keymaps into defaults.
     if let Some(keymap_data) = keymap_overrides {
-        KeyMap::from(keymap_data).map(|data| keymap.merge(data))?;
+        KeyMap::from_with_leader(keymap_data, leader).map(|data| keymap.merge(data))?;
     }
 
     Ok(keymap)
 }
 
+/// Reads the leader key from preference data. This is needed before
+/// preferences are built, as it's used to load their keymap.
+fn leader(data: Option<&Yaml>) -> &str {
+    data
+        .and_then(|data| data[LEADER_KEY].as_str())
+        .unwrap_or(LEADER_DEFAULT)
+}
+
 /// Maps a path to its file extension.
 fn path_extension(path: Option<&PathBuf>) -> Option<&str> {
     path
@@ -292,10 +810,60 @@ fn default_open_mode_exclusions() -> Result<Option<Vec<ExclusionPattern>>> {
 #[cfg(test)]
 mod tests {
     use super::{ExclusionPattern, Preferences, YamlLoader};
+    use models::application::IdleAction;
+    use util::LineEnding;
+    use util::test_helpers::TempDir;
+    use util::uuid::UuidFormat;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::fs::{self, File};
+    use std::io::Write;
     use std::path::PathBuf;
+    use std::time::Duration;
     use input::KeyMap;
     use yaml::yaml::Hash;
 
+    #[test]
+    fn error_log_path_returns_none_by_default() {
+        let preferences = Preferences::new(None);
+
+        assert_eq!(preferences.error_log_path().unwrap(), None);
+    }
+
+    #[test]
+    fn error_log_path_returns_none_when_disabled() {
+        let data = YamlLoader::load_from_str("error_log: false").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.error_log_path().unwrap(), None);
+    }
+
+    #[test]
+    fn error_log_path_returns_user_defined_path() {
+        let data = YamlLoader::load_from_str("error_log: /tmp/amp.log").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(
+            preferences.error_log_path().unwrap(),
+            Some(PathBuf::from("/tmp/amp.log"))
+        );
+    }
+
+    #[test]
+    fn leader_returns_space_by_default() {
+        let preferences = Preferences::new(None);
+
+        assert_eq!(preferences.leader(), "space");
+    }
+
+    #[test]
+    fn leader_returns_user_defined_leader() {
+        let data = YamlLoader::load_from_str("leader: \",\"").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.leader(), ",");
+    }
+
     #[test]
     fn preferences_returns_user_defined_theme_name() {
         let data = YamlLoader::load_from_str("theme: \"my_theme\"").unwrap();
@@ -403,6 +971,189 @@ mod tests {
         assert_eq!(preferences.line_wrapping(), false);
     }
 
+    #[test]
+    fn preferences_returns_user_defined_line_endings() {
+        let data = YamlLoader::load_from_str("line_endings: crlf").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.line_endings(None), Some(LineEnding::Crlf));
+    }
+
+    #[test]
+    fn preferences_returns_no_line_endings_when_set_to_auto() {
+        let data = YamlLoader::load_from_str("line_endings: auto").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.line_endings(None), None);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_wrap_cursor() {
+        let data = YamlLoader::load_from_str("wrap_cursor: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.wrap_cursor(), true);
+    }
+
+    #[test]
+    fn set_wrap_cursor_overrides_user_defined_value() {
+        let data = YamlLoader::load_from_str("wrap_cursor: true").unwrap();
+        let mut preferences = Preferences::new(data.into_iter().nth(0));
+        preferences.set_wrap_cursor(false);
+
+        assert_eq!(preferences.wrap_cursor(), false);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_horizontal_scroll_margin() {
+        let data = YamlLoader::load_from_str("horizontal_scroll_margin: 10").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.horizontal_scroll_margin(), 10);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_annotation_patterns() {
+        let data = YamlLoader::load_from_str("annotation_patterns: [HACK, NOTE]").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.annotation_patterns(), vec!["HACK".to_string(), "NOTE".to_string()]);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_browser_command() {
+        let data = YamlLoader::load_from_str("browser_command: firefox --new-tab").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.browser_command(), vec!["firefox".to_string(), "--new-tab".to_string()]);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_uuid_format() {
+        let data = YamlLoader::load_from_str("uuid_format: plain").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.uuid_format(), UuidFormat::Plain);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_open_extracted_file() {
+        let data = YamlLoader::load_from_str("open_extracted_file: false").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.open_extracted_file(), false);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_smart_home() {
+        let data = YamlLoader::load_from_str("smart_home: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.smart_home(), true);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_lint_whitespace_on_open() {
+        let data = YamlLoader::load_from_str("lint_whitespace_on_open: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.lint_whitespace_on_open(), true);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_normalize_indentation() {
+        let data = YamlLoader::load_from_str("normalize_indentation: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.normalize_indentation(), true);
+    }
+
+    #[test]
+    fn normalize_indentation_defaults_to_false() {
+        let preferences = Preferences::new(None);
+
+        assert_eq!(preferences.normalize_indentation(), false);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_scrollbar() {
+        let data = YamlLoader::load_from_str("scrollbar: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.scrollbar(), true);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_start_screen() {
+        let data = YamlLoader::load_from_str("start_screen: false").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.start_screen(), false);
+    }
+
+    #[test]
+    fn spell_check_dictionary_caches_load_failures() {
+        let dir = TempDir::new("missing-dictionary");
+        let path = dir.join("dictionary");
+        let data = YamlLoader::load_from_str(
+            &format!("spell_check_dictionary: {}", path.to_string_lossy())
+        ).unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+        assert!(preferences.spell_check_dictionary().is_err());
+
+        // The dictionary isn't loaded again once it's been found to be missing.
+        fs::write(&path, "amp\n").unwrap();
+        assert!(preferences.spell_check_dictionary().is_err());
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_spell_check() {
+        let data = YamlLoader::load_from_str("spell_check: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.spell_check(), true);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_zen_mode_width() {
+        let data = YamlLoader::load_from_str("zen_mode_width: 72").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.zen_mode_width(), Some(72));
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_text_width() {
+        let data = YamlLoader::load_from_str("text_width: 72").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.text_width(), 72);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_auto_pairs() {
+        let data = YamlLoader::load_from_str("auto_pairs: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.auto_pairs(), true);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_auto_reload() {
+        let data = YamlLoader::load_from_str("auto_reload: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.auto_reload(), true);
+    }
+
+    #[test]
+    fn preferences_returns_user_defined_center_on_search() {
+        let data = YamlLoader::load_from_str("center_on_search: false").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.center_on_search(), false);
+    }
+
     #[test]
     fn tab_content_uses_tab_width_spaces_when_soft_tabs_are_enabled() {
         let data = YamlLoader::load_from_str("soft_tabs: true\ntab_width: 5").unwrap();
@@ -497,11 +1248,95 @@ mod tests {
         let mut preferences = Preferences{
             data: None,
             keymap: KeyMap::from(&Hash::new()).unwrap(),
-            theme: None
+            theme: None,
+            wrap_cursor: None,
+            editor_configs: RefCell::new(HashMap::new()),
+            dictionary: RefCell::new(None),
         };
 
         // Reload the preferences, ensuring that it refreshes the keymap.
         preferences.reload().unwrap();
         assert!(preferences.keymap().get("normal").is_some());
     }
+
+    #[test]
+    fn load_document_from_reads_the_specified_config_path() {
+        let dir = TempDir::new("config-path");
+        let config_path = dir.join("config.yml");
+        let mut config_file = File::create(&config_path).unwrap();
+        write!(config_file, "amp_config_path_test: true").unwrap();
+
+        let data = super::load_document_from(Some(config_path)).unwrap();
+        assert_eq!(data.unwrap()["amp_config_path_test"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn load_document_from_falls_back_to_defaults_for_missing_config_paths() {
+        let dir = TempDir::new("missing-config-path");
+        let config_path = dir.join("config.yml");
+
+        assert!(super::load_document_from(Some(config_path)).unwrap().is_none());
+    }
+
+    #[test]
+    fn data_directory_from_returns_the_specified_path() {
+        let data_path = PathBuf::from("data");
+
+        assert_eq!(super::data_directory_from(Some(data_path.clone())).unwrap(), data_path);
+    }
+
+    #[test]
+    fn editor_config_settings_take_precedence_over_user_preferences() {
+        let dir = TempDir::new("preferences-editor-config");
+        let mut file = File::create(dir.join(".editorconfig")).unwrap();
+        write!(file, "root = true\n[*.rs]\nindent_style = tab\nindent_size = 8\n").unwrap();
+
+        let data = YamlLoader::load_from_str("tab_width: 4\nsoft_tabs: true").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+        let matching_path = dir.join("main.rs");
+        let other_path = dir.join("README.md");
+
+        assert_eq!(preferences.tab_width(Some(&matching_path)), 8);
+        assert_eq!(preferences.soft_tabs(Some(&matching_path)), false);
+        assert_eq!(preferences.tab_width(Some(&other_path)), 4);
+        assert_eq!(preferences.soft_tabs(Some(&other_path)), true);
+    }
+
+    #[test]
+    fn alternate_file_rules_returns_defaults() {
+        let preferences = Preferences::new(None);
+
+        assert_eq!(
+            preferences.alternate_file_rules()[0],
+            (String::from(".h"), String::from(".c"))
+        );
+    }
+
+    #[test]
+    fn alternate_file_rules_prepends_user_defined_rules() {
+        let data = YamlLoader::load_from_str("alternate_files:\n  - [\".ts\", \".spec.ts\"]").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(
+            preferences.alternate_file_rules()[0],
+            (String::from(".ts"), String::from(".spec.ts"))
+        );
+    }
+
+    #[test]
+    fn idle_timeout_is_disabled_by_default() {
+        let preferences = Preferences::new(None);
+
+        assert_eq!(preferences.idle_timeout(), None);
+        assert_eq!(preferences.idle_action(), IdleAction::Save);
+    }
+
+    #[test]
+    fn idle_timeout_and_action_can_be_configured() {
+        let data = YamlLoader::load_from_str("idle_timeout: 300\nidle_action: lock").unwrap();
+        let preferences = Preferences::new(data.into_iter().nth(0));
+
+        assert_eq!(preferences.idle_timeout(), Some(Duration::from_secs(300)));
+        assert_eq!(preferences.idle_action(), IdleAction::Lock);
+    }
 }
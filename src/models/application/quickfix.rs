@@ -0,0 +1,192 @@
+use bloodhound::ExclusionPattern;
+use errors::*;
+use git2::Repository;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use util::glob;
+
+/// A location (e.g. a search match or compiler error) in the quickfix list,
+/// along with a message describing it. Lines are zero-indexed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuickfixEntry {
+    pub path: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for QuickfixEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.path.to_string_lossy(), self.line + 1, self.message)
+    }
+}
+
+/// Finds the lines containing the query, returning an entry for each.
+pub fn search(path: PathBuf, content: &str, query: &str) -> Vec<QuickfixEntry> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|&(_, line)| line.contains(query))
+        .map(|(index, line)| {
+            QuickfixEntry {
+                path: path.clone(),
+                line: index,
+                message: line.trim().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Finds the lines containing the query in the files beneath the root,
+/// skipping excluded files, those ignored by git, and those that aren't
+/// text (i.e. aren't valid UTF-8, or contain null bytes).
+pub fn search_files(root: &Path, query: &str, exclusions: &[ExclusionPattern]) -> Result<Vec<QuickfixEntry>> {
+    let repo = Repository::discover(root).ok();
+    let mut entries = Vec::new();
+
+    for path in glob::find_unignored(root, "**", exclusions, repo.as_ref())? {
+        if let Ok(content) = fs::read_to_string(&path) {
+            if !content.contains('\0') {
+                entries.extend(search(path, &content, query));
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Finds lines with trailing whitespace or mixed indentation, returning an
+/// entry for each problem. When using soft tabs, any tab in a line's
+/// indentation counts as mixed; otherwise, only tabs following spaces do.
+pub fn lint_whitespace(path: PathBuf, content: &str, soft_tabs: bool) -> Vec<QuickfixEntry> {
+    let mut entries = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed_line = line.trim_left();
+        if line.trim_right() != line {
+            entries.push(QuickfixEntry {
+                path: path.clone(),
+                line: index,
+                message: String::from("Trailing whitespace"),
+            });
+        }
+
+        // Whitespace-only lines have no indentation to speak of.
+        if trimmed_line.is_empty() {
+            continue;
+        }
+
+        let indentation = &line[..line.len() - trimmed_line.len()];
+        let mixed = if soft_tabs {
+            indentation.contains('\t')
+        } else {
+            indentation.contains(" \t")
+        };
+        if mixed {
+            entries.push(QuickfixEntry {
+                path: path.clone(),
+                line: index,
+                message: String::from("Mixed indentation"),
+            });
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use bloodhound::ExclusionPattern;
+    use std::fs;
+    use std::path::PathBuf;
+    use super::{lint_whitespace, search, search_files, QuickfixEntry};
+    use util::test_helpers::TempDir;
+
+    #[test]
+    fn search_returns_an_entry_for_each_matching_line() {
+        let path = PathBuf::from("src/main.rs");
+
+        assert_eq!(
+            search(path.clone(), "amp\n  editor amp\nbuffer", "amp"),
+            vec![
+                QuickfixEntry { path: path.clone(), line: 0, message: String::from("amp") },
+                QuickfixEntry { path: path.clone(), line: 1, message: String::from("editor amp") },
+            ]
+        );
+    }
+
+    #[test]
+    fn search_files_skips_excluded_and_binary_files() {
+        let root = TempDir::new("quickfix-search-files");
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join("first.txt"), "amp\neditor\namp\n").unwrap();
+        fs::write(root.join("second.txt"), "buffer\namp\n").unwrap();
+        fs::write(root.join("binary"), b"amp\0\xff").unwrap();
+        fs::write(root.join("nul.bin"), "amp\0").unwrap();
+        fs::write(root.join("target/build.txt"), "amp\n").unwrap();
+        let exclusions = vec![ExclusionPattern::new("**/target").unwrap()];
+
+        assert_eq!(
+            search_files(root.path(), "amp", &exclusions)
+                .unwrap()
+                .into_iter()
+                .map(|entry| (entry.path, entry.line))
+                .collect::<Vec<_>>(),
+            vec![
+                (root.join("first.txt"), 0),
+                (root.join("first.txt"), 2),
+                (root.join("second.txt"), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_display_their_location_and_message() {
+        let entry = QuickfixEntry {
+            path: PathBuf::from("src/main.rs"),
+            line: 9,
+            message: String::from("amp"),
+        };
+
+        assert_eq!(entry.to_string(), "src/main.rs:10: amp");
+    }
+
+    #[test]
+    fn lint_whitespace_flags_trailing_whitespace() {
+        let path = PathBuf::from("src/main.rs");
+
+        assert_eq!(
+            lint_whitespace(path.clone(), "amp \neditor\n  \nbuffer\t", true),
+            vec![
+                QuickfixEntry { path: path.clone(), line: 0, message: String::from("Trailing whitespace") },
+                QuickfixEntry { path: path.clone(), line: 2, message: String::from("Trailing whitespace") },
+                QuickfixEntry { path: path.clone(), line: 3, message: String::from("Trailing whitespace") },
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_whitespace_flags_tabs_after_spaces_when_using_hard_tabs() {
+        let path = PathBuf::from("src/main.rs");
+
+        assert_eq!(
+            lint_whitespace(path.clone(), "\tamp\n  \teditor\n\t  buffer", false),
+            vec![
+                QuickfixEntry { path: path.clone(), line: 1, message: String::from("Mixed indentation") },
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_whitespace_flags_any_indentation_tabs_when_using_soft_tabs() {
+        let path = PathBuf::from("src/main.rs");
+
+        assert_eq!(
+            lint_whitespace(path.clone(), "  amp\n\teditor\n  \tbuffer\nworkspace\tpath", true),
+            vec![
+                QuickfixEntry { path: path.clone(), line: 1, message: String::from("Mixed indentation") },
+                QuickfixEntry { path: path.clone(), line: 2, message: String::from("Mixed indentation") },
+            ]
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use errors::*;
+use models::application::Preferences;
+use scribe::Workspace;
+use scribe::buffer::Position;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use util::codec;
+
+const SESSION_DIRECTORY: &str = "sessions";
+
+/// An open buffer's path and cursor position, as recorded in a session.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionBuffer {
+    pub path: PathBuf,
+    pub cursor: Position,
+}
+
+/// The set of open buffers in a workspace, in order,
+/// starting with the buffer that was current when saved.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Session {
+    pub buffers: Vec<SessionBuffer>,
+}
+
+impl Session {
+    /// Records the workspace's buffers, leaving the current buffer unchanged.
+    /// Buffers without a path (i.e. that have never been saved) are skipped.
+    pub fn capture(workspace: &mut Workspace) -> Session {
+        let mut buffers = Vec::new();
+        let id = workspace.current_buffer().and_then(|buffer| buffer.id);
+
+        if id.is_some() {
+            loop {
+                if let Some(buffer) = workspace.current_buffer() {
+                    if let Some(ref path) = buffer.path {
+                        buffers.push(SessionBuffer {
+                            path: path.clone(),
+                            cursor: *buffer.cursor,
+                        });
+                    }
+                }
+
+                workspace.next_buffer();
+                if workspace.current_buffer().and_then(|buffer| buffer.id) == id {
+                    break;
+                }
+            }
+        }
+
+        Session { buffers }
+    }
+
+    /// Serializes the session with one buffer per line, in the form
+    /// `<line>:<offset>\t<path>`. Line breaks (and percent signs) in
+    /// paths are percent-encoded, so that they don't span lines.
+    pub fn serialize(&self) -> String {
+        self.buffers
+            .iter()
+            .map(|buffer| {
+                format!(
+                    "{}:{}\t{}\n",
+                    buffer.cursor.line,
+                    buffer.cursor.offset,
+                    encode_path(&buffer.path)
+                )
+            })
+            .collect()
+    }
+
+    pub fn deserialize(data: &str) -> Result<Session> {
+        let mut buffers = Vec::new();
+
+        for (index, line) in data.lines().enumerate().filter(|&(_, line)| !line.is_empty()) {
+            let mut fields = line.splitn(2, '\t');
+            let position = fields.next().unwrap_or("");
+            let path = fields
+                .next()
+                .ok_or_else(|| format!("Session line {} is missing a path", index + 1))
+                .and_then(|path| {
+                    codec::url_decode(path)
+                        .map_err(|_| format!("Session line {} has an invalid path", index + 1))
+                })?;
+            let mut offsets = position.splitn(2, ':').map(|value| value.parse::<usize>());
+
+            match (offsets.next(), offsets.next()) {
+                (Some(Ok(line)), Some(Ok(offset))) => {
+                    buffers.push(SessionBuffer {
+                        path: PathBuf::from(path),
+                        cursor: Position { line, offset },
+                    });
+                }
+                _ => bail!("Session line {} has an invalid cursor position", index + 1),
+            }
+        }
+
+        Ok(Session { buffers })
+    }
+
+    /// Removes buffers whose files no longer exist, returning their paths.
+    pub fn remove_missing(&mut self) -> Vec<PathBuf> {
+        let (existing, missing): (Vec<_>, Vec<_>) = self.buffers
+            .drain(..)
+            .partition(|buffer| buffer.path.exists());
+        self.buffers = existing;
+
+        missing.into_iter().map(|buffer| buffer.path).collect()
+    }
+
+    pub fn save(&self, workspace_path: &Path) -> Result<()> {
+        self.save_in(&Preferences::data_directory()?, workspace_path)
+    }
+
+    /// Saves the session for the workspace under the specified data directory.
+    pub fn save_in(&self, data_directory: &Path, workspace_path: &Path) -> Result<()> {
+        let path = session_path(data_directory, workspace_path);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)
+                .chain_err(|| "Couldn't create session directory")?;
+        }
+
+        File::create(&path)
+            .and_then(|mut file| file.write_all(self.serialize().as_bytes()))
+            .chain_err(|| format!("Couldn't write session to {}", path.to_string_lossy()))
+    }
+
+    pub fn load(workspace_path: &Path) -> Result<Session> {
+        Session::load_from(&Preferences::data_directory()?, workspace_path)
+    }
+
+    /// Loads the workspace's session from the specified data directory.
+    pub fn load_from(data_directory: &Path, workspace_path: &Path) -> Result<Session> {
+        let path = session_path(data_directory, workspace_path);
+        let mut data = String::new();
+        File::open(&path)
+            .and_then(|mut file| file.read_to_string(&mut data))
+            .chain_err(|| "No saved session found for this workspace")?;
+
+        Session::deserialize(&data)
+    }
+}
+
+fn encode_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('%', "%25")
+        .replace('\n', "%0A")
+        .replace('\r', "%0D")
+}
+
+/// Sessions are stored in the data directory, named using a hash of the
+/// workspace path, which (unlike the path itself) is never too long to be
+/// used as a file name.
+fn session_path(data_directory: &Path, workspace_path: &Path) -> PathBuf {
+    data_directory
+        .join(SESSION_DIRECTORY)
+        .join(format!("{:016x}", path_hash(workspace_path)))
+}
+
+/// A 64-bit FNV-1a hash of the path. Unlike std's hashers, its output
+/// is guaranteed not to change between releases, which would orphan
+/// previously saved sessions.
+fn path_hash(path: &Path) -> u64 {
+    path.to_string_lossy().bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use scribe::buffer::Position;
+    use std::fs::File;
+    use std::path::{Path, PathBuf};
+    use super::{session_path, Session, SessionBuffer};
+    use util::test_helpers::TempDir;
+
+    fn session(paths: Vec<PathBuf>) -> Session {
+        Session {
+            buffers: paths
+                .into_iter()
+                .enumerate()
+                .map(|(index, path)| {
+                    SessionBuffer { path, cursor: Position { line: index + 1, offset: index + 2 } }
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn sessions_survive_serialization() {
+        let session = session(vec![
+            PathBuf::from("src/main.rs"),
+            PathBuf::from("path with spaces/lib.rs"),
+        ]);
+        let data = session.serialize();
+
+        assert_eq!(data, "1:2\tsrc/main.rs\n2:3\tpath with spaces/lib.rs\n");
+        assert_eq!(Session::deserialize(&data).unwrap(), session);
+    }
+
+    #[test]
+    fn serialize_keeps_paths_containing_line_breaks_on_one_line() {
+        let session = session(vec![PathBuf::from("line\nbreak.rs")]);
+        let data = session.serialize();
+
+        assert_eq!(data.lines().count(), 1);
+        assert_eq!(Session::deserialize(&data).unwrap(), session);
+    }
+
+    #[test]
+    fn session_path_is_a_fixed_length_name_unique_to_the_workspace() {
+        let data_directory = Path::new("data");
+        let long_path = PathBuf::from("/").join("workspace".repeat(100));
+        let path = session_path(data_directory, &long_path);
+
+        assert_eq!(path.parent(), Some(data_directory.join("sessions").as_path()));
+        assert_eq!(path.file_name().unwrap().len(), 16);
+        assert_ne!(path, session_path(data_directory, Path::new("/workspace")));
+    }
+
+    #[test]
+    fn sessions_survive_saving_and_loading() {
+        let data_directory = TempDir::new("session-save");
+        let session = session(vec![PathBuf::from("src/main.rs")]);
+
+        session.save_in(data_directory.path(), Path::new("/workspace")).unwrap();
+
+        assert_eq!(Session::load_from(data_directory.path(), Path::new("/workspace")).unwrap(), session);
+        assert!(Session::load_from(data_directory.path(), Path::new("/elsewhere")).is_err());
+    }
+
+    #[test]
+    fn deserialize_rejects_invalid_positions() {
+        assert!(Session::deserialize("1:amp\tsrc/main.rs\n").is_err());
+        assert!(Session::deserialize("src/main.rs\n").is_err());
+    }
+
+    #[test]
+    fn remove_missing_skips_buffers_whose_files_vanished() {
+        let dir = TempDir::new("session-remove-missing");
+        let existing = dir.join("existing.rs");
+        let missing = dir.join("missing.rs");
+        File::create(&existing).unwrap();
+
+        let mut session = Session::deserialize(
+            &session(vec![existing.clone(), missing.clone()]).serialize()
+        ).unwrap();
+
+        assert_eq!(session.remove_missing(), vec![missing]);
+        assert_eq!(session.buffers, vec![
+            SessionBuffer { path: existing, cursor: Position { line: 1, offset: 2 } }
+        ]);
+    }
+
+    #[test]
+    fn capture_records_buffers_starting_with_the_current_one() {
+        let mut app = ::models::Application::new(&Vec::new()).unwrap();
+        let dir = TempDir::new("session-capture");
+        for name in &["first.rs", "second.rs"] {
+            File::create(dir.join(name)).unwrap();
+            app.workspace.open_buffer(&dir.join(name)).unwrap();
+        }
+        app.workspace.next_buffer();
+
+        let session = Session::capture(&mut app.workspace);
+        let paths: Vec<PathBuf> = session.buffers.iter().map(|b| b.path.clone()).collect();
+
+        assert_eq!(paths.first(), app.workspace.current_buffer().unwrap().path.as_ref());
+        assert_eq!(paths.len(), 2);
+    }
+}
@@ -0,0 +1,101 @@
+use models::application::Session;
+use std::path::{Path, PathBuf};
+
+/// The maximum number of recent files listed, each selectable using a digit.
+const RECENT_FILE_LIMIT: usize = 9;
+
+/// Keys for common commands, as bound in the default keymap.
+const COMMON_COMMANDS: [(&str, &str); 5] = [
+    ("space", "Open a file"),
+    ("B", "Create a new buffer"),
+    ("0", "Run a command"),
+    ("?", "View the quick start guide"),
+    ("Q", "Quit"),
+];
+
+/// Shown in place of an empty workspace when amp is launched without any
+/// files, listing the files recorded in the workspace's saved session.
+#[derive(Debug, Default, PartialEq)]
+pub struct StartScreen {
+    recent_files: Vec<PathBuf>,
+}
+
+impl StartScreen {
+    /// Builds a start screen for the workspace; those without a saved
+    /// session (or with an unreadable one) simply don't list recent files.
+    pub fn new(workspace_path: &Path) -> StartScreen {
+        let recent_files = Session::load(workspace_path)
+            .map(|mut session| {
+                session.remove_missing();
+                session.buffers.into_iter().map(|buffer| buffer.path).collect()
+            })
+            .unwrap_or_default();
+
+        StartScreen::with_recent_files(recent_files)
+    }
+
+    pub fn with_recent_files(mut recent_files: Vec<PathBuf>) -> StartScreen {
+        recent_files.truncate(RECENT_FILE_LIMIT);
+
+        StartScreen { recent_files }
+    }
+
+    /// The recent file listed using the specified (one-based) number.
+    pub fn recent_file(&self, number: usize) -> Option<&PathBuf> {
+        number.checked_sub(1).and_then(|index| self.recent_files.get(index))
+    }
+
+    /// The screen's content, with recent file paths
+    /// displayed relative to the workspace, where possible.
+    pub fn lines(&self, workspace_path: &Path) -> Vec<String> {
+        let mut lines = vec![
+            format!("Amp v{}", env!("CARGO_PKG_VERSION")),
+            String::from("© 2015-2018 Jordan MacDonald"),
+        ];
+
+        if !self.recent_files.is_empty() {
+            lines.push(String::new());
+            lines.push(String::from("Recent files"));
+            for (index, path) in self.recent_files.iter().enumerate() {
+                let path = path.strip_prefix(workspace_path).unwrap_or(path);
+                lines.push(format!("  {:<6} {}", index + 1, path.to_string_lossy()));
+            }
+        }
+
+        lines.push(String::new());
+        for &(key, description) in COMMON_COMMANDS.iter() {
+            lines.push(format!("  {:<6} {}", key, description));
+        }
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use super::StartScreen;
+
+    #[test]
+    fn lines_list_recent_files_relative_to_the_workspace() {
+        let start_screen = StartScreen::with_recent_files(vec![
+            PathBuf::from("/amp/src/main.rs"),
+            PathBuf::from("/elsewhere/notes.md"),
+        ]);
+        let lines = start_screen.lines(Path::new("/amp"));
+
+        assert_eq!(lines[3], "Recent files");
+        assert_eq!(lines[4], "  1      src/main.rs");
+        assert_eq!(lines[5], "  2      /elsewhere/notes.md");
+        assert_eq!(lines.last().unwrap(), "  Q      Quit");
+    }
+
+    #[test]
+    fn recent_file_uses_one_based_numbers() {
+        let start_screen = StartScreen::with_recent_files(vec![PathBuf::from("main.rs")]);
+
+        assert_eq!(start_screen.recent_file(0), None);
+        assert_eq!(start_screen.recent_file(1), Some(&PathBuf::from("main.rs")));
+        assert_eq!(start_screen.recent_file(2), None);
+    }
+}
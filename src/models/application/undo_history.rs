@@ -0,0 +1,113 @@
+/// A labelled record of the undoable changes made to a buffer. Scribe's
+/// history doesn't describe its entries, so this follows along with it,
+/// adding a label each time a command's operation group changes the buffer,
+/// and moving through them as those changes are undone and redone.
+#[derive(Debug, Default, PartialEq)]
+pub struct UndoHistory {
+    labels: Vec<String>,
+    position: usize,
+}
+
+/// A buffer's revision and the label for a change that's in progress (i.e.
+/// an insert mode session), used to record the change once it's complete.
+/// Sessions entered without an edit are labelled by the first one they make.
+pub struct PendingChange {
+    pub buffer_id: usize,
+    pub revision: usize,
+    pub label: Option<String>,
+}
+
+impl UndoHistory {
+    pub fn new() -> UndoHistory {
+        UndoHistory::default()
+    }
+
+    /// Records a change at the current position,
+    /// discarding any changes that have been undone.
+    pub fn record<T: Into<String>>(&mut self, label: T) {
+        self.labels.truncate(self.position);
+        self.labels.push(label.into());
+        self.position = self.labels.len();
+    }
+
+    pub fn undo(&mut self) {
+        self.position = self.position.saturating_sub(1);
+    }
+
+    pub fn redo(&mut self) {
+        if self.position < self.labels.len() {
+            self.position += 1;
+        }
+    }
+
+    /// Lists changes from newest to oldest, marking the buffer's current
+    /// state. Changes above the marker have been undone and can be redone;
+    /// those below it are reverted, in order, by undoing.
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+
+        for (index, label) in self.labels.iter().enumerate().rev() {
+            let number = index + 1;
+            let marker = if number == self.position { ">" } else { " " };
+            let state = if number > self.position { " (undone)" } else { "" };
+            summary.push_str(&format!("{} {:>3} {}{}\n", marker, number, label, state));
+        }
+        let marker = if self.position == 0 { ">" } else { " " };
+        summary.push_str(&format!("{} {:>3} (original)\n", marker, 0));
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UndoHistory;
+
+    #[test]
+    fn summary_marks_the_current_position() {
+        let mut history = UndoHistory::new();
+        history.record("buffer::delete_line");
+        history.record("application::switch_to_insert_mode");
+        history.record("buffer::paste");
+        history.undo();
+
+        assert_eq!(
+            history.summary(),
+            "    3 buffer::paste (undone)\n\
+             >   2 application::switch_to_insert_mode\n\
+             \x20   1 buffer::delete_line\n\
+             \x20   0 (original)\n"
+        );
+    }
+
+    #[test]
+    fn summary_marks_the_original_state_when_everything_is_undone() {
+        let mut history = UndoHistory::new();
+        history.record("buffer::delete_line");
+        history.undo();
+        history.undo();
+
+        assert_eq!(
+            history.summary(),
+            "    1 buffer::delete_line (undone)\n\
+             >   0 (original)\n"
+        );
+    }
+
+    #[test]
+    fn record_discards_undone_changes() {
+        let mut history = UndoHistory::new();
+        history.record("buffer::delete_line");
+        history.record("buffer::paste");
+        history.undo();
+        history.record("buffer::indent_line");
+        history.redo();
+
+        assert_eq!(
+            history.summary(),
+            ">   2 buffer::indent_line\n\
+             \x20   1 buffer::delete_line\n\
+             \x20   0 (original)\n"
+        );
+    }
+}
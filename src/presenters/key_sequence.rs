@@ -0,0 +1,25 @@
+use input::Key;
+use scribe::buffer::Position;
+use pad::PadStr;
+use view::{Colors, Style, View};
+
+/// Draws the possible completions for a pending key sequence
+/// above the status line, over top of the current mode's content.
+pub fn display(view: &mut View, completions: &[(Key, String)]) {
+    // Leave room for the status line, and at least one line of content.
+    let available_lines = view.height().saturating_sub(2);
+    let visible_completions = &completions[..completions.len().min(available_lines)];
+    let first_line = view.height().saturating_sub(1 + visible_completions.len());
+
+    for (index, &(ref key, ref command)) in visible_completions.iter().enumerate() {
+        let content = format!(" {:>9}  {}", key.to_string(), command);
+        let _ = view.print(
+            &Position { line: first_line + index, offset: 0 },
+            Style::Default,
+            Colors::Focused,
+            &content.pad_to_width(view.width())
+        );
+    }
+
+    view.present();
+}
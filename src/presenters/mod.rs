@@ -1,4 +1,7 @@
+pub mod key_sequence;
 pub mod modes;
+pub mod outline;
+pub mod start_screen;
 
 use std::path::{Path, PathBuf};
 use scribe::Workspace;
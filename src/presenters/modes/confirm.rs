@@ -1,8 +1,9 @@
 use errors::*;
+use models::application::modes::ConfirmMode;
 use scribe::Workspace;
 use view::{Colors, StatusLineData, Style, View};
 
-pub fn display(workspace: &mut Workspace, view: &mut View) -> Result<()> {
+pub fn display(workspace: &mut Workspace, mode: &ConfirmMode, view: &mut View) -> Result<()> {
     // Wipe the slate clean.
     view.clear();
 
@@ -11,13 +12,13 @@ pub fn display(workspace: &mut Workspace, view: &mut View) -> Result<()> {
         view.draw_buffer(buf, None, None)?;
     }
 
-    // Draw the status line as a search prompt.
-    let confirmation = "Are you sure? (y/n)".to_string();
+    // Draw the status line as a prompt listing the available choices.
+    let confirmation = mode.status();
     view.draw_status_line(&[
         StatusLineData {
             content: confirmation,
             style: Style::Bold,
-            colors: Colors::Warning,
+            colors: Colors::StatusLine("confirm"),
         }
     ]);
 
@@ -0,0 +1,33 @@
+use errors::*;
+use scribe::Workspace;
+use models::application::modes::FindCharMode;
+use view::{Colors, StatusLineData, Style, View};
+
+pub fn display(workspace: &mut Workspace, mode: &FindCharMode, view: &mut View) -> Result<()> {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = workspace.current_buffer() {
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(buf, None, None)?;
+
+        // Draw the status line as a prompt for the target character.
+        let prompt = if mode.search.till {
+            "Till character"
+        } else {
+            "Find character"
+        };
+        view.draw_status_line(&[
+            StatusLineData {
+                content: prompt.to_string(),
+                style: Style::Default,
+                colors: Colors::StatusLine("find_char"),
+            }
+        ]);
+    }
+
+    // Render the changes to the screen.
+    view.present();
+
+    Ok(())
+}
@@ -12,13 +12,14 @@ pub fn display(workspace: &mut Workspace, view: &mut View) -> Result<()> {
     if let Some(buf) = workspace.current_buffer() {
         // Draw the visible set of tokens to the terminal.
         view.draw_buffer(buf, None, None)?;
+        view.draw_scrollbar(buf)?;
 
         // Draw the status line.
         view.draw_status_line(&[
             StatusLineData {
                 content: " INSERT ".to_string(),
                 style: Style::Default,
-                colors: Colors::Insert,
+                colors: Colors::StatusLine("insert"),
             },
             buffer_status
         ]);
@@ -21,7 +21,7 @@ pub fn display(workspace: &mut Workspace, mode: &mut JumpMode, view: &mut View)
             StatusLineData {
                 content: " JUMP ".to_string(),
                 style: Style::Default,
-                colors: Colors::Inverted,
+                colors: Colors::StatusLine("jump"),
             },
             buffer_status
         ]);
@@ -0,0 +1,46 @@
+use errors::*;
+use scribe::Workspace;
+use scribe::buffer::Position;
+use models::application::modes::LineFilterMode;
+use unicode_segmentation::UnicodeSegmentation;
+use view::{Colors, StatusLineData, Style, View};
+
+pub fn display(workspace: &mut Workspace, mode: &LineFilterMode, view: &mut View) -> Result<()> {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buffer) = workspace.current_buffer() {
+        view.draw_buffer(buffer, None, None)?;
+    }
+
+    let mode_display = format!(" {} ", mode);
+    let pattern_input = format!(" {}", mode.input);
+    let cursor_offset =
+        mode_display.graphemes(true).count() +
+        pattern_input.graphemes(true).count();
+
+    view.draw_status_line(&[
+        StatusLineData {
+            content: mode_display,
+            style: Style::Default,
+            colors: Colors::StatusLine("line_filter"),
+        },
+        StatusLineData {
+            content: pattern_input,
+            style: Style::Default,
+            colors: Colors::Focused,
+        },
+    ]);
+
+    // Move the cursor to the end of the pattern input.
+    let cursor_line = view.height() - 1;
+    view.set_cursor(Some(Position {
+        line: cursor_line,
+        offset: cursor_offset
+    }));
+
+    // Render the changes to the screen.
+    view.present();
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use errors::*;
 use scribe::Workspace;
-use scribe::buffer::Position;
+use scribe::buffer::{LineRange, Position};
+use util;
 use models::application::modes::LineJumpMode;
 use view::{Colors, StatusLineData, Style, View};
 
@@ -9,8 +10,12 @@ pub fn display(workspace: &mut Workspace, mode: &LineJumpMode, view: &mut View)
     view.clear();
 
     if let Some(buf) = workspace.current_buffer() {
-        // Draw the visible set of tokens to the terminal.
-        view.draw_buffer(buf, None, None)?;
+        // Draw the visible set of tokens to the terminal,
+        // highlighting the line being previewed, if any.
+        let preview = mode.preview_line.map(|line| {
+            vec![util::inclusive_range(&LineRange::new(line, line), buf)]
+        });
+        view.draw_buffer(buf, preview.as_ref().map(|p| p.as_slice()), None)?;
 
         // Draw the status line as an input prompt.
         let input_prompt = format!("Go to line: {}", mode.input);
@@ -19,7 +24,7 @@ pub fn display(workspace: &mut Workspace, mode: &LineJumpMode, view: &mut View)
             StatusLineData {
                 content: input_prompt,
                 style: Style::Default,
-                colors: Colors::Default,
+                colors: Colors::StatusLine("line_jump"),
             }
         ]);
 
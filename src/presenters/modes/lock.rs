@@ -0,0 +1,18 @@
+use errors::*;
+use scribe::buffer::Position;
+use view::{Colors, Style, View};
+
+pub fn display(view: &mut View) -> Result<()> {
+    // Blank the screen, leaving only a hint on how to resume.
+    view.clear();
+    view.print(&Position{ line: 0, offset: 0 },
+               Style::Default,
+               Colors::Default,
+               &"Locked; press any key to resume.")?;
+    view.set_cursor(None);
+
+    // Render the changes to the screen.
+    view.present();
+
+    Ok(())
+}
@@ -1,10 +1,17 @@
 pub mod confirm;
+pub mod find_char;
 pub mod insert;
 pub mod jump;
+pub mod line_filter;
 pub mod line_jump;
+pub mod lock;
 pub mod path;
+pub mod replace_char;
 pub mod normal;
+pub mod outline;
 pub mod search;
 pub mod search_select;
+pub mod separator;
 pub mod select;
 pub mod select_line;
+pub mod shell_command;
@@ -2,23 +2,34 @@ use errors::*;
 use scribe::Workspace;
 use presenters::{current_buffer_status_line_data, git_status_line_data};
 use git2::Repository;
+use util::blame::BlameCache;
 use view::{Colors, StatusLineData, Style, View};
 
-pub fn display(workspace: &mut Workspace, view: &mut View, repo: &Option<Repository>) -> Result<()> {
+pub fn display(workspace: &mut Workspace, view: &mut View, repo: &Option<Repository>, blame: &mut Option<BlameCache>) -> Result<()> {
     // Wipe the slate clean.
     view.clear();
 
     let buffer_status = current_buffer_status_line_data(workspace);
 
     if let Some(buf) = workspace.current_buffer() {
-        // Draw the visible set of tokens to the terminal.
-        view.draw_buffer(buf, None, None)?;
+        // Draw the visible set of tokens to the terminal,
+        // alongside blame annotations, if they've been toggled on.
+        let annotations = match (blame.as_mut(), repo.as_ref()) {
+            (Some(cache), Some(repo)) => {
+                let revision = view.buffer_revision(buf)?;
+
+                Some(cache.annotations(repo, buf, revision)?)
+            }
+            _ => None,
+        };
+        view.draw_annotated_buffer(buf, None, None, annotations.as_ref().map(|a| a.as_slice()))?;
+        view.draw_scrollbar(buf)?;
 
         // Determine mode display color based on buffer modification status.
         let colors = if buf.modified() {
             Colors::Warning
         } else {
-            Colors::Inverted
+            Colors::StatusLine("normal")
         };
 
         // Build the status line mode and buffer title display.
@@ -0,0 +1,34 @@
+use errors::*;
+use presenters::current_buffer_status_line_data;
+use scribe::Workspace;
+use view::{Colors, StatusLineData, Style, View};
+
+pub fn display(workspace: &mut Workspace, view: &mut View) -> Result<()> {
+    // Wipe the slate clean.
+    view.clear();
+
+    let buffer_status = current_buffer_status_line_data(workspace);
+
+    if let Some(buf) = workspace.current_buffer() {
+        view.draw_buffer(buf, None, None)?;
+        view.draw_scrollbar(buf)?;
+
+        // Draw the status line. The outline itself is
+        // drawn by the view, as a sidebar.
+        view.draw_status_line(&[
+            StatusLineData {
+                content: " OUTLINE ".to_string(),
+                style: Style::Default,
+                colors: Colors::Inverted,
+            },
+            buffer_status
+        ]);
+    }
+
+    view.set_cursor(None);
+
+    // Render the changes to the screen.
+    view.present();
+
+    Ok(())
+}
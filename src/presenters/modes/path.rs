@@ -9,9 +9,11 @@ pub fn display(workspace: &mut Workspace, mode: &PathMode, view: &mut View) -> R
     // Wipe the slate clean.
     view.clear();
 
-    // Draw the visible set of tokens to the terminal.
-    let buffer = workspace.current_buffer().ok_or(BUFFER_MISSING)?;
-    view.draw_buffer(buffer, None, None)?;
+    // Draw the visible set of tokens to the terminal. Globs
+    // can be opened without a buffer, so it may not be present.
+    if let Some(buffer) = workspace.current_buffer() {
+        view.draw_buffer(buffer, None, None)?;
+    }
 
     let mode_display = format!(" {} ", mode);
     let search_input = format!(
@@ -27,7 +29,7 @@ pub fn display(workspace: &mut Workspace, mode: &PathMode, view: &mut View) -> R
         StatusLineData {
             content: mode_display,
             style: Style::Default,
-            colors: Colors::PathMode,
+            colors: Colors::StatusLine("path"),
         },
         StatusLineData {
             content: search_input,
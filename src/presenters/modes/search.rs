@@ -11,7 +11,7 @@ pub fn display(workspace: &mut Workspace, mode: &SearchMode, view: &mut View) ->
 
     // Draw the visible set of tokens to the terminal.
     let buffer = workspace.current_buffer().ok_or(BUFFER_MISSING)?;
-    view.draw_buffer(buffer, mode.results.as_ref().map(|r| r.as_slice()), None)?;
+    view.draw_buffer(buffer, mode.highlights(), None)?;
 
     let mode_display = format!(" {} ", mode);
     let search_input = format!(
@@ -38,7 +38,7 @@ pub fn display(workspace: &mut Workspace, mode: &SearchMode, view: &mut View) ->
         StatusLineData {
             content: mode_display,
             style: Style::Default,
-            colors: Colors::SearchMode,
+            colors: Colors::StatusLine("search"),
         },
         StatusLineData {
             content: search_input,
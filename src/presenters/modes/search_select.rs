@@ -8,6 +8,7 @@ use scribe::Workspace;
 use scribe::buffer::Position;
 use view::{Colors, StatusLineData, Style, View};
 use unicode_segmentation::UnicodeSegmentation;
+use util::fuzzy_match;
 
 pub fn display<T: Display>(workspace: &mut Workspace, mode: &mut SearchSelectMode<T>, view: &mut View) -> Result<()> {
     let mode_config = mode.config().clone();
@@ -38,7 +39,9 @@ pub fn display<T: Display>(workspace: &mut Workspace, mode: &mut SearchSelectMod
                    &message.pad_to_width(view.width()))?;
     } else {
         // Draw the list of search results.
+        let query = mode.query().clone();
         for (line, result) in mode.results().enumerate() {
+            let result = result.to_string();
             let (content, colors, style) = if line == mode.selected_index() {
                 (format!("> {}", result), Colors::Focused, Style::Bold)
             } else {
@@ -49,6 +52,34 @@ pub fn display<T: Display>(workspace: &mut Workspace, mode: &mut SearchSelectMod
                        style,
                        colors,
                        &padded_content)?;
+
+            // Highlight the characters matched by the query. Matches are
+            // character indices, whereas the view lays content out by grapheme,
+            // so map each to its cluster's column, skipping those off-screen.
+            if let Some((_, indices)) = fuzzy_match::score_with_indices(&query, &result) {
+                let graphemes: Vec<&str> = result.graphemes(true).collect();
+                let mut columns = Vec::new();
+                for (column, grapheme) in graphemes.iter().enumerate() {
+                    columns.extend(grapheme.chars().map(|_| column));
+                }
+
+                let mut matched_columns: Vec<usize> = indices
+                    .into_iter()
+                    .filter_map(|index| columns.get(index).cloned())
+                    .collect();
+                matched_columns.dedup();
+                for column in matched_columns {
+                    let offset = column + 2;
+                    if offset >= view.width() {
+                        break;
+                    }
+
+                    view.print(&Position{ line, offset },
+                               Style::Bold,
+                               Colors::SearchMode,
+                               &graphemes[column])?;
+                }
+            }
         }
     }
 
@@ -5,24 +5,26 @@ use scribe::buffer::Range;
 use presenters::current_buffer_status_line_data;
 use view::{Colors, StatusLineData, Style, View};
 
-pub fn display(workspace: &mut Workspace, mode: &SelectMode, view: &mut View) -> Result<()> {
+pub fn display(workspace: &mut Workspace, mode: &SelectMode, selections: &[Range], view: &mut View) -> Result<()> {
     // Wipe the slate clean.
     view.clear();
 
     let buffer_status = current_buffer_status_line_data(workspace);
 
     if let Some(buf) = workspace.current_buffer() {
-        let selected_range = Range::new(mode.anchor, *buf.cursor.clone());
+        // Highlight the current selection alongside any additional ones.
+        let mut selected_ranges = selections.to_vec();
+        selected_ranges.push(Range::new(mode.anchor, *buf.cursor.clone()));
 
         // Draw the visible set of tokens to the terminal.
-        view.draw_buffer(buf, Some(&[selected_range]), None)?;
+        view.draw_buffer(buf, Some(&selected_ranges), None)?;
 
         // Draw the status line.
         view.draw_status_line(&[
             StatusLineData {
                 content: " SELECT ".to_string(),
                 style: Style::Default,
-                colors: Colors::SelectMode,
+                colors: Colors::StatusLine("select"),
             },
             buffer_status
         ]);
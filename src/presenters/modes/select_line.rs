@@ -22,7 +22,7 @@ pub fn display(workspace: &mut Workspace, mode: &SelectLineMode, view: &mut View
             StatusLineData {
                 content: " SELECT LINE ".to_string(),
                 style: Style::Default,
-                colors: Colors::SelectMode,
+                colors: Colors::StatusLine("select_line"),
             },
             buffer_status
         ]);
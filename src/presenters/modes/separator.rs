@@ -0,0 +1,27 @@
+use errors::*;
+use scribe::Workspace;
+use view::{Colors, StatusLineData, Style, View};
+
+pub fn display(workspace: &mut Workspace, view: &mut View) -> Result<()> {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = workspace.current_buffer() {
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(buf, None, None)?;
+
+        // Draw the status line as a prompt for the separator character.
+        view.draw_status_line(&[
+            StatusLineData {
+                content: "Separator character".to_string(),
+                style: Style::Default,
+                colors: Colors::StatusLine("separator"),
+            }
+        ]);
+    }
+
+    // Render the changes to the screen.
+    view.present();
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use models::application::modes::Outline;
+use std::cmp;
+use view::{Colors, Sidebar, StatusLineData, Style, View};
+
+const MAX_WIDTH: usize = 30;
+
+/// Lays out the outline as a sidebar, scrolled just enough to keep the
+/// selected entry visible. The selection is only highlighted when the
+/// outline has focus; otherwise, it's emboldened to track the cursor.
+pub fn sidebar(outline: &Outline, focused: bool, view: &View) -> Sidebar {
+    let width = cmp::min(view.width() / 3, MAX_WIDTH);
+    let height = view.height().saturating_sub(1);
+    let selected_index = outline.entries.selected_index();
+    let offset = (selected_index + 1).saturating_sub(height);
+
+    let lines = outline.entries
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(index, entry)| {
+            let (style, colors) = if index != selected_index {
+                (Style::Default, Colors::Default)
+            } else if focused {
+                (Style::Bold, Colors::Focused)
+            } else {
+                (Style::Bold, Colors::Default)
+            };
+
+            StatusLineData {
+                content: format!(" {}", entry),
+                style,
+                colors,
+            }
+        })
+        .collect();
+
+    Sidebar { width, lines }
+}
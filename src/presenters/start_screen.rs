@@ -0,0 +1,17 @@
+use errors::*;
+use models::application::StartScreen;
+use std::path::Path;
+use view::View;
+
+pub fn display(start_screen: &StartScreen, workspace_path: &Path, view: &mut View) -> Result<()> {
+    // Wipe the slate clean.
+    view.clear();
+
+    view.draw_centered_block(&start_screen.lines(workspace_path))?;
+    view.set_cursor(None);
+
+    // Render the changes to the screen.
+    view.present();
+
+    Ok(())
+}
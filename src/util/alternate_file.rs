@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+/// Builds a list of companion paths for the specified path (e.g. `amp.c` for
+/// `amp.h`), using rules that pair file name suffixes in both directions.
+/// Only rules for the most specific (longest) matching suffix are applied,
+/// so that `amp_test.go` is paired with `amp.go`, but not `amp_test_test.go`.
+pub fn candidates(path: &Path, rules: &[(String, String)]) -> Vec<PathBuf> {
+    let file_name = match path.file_name().and_then(|name| name.to_str()) {
+        Some(file_name) => file_name,
+        None => return Vec::new(),
+    };
+
+    let mut matches: Vec<(&str, &str)> = Vec::new();
+    for &(ref first, ref second) in rules {
+        for &(from, to) in [(first, second), (second, first)].iter() {
+            if file_name.ends_with(from.as_str()) {
+                matches.push((from, to));
+            }
+        }
+    }
+    let suffix_length = match matches.iter().map(|&(from, _)| from.len()).max() {
+        Some(length) => length,
+        None => return Vec::new(),
+    };
+
+    let stem = &file_name[..file_name.len() - suffix_length];
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for (from, to) in matches {
+        if from.len() != suffix_length {
+            continue;
+        }
+
+        let candidate = path.with_file_name(format!("{}{}", stem, to));
+        if candidate != path && !paths.contains(&candidate) {
+            paths.push(candidate);
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::candidates;
+    use std::path::{Path, PathBuf};
+
+    fn rules() -> Vec<(String, String)> {
+        vec![
+            (String::from(".h"), String::from(".c")),
+            (String::from(".h"), String::from(".cpp")),
+            (String::from("mod.rs"), String::from("lib.rs")),
+            (String::from("_test.go"), String::from(".go")),
+        ]
+    }
+
+    #[test]
+    fn candidates_pairs_headers_with_sources_in_both_directions() {
+        assert_eq!(
+            candidates(Path::new("src/amp.h"), &rules()),
+            vec![PathBuf::from("src/amp.c"), PathBuf::from("src/amp.cpp")]
+        );
+        assert_eq!(
+            candidates(Path::new("src/amp.cpp"), &rules()),
+            vec![PathBuf::from("src/amp.h")]
+        );
+    }
+
+    #[test]
+    fn candidates_pairs_whole_file_names() {
+        assert_eq!(
+            candidates(Path::new("src/lib.rs"), &rules()),
+            vec![PathBuf::from("src/mod.rs")]
+        );
+    }
+
+    #[test]
+    fn candidates_prefers_more_specific_suffixes() {
+        assert_eq!(
+            candidates(Path::new("amp_test.go"), &rules()),
+            vec![PathBuf::from("amp.go")]
+        );
+        assert_eq!(
+            candidates(Path::new("amp.go"), &rules()),
+            vec![PathBuf::from("amp_test.go")]
+        );
+    }
+
+    #[test]
+    fn candidates_returns_nothing_without_a_matching_rule() {
+        assert!(candidates(Path::new("amp.rb"), &rules()).is_empty());
+    }
+}
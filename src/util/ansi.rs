@@ -0,0 +1,98 @@
+const ESCAPE: char = '\u{1B}';
+const BELL: char = '\u{7}';
+const CSI: char = '\u{9B}';
+
+/// Removes ANSI escape sequences (e.g. the SGR sequences used to color
+/// terminal output) from the content, leaving everything else intact.
+/// Handles CSI and OSC sequences, as well as two-character escapes.
+pub fn strip(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut characters = content.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            ESCAPE => match characters.peek().cloned() {
+                Some('[') => {
+                    characters.next();
+                    skip_control_sequence(&mut characters);
+                }
+                Some(']') => {
+                    characters.next();
+                    skip_operating_system_command(&mut characters);
+                }
+                // Other escapes consist of a single character in the 0x40-0x5F range.
+                Some('@'..='_') => {
+                    characters.next();
+                }
+                _ => (),
+            },
+            CSI => skip_control_sequence(&mut characters),
+            _ => stripped.push(character),
+        }
+    }
+
+    stripped
+}
+
+/// Consumes parameter and intermediate bytes, up to and including the final
+/// byte (in the 0x40-0x7E range) that terminates a control sequence.
+fn skip_control_sequence<I: Iterator<Item=char>>(characters: &mut I) {
+    for character in characters {
+        if let '\u{40}'..='\u{7E}' = character {
+            break;
+        }
+    }
+}
+
+/// Consumes characters up to and including the terminating
+/// BEL character or string terminator (ESC followed by "\").
+fn skip_operating_system_command<I: Iterator<Item=char>>(characters: &mut ::std::iter::Peekable<I>) {
+    while let Some(character) = characters.next() {
+        match character {
+            BELL => break,
+            ESCAPE => {
+                if characters.peek() == Some(&'\\') {
+                    characters.next();
+                }
+                break;
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip;
+
+    #[test]
+    fn strip_removes_color_codes() {
+        assert_eq!(
+            strip("\u{1B}[1;31merror\u{1B}[0m: \u{1B}[38;5;208mbuild\u{1B}[m failed"),
+            "error: build failed"
+        );
+    }
+
+    #[test]
+    fn strip_removes_other_control_sequences() {
+        assert_eq!(strip("\u{1B}[2K\u{1B}[1Gdone\u{9B}0m"), "done");
+    }
+
+    #[test]
+    fn strip_removes_operating_system_commands() {
+        assert_eq!(
+            strip("\u{1B}]0;title\u{7}amp \u{1B}]8;;https://amp.rs\u{1B}\\link\u{1B}]8;;\u{1B}\\"),
+            "amp link"
+        );
+    }
+
+    #[test]
+    fn strip_leaves_brackets_in_ordinary_text_alone() {
+        assert_eq!(strip("[INFO] values[0] = [1m]"), "[INFO] values[0] = [1m]");
+    }
+
+    #[test]
+    fn strip_preserves_multi_line_content() {
+        assert_eq!(strip("\u{1B}[32mok\u{1B}[0m\nnext\n"), "ok\nnext\n");
+    }
+}
@@ -0,0 +1,270 @@
+use errors::*;
+use git2::Repository;
+use pad::PadStr;
+use scribe::Buffer;
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+
+const SHA_WIDTH: usize = 7;
+const AUTHOR_WIDTH: usize = 12;
+const UNCOMMITTED_SHA: &str = "-------";
+const UNCOMMITTED_AUTHOR: &str = "uncommitted";
+
+/// A contiguous set of lines attributed to the same commit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlameHunk {
+    /// Zero-based buffer line at which the hunk starts.
+    pub start_line: usize,
+    pub line_count: usize,
+
+    /// The commit's SHA, or None if the lines haven't been committed.
+    pub commit: Option<String>,
+    pub author: String,
+}
+
+/// Per-buffer blame annotations, keyed by buffer ID and
+/// tagged with the buffer revision they were generated for.
+#[derive(Default)]
+pub struct BlameCache {
+    entries: HashMap<usize, (usize, Rc<Vec<String>>)>,
+}
+
+impl BlameCache {
+    pub fn new() -> BlameCache {
+        BlameCache::default()
+    }
+
+    /// Returns annotations for the buffer, blaming it only if
+    /// its revision has changed since the last call.
+    pub fn annotations(&mut self, repo: &Repository, buffer: &Buffer, revision: usize) -> Result<Rc<Vec<String>>> {
+        let id = buffer.id.ok_or("Buffer has no ID")?;
+
+        if let Some(&(cached_revision, ref annotations)) = self.entries.get(&id) {
+            if cached_revision == revision {
+                return Ok(annotations.clone());
+            }
+        }
+
+        let hunks = hunks(repo, buffer)?;
+        let annotations = Rc::new(annotations(&hunks, buffer.line_count()));
+        self.entries.insert(id, (revision, annotations.clone()));
+
+        Ok(annotations)
+    }
+}
+
+/// Maps blame hunks into fixed-width, per-line annotation strings.
+/// Lines not covered by a hunk are annotated as uncommitted.
+pub fn annotations(hunks: &[BlameHunk], line_count: usize) -> Vec<String> {
+    let uncommitted = annotation(UNCOMMITTED_SHA, UNCOMMITTED_AUTHOR);
+    let mut annotations = vec![uncommitted.clone(); line_count];
+
+    for hunk in hunks {
+        let content = match hunk.commit {
+            Some(ref sha) => annotation(sha, &hunk.author),
+            None => uncommitted.clone(),
+        };
+
+        for line in hunk.start_line..hunk.start_line + hunk.line_count {
+            if let Some(entry) = annotations.get_mut(line) {
+                *entry = content.clone();
+            }
+        }
+    }
+
+    annotations
+}
+
+fn annotation(sha: &str, author: &str) -> String {
+    let short_sha: String = sha.chars().take(SHA_WIDTH).collect();
+    let short_author: String = author.chars().take(AUTHOR_WIDTH).collect();
+
+    format!(
+        "{} {} ",
+        short_sha.pad_to_width(SHA_WIDTH),
+        short_author.pad_to_width(AUTHOR_WIDTH)
+    )
+}
+
+fn hunks(repo: &Repository, buffer: &Buffer) -> Result<Vec<BlameHunk>> {
+    let buffer_path = buffer.path.as_ref().ok_or(BUFFER_PATH_MISSING)?;
+    let repo_path = repo.workdir().ok_or("No path found for the repository")?;
+    let relative_path = buffer_path.strip_prefix(repo_path).chain_err(|| {
+        "Failed to build a relative buffer path"
+    })?;
+
+    // Blame the buffer's contents (passed via stdin),
+    // rather than the file, so that unsaved edits line up.
+    let mut child = Command::new("git")
+        .arg("blame")
+        .arg("--porcelain")
+        .arg("--contents")
+        .arg("-")
+        .arg("--")
+        .arg(relative_path)
+        .current_dir(repo_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .chain_err(|| "Couldn't run git blame")?;
+    child
+        .stdin
+        .take()
+        .ok_or("Couldn't write to git blame")?
+        .write_all(buffer.data().as_bytes())
+        .chain_err(|| "Couldn't write to git blame")?;
+    let output = child.wait_with_output().chain_err(|| "Couldn't run git blame")?;
+
+    if !output.status.success() {
+        bail!(format!(
+            "Couldn't blame the current file: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses `git blame --porcelain` output into single-line hunks. Author
+/// details are only listed the first time a commit appears, so they're
+/// remembered and reused for its subsequent lines.
+fn parse_porcelain(output: &str) -> Vec<BlameHunk> {
+    let mut authors: HashMap<&str, &str> = HashMap::new();
+    let mut hunks = Vec::new();
+    let mut current: Option<(&str, usize)> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            // Content lines end each entry.
+            if let Some((sha, final_line)) = current.take() {
+                let uncommitted = sha.chars().all(|c| c == '0');
+
+                hunks.push(BlameHunk {
+                    // Blame line numbers are one-based.
+                    start_line: final_line.saturating_sub(1),
+                    line_count: 1,
+                    commit: if uncommitted { None } else { Some(sha.to_string()) },
+                    author: authors.get(sha).cloned().unwrap_or("").to_string(),
+                });
+            }
+        } else if line.starts_with("author ") {
+            if let Some((sha, _)) = current {
+                authors.insert(sha, &line["author ".len()..]);
+            }
+        } else if current.is_none() {
+            // Entries start with the commit, original line, and final line.
+            let mut fields = line.split(' ');
+            if let (Some(sha), Some(_), Some(final_line)) = (fields.next(), fields.next(), fields.next()) {
+                if let Ok(final_line) = final_line.parse() {
+                    current = Some((sha, final_line));
+                }
+            }
+        }
+    }
+
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_porcelain, BlameHunk};
+
+    #[test]
+    fn annotations_map_hunks_to_lines() {
+        let hunks = vec![
+            BlameHunk {
+                start_line: 0,
+                line_count: 2,
+                commit: Some(String::from("1234567890abcdef")),
+                author: String::from("Jordan MacDonald"),
+            },
+            BlameHunk {
+                start_line: 2,
+                line_count: 1,
+                commit: None,
+                author: String::from("Not Committed Yet"),
+            },
+            BlameHunk {
+                start_line: 3,
+                line_count: 1,
+                commit: Some(String::from("abcdef1234567890")),
+                author: String::from("Amp"),
+            },
+        ];
+
+        assert_eq!(
+            super::annotations(&hunks, 5),
+            vec![
+                "1234567 Jordan MacDo ",
+                "1234567 Jordan MacDo ",
+                "------- uncommitted  ",
+                "abcdef1 Amp          ",
+                "------- uncommitted  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn annotations_ignore_hunks_beyond_line_count() {
+        let hunks = vec![
+            BlameHunk {
+                start_line: 1,
+                line_count: 3,
+                commit: Some(String::from("1234567890abcdef")),
+                author: String::from("Amp"),
+            },
+        ];
+
+        assert_eq!(
+            super::annotations(&hunks, 2),
+            vec![
+                "------- uncommitted  ",
+                "1234567 Amp          ",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_porcelain_reuses_authors_for_repeated_commits() {
+        let output = "\
+1234567890abcdef1234567890abcdef12345678 1 1 2
+author Jordan MacDonald
+summary Initial commit
+filename src/main.rs
+\tfn main() {
+1234567890abcdef1234567890abcdef12345678 2 2
+\t}
+0000000000000000000000000000000000000000 3 3 1
+author External file (--contents)
+filename src/main.rs
+\t// TODO
+";
+
+        assert_eq!(
+            parse_porcelain(output),
+            vec![
+                BlameHunk {
+                    start_line: 0,
+                    line_count: 1,
+                    commit: Some(String::from("1234567890abcdef1234567890abcdef12345678")),
+                    author: String::from("Jordan MacDonald"),
+                },
+                BlameHunk {
+                    start_line: 1,
+                    line_count: 1,
+                    commit: Some(String::from("1234567890abcdef1234567890abcdef12345678")),
+                    author: String::from("Jordan MacDonald"),
+                },
+                BlameHunk {
+                    start_line: 2,
+                    line_count: 1,
+                    commit: None,
+                    author: String::from("External file (--contents)"),
+                },
+            ]
+        );
+    }
+}
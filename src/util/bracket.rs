@@ -0,0 +1,134 @@
+use scribe::buffer::Position;
+use unicode_segmentation::UnicodeSegmentation;
+
+const PAIRS: [(&str, &str); 3] = [("(", ")"), ("[", "]"), ("{", "}")];
+
+/// The result of looking for the partner of a bracket.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BracketMatch {
+    Matched(Position),
+    Unmatched,
+}
+
+/// Finds the bracket paired with the one at the specified position, skipping
+/// over nested pairs of the same type. Returns `None` if there isn't a
+/// bracket at the position, so that callers can run this cheaply for
+/// every cursor position.
+pub fn find_match(data: &str, position: &Position) -> Option<BracketMatch> {
+    let line = data.lines().nth(position.line)?;
+    let bracket = line.graphemes(true).nth(position.offset)?;
+
+    if let Some(&(open, close)) = PAIRS.iter().find(|&&(open, _)| open == bracket) {
+        Some(scan_forward(data, position, open, close))
+    } else if let Some(&(open, close)) = PAIRS.iter().find(|&&(_, close)| close == bracket) {
+        Some(scan_backward(data, position, close, open))
+    } else {
+        None
+    }
+}
+
+fn scan_forward(data: &str, position: &Position, bracket: &str, partner: &str) -> BracketMatch {
+    let mut depth = 0;
+
+    for (line_number, line) in data.lines().enumerate().skip(position.line) {
+        for (offset, grapheme) in line.graphemes(true).enumerate() {
+            if line_number == position.line && offset <= position.offset {
+                continue;
+            }
+
+            if grapheme == bracket {
+                depth += 1;
+            } else if grapheme == partner {
+                if depth == 0 {
+                    return BracketMatch::Matched(Position{ line: line_number, offset });
+                }
+                depth -= 1;
+            }
+        }
+    }
+
+    BracketMatch::Unmatched
+}
+
+fn scan_backward(data: &str, position: &Position, bracket: &str, partner: &str) -> BracketMatch {
+    let mut depth = 0;
+    let lines: Vec<&str> = data.lines().take(position.line + 1).collect();
+
+    for (line_number, line) in lines.iter().enumerate().rev() {
+        let graphemes: Vec<&str> = line.graphemes(true).collect();
+
+        for (offset, &grapheme) in graphemes.iter().enumerate().rev() {
+            if line_number == position.line && offset >= position.offset {
+                continue;
+            }
+
+            if grapheme == bracket {
+                depth += 1;
+            } else if grapheme == partner {
+                if depth == 0 {
+                    return BracketMatch::Matched(Position{ line: line_number, offset });
+                }
+                depth -= 1;
+            }
+        }
+    }
+
+    BracketMatch::Unmatched
+}
+
+#[cfg(test)]
+mod tests {
+    use scribe::buffer::Position;
+    use super::{find_match, BracketMatch};
+
+    #[test]
+    fn find_match_returns_none_when_not_on_a_bracket() {
+        assert_eq!(find_match("(a)", &Position{ line: 0, offset: 1 }), None);
+        assert_eq!(find_match("(a)", &Position{ line: 0, offset: 3 }), None);
+        assert_eq!(find_match("(a)", &Position{ line: 1, offset: 0 }), None);
+    }
+
+    #[test]
+    fn find_match_finds_closing_brackets_skipping_nested_pairs() {
+        assert_eq!(
+            find_match("fn(a, (b)) {\n  [c]\n}", &Position{ line: 0, offset: 2 }),
+            Some(BracketMatch::Matched(Position{ line: 0, offset: 9 }))
+        );
+        assert_eq!(
+            find_match("fn(a, (b)) {\n  [c]\n}", &Position{ line: 0, offset: 11 }),
+            Some(BracketMatch::Matched(Position{ line: 2, offset: 0 }))
+        );
+    }
+
+    #[test]
+    fn find_match_finds_opening_brackets_skipping_nested_pairs() {
+        assert_eq!(
+            find_match("fn(a, (b)) {\n  [c]\n}", &Position{ line: 0, offset: 9 }),
+            Some(BracketMatch::Matched(Position{ line: 0, offset: 2 }))
+        );
+        assert_eq!(
+            find_match("fn(a, (b)) {\n  [c]\n}", &Position{ line: 2, offset: 0 }),
+            Some(BracketMatch::Matched(Position{ line: 0, offset: 11 }))
+        );
+    }
+
+    #[test]
+    fn find_match_handles_multi_byte_characters() {
+        assert_eq!(
+            find_match("(é)", &Position{ line: 0, offset: 0 }),
+            Some(BracketMatch::Matched(Position{ line: 0, offset: 2 }))
+        );
+    }
+
+    #[test]
+    fn find_match_reports_unmatched_brackets() {
+        assert_eq!(
+            find_match("((a)", &Position{ line: 0, offset: 0 }),
+            Some(BracketMatch::Unmatched)
+        );
+        assert_eq!(
+            find_match("a]", &Position{ line: 0, offset: 1 }),
+            Some(BracketMatch::Unmatched)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt;
+
+/// Details about a single character, used to diagnose
+/// invisible, look-alike, or incorrectly encoded content.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharInfo {
+    pub codepoint: u32,
+    pub bytes: Vec<u8>,
+    pub name: Option<String>,
+    pub category: &'static str,
+}
+
+impl CharInfo {
+    pub fn new(character: char) -> CharInfo {
+        let mut buffer = [0; 4];
+
+        CharInfo {
+            codepoint: character as u32,
+            bytes: character.encode_utf8(&mut buffer).as_bytes().to_vec(),
+            name: name(character),
+            category: category(character),
+        }
+    }
+}
+
+impl fmt::Display for CharInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "U+{:04X}", self.codepoint)?;
+        if let Some(ref name) = self.name {
+            write!(f, " {}", name)?;
+        }
+        let bytes: Vec<String> = self.bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+
+        write!(f, " (UTF-8: {}, {})", bytes.join(" "), self.category)
+    }
+}
+
+/// The character's Unicode name. Without a full copy of the Unicode
+/// character database, only ASCII characters and a handful of commonly
+/// confused (or invisible) characters can be named.
+fn name(character: char) -> Option<String> {
+    const DIGITS: [&str; 10] = [
+        "ZERO", "ONE", "TWO", "THREE", "FOUR", "FIVE", "SIX", "SEVEN", "EIGHT", "NINE"
+    ];
+
+    if character.is_ascii_uppercase() {
+        return Some(format!("LATIN CAPITAL LETTER {}", character));
+    } else if character.is_ascii_lowercase() {
+        return Some(format!("LATIN SMALL LETTER {}", character.to_ascii_uppercase()));
+    } else if let Some(digit) = character.to_digit(10) {
+        return Some(format!("DIGIT {}", DIGITS[digit as usize]));
+    }
+
+    let name = match character {
+        '\0' => "NULL",
+        '\t' => "CHARACTER TABULATION",
+        '\n' => "LINE FEED (LF)",
+        '\r' => "CARRIAGE RETURN (CR)",
+        '\u{1B}' => "ESCAPE",
+        ' ' => "SPACE",
+        '!' => "EXCLAMATION MARK",
+        '"' => "QUOTATION MARK",
+        '#' => "NUMBER SIGN",
+        '$' => "DOLLAR SIGN",
+        '%' => "PERCENT SIGN",
+        '&' => "AMPERSAND",
+        '\'' => "APOSTROPHE",
+        '(' => "LEFT PARENTHESIS",
+        ')' => "RIGHT PARENTHESIS",
+        '*' => "ASTERISK",
+        '+' => "PLUS SIGN",
+        ',' => "COMMA",
+        '-' => "HYPHEN-MINUS",
+        '.' => "FULL STOP",
+        '/' => "SOLIDUS",
+        ':' => "COLON",
+        ';' => "SEMICOLON",
+        '<' => "LESS-THAN SIGN",
+        '=' => "EQUALS SIGN",
+        '>' => "GREATER-THAN SIGN",
+        '?' => "QUESTION MARK",
+        '@' => "COMMERCIAL AT",
+        '[' => "LEFT SQUARE BRACKET",
+        '\\' => "REVERSE SOLIDUS",
+        ']' => "RIGHT SQUARE BRACKET",
+        '^' => "CIRCUMFLEX ACCENT",
+        '_' => "LOW LINE",
+        '`' => "GRAVE ACCENT",
+        '{' => "LEFT CURLY BRACKET",
+        '|' => "VERTICAL LINE",
+        '}' => "RIGHT CURLY BRACKET",
+        '~' => "TILDE",
+        '\u{7F}' => "DELETE",
+        '\u{A0}' => "NO-BREAK SPACE",
+        '\u{AD}' => "SOFT HYPHEN",
+        '\u{2002}' => "EN SPACE",
+        '\u{2003}' => "EM SPACE",
+        '\u{2009}' => "THIN SPACE",
+        '\u{200B}' => "ZERO WIDTH SPACE",
+        '\u{200C}' => "ZERO WIDTH NON-JOINER",
+        '\u{200D}' => "ZERO WIDTH JOINER",
+        '\u{200E}' => "LEFT-TO-RIGHT MARK",
+        '\u{200F}' => "RIGHT-TO-LEFT MARK",
+        '\u{2010}' => "HYPHEN",
+        '\u{2011}' => "NON-BREAKING HYPHEN",
+        '\u{2013}' => "EN DASH",
+        '\u{2014}' => "EM DASH",
+        '\u{2018}' => "LEFT SINGLE QUOTATION MARK",
+        '\u{2019}' => "RIGHT SINGLE QUOTATION MARK",
+        '\u{201C}' => "LEFT DOUBLE QUOTATION MARK",
+        '\u{201D}' => "RIGHT DOUBLE QUOTATION MARK",
+        '\u{2026}' => "HORIZONTAL ELLIPSIS",
+        '\u{2028}' => "LINE SEPARATOR",
+        '\u{2029}' => "PARAGRAPH SEPARATOR",
+        '\u{202F}' => "NARROW NO-BREAK SPACE",
+        '\u{2060}' => "WORD JOINER",
+        '\u{2212}' => "MINUS SIGN",
+        '\u{3000}' => "IDEOGRAPHIC SPACE",
+        '\u{FEFF}' => "ZERO WIDTH NO-BREAK SPACE",
+        '\u{FFFD}' => "REPLACEMENT CHARACTER",
+        _ => return None,
+    };
+
+    Some(name.to_string())
+}
+
+/// An approximation of the character's Unicode general category,
+/// derived from the character classes available in the standard library.
+fn category(character: char) -> &'static str {
+    match character {
+        '\u{AD}' | '\u{200B}'..='\u{200F}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}' => "format",
+        _ if character.is_control() => "control",
+        _ if character.is_whitespace() => "separator",
+        _ if character.is_uppercase() => "uppercase letter",
+        _ if character.is_lowercase() => "lowercase letter",
+        _ if character.is_alphabetic() => "letter",
+        _ if character.is_numeric() => "number",
+        _ if character.is_ascii_punctuation() => "punctuation or symbol",
+        _ => "symbol or other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CharInfo;
+
+    #[test]
+    fn new_describes_ascii_characters() {
+        let info = CharInfo::new('a');
+
+        assert_eq!(info.codepoint, 0x61);
+        assert_eq!(info.bytes, vec![0x61]);
+        assert_eq!(info.name, Some(String::from("LATIN SMALL LETTER A")));
+        assert_eq!(info.category, "lowercase letter");
+    }
+
+    #[test]
+    fn new_describes_accented_characters() {
+        let info = CharInfo::new('é');
+
+        assert_eq!(info.codepoint, 0xE9);
+        assert_eq!(info.bytes, vec![0xC3, 0xA9]);
+        assert_eq!(info.category, "lowercase letter");
+    }
+
+    #[test]
+    fn new_describes_emoji() {
+        let info = CharInfo::new('😀');
+
+        assert_eq!(info.codepoint, 0x1F600);
+        assert_eq!(info.bytes, vec![0xF0, 0x9F, 0x98, 0x80]);
+    }
+
+    #[test]
+    fn new_names_invisible_characters() {
+        let info = CharInfo::new('\u{200B}');
+
+        assert_eq!(info.name, Some(String::from("ZERO WIDTH SPACE")));
+        assert_eq!(info.category, "format");
+    }
+
+    #[test]
+    fn display_includes_the_codepoint_name_and_bytes() {
+        assert_eq!(
+            CharInfo::new('\u{A0}').to_string(),
+            "U+00A0 NO-BREAK SPACE (UTF-8: C2 A0, separator)"
+        );
+        assert_eq!(
+            CharInfo::new('é').to_string(),
+            "U+00E9 (UTF-8: C3 A9, lowercase letter)"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use errors::*;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the content's UTF-8 bytes using standard, padded base64.
+pub fn base64_encode(content: &str) -> String {
+    let mut encoded = String::with_capacity((content.len() + 2) / 3 * 4);
+
+    for chunk in content.as_bytes().chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let group = (u32::from(bytes[0]) << 16) | (u32::from(bytes[1]) << 8) | u32::from(bytes[2]);
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - index * 6)) & 0x3F;
+                encoded.push(BASE64_ALPHABET[sextet as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Decodes standard base64 content (ignoring whitespace, such as line
+/// breaks) whose decoded bytes are expected to be valid UTF-8.
+pub fn base64_decode(content: &str) -> Result<String> {
+    let characters: Vec<u8> = content.bytes().filter(|b| !(*b as char).is_whitespace()).collect();
+    if characters.len() % 4 != 0 {
+        bail!("Invalid base64 content: length isn't a multiple of four");
+    }
+
+    let mut bytes = Vec::with_capacity(characters.len() / 4 * 3);
+    for (chunk_index, chunk) in characters.chunks(4).enumerate() {
+        let last_chunk = (chunk_index + 1) * 4 == characters.len();
+        let padding = chunk.iter().rev().take_while(|&&b| b == b'=').count();
+        if padding > 2 || (padding > 0 && !last_chunk) {
+            bail!("Invalid base64 content: unexpected padding");
+        }
+
+        let mut group = 0;
+        for &character in &chunk[..4 - padding] {
+            let sextet = BASE64_ALPHABET
+                .iter()
+                .position(|&b| b == character)
+                .ok_or_else(|| format!("Invalid base64 character: {}", character as char))?;
+            group = (group << 6) | sextet as u32;
+        }
+        group <<= 6 * padding as u32;
+
+        for index in 0..3 - padding {
+            bytes.push((group >> (16 - index * 8)) as u8);
+        }
+    }
+
+    String::from_utf8(bytes).chain_err(|| "Decoded base64 content isn't valid UTF-8")
+}
+
+/// Percent-encodes everything but unreserved URL characters (RFC 3986).
+pub fn url_encode(content: &str) -> String {
+    let mut encoded = String::with_capacity(content.len());
+
+    for byte in content.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+/// Decodes percent-encoded content, whose decoded
+/// bytes are expected to be valid UTF-8.
+pub fn url_decode(content: &str) -> Result<String> {
+    let mut bytes = Vec::with_capacity(content.len());
+    let mut remaining = content.bytes();
+
+    while let Some(byte) = remaining.next() {
+        if byte == b'%' {
+            let digits = [remaining.next(), remaining.next()];
+            let value = match digits {
+                [Some(high), Some(low)] => {
+                    let hex = [high, low];
+                    ::std::str::from_utf8(&hex)
+                        .ok()
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                }
+                _ => None,
+            };
+            bytes.push(value.ok_or("Invalid URL encoding: \"%\" must be followed by two hex digits")?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    String::from_utf8(bytes).chain_err(|| "Decoded URL content isn't valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64_decode, base64_encode, url_decode, url_encode};
+
+    #[test]
+    fn base64_encode_pads_partial_groups() {
+        assert_eq!(base64_encode(""), "");
+        assert_eq!(base64_encode("a"), "YQ==");
+        assert_eq!(base64_encode("am"), "YW0=");
+        assert_eq!(base64_encode("amp"), "YW1w");
+        assert_eq!(base64_encode("amp editor"), "YW1wIGVkaXRvcg==");
+    }
+
+    #[test]
+    fn base64_decode_reverses_encoding() {
+        for content in &["", "a", "am", "amp", "amp editor", "naïve ☃"] {
+            assert_eq!(base64_decode(&base64_encode(content)).unwrap(), *content);
+        }
+    }
+
+    #[test]
+    fn base64_decode_ignores_whitespace() {
+        assert_eq!(base64_decode("YW1wIGVk\naXRvcg==\n").unwrap(), "amp editor");
+    }
+
+    #[test]
+    fn base64_decode_rejects_invalid_content() {
+        assert!(base64_decode("YW1").is_err());
+        assert!(base64_decode("YW1*").is_err());
+        assert!(base64_decode("YQ==YQ==").is_err());
+        assert!(base64_decode("/w==").is_err());
+    }
+
+    #[test]
+    fn url_encode_escapes_reserved_and_multi_byte_characters() {
+        assert_eq!(
+            url_encode("a b/c?d=é&e~f"),
+            "a%20b%2Fc%3Fd%3D%C3%A9%26e~f"
+        );
+    }
+
+    #[test]
+    fn url_decode_reverses_encoding() {
+        let content = "https://amp.rs/docs?q=naïve ☃&page=1";
+
+        assert_eq!(url_decode(&url_encode(content)).unwrap(), content);
+    }
+
+    #[test]
+    fn url_decode_rejects_invalid_content() {
+        assert!(url_decode("100%").is_err());
+        assert!(url_decode("%zz").is_err());
+        assert!(url_decode("%FF").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::Path;
+
+/// The delimiters used to open and close a block comment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockComment {
+    pub start: &'static str,
+    pub end: &'static str,
+}
+
+const C_STYLE: BlockComment = BlockComment { start: "/*", end: "*/" };
+const MARKUP: BlockComment = BlockComment { start: "<!--", end: "-->" };
+
+/// Block comment delimiters, keyed by file extension.
+const BLOCK_COMMENTS: [(&str, BlockComment); 25] = [
+    ("c", C_STYLE),
+    ("cc", C_STYLE),
+    ("cpp", C_STYLE),
+    ("cs", C_STYLE),
+    ("css", C_STYLE),
+    ("go", C_STYLE),
+    ("h", C_STYLE),
+    ("hpp", C_STYLE),
+    ("java", C_STYLE),
+    ("js", C_STYLE),
+    ("kt", C_STYLE),
+    ("less", C_STYLE),
+    ("php", C_STYLE),
+    ("rs", C_STYLE),
+    ("scala", C_STYLE),
+    ("scss", C_STYLE),
+    ("swift", C_STYLE),
+    ("ts", C_STYLE),
+    ("htm", MARKUP),
+    ("html", MARKUP),
+    ("md", MARKUP),
+    ("svg", MARKUP),
+    ("vue", MARKUP),
+    ("xml", MARKUP),
+    ("hs", BlockComment { start: "{-", end: "-}" }),
+];
+
+/// Looks up the block comment delimiters for the path's file type.
+pub fn block_comment_for(path: &Path) -> Option<BlockComment> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+
+    BLOCK_COMMENTS
+        .iter()
+        .find(|&&(candidate, _)| candidate == extension)
+        .map(|&(_, comment)| comment)
+}
+
+/// Wraps the content in a block comment or, if it's already wrapped in
+/// one, unwraps it. Surrounding whitespace (e.g. indentation, or the
+/// trailing newline of a line selection) is left outside of the comment.
+pub fn toggle(content: &str, comment: BlockComment) -> String {
+    let core = content.trim();
+    if core.is_empty() {
+        return content.to_string();
+    }
+    let core_start = content.find(core).unwrap_or(0);
+    let prefix = &content[..core_start];
+    let suffix = &content[core_start + core.len()..];
+
+    let is_wrapped = core.len() >= comment.start.len() + comment.end.len() &&
+        core.starts_with(comment.start) &&
+        core.ends_with(comment.end);
+    if is_wrapped {
+        let inner = &core[comment.start.len()..core.len() - comment.end.len()];
+        let inner = if inner.starts_with(' ') { &inner[1..] } else { inner };
+        let inner = if inner.ends_with(' ') { &inner[..inner.len() - 1] } else { inner };
+
+        format!("{}{}{}", prefix, inner, suffix)
+    } else {
+        format!("{}{} {} {}{}", prefix, comment.start, core, comment.end, suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use super::{block_comment_for, toggle, BlockComment};
+
+    const RUST: BlockComment = BlockComment { start: "/*", end: "*/" };
+
+    #[test]
+    fn block_comment_for_uses_the_file_extension() {
+        assert_eq!(block_comment_for(Path::new("src/main.rs")).map(|c| c.start), Some("/*"));
+        assert_eq!(block_comment_for(Path::new("index.HTML")).map(|c| c.end), Some("-->"));
+        assert_eq!(block_comment_for(Path::new("script.sh")), None);
+        assert_eq!(block_comment_for(Path::new("Makefile")), None);
+    }
+
+    #[test]
+    fn toggle_wraps_and_unwraps_content() {
+        let wrapped = toggle("let amp = 1;", RUST);
+        assert_eq!(wrapped, "/* let amp = 1; */");
+        assert_eq!(toggle(&wrapped, RUST), "let amp = 1;");
+    }
+
+    #[test]
+    fn toggle_leaves_surrounding_whitespace_outside_of_the_comment() {
+        let wrapped = toggle("    let a;\n    let b;\n", RUST);
+        assert_eq!(wrapped, "    /* let a;\n    let b; */\n");
+        assert_eq!(toggle(&wrapped, RUST), "    let a;\n    let b;\n");
+    }
+
+    #[test]
+    fn toggle_unwraps_comments_without_inner_spacing() {
+        assert_eq!(toggle("/*amp*/", RUST), "amp");
+    }
+}
@@ -0,0 +1,124 @@
+use errors::*;
+use git2::Patch;
+use models::Application;
+use scribe::{Buffer, Workspace};
+use scribe::buffer::GapBuffer;
+use std::path::{Path, PathBuf};
+use util;
+
+/// Builds a unified diff between two versions of content.
+pub fn unified(old: &[u8], old_path: &Path, new: &[u8], new_path: &Path) -> Result<String> {
+    let mut patch = Patch::from_buffers(old, Some(old_path), new, Some(new_path), None)
+        .chain_err(|| "Couldn't diff the content")?;
+    let diff = patch.to_buf().chain_err(|| "Couldn't format the diff")?;
+
+    Ok(diff.as_str().ok_or("The diff isn't valid UTF-8")?.to_string())
+}
+
+/// Diffs the current buffer against the open buffer with the specified
+/// path, leaving the current buffer unchanged.
+pub fn current_buffer_against(workspace: &mut Workspace, path: &Path) -> Result<String> {
+    let (id, current_path, current_data) = {
+        let buffer = workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        let path = buffer.path.clone().unwrap_or_else(|| PathBuf::from("untitled"));
+
+        (buffer.id, path, buffer.data())
+    };
+
+    // Cycle through the buffers to find the other one,
+    // stopping once we're back at the original buffer.
+    let mut other_data = None;
+    loop {
+        workspace.next_buffer();
+        let buffer = workspace.current_buffer().ok_or(BUFFER_MISSING)?;
+        if buffer.id == id {
+            break;
+        }
+        if buffer.path.as_ref().map(|p| p == path).unwrap_or(false) {
+            other_data = Some(buffer.data());
+        }
+    }
+    let other_data = other_data
+        .ok_or_else(|| format!("Couldn't find an open buffer for {}", path.to_string_lossy()))?;
+
+    unified(current_data.as_bytes(), &current_path, other_data.as_bytes(), path)
+}
+
+/// Opens the diff in a new, path-less, read-only buffer, so
+/// that it can't be accidentally edited or saved over either file.
+pub fn open(diff: &str, app: &mut Application) -> Result<()> {
+    util::add_buffer(Buffer::new(), app)?;
+    let syntax_definition = app.workspace.syntax_set
+        .find_syntax_by_extension("diff")
+        .cloned();
+    if let Some(buffer) = app.workspace.current_buffer() {
+        // Set the content without recording its insertion, which
+        // could otherwise be undone despite the buffer being read-only.
+        *buffer.cursor.data.borrow_mut() = GapBuffer::new(diff.to_string());
+        buffer.syntax_definition = syntax_definition;
+        if let Some(id) = buffer.id {
+            app.read_only_buffers.insert(id);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use models::Application;
+    use scribe::{Buffer, Workspace};
+use scribe::buffer::GapBuffer;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn open_adds_a_read_only_buffer_without_a_path() {
+        let mut app = Application::new(&Vec::new()).unwrap();
+        super::open("-amp\n+editor\n", &mut app).unwrap();
+
+        let buffer = app.workspace.current_buffer().unwrap();
+        assert_eq!(buffer.data(), "-amp\n+editor\n");
+        assert!(buffer.path.is_none());
+        assert!(app.read_only_buffers.contains(&buffer.id.unwrap()));
+    }
+
+    #[test]
+    fn unified_includes_added_and_removed_lines() {
+        let diff = super::unified(
+            b"amp\neditor\n",
+            Path::new("old.txt"),
+            b"amp\ntext editor\n",
+            Path::new("new.txt")
+        ).unwrap();
+        let lines: Vec<&str> = diff.lines().collect();
+
+        assert!(lines.contains(&"--- a/old.txt"));
+        assert!(lines.contains(&"+++ b/new.txt"));
+        assert!(lines.contains(&"@@ -1,2 +1,2 @@"));
+        assert!(lines.contains(&" amp"));
+        assert!(lines.contains(&"-editor"));
+        assert!(lines.contains(&"+text editor"));
+    }
+
+    #[test]
+    fn current_buffer_against_diffs_two_open_buffers() {
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        for &(path, content) in &[("first.txt", "amp\neditor\n"), ("second.txt", "amp\n")] {
+            let mut buffer = Buffer::new();
+            buffer.insert(content);
+            buffer.path = Some(PathBuf::from(path));
+            workspace.add_buffer(buffer);
+        }
+        while workspace.current_buffer().unwrap().path != Some(PathBuf::from("first.txt")) {
+            workspace.next_buffer();
+        }
+        let current_id = workspace.current_buffer().unwrap().id;
+
+        let diff = super::current_buffer_against(&mut workspace, Path::new("second.txt")).unwrap();
+        let lines: Vec<&str> = diff.lines().collect();
+
+        assert!(lines.contains(&"-editor"));
+        assert!(!lines.iter().any(|line| line.starts_with("+") && !line.starts_with("+++")));
+        assert_eq!(workspace.current_buffer().unwrap().id, current_id);
+    }
+}
@@ -0,0 +1,224 @@
+use errors::*;
+use regex::Regex;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use util::{glob, LineEnding};
+
+const FILE_NAME: &str = ".editorconfig";
+
+/// Per-file settings derived from `.editorconfig` files (see
+/// https://editorconfig.org). Unspecified settings are left as None,
+/// so that they can fall back to user preferences.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EditorConfig {
+    pub soft_tabs: Option<bool>,
+    pub tab_width: Option<usize>,
+    pub line_ending: Option<LineEnding>,
+    pub trim_trailing_whitespace: Option<bool>,
+    pub insert_final_newline: Option<bool>,
+}
+
+impl EditorConfig {
+    /// Discovers `.editorconfig` files in the path's ancestor directories, up
+    /// to and including one marked as root, and applies their matching
+    /// sections, giving precedence to those closest to the path.
+    pub fn for_path(path: &Path) -> Result<EditorConfig> {
+        // Collect config files from the innermost directory outwards.
+        let mut configs: Vec<(PathBuf, String)> = Vec::new();
+        let mut directory = path.parent();
+        while let Some(dir) = directory {
+            let config_path = dir.join(FILE_NAME);
+            if config_path.is_file() {
+                let mut content = String::new();
+                File::open(&config_path)
+                    .and_then(|mut file| file.read_to_string(&mut content))
+                    .chain_err(|| format!("Couldn't read {}", config_path.to_string_lossy()))?;
+                let root = is_root(&content);
+                configs.push((dir.to_path_buf(), content));
+
+                if root { break; }
+            }
+
+            directory = dir.parent();
+        }
+
+        let mut config = EditorConfig::default();
+        for &(ref dir, ref content) in configs.iter().rev() {
+            if let Ok(relative_path) = path.strip_prefix(dir) {
+                let relative_path = relative_path
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                config.apply(content, &relative_path);
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Applies the properties from any sections in the provided
+    /// config content whose globs match the (relative) path.
+    pub fn apply(&mut self, content: &str, relative_path: &str) {
+        let mut section_matches = false;
+
+        for line in content.lines().map(|line| line.trim()) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                section_matches = glob_matches(&line[1..line.len() - 1], relative_path);
+            } else if section_matches {
+                let mut parts = line.splitn(2, '=');
+                if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+                    self.set(&key.trim().to_lowercase(), &value.trim().to_lowercase());
+                }
+            }
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "indent_style" => {
+                self.soft_tabs = match value {
+                    "space" => Some(true),
+                    "tab" => Some(false),
+                    _ => self.soft_tabs,
+                }
+            }
+            "indent_size" | "tab_width" => {
+                if let Ok(width) = value.parse() {
+                    self.tab_width = Some(width);
+                }
+            }
+            "end_of_line" => {
+                self.line_ending = match value {
+                    "lf" => Some(LineEnding::Lf),
+                    "crlf" => Some(LineEnding::Crlf),
+                    _ => self.line_ending,
+                }
+            }
+            "trim_trailing_whitespace" => {
+                self.trim_trailing_whitespace = parse_bool(value).or(self.trim_trailing_whitespace)
+            }
+            "insert_final_newline" => {
+                self.insert_final_newline = parse_bool(value).or(self.insert_final_newline)
+            }
+            _ => (),
+        }
+    }
+}
+
+fn is_root(content: &str) -> bool {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .take_while(|line| !line.starts_with('['))
+        .any(|line| {
+            let mut parts = line.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(key), Some(value)) => {
+                    key.trim().to_lowercase() == "root" &&
+                        value.trim().to_lowercase() == "true"
+                }
+                _ => false,
+            }
+        })
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Matches a section glob against a path relative to the config file's
+/// directory. Globs without a slash match file names in any directory.
+fn glob_matches(glob: &str, relative_path: &str) -> bool {
+    let pattern = if glob.contains('/') {
+        format!("^{}$", glob::to_regex(glob.trim_left_matches('/')))
+    } else {
+        format!("^(?:.*/)?{}$", glob::to_regex(glob))
+    };
+
+    Regex::new(&pattern)
+        .map(|regex| regex.is_match(relative_path))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_matches, EditorConfig};
+    use std::fs::File;
+    use std::io::Write;
+    use util::LineEnding;
+    use util::test_helpers::TempDir;
+
+    #[test]
+    fn glob_matches_file_names_in_any_directory_without_slashes() {
+        assert!(glob_matches("*.rs", "main.rs"));
+        assert!(glob_matches("*.rs", "src/main.rs"));
+        assert!(!glob_matches("*.rs", "main.rb"));
+    }
+
+    #[test]
+    fn glob_matches_relative_paths_with_slashes() {
+        assert!(glob_matches("src/*.rs", "src/main.rs"));
+        assert!(!glob_matches("src/*.rs", "src/view/mod.rs"));
+        assert!(glob_matches("src/**.rs", "src/view/mod.rs"));
+    }
+
+    #[test]
+    fn glob_matches_double_star_directories_as_one_or_more() {
+        assert!(glob_matches("src/**/*.rs", "src/view/mod.rs"));
+        assert!(!glob_matches("src/**/*.rs", "src/main.rs"));
+    }
+
+    #[test]
+    fn glob_matches_alternatives_and_character_classes() {
+        assert!(glob_matches("*.{yml,yaml}", "config.yaml"));
+        assert!(glob_matches("file[0-9].txt", "file1.txt"));
+        assert!(!glob_matches("file[!0-9].txt", "file1.txt"));
+    }
+
+    #[test]
+    fn for_path_applies_matching_sections() {
+        let dir = TempDir::new("editor-config");
+        let mut file = File::create(dir.join(".editorconfig")).unwrap();
+        write!(
+            file,
+            "root = true\n\n\
+             [*]\n\
+             indent_style = space\n\
+             indent_size = 4\n\
+             end_of_line = crlf\n\n\
+             [*.md]\n\
+             indent_style = tab\n\
+             trim_trailing_whitespace = false\n\
+             insert_final_newline = false\n"
+        ).unwrap();
+
+        assert_eq!(
+            EditorConfig::for_path(&dir.join("README.md")).unwrap(),
+            EditorConfig {
+                soft_tabs: Some(false),
+                tab_width: Some(4),
+                line_ending: Some(LineEnding::Crlf),
+                trim_trailing_whitespace: Some(false),
+                insert_final_newline: Some(false),
+            }
+        );
+        assert_eq!(
+            EditorConfig::for_path(&dir.join("src").join("main.rs")).unwrap(),
+            EditorConfig {
+                soft_tabs: Some(true),
+                tab_width: Some(4),
+                line_ending: Some(LineEnding::Crlf),
+                trim_trailing_whitespace: None,
+                insert_final_newline: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use errors::*;
+use std::char;
+use std::fmt;
+
+/// Character encodings that Amp can read and write. Buffers are always
+/// edited as UTF-8; the original encoding is tracked separately so that
+/// it can be re-applied when the buffer is written to disk.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// The order in which encodings are cycled through when forced.
+const ENCODINGS: [Encoding; 5] = [
+    Encoding::Utf8,
+    Encoding::Utf8Bom,
+    Encoding::Utf16Le,
+    Encoding::Utf16Be,
+    Encoding::Latin1,
+];
+
+impl Encoding {
+    /// Determines the content's encoding using its byte order mark, falling
+    /// back to UTF-8 if it's valid, and Latin-1 otherwise (which accepts any
+    /// byte sequence). Returns the decoded content alongside its encoding.
+    pub fn decode(content: &[u8]) -> (String, Encoding) {
+        if content.starts_with(UTF8_BOM) {
+            let data = String::from_utf8_lossy(&content[UTF8_BOM.len()..]).into_owned();
+            (data, Encoding::Utf8Bom)
+        } else if content.starts_with(UTF16LE_BOM) {
+            (decode_utf16(&content[UTF16LE_BOM.len()..], false), Encoding::Utf16Le)
+        } else if content.starts_with(UTF16BE_BOM) {
+            (decode_utf16(&content[UTF16BE_BOM.len()..], true), Encoding::Utf16Be)
+        } else {
+            match String::from_utf8(content.to_vec()) {
+                Ok(data) => (data, Encoding::Utf8),
+                Err(_) => {
+                    let data = content.iter().map(|&byte| byte as char).collect();
+                    (data, Encoding::Latin1)
+                }
+            }
+        }
+    }
+
+    /// Converts UTF-8 content to this encoding, failing if it contains
+    /// characters that can't be represented (i.e. beyond Latin-1).
+    pub fn encode(&self, content: &str) -> Result<Vec<u8>> {
+        let data = match *self {
+            Encoding::Utf8 => content.as_bytes().to_vec(),
+            Encoding::Utf8Bom => {
+                let mut data = UTF8_BOM.to_vec();
+                data.extend_from_slice(content.as_bytes());
+                data
+            }
+            Encoding::Utf16Le => {
+                let mut data = UTF16LE_BOM.to_vec();
+                for unit in content.encode_utf16() {
+                    data.push(unit as u8);
+                    data.push((unit >> 8) as u8);
+                }
+                data
+            }
+            Encoding::Utf16Be => {
+                let mut data = UTF16BE_BOM.to_vec();
+                for unit in content.encode_utf16() {
+                    data.push((unit >> 8) as u8);
+                    data.push(unit as u8);
+                }
+                data
+            }
+            Encoding::Latin1 => {
+                if let Some((index, c)) = content.char_indices().find(|&(_, c)| c as u32 > 255) {
+                    bail!(
+                        "Line {} contains '{}', which can't be saved as Latin-1; \
+                         use buffer::force_encoding to choose another encoding",
+                        content[..index].matches('\n').count() + 1,
+                        c
+                    );
+                }
+
+                content.chars().map(|c| c as u8).collect()
+            }
+        };
+
+        Ok(data)
+    }
+
+    /// The encoding that follows this one when cycling through them.
+    pub fn next(&self) -> Encoding {
+        let index = ENCODINGS.iter().position(|encoding| encoding == self).unwrap_or(0);
+
+        ENCODINGS[(index + 1) % ENCODINGS.len()]
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf8Bom => "UTF-8 with BOM",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Latin1 => "Latin-1",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Decodes UTF-16 content, replacing invalid sequences
+/// (including any trailing odd byte) with U+FFFD.
+fn decode_utf16(content: &[u8], big_endian: bool) -> String {
+    let units = content
+        .chunks(2)
+        .map(|pair| {
+            if pair.len() < 2 {
+                0xFFFD
+            } else if big_endian {
+                u16::from(pair[0]) << 8 | u16::from(pair[1])
+            } else {
+                u16::from(pair[1]) << 8 | u16::from(pair[0])
+            }
+        });
+
+    char::decode_utf16(units)
+        .map(|result| result.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoding;
+
+    #[test]
+    fn decode_detects_utf8() {
+        assert_eq!(
+            Encoding::decode("amp é".as_bytes()),
+            (String::from("amp é"), Encoding::Utf8)
+        );
+    }
+
+    #[test]
+    fn decode_falls_back_to_latin1_for_invalid_utf8() {
+        assert_eq!(
+            Encoding::decode(&[b'c', b'a', b'f', 0xE9]),
+            (String::from("café"), Encoding::Latin1)
+        );
+    }
+
+    #[test]
+    fn decode_detects_byte_order_marks() {
+        assert_eq!(
+            Encoding::decode(&[0xEF, 0xBB, 0xBF, b'a']),
+            (String::from("a"), Encoding::Utf8Bom)
+        );
+        assert_eq!(
+            Encoding::decode(&[0xFF, 0xFE, b'a', 0]),
+            (String::from("a"), Encoding::Utf16Le)
+        );
+        assert_eq!(
+            Encoding::decode(&[0xFE, 0xFF, 0, b'a']),
+            (String::from("a"), Encoding::Utf16Be)
+        );
+    }
+
+    #[test]
+    fn decode_replaces_invalid_utf16_sequences() {
+        assert_eq!(
+            Encoding::decode(&[0xFF, 0xFE, 0x00, 0xD8, b'a', 0, b'b']),
+            (String::from("\u{FFFD}a\u{FFFD}"), Encoding::Utf16Le)
+        );
+    }
+
+    #[test]
+    fn encode_round_trips_decoded_content() {
+        let samples: Vec<Vec<u8>> = vec![
+            vec![b'c', b'a', b'f', 0xE9],
+            vec![0xEF, 0xBB, 0xBF, b'a'],
+            vec![0xFF, 0xFE, b'a', 0],
+            vec![0xFE, 0xFF, 0, b'a'],
+        ];
+
+        for sample in samples {
+            let (content, encoding) = Encoding::decode(&sample);
+            assert_eq!(encoding.encode(&content).unwrap(), sample);
+        }
+    }
+
+    #[test]
+    fn encode_rejects_unrepresentable_latin1_characters() {
+        let error = Encoding::Latin1.encode("é\na€").unwrap_err();
+
+        assert!(error.to_string().starts_with("Line 2 contains '€'"));
+        assert!(error.to_string().contains("buffer::force_encoding"));
+    }
+}
@@ -0,0 +1,102 @@
+use errors::*;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Builds a log entry for the specified error, including the mode that was
+/// active when it occurred, along with the chain of errors that caused it.
+pub fn format_entry(error: &Error, mode: &str, time: SystemTime) -> String {
+    let mut entry = format!("[{}] ({}) error: {}\n", timestamp(time), mode, error);
+
+    for cause in error.iter().skip(1) {
+        entry.push_str(&format!("  caused by: {}\n", cause));
+    }
+
+    entry
+}
+
+/// Appends an entry to the log at the specified path, creating it if it
+/// doesn't already exist. Logging is disabled when no path is provided.
+pub fn append(path: Option<&Path>, entry: &str) -> Result<()> {
+    let path = match path {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(entry.as_bytes()))
+        .chain_err(|| format!("Couldn't write to error log at {}", path.to_string_lossy()))
+}
+
+/// Formats the time as a UTC date and time (e.g. "2017-01-31 23:59:59 UTC").
+pub fn timestamp(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (year, month, day) = civil_date(seconds / 86_400);
+    let time_of_day = seconds % 86_400;
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        time_of_day / 3600,
+        time_of_day % 3600 / 60,
+        time_of_day % 60
+    )
+}
+
+/// Converts a number of days since the Unix epoch to a (year, month, day)
+/// Gregorian calendar date, using Howard Hinnant's civil_from_days algorithm.
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::*;
+    use std::fs;
+    use std::time::{Duration, UNIX_EPOCH};
+    use util::test_helpers::TempDir;
+
+    #[test]
+    fn format_entry_includes_timestamp_mode_and_causes() {
+        let error: Error = Error::from("Couldn't open file")
+            .chain_err(|| "Couldn't save buffer");
+        let time = UNIX_EPOCH + Duration::from_secs(1_485_907_199);
+
+        assert_eq!(
+            super::format_entry(&error, "normal", time),
+            "[2017-01-31 23:59:59 UTC] (normal) error: Couldn't save buffer\n  caused by: Couldn't open file\n"
+        );
+    }
+
+    #[test]
+    fn append_writes_entries_to_the_log() {
+        let dir = TempDir::new("error-log-append");
+        let path = dir.join("errors.log");
+
+        super::append(Some(&path), "first\n").unwrap();
+        super::append(Some(&path), "second\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+    }
+
+    #[test]
+    fn append_is_a_no_op_without_a_path() {
+        assert!(super::append(None, "entry\n").is_ok());
+    }
+}
@@ -0,0 +1,158 @@
+use errors::*;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Evaluates a basic arithmetic expression, supporting `+ - * / ( )`,
+/// unary negation, and decimals, returning its formatted result.
+pub fn evaluate(expression: &str) -> Result<String> {
+    let mut parser = Parser { characters: expression.chars().peekable() };
+    let value = parser.expression()?;
+    parser.skip_whitespace();
+    if let Some(c) = parser.characters.next() {
+        bail!("Unexpected \"{}\" in expression", c);
+    }
+
+    format(value)
+}
+
+/// Formats whole numbers without a decimal point, and rounds others
+/// to avoid floating point noise (e.g. 0.1 + 0.2 = 0.3).
+fn format(value: f64) -> Result<String> {
+    if !value.is_finite() {
+        bail!("Expression result is out of range");
+    }
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        return Ok(format!("{}", value as i64));
+    }
+
+    let formatted = format!("{:.10}", value);
+    let formatted = formatted.trim_right_matches('0').trim_right_matches('.');
+
+    Ok(if formatted == "-0" { "0".to_string() } else { formatted.to_string() })
+}
+
+/// A recursive descent parser that evaluates as it parses, using the grammar:
+///
+///   expression = term, { ("+" | "-"), term }
+///   term       = factor, { ("*" | "/"), factor }
+///   factor     = "-", factor | "(", expression, ")" | number
+struct Parser<'a> {
+    characters: Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn expression(&mut self) -> Result<f64> {
+        let mut value = self.term()?;
+
+        loop {
+            match self.peek() {
+                Some('+') => { self.characters.next(); value += self.term()?; }
+                Some('-') => { self.characters.next(); value -= self.term()?; }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<f64> {
+        let mut value = self.factor()?;
+
+        loop {
+            match self.peek() {
+                Some('*') => { self.characters.next(); value *= self.factor()?; }
+                Some('/') => {
+                    self.characters.next();
+                    let divisor = self.factor()?;
+                    if divisor == 0.0 {
+                        bail!("Division by zero");
+                    }
+                    value /= divisor;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Result<f64> {
+        match self.peek() {
+            Some('-') => {
+                self.characters.next();
+                Ok(-self.factor()?)
+            }
+            Some('(') => {
+                self.characters.next();
+                let value = self.expression()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.characters.next();
+                        Ok(value)
+                    }
+                    _ => bail!("Expected \")\" in expression"),
+                }
+            }
+            Some(c) if c.is_digit(10) || c == '.' => self.number(),
+            Some(c) => bail!("Unexpected \"{}\" in expression", c),
+            None => bail!("Unexpected end of expression"),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64> {
+        let mut number = String::new();
+        while let Some(&c) = self.characters.peek() {
+            if !c.is_digit(10) && c != '.' {
+                break;
+            }
+            number.push(c);
+            self.characters.next();
+        }
+
+        number.parse().chain_err(|| format!("Invalid number \"{}\" in expression", number))
+    }
+
+    /// Returns the next non-whitespace character, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.characters.peek().cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.characters.peek().map(|c| c.is_whitespace()).unwrap_or(false) {
+            self.characters.next();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::evaluate;
+
+    #[test]
+    fn evaluate_respects_operator_precedence() {
+        assert_eq!(evaluate("2 + 3 * 4").unwrap(), "14");
+        assert_eq!(evaluate("(2 + 3) * 4").unwrap(), "20");
+        assert_eq!(evaluate("10 - 4 - 3").unwrap(), "3");
+        assert_eq!(evaluate("12 / 3 / 2").unwrap(), "2");
+    }
+
+    #[test]
+    fn evaluate_supports_decimals_and_negation() {
+        assert_eq!(evaluate("0.1 + 0.2").unwrap(), "0.3");
+        assert_eq!(evaluate("-1.5 * -(2)").unwrap(), "3");
+        assert_eq!(evaluate("1 / 4").unwrap(), "0.25");
+        assert_eq!(evaluate("-1 / 3").unwrap(), "-0.3333333333");
+    }
+
+    #[test]
+    fn evaluate_rejects_division_by_zero() {
+        assert!(evaluate("(1+2)/0").is_err());
+    }
+
+    #[test]
+    fn evaluate_rejects_invalid_expressions() {
+        assert!(evaluate("").is_err());
+        assert!(evaluate("2 +").is_err());
+        assert!(evaluate("(2 + 3").is_err());
+        assert!(evaluate("2 3").is_err());
+        assert!(evaluate("1.2.3").is_err());
+        assert!(evaluate("amp").is_err());
+    }
+}
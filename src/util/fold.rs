@@ -0,0 +1,131 @@
+use luthor::token::{Category, Token};
+use scribe::buffer::{LineRange, Position};
+use unicode_segmentation::UnicodeSegmentation;
+
+const PAIRS: [(&str, &str); 3] = [("{", "}"), ("[", "]"), ("(", ")")];
+
+/// Finds the innermost brace/bracket region enclosing the specified
+/// position that spans multiple lines, returning the lines from its
+/// opening to its closing delimiter. Delimiters inside string and
+/// comment tokens are ignored, as are unbalanced closing delimiters.
+pub fn brace_region(tokens: &[Token], position: &Position) -> Option<LineRange> {
+    let mut openings: Vec<(&str, Position)> = Vec::new();
+    let mut current = Position{ line: 0, offset: 0 };
+
+    for token in tokens {
+        let skipped = token.category == Category::String || token.category == Category::Comment;
+        if !skipped {
+            if let Some(&(opening, _)) = PAIRS.iter().find(|&&(opening, _)| opening == token.lexeme) {
+                openings.push((opening, current));
+            } else if let Some(&(opening, _)) = PAIRS.iter().find(|&&(_, closing)| closing == token.lexeme) {
+                if openings.last().map(|&(open, _)| open == opening).unwrap_or(false) {
+                    let (_, start) = openings.pop().unwrap();
+
+                    // Regions close from the inside out, so the first
+                    // enclosing region we encounter is the innermost one.
+                    if start.line < current.line && start <= *position && *position <= current {
+                        return Some(LineRange::new(start.line, current.line));
+                    }
+                }
+            }
+        }
+
+        advance(&mut current, &token.lexeme);
+    }
+
+    None
+}
+
+/// Moves the position past the specified lexeme.
+fn advance(position: &mut Position, lexeme: &str) {
+    for grapheme in lexeme.graphemes(true) {
+        if grapheme == "\n" || grapheme == "\r\n" {
+            position.line += 1;
+            position.offset = 0;
+        } else {
+            position.offset += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use luthor::token::{Category, Token};
+    use scribe::buffer::{LineRange, Position};
+    use super::brace_region;
+
+    fn token(lexeme: &str, category: Category) -> Token {
+        Token{ lexeme: lexeme.to_string(), category }
+    }
+
+    // fn amp() {
+    //     if ready {
+    //         go("}");
+    //     }
+    // }
+    fn sample_tokens() -> Vec<Token> {
+        vec![
+            token("fn", Category::Keyword),
+            token(" ", Category::Whitespace),
+            token("amp", Category::Identifier),
+            token("(", Category::Text),
+            token(")", Category::Text),
+            token(" ", Category::Whitespace),
+            token("{", Category::Text),
+            token("\n    ", Category::Whitespace),
+            token("if", Category::Keyword),
+            token(" ", Category::Whitespace),
+            token("ready", Category::Identifier),
+            token(" ", Category::Whitespace),
+            token("{", Category::Text),
+            token("\n        ", Category::Whitespace),
+            token("go", Category::Identifier),
+            token("(", Category::Text),
+            token("\"}\"", Category::String),
+            token(")", Category::Text),
+            token(";", Category::Text),
+            token("\n    ", Category::Whitespace),
+            token("}", Category::Text),
+            token("\n", Category::Whitespace),
+            token("}", Category::Text),
+        ]
+    }
+
+    #[test]
+    fn brace_region_finds_the_innermost_enclosing_region() {
+        let tokens = sample_tokens();
+
+        assert_eq!(
+            brace_region(&tokens, &Position{ line: 2, offset: 8 }),
+            Some(LineRange::new(1, 3))
+        );
+        assert_eq!(
+            brace_region(&tokens, &Position{ line: 1, offset: 13 }),
+            Some(LineRange::new(1, 3))
+        );
+    }
+
+    #[test]
+    fn brace_region_finds_the_outer_region_outside_of_nested_ones() {
+        let tokens = sample_tokens();
+
+        assert_eq!(
+            brace_region(&tokens, &Position{ line: 1, offset: 4 }),
+            Some(LineRange::new(0, 4))
+        );
+        assert_eq!(
+            brace_region(&tokens, &Position{ line: 4, offset: 0 }),
+            Some(LineRange::new(0, 4))
+        );
+    }
+
+    #[test]
+    fn brace_region_ignores_single_line_regions_and_positions_outside_of_regions() {
+        let tokens = sample_tokens();
+
+        // Neither "amp" nor the start of the line fall within a multi-line region.
+        assert_eq!(brace_region(&tokens, &Position{ line: 0, offset: 4 }), None);
+        assert_eq!(brace_region(&tokens, &Position{ line: 0, offset: 0 }), None);
+        assert_eq!(brace_region(&tokens, &Position{ line: 5, offset: 0 }), None);
+    }
+}
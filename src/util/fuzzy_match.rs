@@ -0,0 +1,255 @@
+use fragment::matching::AsStr;
+use std::cmp::Ordering;
+
+// Scoring weights applied to each matched character.
+const MATCH_SCORE: usize = 1;
+const CONSECUTIVE_BONUS: usize = 4;
+const WORD_START_BONUS: usize = 8;
+const PATH_BOUNDARY_BONUS: usize = 10;
+
+/// Scores a candidate against a query, returning None if the query's
+/// characters don't appear (case-insensitively) in order within the
+/// candidate. Higher scores are better; matched characters that extend a
+/// contiguous run, start a word, or start a path component are favoured.
+/// Whitespace-separated query terms are scored independently and summed.
+pub fn score(query: &str, candidate: &str) -> Option<usize> {
+    score_with_indices(query, candidate).map(|(score, _)| score)
+}
+
+/// Like score, but also returns the (sorted, character-based) indices
+/// of the candidate characters matched by the query.
+pub fn score_with_indices(query: &str, candidate: &str) -> Option<(usize, Vec<usize>)> {
+    let candidate: Vec<char> = candidate.chars().collect();
+
+    query.split_whitespace().fold(Some((0, Vec::new())), |total, term| {
+        total.and_then(|(total, mut indices)| {
+            score_term(term, &candidate).map(|(score, term_indices)| {
+                indices.extend(term_indices);
+                indices.sort();
+                indices.dedup();
+
+                (total + score, indices)
+            })
+        })
+    })
+}
+
+fn score_term(term: &str, candidate: &[char]) -> Option<(usize, Vec<usize>)> {
+    let query: Vec<char> = term.to_lowercase().chars().collect();
+
+    // For each query character, track the best (score, run length, preceding
+    // match index) for a match of the query so far, ending with that
+    // character at a given candidate index.
+    let mut rows: Vec<Vec<Option<(usize, usize, Option<usize>)>>> = Vec::new();
+    for (query_index, query_char) in query.iter().enumerate() {
+        let mut current = vec![None; candidate.len()];
+        let mut best_preceding: Option<(usize, usize)> = None;
+
+        for (index, candidate_char) in candidate.iter().enumerate() {
+            // Fold in matches that end before the previous character;
+            // these can be extended by this one, but not contiguously.
+            if query_index > 0 && index > 1 {
+                if let Some((score, _, _)) = rows[query_index - 1][index - 2] {
+                    best_preceding = best_preceding.max(Some((score, index - 2)));
+                }
+            }
+
+            if !candidate_char.to_lowercase().eq(query_char.to_lowercase()) {
+                continue;
+            }
+
+            let char_score = MATCH_SCORE + boundary_bonus(candidate, index);
+            current[index] = if query_index == 0 {
+                Some((char_score, 1, None))
+            } else {
+                let contiguous = if index > 0 {
+                    rows[query_index - 1][index - 1].map(|(score, run, _)| {
+                        (score + char_score + CONSECUTIVE_BONUS * run, run + 1, Some(index - 1))
+                    })
+                } else {
+                    None
+                };
+                let separate = best_preceding.map(|(score, preceding_index)| {
+                    (score + char_score, 1, Some(preceding_index))
+                });
+
+                contiguous.max(separate)
+            };
+        }
+
+        rows.push(current);
+    }
+
+    // Find the best match for the full query, and
+    // walk its preceding match indices back to the start.
+    let (score, mut index) = rows.last()?
+        .iter()
+        .enumerate()
+        .filter_map(|(index, result)| result.map(|(score, _, _)| (score, index)))
+        .max()?;
+    let mut indices = vec![index];
+    for row in rows.iter().rev() {
+        match row[index] {
+            Some((_, _, Some(preceding_index))) => {
+                index = preceding_index;
+                indices.push(index);
+            }
+            _ => break,
+        }
+    }
+    indices.reverse();
+
+    Some((score, indices))
+}
+
+/// Finds the candidates matching the query, ordered by descending score.
+/// Ties are broken in favour of shorter candidates.
+pub fn find<'a, T: AsStr>(query: &str, candidates: &'a [T], max_results: usize) -> Vec<&'a T> {
+    rank(
+        query,
+        candidates.iter().map(|candidate| (candidate, candidate.as_str())),
+        max_results,
+    )
+}
+
+/// Like `find`, but for candidates paired with the text they're matched
+/// against. Each candidate is scored once, before the results are ranked.
+pub fn rank<T, S, I>(query: &str, candidates: I, max_results: usize) -> Vec<T>
+where
+    S: AsRef<str>,
+    I: IntoIterator<Item = (T, S)>,
+{
+    let mut results: Vec<(usize, usize, T)> = candidates
+        .into_iter()
+        .filter_map(|(candidate, text)| {
+            let text = text.as_ref();
+            score(query, text).map(|score| (score, text.len(), candidate))
+        })
+        .collect();
+
+    results.sort_by(|&(score, length, _), &(other_score, other_length, _)| {
+        match other_score.cmp(&score) {
+            Ordering::Equal => length.cmp(&other_length),
+            ordering => ordering,
+        }
+    });
+    results.truncate(max_results);
+
+    results.into_iter().map(|(_, _, candidate)| candidate).collect()
+}
+
+fn boundary_bonus(candidate: &[char], index: usize) -> usize {
+    if index == 0 {
+        return WORD_START_BONUS;
+    }
+
+    let previous = candidate[index - 1];
+    let current = candidate[index];
+    if previous == '/' || previous == '\\' || previous == ':' {
+        PATH_BOUNDARY_BONUS
+    } else if !previous.is_alphanumeric() || (previous.is_lowercase() && current.is_uppercase()) {
+        WORD_START_BONUS
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, rank, score, score_with_indices};
+
+    #[test]
+    fn score_returns_none_when_query_is_not_a_subsequence() {
+        assert_eq!(score("amp", "map"), None);
+    }
+
+    #[test]
+    fn score_ignores_case() {
+        assert!(score("AMP", "amp").is_some());
+        assert!(score("amp", "AMP").is_some());
+    }
+
+    #[test]
+    fn score_matches_whitespace_separated_terms_independently() {
+        assert!(score("cmd app", "application/command").is_some());
+        assert_eq!(score("cmd xyz", "application/command"), None);
+    }
+
+    #[test]
+    fn score_with_indices_returns_matched_character_indices() {
+        assert_eq!(
+            score_with_indices("amc", "application/mod.rs/command").map(|(_, indices)| indices),
+            Some(vec![0, 12, 19])
+        );
+    }
+
+    #[test]
+    fn score_with_indices_merges_indices_for_multiple_terms() {
+        assert_eq!(
+            score_with_indices("cmd app", "app/cmd").map(|(_, indices)| indices),
+            Some(vec![0, 1, 2, 4, 5, 6])
+        );
+    }
+
+    #[test]
+    fn score_prefers_contiguous_runs_over_scattered_matches() {
+        assert!(score("amc", "xamcx") > score("amc", "xaxmxcx"));
+    }
+
+    #[test]
+    fn score_prefers_word_starts_over_mid_word_matches() {
+        assert!(score("mc", "xx_mx_cx") > score("mc", "xxmxxcx"));
+        assert!(score("mc", "xxMxxCx") > score("mc", "xxmxxcx"));
+    }
+
+    #[test]
+    fn score_prefers_path_boundaries_over_word_starts() {
+        assert!(score("mc", "xx/mx/cx") > score("mc", "xx_mx_cx"));
+    }
+
+    #[test]
+    fn find_orders_candidates_by_score() {
+        let candidates = vec![
+            "abmxc",
+            "xamcx",
+            "application/mod.rs/command",
+            "ambient_crate",
+            "no match",
+        ];
+
+        assert_eq!(
+            find("amc", &candidates, 5),
+            vec![
+                &"application/mod.rs/command",
+                &"ambient_crate",
+                &"xamcx",
+                &"abmxc",
+            ]
+        );
+    }
+
+    #[test]
+    fn find_prefers_shorter_candidates_with_equal_scores() {
+        let candidates = vec!["amp_editor", "amp"];
+        assert_eq!(find("amp", &candidates, 5), vec![&"amp", &"amp_editor"]);
+    }
+
+    #[test]
+    fn find_limits_results() {
+        let candidates = vec!["amp", "amp_editor", "amplify"];
+        assert_eq!(find("amp", &candidates, 2).len(), 2);
+    }
+
+    #[test]
+    fn rank_scores_all_candidates_before_limiting_results() {
+        // The best match comes last, so it'd be lost if
+        // the candidates were truncated before being ranked.
+        let candidates = vec![
+            (1, "axxmxxc"),
+            (2, "axmxcxx"),
+            (3, "amc"),
+        ];
+
+        assert_eq!(rank("amc", candidates, 1), vec![3]);
+    }
+}
@@ -0,0 +1,195 @@
+use bloodhound::ExclusionPattern;
+use errors::*;
+use git2::Repository;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Converts a glob into an (unanchored) regular expression pattern. Single
+/// stars don't match path separators, while double stars match across them.
+pub fn to_regex(glob: &str) -> String {
+    convert(glob, false)
+}
+
+/// Like `to_regex`, except that a double star followed by
+/// a slash also matches zero directories (e.g. "src/**/*.rs"
+/// matches "src/main.rs"), as is conventional for file paths.
+pub fn to_path_regex(glob: &str) -> String {
+    convert(glob, true)
+}
+
+fn convert(glob: &str, optional_directories: bool) -> String {
+    let mut pattern = String::new();
+    let mut chars = glob.chars().peekable();
+    let mut in_braces = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    if optional_directories && chars.peek() == Some(&'/') {
+                        chars.next();
+                        pattern.push_str("(?:.*/)?");
+                    } else {
+                        pattern.push_str(".*");
+                    }
+                } else {
+                    pattern.push_str("[^/]*");
+                }
+            }
+            '?' => pattern.push_str("[^/]"),
+            '[' => {
+                pattern.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    pattern.push('^');
+                }
+            }
+            ']' => pattern.push(']'),
+            '{' => {
+                in_braces = true;
+                pattern.push_str("(?:");
+            }
+            '}' if in_braces => {
+                in_braces = false;
+                pattern.push(')');
+            }
+            ',' if in_braces => pattern.push('|'),
+            '\\' | '.' | '+' | '(' | ')' | '|' | '^' | '$' | '}' => {
+                pattern.push('\\');
+                pattern.push(c);
+            }
+            _ => pattern.push(c),
+        }
+    }
+
+    pattern
+}
+
+/// Finds the files beneath the root whose root-relative paths match the
+/// glob, skipping any paths matched by the exclusions, as well as those
+/// ignored by the repository, if there is one. Results are sorted.
+pub fn find_unignored(root: &Path, glob: &str, exclusions: &[ExclusionPattern], repo: Option<&Repository>) -> Result<Vec<PathBuf>> {
+    let (repo, workdir) = match repo.and_then(|repo| repo.workdir().map(|workdir| (repo, workdir))) {
+        Some(repo_and_workdir) => repo_and_workdir,
+        None => return find(root, glob, exclusions, |_| false),
+    };
+
+    find(root, glob, exclusions, |path| {
+        path.strip_prefix(workdir)
+            .map(|relative_path| repo.is_path_ignored(relative_path).unwrap_or(false))
+            .unwrap_or(false)
+    })
+}
+
+/// Finds the files beneath the root whose root-relative paths match the
+/// glob, skipping any paths matched by the exclusions or the ignored
+/// predicate (ignored directories aren't searched). Results are sorted.
+/// Symlinked directories are followed, but each directory is only visited
+/// once, so that symlink loops don't cause an endless search.
+pub fn find<F: Fn(&Path) -> bool>(root: &Path, glob: &str, exclusions: &[ExclusionPattern], ignored: F) -> Result<Vec<PathBuf>> {
+    let regex = Regex::new(&format!("^{}$", to_path_regex(glob.trim_left_matches("./"))))
+        .chain_err(|| format!("Invalid glob: {}", glob))?;
+    let mut paths = Vec::new();
+    let mut directories = vec![root.to_path_buf()];
+    let mut visited = HashSet::new();
+
+    while let Some(directory) = directories.pop() {
+        let canonical_directory = fs::canonicalize(&directory).chain_err(|| {
+            format!("Couldn't resolve {}", directory.to_string_lossy())
+        })?;
+        if !visited.insert(canonical_directory) {
+            continue;
+        }
+
+        let entries = fs::read_dir(&directory).chain_err(|| {
+            format!("Couldn't read {}", directory.to_string_lossy())
+        })?;
+
+        for entry in entries {
+            let path = entry.chain_err(|| "Couldn't read directory entry")?.path();
+            if exclusions.iter().any(|exclusion| exclusion.matches_path(&path)) || ignored(&path) {
+                continue;
+            }
+
+            if path.is_dir() {
+                directories.push(path);
+            } else if let Ok(relative_path) = path.strip_prefix(root) {
+                let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+                if regex.is_match(&relative_path) {
+                    paths.push(path.clone());
+                }
+            }
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use bloodhound::ExclusionPattern;
+    use regex::Regex;
+    use std::fs::{self, File};
+    use super::{find, to_path_regex, to_regex};
+    use util::test_helpers::TempDir;
+
+    fn matches(glob: &str, path: &str) -> bool {
+        Regex::new(&format!("^{}$", to_regex(glob))).unwrap().is_match(path)
+    }
+
+    fn path_matches(glob: &str, path: &str) -> bool {
+        Regex::new(&format!("^{}$", to_path_regex(glob))).unwrap().is_match(path)
+    }
+
+    #[test]
+    fn double_stars_followed_by_a_slash_match_one_or_more_directories() {
+        assert!(!matches("src/**/*.rs", "src/main.rs"));
+        assert!(matches("src/**/*.rs", "src/view/mod.rs"));
+    }
+
+    #[test]
+    fn path_double_stars_followed_by_a_slash_match_zero_or_more_directories() {
+        assert!(path_matches("src/**/*.rs", "src/main.rs"));
+        assert!(path_matches("src/**/*.rs", "src/view/buffer/mod.rs"));
+        assert!(!path_matches("src/**/*.rs", "tests/main.rs"));
+    }
+
+    #[test]
+    fn single_stars_dont_match_across_directories() {
+        assert!(matches("src/*.rs", "src/main.rs"));
+        assert!(!matches("src/*.rs", "src/view/mod.rs"));
+    }
+
+    #[test]
+    fn find_returns_sorted_matching_files_and_skips_exclusions() {
+        let root = TempDir::new("glob-find");
+        fs::create_dir_all(root.join("src/view")).unwrap();
+        fs::create_dir_all(root.join("target")).unwrap();
+        for path in &["src/main.rs", "src/view/mod.rs", "src/notes.md", "target/build.rs"] {
+            File::create(root.join(path)).unwrap();
+        }
+        let exclusions = vec![ExclusionPattern::new("**/target").unwrap()];
+
+        assert_eq!(
+            find(root.path(), "**/*.rs", &exclusions, |_| false).unwrap(),
+            vec![root.join("src/main.rs"), root.join("src/view/mod.rs")]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_visits_symlinked_directories_once() {
+        use std::os::unix::fs::symlink;
+
+        let root = TempDir::new("glob-symlink");
+        fs::create_dir_all(root.join("src")).unwrap();
+        File::create(root.join("src/main.rs")).unwrap();
+        symlink(root.path(), root.join("src/loop")).unwrap();
+
+        assert_eq!(find(root.path(), "**/*.rs", &[], |_| false).unwrap(), vec![root.join("src/main.rs")]);
+    }
+}
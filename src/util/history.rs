@@ -0,0 +1,11 @@
+use scribe::Buffer;
+
+/// Ends the buffer's operation group, unless its changes are being grouped by
+/// an enclosing change (i.e. the key binding running the command). Scribe's
+/// groups don't nest, so ending the command's own group would also end the
+/// enclosing one, splitting the binding's changes across history entries.
+pub fn end_operation_group(buffer: &mut Buffer, enclosing_group: Option<usize>) {
+    if enclosing_group.is_none() || buffer.id != enclosing_group {
+        buffer.end_operation_group();
+    }
+}
@@ -0,0 +1,189 @@
+use errors::*;
+use scribe::Buffer;
+use scribe::buffer::{GapBuffer, Position};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use util::Encoding;
+
+#[cfg(windows)]
+const NULL_DEVICE: &str = "NUL";
+#[cfg(not(windows))]
+const NULL_DEVICE: &str = "/dev/null";
+
+/// Line terminator styles that Amp can read and write. Buffers are always
+/// edited using LF line endings; the original style is tracked separately
+/// so that it can be re-applied when the buffer is written to disk.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    /// Determines the dominant line ending style used in the specified
+    /// content, defaulting to LF for content without any line endings.
+    pub fn detect(content: &str) -> LineEnding {
+        let crlf_count = content.matches("\r\n").count();
+        let lf_count = content.matches('\n').count() - crlf_count;
+
+        if crlf_count > lf_count {
+            LineEnding::Crlf
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    /// Converts LF-terminated content to this line ending style.
+    pub fn apply(&self, content: &str) -> String {
+        match *self {
+            LineEnding::Lf => content.to_string(),
+            LineEnding::Crlf => content.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// The line ending and encoding used to write a buffer to disk.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FileFormat {
+    pub line_ending: LineEnding,
+    pub encoding: Encoding,
+}
+
+impl FileFormat {
+    /// Converts LF-terminated UTF-8 content to this format.
+    pub fn encode(&self, content: &str) -> Result<Vec<u8>> {
+        self.encoding.encode(&self.line_ending.apply(content))
+    }
+}
+
+impl Default for FileFormat {
+    fn default() -> FileFormat {
+        FileFormat{ line_ending: LineEnding::Lf, encoding: Encoding::Utf8 }
+    }
+}
+
+/// Loads the file at the specified path into a buffer, decoding it as UTF-8
+/// and converting CRLF line endings to LF, and returns it along with the file's
+/// format (its dominant line ending and original encoding). The conversion
+/// isn't recorded in the buffer's history, so it can't be undone.
+pub fn load_buffer(path: &Path) -> Result<(Buffer, FileFormat)> {
+    let (buffer, data) = match Buffer::from_file(path) {
+        Ok(buffer) => {
+            let data = buffer.data().into_bytes();
+            (buffer, data)
+        }
+        // Scribe only reads UTF-8; decode anything else ourselves.
+        Err(ref error) if error.kind() == io::ErrorKind::InvalidData => {
+            let mut buffer = Buffer::new();
+            buffer.path = Some(path.canonicalize()
+                .chain_err(|| format!("Couldn't open {}", path.to_string_lossy()))?);
+            mark_saved(&mut buffer)?;
+
+            (buffer, read(path)?)
+        }
+        Err(error) => {
+            return Err(error).chain_err(|| format!("Couldn't open {}", path.to_string_lossy()));
+        }
+    };
+
+    let (content, encoding) = Encoding::decode(&data);
+    let line_ending = LineEnding::detect(&content);
+    let content = normalize(&content);
+    if content.as_bytes() != &data[..] {
+        *buffer.cursor.data.borrow_mut() = GapBuffer::new(content);
+    }
+
+    Ok((buffer, FileFormat{ line_ending, encoding }))
+}
+
+/// Reloads the buffer's content from its file in place, as `load_buffer`
+/// does, keeping its ID, syntax definition, and change callback (which is
+/// then run). Returns the file's format.
+pub fn reload_buffer(buffer: &mut Buffer) -> Result<FileFormat> {
+    let path = buffer.path.clone().ok_or(BUFFER_PATH_MISSING)?;
+    let (mut reloaded, format) = load_buffer(&path)?;
+    reloaded.id = buffer.id;
+    reloaded.syntax_definition = buffer.syntax_definition.take();
+    reloaded.change_callback = buffer.change_callback.take();
+    mem::swap(buffer, &mut reloaded);
+
+    if let Some(ref callback) = buffer.change_callback {
+        callback(Position{ line: 0, offset: 0 });
+    }
+
+    Ok(format)
+}
+
+/// Reads the raw content of the file at the specified path.
+fn read(path: &Path) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut data))
+        .chain_err(|| format!("Couldn't read {}", path.to_string_lossy()))?;
+
+    Ok(data)
+}
+
+/// Converts CRLF line endings to LF. Carriage returns
+/// that aren't part of a line ending are left in place.
+pub fn normalize(content: &str) -> String {
+    content.replace("\r\n", "\n")
+}
+
+/// Saves the buffer in the specified format, writing the encoded content to
+/// the existing file in a single pass (rather than replacing the file, so
+/// that symlinks, hard links, ownership, and permissions are left intact).
+pub fn save_buffer(buffer: &mut Buffer, format: FileFormat) -> Result<()> {
+    let path = buffer.path.clone().ok_or(BUFFER_PATH_MISSING)?;
+    let data = format.encode(&buffer.data())?;
+    File::create(&path)
+        .and_then(|mut file| file.write_all(&data))
+        .chain_err(|| "Unable to save buffer")?;
+
+    mark_saved(buffer)
+}
+
+/// Marks the buffer's current state as saved. Scribe only does so when it
+/// writes a buffer to its path, so its content is set aside and it's pointed
+/// at the null device, leaving it nothing to write.
+fn mark_saved(buffer: &mut Buffer) -> Result<()> {
+    let content = mem::replace(&mut *buffer.cursor.data.borrow_mut(), GapBuffer::new(String::new()));
+    let path = mem::replace(&mut buffer.path, Some(PathBuf::from(NULL_DEVICE)));
+    let result = buffer.save().chain_err(|| "Couldn't mark the buffer as saved");
+    buffer.path = path;
+    *buffer.cursor.data.borrow_mut() = content;
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize, LineEnding};
+
+    #[test]
+    fn detect_returns_crlf_when_most_lines_use_crlf() {
+        assert_eq!(LineEnding::detect("amp\r\neditor\r\ntext\n"), LineEnding::Crlf);
+    }
+
+    #[test]
+    fn detect_returns_lf_when_most_lines_use_lf() {
+        assert_eq!(LineEnding::detect("amp\neditor\r\ntext\n"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn detect_returns_lf_when_there_are_no_line_endings() {
+        assert_eq!(LineEnding::detect("amp"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn apply_converts_lf_to_crlf() {
+        assert_eq!(LineEnding::Crlf.apply("amp\neditor\n"), "amp\r\neditor\r\n");
+    }
+
+    #[test]
+    fn normalize_only_converts_crlf_line_endings() {
+        assert_eq!(normalize("amp\r\neditor\rtext\r\n"), "amp\neditor\rtext\n");
+    }
+}
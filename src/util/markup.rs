@@ -0,0 +1,85 @@
+/// Elements that can't have content, and so are never closed.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img",
+    "input", "link", "meta", "param", "source", "track", "wbr",
+];
+
+/// Finds the name of the innermost tag opened, but not yet closed, in the
+/// content, ignoring comments, declarations, and self-closing/void elements.
+pub fn unclosed_tag(content: &str) -> Option<String> {
+    let mut open_tags: Vec<String> = Vec::new();
+    let mut remaining = content;
+
+    while let Some(start) = remaining.find('<') {
+        remaining = &remaining[start + 1..];
+
+        // Skip past comments in their entirety, since they may contain tags.
+        if remaining.starts_with("!--") {
+            match remaining.find("-->") {
+                Some(end) => remaining = &remaining[end + 3..],
+                None => break,
+            }
+            continue;
+        }
+
+        let end = match remaining.find('>') {
+            Some(end) => end,
+            None => break,
+        };
+        let tag = &remaining[..end];
+        remaining = &remaining[end + 1..];
+
+        if tag.starts_with('/') {
+            // Close the matching tag, along with any left open within it.
+            let name = tag_name(&tag[1..]);
+            if let Some(index) = open_tags.iter().rposition(|open_tag| *open_tag == name) {
+                open_tags.truncate(index);
+            }
+        } else if !tag.ends_with('/') {
+            let name = tag_name(tag);
+            let opening = name.chars().next().map(|c| c.is_alphabetic()).unwrap_or(false);
+            if opening && !VOID_ELEMENTS.contains(&name.to_lowercase().as_str()) {
+                open_tags.push(name);
+            }
+        }
+    }
+
+    open_tags.pop()
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_left()
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unclosed_tag;
+
+    #[test]
+    fn unclosed_tag_finds_the_innermost_open_tag() {
+        assert_eq!(unclosed_tag("<div class=\"amp\"><p>text"), Some("p".to_string()));
+        assert_eq!(unclosed_tag("<div><p>text</p>"), Some("div".to_string()));
+    }
+
+    #[test]
+    fn unclosed_tag_ignores_closed_and_self_closing_tags() {
+        assert_eq!(unclosed_tag("<div></div>"), None);
+        assert_eq!(unclosed_tag("<img src=\"amp.png\" />"), None);
+        assert_eq!(unclosed_tag("<br>"), None);
+    }
+
+    #[test]
+    fn unclosed_tag_ignores_comments_and_declarations() {
+        assert_eq!(unclosed_tag("<!DOCTYPE html><!-- <span> -->"), None);
+        assert_eq!(unclosed_tag("<?xml version=\"1.0\"?><item>"), Some("item".to_string()));
+    }
+
+    #[test]
+    fn unclosed_tag_ignores_incomplete_tags() {
+        assert_eq!(unclosed_tag("<ul><li"), Some("ul".to_string()));
+    }
+}
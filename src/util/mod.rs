@@ -1,12 +1,39 @@
+pub use self::encoding::Encoding;
+pub use self::line_ending::{FileFormat, LineEnding};
 pub use self::selectable_vec::SelectableVec;
 
+pub mod alternate_file;
+pub mod ansi;
+pub mod blame;
+pub mod bracket;
+pub mod char_info;
+pub mod codec;
+pub mod comment;
+pub mod diff;
+pub mod editor_config;
+pub mod encoding;
+pub mod error_log;
+pub mod expression;
+pub mod fold;
+pub mod fuzzy_match;
+pub mod glob;
+pub mod history;
+pub mod line_ending;
+pub mod markup;
 pub mod movement_lexer;
 mod selectable_vec;
+pub mod shell;
+pub mod spell_check;
+#[cfg(test)]
+pub mod test_helpers;
 pub mod token;
+pub mod url;
+pub mod uuid;
 
 use errors::*;
 use models::Application;
 use scribe::buffer::{Buffer, LineRange, Position, Range};
+use std::path::{Path, PathBuf};
 
 /// Translates a line range to a regular range, including its last line.
 /// Handles ranges including and end line without trailing newline character.
@@ -49,6 +76,27 @@ pub fn inclusive_range(line_range: &LineRange, buffer: &mut Buffer) -> Range {
                end_position)
 }
 
+/// The first and last lines of the run of consecutive lines containing the
+/// specified line that are, like it, either blank or not (i.e. a paragraph).
+pub fn line_run(lines: &[&str], line: usize) -> Option<(usize, usize)> {
+    let is_blank = |index: usize| lines[index].trim().is_empty();
+    if line >= lines.len() {
+        return None;
+    }
+    let blank = is_blank(line);
+
+    let mut start = line;
+    while start > 0 && is_blank(start - 1) == blank {
+        start -= 1;
+    }
+    let mut end = line;
+    while end + 1 < lines.len() && is_blank(end + 1) == blank {
+        end += 1;
+    }
+
+    Some((start, end))
+}
+
 /// Convenience method to initialize and add a buffer to the workspace.
 pub fn add_buffer(buffer: Buffer, app: &mut Application) -> Result<()> {
     app.workspace.add_buffer(buffer);
@@ -57,6 +105,43 @@ pub fn add_buffer(buffer: Buffer, app: &mut Application) -> Result<()> {
     Ok(())
 }
 
+/// Convenience method to load a file into a buffer with normalized line
+/// endings and encoding, tracking its original format, and add it to the workspace.
+pub fn load_buffer(path: &Path, app: &mut Application) -> Result<()> {
+    let (buffer, format) = line_ending::load_buffer(path)?;
+    add_buffer(buffer, app)?;
+
+    if let Some(id) = app.workspace.current_buffer().and_then(|b| b.id) {
+        app.file_formats.insert(id, format);
+    }
+
+    Ok(())
+}
+
+/// The format used to write the specified buffer to disk: the one it was
+/// loaded with, with its line ending overridden by the preferred one, if set.
+pub fn file_format(app: &Application, id: Option<usize>, path: Option<&PathBuf>) -> FileFormat {
+    let mut format = id
+        .and_then(|id| app.file_formats.get(&id).cloned())
+        .unwrap_or_default();
+    if let Some(line_ending) = app.preferences.borrow().line_endings(path) {
+        format.line_ending = line_ending;
+    }
+
+    format
+}
+
+/// Convenience method to open a file in the workspace, or switch to it if it's
+/// already open, normalizing line endings and encoding for newly loaded files.
+pub fn open_buffer(path: &Path, app: &mut Application) -> Result<()> {
+    if app.workspace.contains_buffer_with_path(path) {
+        app.workspace.open_buffer(path)
+            .chain_err(|| format!("Couldn't open {}", path.to_string_lossy()))
+    } else {
+        load_buffer(path, app)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use scribe::Buffer;
@@ -95,4 +180,14 @@ mod tests {
                                   offset: 0,
                               }));
     }
+
+    #[test]
+    fn line_run_groups_blank_and_non_blank_lines() {
+        let lines = vec!["amp", "editor", "", "  ", "text"];
+
+        assert_eq!(super::line_run(&lines, 1), Some((0, 1)));
+        assert_eq!(super::line_run(&lines, 3), Some((2, 3)));
+        assert_eq!(super::line_run(&lines, 4), Some((4, 4)));
+        assert_eq!(super::line_run(&lines, 5), None);
+    }
 }
@@ -24,6 +24,13 @@ impl<T> SelectableVec<T> {
         self.set.get(self.selected_index)
     }
 
+    /// Selects the element at the specified index, if it exists.
+    pub fn select_index(&mut self, index: usize) {
+        if index < self.set.len() {
+            self.selected_index = index;
+        }
+    }
+
     pub fn select_previous(&mut self) {
         if self.selected_index > 0 {
             self.selected_index -= 1;
@@ -80,4 +87,12 @@ mod tests {
         selectable_vec.select_previous();
         assert_eq!(selectable_vec.selection(), Some(&1));
     }
+
+    #[test]
+    fn select_index_ignores_out_of_range_indices() {
+        let mut selectable_vec: SelectableVec<usize> = SelectableVec::new(vec![0, 1]);
+        selectable_vec.select_index(1);
+        selectable_vec.select_index(2);
+        assert_eq!(selectable_vec.selection(), Some(&1));
+    }
 }
@@ -0,0 +1,107 @@
+use errors::*;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(windows)]
+const SHELL: (&str, &str) = ("cmd", "/C");
+#[cfg(not(windows))]
+const SHELL: (&str, &str) = ("sh", "-c");
+
+/// How often to check whether a running command has exited.
+const POLL_INTERVAL_MS: u64 = 10;
+
+/// Runs the command using the system shell, returning its standard output.
+/// Commands that exit unsuccessfully are treated as errors, described using
+/// their standard error output, as are those that are still running when
+/// the timeout elapses, which are killed.
+pub fn run(command: &str, timeout: Duration) -> Result<String> {
+    let (shell, flag) = SHELL;
+    let mut child = Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .chain_err(|| format!("Couldn't run \"{}\"", command))?;
+
+    // Read the command's output on separate threads, so
+    // that it can't block on a full pipe while we wait.
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let started_at = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().chain_err(|| format!("Couldn't run \"{}\"", command))? {
+            break status;
+        }
+        if started_at.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!(timeout_error(command, timeout));
+        }
+        thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+    };
+
+    // Processes started by the command can keep its output open after it
+    // exits, so the time spent reading it counts towards the timeout, too.
+    let remaining = || timeout.checked_sub(started_at.elapsed()).unwrap_or_default();
+    let (stdout, stderr) = match (stdout.recv_timeout(remaining()), stderr.recv_timeout(remaining())) {
+        (Ok(stdout), Ok(stderr)) => (stdout, stderr),
+        _ => bail!(timeout_error(command, timeout)),
+    };
+
+    if !status.success() {
+        let error = String::from_utf8_lossy(&stderr);
+        bail!(format!("\"{}\" failed ({}): {}", command, status, error.trim()));
+    }
+
+    String::from_utf8(stdout)
+        .chain_err(|| format!("\"{}\" produced output that isn't valid UTF-8", command))
+}
+
+/// Reads the pipe to its end on a new thread, sending the data once it's done.
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut data = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut data);
+        }
+        let _ = sender.send(data);
+    });
+
+    receiver
+}
+
+fn timeout_error(command: &str, timeout: Duration) -> String {
+    format!("\"{}\" didn't finish within {} seconds", command, timeout.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use super::run;
+
+    #[test]
+    fn run_returns_standard_output() {
+        assert_eq!(run("echo amp", Duration::from_secs(10)).unwrap().trim(), "amp");
+    }
+
+    #[test]
+    fn run_rejects_commands_that_fail() {
+        assert!(run("echo amp && exit 3", Duration::from_secs(10)).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_kills_commands_that_exceed_the_timeout() {
+        let started_at = Instant::now();
+
+        assert!(run("sleep 10", Duration::from_millis(100)).is_err());
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+    }
+}
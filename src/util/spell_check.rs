@@ -0,0 +1,248 @@
+use errors::*;
+use scribe::buffer::{Position, Range, Token};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+use syntect::highlighting::ScopeSelectors;
+use unicode_segmentation::UnicodeSegmentation;
+
+const CHECKED_SCOPES: &str = "comment, string";
+
+/// A case-insensitive set of correctly spelled words.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    pub fn new<I, S>(words: I) -> Dictionary
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        Dictionary {
+            words: words
+                .into_iter()
+                .map(|word| word.as_ref().trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Loads a word list with one word per line (e.g. /usr/share/dict/words).
+    pub fn load(path: &Path) -> Result<Dictionary> {
+        let mut data = String::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut data))
+            .chain_err(|| format!("Couldn't read spell check dictionary at {}", path.to_string_lossy()))?;
+
+        Ok(Dictionary::new(data.lines()))
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+
+        self.words.contains(&word) ||
+            // Accept possessives of known words.
+            (word.ends_with("'s") && self.words.contains(&word[..word.len() - 2]))
+    }
+}
+
+/// Finds words in comment and string tokens that aren't in the dictionary,
+/// returning their ranges in document order. Code is never checked, and
+/// words that look like identifiers (e.g. snake_case, camelCase, or those
+/// containing digits) are skipped even when they appear in prose.
+pub fn misspellings<'a, T>(tokens: T, dictionary: &Dictionary) -> Vec<Range>
+    where T: Iterator<Item = Token<'a>>
+{
+    misspellings_between(tokens, dictionary, 0, usize::max_value())
+}
+
+/// Like `misspellings`, but only finds words on the specified lines
+/// (inclusive), and stops consuming tokens once they're past them.
+pub fn misspellings_between<'a, T>(tokens: T, dictionary: &Dictionary, first_line: usize, last_line: usize) -> Vec<Range>
+    where T: Iterator<Item = Token<'a>>
+{
+    let selectors = ScopeSelectors::from_str(CHECKED_SCOPES).unwrap();
+    let mut ranges = Vec::new();
+
+    for token in tokens {
+        if let Token::Lexeme(lexeme) = token {
+            if lexeme.position.line > last_line {
+                break;
+            }
+            if selectors.does_match(lexeme.scope.as_slice()).is_none() {
+                continue;
+            }
+
+            for (word, start, end) in words(lexeme.value, &lexeme.position) {
+                if end.line < first_line || start.line > last_line {
+                    continue;
+                }
+                if is_checkable(word) && !dictionary.contains(word) {
+                    ranges.push(Range::new(start, end));
+                }
+            }
+        }
+    }
+
+    ranges
+}
+
+/// Splits content into whitespace and punctuation-delimited words, along
+/// with their start and end positions, relative to the content's position.
+fn words<'a>(content: &'a str, position: &Position) -> Vec<(&'a str, Position, Position)> {
+    let mut spans = Vec::new();
+    let mut current = *position;
+    let mut word_start: Option<(usize, Position)> = None;
+
+    for (index, grapheme) in content.grapheme_indices(true) {
+        if is_word_grapheme(grapheme) {
+            if word_start.is_none() {
+                word_start = Some((index, current));
+            }
+        } else if let Some((start_index, start)) = word_start.take() {
+            spans.push((trim_word(&content[start_index..index]), start, current));
+        }
+
+        if grapheme == "\n" {
+            current = Position{ line: current.line + 1, offset: 0 };
+        } else {
+            current.offset += 1;
+        }
+    }
+
+    if let Some((start_index, start)) = word_start {
+        spans.push((trim_word(&content[start_index..]), start, current));
+    }
+
+    // Leading/trailing apostrophes are quotes, not part of the word;
+    // shift ranges accordingly so that they only cover the word itself.
+    spans.into_iter().filter_map(|((trimmed, leading, trailing), start, end)| {
+        if trimmed.is_empty() {
+            return None;
+        }
+
+        Some((
+            trimmed,
+            Position{ line: start.line, offset: start.offset + leading },
+            Position{ line: end.line, offset: end.offset - trailing },
+        ))
+    }).collect()
+}
+
+fn trim_word(word: &str) -> (&str, usize, usize) {
+    let trimmed_start = word.trim_left_matches('\'');
+    let leading = word.len() - trimmed_start.len();
+    let trimmed = trimmed_start.trim_right_matches('\'');
+    let trailing = trimmed_start.len() - trimmed.len();
+
+    (trimmed, leading, trailing)
+}
+
+fn is_word_grapheme(grapheme: &str) -> bool {
+    grapheme == "'" || grapheme == "_" || grapheme.chars().all(|c| c.is_alphanumeric())
+}
+
+/// Words containing digits or underscores, or with capitals beyond
+/// the first letter, are treated as code references and skipped.
+fn is_checkable(word: &str) -> bool {
+    word.chars().count() > 1 &&
+        !word.chars().any(|c| c.is_numeric() || c == '_') &&
+        !word.chars().skip(1).any(|c| c.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{misspellings, misspellings_between, Dictionary};
+    use scribe::buffer::{Lexeme, Position, Range, ScopeStack, Token};
+    use std::str::FromStr;
+
+    fn dictionary() -> Dictionary {
+        Dictionary::new(vec!["a", "this", "is", "comment", "string", "word"])
+    }
+
+    #[test]
+    fn misspellings_flags_unknown_words_in_comments() {
+        let tokens = vec![
+            Token::Lexeme(Lexeme{
+                value: "// this is a commnet",
+                position: Position{ line: 1, offset: 4 },
+                scope: ScopeStack::from_str("source.rust comment.line.double-slash.rust").unwrap()
+            })
+        ];
+
+        assert_eq!(
+            misspellings(tokens.into_iter(), &dictionary()),
+            vec![Range::new(Position{ line: 1, offset: 17 }, Position{ line: 1, offset: 24 })]
+        );
+    }
+
+    #[test]
+    fn misspellings_flags_unknown_words_in_strings() {
+        let tokens = vec![
+            Token::Lexeme(Lexeme{
+                value: "\"a strng\"",
+                position: Position{ line: 0, offset: 0 },
+                scope: ScopeStack::from_str("source.rust string.quoted.double.rust").unwrap()
+            })
+        ];
+
+        assert_eq!(
+            misspellings(tokens.into_iter(), &dictionary()),
+            vec![Range::new(Position{ line: 0, offset: 3 }, Position{ line: 0, offset: 8 })]
+        );
+    }
+
+    #[test]
+    fn misspellings_never_flags_code_tokens() {
+        let tokens = vec![
+            Token::Lexeme(Lexeme{
+                value: "fn",
+                position: Position{ line: 0, offset: 0 },
+                scope: ScopeStack::from_str("source.rust storage.type.function.rust").unwrap()
+            }),
+            Token::Lexeme(Lexeme{
+                value: "frobnicate",
+                position: Position{ line: 0, offset: 3 },
+                scope: ScopeStack::from_str("source.rust entity.name.function.rust").unwrap()
+            })
+        ];
+
+        assert!(misspellings(tokens.into_iter(), &dictionary()).is_empty());
+    }
+
+    #[test]
+    fn misspellings_skips_identifiers_within_comments() {
+        let tokens = vec![
+            Token::Lexeme(Lexeme{
+                value: "// this is_word isWord word2 word's 'word'",
+                position: Position{ line: 0, offset: 0 },
+                scope: ScopeStack::from_str("source.rust comment.line.double-slash.rust").unwrap()
+            })
+        ];
+
+        assert!(misspellings(tokens.into_iter(), &dictionary()).is_empty());
+    }
+
+    #[test]
+    fn misspellings_between_only_flags_words_on_the_specified_lines() {
+        let comment = |value: &'static str, line: usize| Token::Lexeme(Lexeme{
+            value,
+            position: Position{ line, offset: 0 },
+            scope: ScopeStack::from_str("source.rust comment.line.double-slash.rust").unwrap()
+        });
+        let tokens = vec![comment("// commnet", 0), comment("// strng", 1), comment("// wrod", 2)];
+
+        assert_eq!(
+            misspellings_between(tokens.into_iter(), &dictionary(), 1, 1),
+            vec![Range::new(Position{ line: 1, offset: 3 }, Position{ line: 1, offset: 8 })]
+        );
+    }
+
+    #[test]
+    fn dictionary_is_case_insensitive() {
+        assert!(dictionary().contains("This"));
+        assert!(Dictionary::new(vec!["Word"]).contains("word"));
+    }
+}
@@ -0,0 +1,53 @@
+use git2::{Repository, Signature};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// A fresh directory for a test's files, unique to the test and process,
+/// which is removed (along with its contents) once it's dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        let path = env::temp_dir().join(format!("amp-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        // Buffers track canonical paths; match them.
+        TempDir{ path: path.canonicalize().unwrap() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn join<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.path.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Initializes a repository in the directory, writing the content to the file
+/// at the specified path within it, and committing that as the first commit.
+pub fn commit_file(dir: &TempDir, path: &str, content: &str) -> Repository {
+    fs::write(dir.join(path), content).unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    {
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Amp", "amp@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial commit", &tree, &[]).unwrap();
+    }
+
+    repo
+}
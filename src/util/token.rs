@@ -1,6 +1,14 @@
 use util::movement_lexer;
 use scribe::buffer::{Buffer, Position};
-use luthor::token::Category;
+use luthor::token::{Category, Token};
+use std::iter;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The number of lines on either side of the cursor that are tokenized when
+/// looking for an adjacent token. The window is doubled whenever a token can't
+/// be found within it, so that large buffers don't need to be tokenized in
+/// their entirety to move between neighbouring tokens.
+const TOKEN_WINDOW_SIZE: usize = 100;
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum Direction {
@@ -12,19 +20,104 @@ pub fn adjacent_token_position(buffer: &mut Buffer,
                            whitespace: bool,
                            direction: Direction)
                            -> Option<(Position)> {
+    adjacent_token_position_using(buffer, whitespace, direction, movement_lexer::lex)
+}
+
+/// Finds the start of the adjacent word, where a word is any run of
+/// non-whitespace characters (e.g. `foo.bar`), rather than a lexed token.
+pub fn adjacent_word_position(buffer: &mut Buffer, direction: Direction) -> Option<Position> {
+    adjacent_token_position_using(buffer, false, direction, lex_words)
+}
+
+/// Splits data into alternating runs of whitespace and non-whitespace
+/// characters, without separating punctuation from the text around it.
+fn lex_words(data: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+
+    for character in data.chars() {
+        let category = if character.is_whitespace() {
+            Category::Whitespace
+        } else {
+            Category::Text
+        };
+
+        // Extend the current run if the character belongs to it.
+        if let Some(token) = tokens.last_mut() {
+            if token.category == category {
+                token.lexeme.push(character);
+                continue;
+            }
+        }
+        tokens.push(Token{ lexeme: character.to_string(), category });
+    }
+
+    tokens
+}
+
+/// Tokenizes a window of lines around the cursor using the provided lexer,
+/// widening the window until an adjacent token is found, or until the window
+/// covers the entire buffer.
+fn adjacent_token_position_using<F>(buffer: &mut Buffer,
+                                    whitespace: bool,
+                                    direction: Direction,
+                                    mut lex: F)
+                                    -> Option<Position>
+                                    where F: FnMut(&str) -> Vec<Token> {
+    let data = buffer.data();
+    let last_line = data.matches('\n').count();
+    let cursor = *buffer.cursor;
+    let mut window_size = TOKEN_WINDOW_SIZE;
+
+    loop {
+        let start_line = cursor.line.checked_sub(window_size).unwrap_or(0);
+        let end_line = cursor.line + window_size;
+        let covers_buffer = start_line == 0 && end_line >= last_line;
+
+        // Find the token relative to the window, and then
+        // translate it back to an absolute buffer position.
+        let window_cursor = Position {
+            line: cursor.line - start_line,
+            offset: cursor.offset,
+        };
+        let position = find_adjacent_token(
+            lex(line_window(&data, start_line, end_line)),
+            window_cursor,
+            whitespace,
+            direction
+        ).map(|position| Position {
+            line: position.line + start_line,
+            offset: position.offset,
+        });
+
+        match position {
+            // Tokens on the first line of a window may have started on a
+            // preceding line; only trust those when there's nothing above.
+            Some(position) if start_line == 0 || position.line > start_line => {
+                return Some(position);
+            }
+            _ if covers_buffer => return position,
+            _ => window_size *= 2,
+        }
+    }
+}
+
+fn find_adjacent_token(tokens: Vec<Token>,
+                       cursor: Position,
+                       whitespace: bool,
+                       direction: Direction)
+                       -> Option<Position> {
     let mut line = 0;
     let mut offset = 0;
     let mut previous_position = Position {
         line: 0,
         offset: 0,
     };
-    let tokens = movement_lexer::lex(&buffer.data());
     for token in tokens {
         let position = Position {
             line,
             offset,
         };
-        if position > *buffer.cursor && direction == Direction::Forward {
+        if position > cursor && direction == Direction::Forward {
             // We've found the next token!
             if whitespace {
                 // We're allowing whitespace, so return the token.
@@ -62,7 +155,7 @@ pub fn adjacent_token_position(buffer: &mut Buffer,
             line,
             offset,
         };
-        if next_position >= *buffer.cursor && direction == Direction::Backward {
+        if next_position >= cursor && direction == Direction::Backward {
             match token.category {
                 Category::Whitespace => {
                     return Some(previous_position);
@@ -81,3 +174,149 @@ pub fn adjacent_token_position(buffer: &mut Buffer,
     None
 }
 
+/// Finds the position of the last character of the token at or after the
+/// cursor, skipping whitespace. If the cursor is already on the last character
+/// of a token, the end of the following token is returned instead.
+pub fn token_end_position(buffer: &Buffer) -> Option<Position> {
+    let data = buffer.data();
+    let last_line = data.matches('\n').count();
+    let cursor = *buffer.cursor;
+    let mut window_size = TOKEN_WINDOW_SIZE;
+
+    loop {
+        // Tokens preceding the cursor line are irrelevant,
+        // so the window only needs to extend forward.
+        let end_line = cursor.line + window_size;
+        let window_cursor = Position {
+            line: 0,
+            offset: cursor.offset,
+        };
+        let position = find_token_end(
+            movement_lexer::lex(line_window(&data, cursor.line, end_line)),
+            window_cursor
+        ).map(|position| Position {
+            line: position.line + cursor.line,
+            offset: position.offset,
+        });
+
+        if position.is_some() || end_line >= last_line {
+            return position;
+        }
+        window_size *= 2;
+    }
+}
+
+fn find_token_end(tokens: Vec<Token>, cursor: Position) -> Option<Position> {
+    let mut line = 0;
+    let mut offset = 0;
+
+    for token in tokens {
+        // Advance to the end of the token. Offsets are
+        // grapheme indices, so count those rather than bytes.
+        match token.lexeme.split('\n').count() {
+            1 => offset += token.lexeme.graphemes(true).count(),
+            n => {
+                line += n - 1;
+                offset = token.lexeme.split('\n').last().unwrap().graphemes(true).count();
+            }
+        };
+
+        if let Category::Whitespace = token.category {
+            continue;
+        }
+
+        // Step back onto the token's last grapheme.
+        let end = Position {
+            line,
+            offset: offset.saturating_sub(1),
+        };
+        if end > cursor {
+            return Some(end);
+        }
+    }
+
+    None
+}
+
+/// Returns the slice of data spanning the specified (inclusive) line range.
+pub fn line_window(data: &str, start_line: usize, end_line: usize) -> &str {
+    let mut line_starts = iter::once(0).chain(
+        data.match_indices('\n').map(|(index, _)| index + 1)
+    );
+    let start = line_starts.nth(start_line).unwrap_or(data.len());
+    let end = line_starts.nth(end_line - start_line).unwrap_or(data.len());
+
+    &data[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+    use super::Direction;
+    use luthor::token::{Category, Token};
+    use util::movement_lexer;
+
+    #[test]
+    fn adjacent_token_position_only_tokenizes_lines_near_the_cursor() {
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor\n".repeat(10000));
+        buffer.cursor.move_to(Position{ line: 5000, offset: 0 });
+
+        // Track the amount of content handed to the lexer.
+        let mut lexed_length = 0;
+        let position = super::adjacent_token_position_using(
+            &mut buffer,
+            false,
+            Direction::Forward,
+            |data| {
+                lexed_length += data.len();
+                movement_lexer::lex(data)
+            }
+        );
+
+        assert_eq!(position, Some(Position{ line: 5000, offset: 4 }));
+        assert!(lexed_length < buffer.data().len() / 10);
+    }
+
+    #[test]
+    fn adjacent_token_position_widens_its_window_to_find_distant_tokens() {
+        let mut buffer = Buffer::new();
+        buffer.insert(format!("amp{}editor", "\n".repeat(500)));
+
+        let position = super::adjacent_token_position(&mut buffer, false, Direction::Forward);
+
+        assert_eq!(position, Some(Position{ line: 500, offset: 0 }));
+    }
+
+    #[test]
+    fn lex_words_only_splits_on_whitespace() {
+        assert_eq!(
+            super::lex_words("foo.bar  baz\n"),
+            vec![
+                Token{ lexeme: "foo.bar".to_string(), category: Category::Text },
+                Token{ lexeme: "  ".to_string(), category: Category::Whitespace },
+                Token{ lexeme: "baz".to_string(), category: Category::Text },
+                Token{ lexeme: "\n".to_string(), category: Category::Whitespace },
+            ]
+        );
+    }
+
+    #[test]
+    fn token_end_position_skips_whitespace_and_finds_following_token_end() {
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n\n  editor");
+        buffer.cursor.move_to(Position{ line: 0, offset: 2 });
+
+        assert_eq!(super::token_end_position(&buffer), Some(Position{ line: 2, offset: 7 }));
+    }
+
+    #[test]
+    fn token_end_position_counts_multibyte_characters_as_single_offsets() {
+        let mut buffer = Buffer::new();
+        buffer.insert("café e\u{301}tude");
+        buffer.cursor.move_to(Position{ line: 0, offset: 4 });
+
+        assert_eq!(super::token_end_position(&buffer), Some(Position{ line: 0, offset: 9 }));
+    }
+}
@@ -0,0 +1,131 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+const URL_PREFIXES: [&str; 3] = ["https://", "http://", "www."];
+
+/// Finds the URL spanning the specified (grapheme) offset in a line,
+/// recognizing `http(s)://` and `www.` forms. The latter are expanded
+/// to a full `http://` URL, so that they can be handed to a browser.
+pub fn url_at(line: &str, offset: usize) -> Option<String> {
+    // Translate the grapheme offset to a byte index.
+    let index = line.grapheme_indices(true).nth(offset).map(|(index, _)| index)?;
+    let mut search_start = 0;
+
+    while let Some((start, prefix)) = next_prefix(line, search_start) {
+        let end = url_end(line, start);
+        if index >= start && index < end {
+            let url = &line[start..end];
+
+            return if prefix == "www." {
+                Some(format!("http://{}", url))
+            } else {
+                Some(url.to_string())
+            };
+        }
+        search_start = end.max(start + prefix.len());
+    }
+
+    None
+}
+
+/// Finds the earliest URL prefix at or after the specified byte index.
+/// Prefixes preceded by a word character (e.g. "awww.") are ignored.
+fn next_prefix(line: &str, from: usize) -> Option<(usize, &'static str)> {
+    let lowercase = line.to_lowercase();
+    let mut search_start = from;
+
+    // Lowercasing can change byte lengths for non-ASCII content; fall back
+    // to a case-sensitive search rather than using mismatched indices.
+    let haystack = if lowercase.len() == line.len() { lowercase.as_str() } else { line };
+
+    while search_start < haystack.len() {
+        let (start, prefix) = URL_PREFIXES
+            .iter()
+            .filter_map(|prefix| {
+                haystack[search_start..].find(prefix).map(|index| (search_start + index, *prefix))
+            })
+            .min_by_key(|&(index, _)| index)?;
+        let preceded_by_word = line[..start]
+            .chars()
+            .next_back()
+            .map(|c| c.is_alphanumeric() || c == '/' || c == '.')
+            .unwrap_or(false);
+
+        if !preceded_by_word {
+            return Some((start, prefix));
+        }
+        search_start = start + prefix.len();
+    }
+
+    None
+}
+
+/// Finds the end of the URL starting at the specified byte index, excluding
+/// trailing punctuation (e.g. a sentence-ending period) and unbalanced
+/// closing brackets (e.g. a URL wrapped in parentheses).
+fn url_end(line: &str, start: usize) -> usize {
+    let mut end = line[start..]
+        .find(|c: char| c.is_whitespace() || "\"'<>`".contains(c))
+        .map(|index| start + index)
+        .unwrap_or_else(|| line.len());
+
+    loop {
+        let url = &line[start..end];
+        let trailing = match url.chars().next_back() {
+            Some(c) => c,
+            None => break,
+        };
+        let trim = match trailing {
+            '.' | ',' | ';' | ':' | '!' | '?' => true,
+            ')' => url.matches(')').count() > url.matches('(').count(),
+            ']' => url.matches(']').count() > url.matches('[').count(),
+            '}' => url.matches('}').count() > url.matches('{').count(),
+            _ => false,
+        };
+        if !trim {
+            break;
+        }
+        end -= trailing.len_utf8();
+    }
+
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::url_at;
+
+    #[test]
+    fn url_at_excludes_surrounding_punctuation() {
+        let line = "See (https://github.com/jmacdonald/amp), or \"http://amp.rs/docs\".";
+
+        assert_eq!(url_at(line, 6), Some("https://github.com/jmacdonald/amp".to_string()));
+        assert_eq!(url_at(line, 50), Some("http://amp.rs/docs".to_string()));
+    }
+
+    #[test]
+    fn url_at_keeps_balanced_brackets() {
+        let line = "[wiki](https://en.wikipedia.org/wiki/Amp_(editor)).";
+
+        assert_eq!(url_at(line, 20), Some("https://en.wikipedia.org/wiki/Amp_(editor)".to_string()));
+    }
+
+    #[test]
+    fn url_at_expands_www_urls() {
+        assert_eq!(url_at("visit www.amp.rs, today", 8), Some("http://www.amp.rs".to_string()));
+    }
+
+    #[test]
+    fn url_at_returns_none_outside_of_urls() {
+        let line = "See https://amp.rs for details";
+
+        assert_eq!(url_at(line, 1), None);
+        assert_eq!(url_at(line, 3), None);
+        assert_eq!(url_at(line, 20), None);
+        assert_eq!(url_at(line, 100), None);
+    }
+
+    #[test]
+    fn url_at_uses_grapheme_offsets() {
+        assert_eq!(url_at("ünïcödé https://amp.rs", 10), Some("https://amp.rs".to_string()));
+    }
+}
@@ -0,0 +1,125 @@
+use errors::*;
+use std::collections::hash_map::RandomState;
+use std::fs::File;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Read;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const RANDOM_DEVICE: &str = "/dev/urandom";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UuidFormat {
+    /// e.g. 0f8fad5b-d9cb-469f-a165-70867728950e
+    Hyphenated,
+    /// e.g. 0f8fad5bd9cb469fa16570867728950e
+    Plain,
+}
+
+/// A source of random bytes, injectable so that generated IDs can be tested.
+pub trait RandomSource {
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<()>;
+}
+
+/// Reads from the system's random device, falling back to std's randomly
+/// keyed hasher where one isn't available. Neither is suitable for secrets,
+/// but both are more than adequate for generating unique identifiers.
+pub struct SystemRandom;
+
+impl RandomSource for SystemRandom {
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<()> {
+        if let Ok(mut device) = File::open(RANDOM_DEVICE) {
+            if device.read_exact(bytes).is_ok() {
+                return Ok(());
+            }
+        }
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.subsec_nanos())
+            .unwrap_or(0);
+        for (index, chunk) in bytes.chunks_mut(8).enumerate() {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u32(nanos);
+            hasher.write_usize(index);
+            let value = hasher.finish();
+            for (offset, byte) in chunk.iter_mut().enumerate() {
+                *byte = (value >> (offset * 8)) as u8;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates a random (version 4) UUID in the specified format.
+pub fn generate<R: RandomSource>(source: &mut R, format: UuidFormat) -> Result<String> {
+    let mut bytes = [0u8; 16];
+    source.fill(&mut bytes).chain_err(|| "Couldn't generate random bytes for UUID")?;
+
+    // Set the version (4) and variant (RFC 4122) bits.
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let uuid = match format {
+        UuidFormat::Hyphenated => format!(
+            "{}-{}-{}-{}-{}",
+            hex[0..4].concat(),
+            hex[4..6].concat(),
+            hex[6..8].concat(),
+            hex[8..10].concat(),
+            hex[10..16].concat()
+        ),
+        UuidFormat::Plain => hex.concat(),
+    };
+
+    Ok(uuid)
+}
+
+/// Fills requested bytes with a fixed value, for deterministic output.
+#[cfg(test)]
+pub struct FixedRandom(pub u8);
+
+#[cfg(test)]
+impl RandomSource for FixedRandom {
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<()> {
+        for byte in bytes.iter_mut() {
+            *byte = self.0;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, FixedRandom, SystemRandom, UuidFormat};
+
+    #[test]
+    fn generate_sets_version_and_variant_bits() {
+        assert_eq!(
+            generate(&mut FixedRandom(0xff), UuidFormat::Hyphenated).unwrap(),
+            "ffffffff-ffff-4fff-bfff-ffffffffffff"
+        );
+        assert_eq!(
+            generate(&mut FixedRandom(0x00), UuidFormat::Hyphenated).unwrap(),
+            "00000000-0000-4000-8000-000000000000"
+        );
+    }
+
+    #[test]
+    fn generate_supports_plain_format() {
+        assert_eq!(
+            generate(&mut FixedRandom(0xab), UuidFormat::Plain).unwrap(),
+            "abababababab4bababababababababab"
+        );
+    }
+
+    #[test]
+    fn generate_produces_distinct_ids_using_system_randomness() {
+        let first = generate(&mut SystemRandom, UuidFormat::Plain).unwrap();
+        let second = generate(&mut SystemRandom, UuidFormat::Plain).unwrap();
+
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use syntect::highlighting::Style;
+use view::buffer::RenderState;
+
+/// Identifies the conditions under which cached highlighting was produced.
+/// Scrolling, resizing, or changing themes will produce a different key,
+/// at which point previously highlighted lines are discarded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighlightKey {
+    pub scroll_offset: usize,
+    pub width: usize,
+    pub height: usize,
+    pub theme: String,
+}
+
+/// Styled lexemes for the visible lines of a buffer, allowing consecutive
+/// renders of an unchanged buffer to skip parsing and highlighting entirely.
+/// Along with the lexemes, the cache tracks the render state following the
+/// last highlighted line, so that rendering can resume beyond the cached
+/// lines if more of the buffer becomes visible.
+#[derive(Default)]
+pub struct HighlightCache {
+    key: Option<HighlightKey>,
+    lines: HashMap<usize, Vec<(Style, String)>>,
+    resume_state: Option<(usize, RenderState)>,
+    highlight_count: usize,
+}
+
+impl HighlightCache {
+    pub fn new() -> HighlightCache {
+        HighlightCache::default()
+    }
+
+    /// Prepares the cache for use with the specified key, discarding
+    /// its contents if they were produced with a different key.
+    pub fn prepare(&mut self, key: HighlightKey) {
+        if self.key.as_ref() != Some(&key) {
+            self.invalidate();
+            self.key = Some(key);
+        }
+    }
+
+    /// Discards all cached lexemes and render state.
+    pub fn invalidate(&mut self) {
+        self.key = None;
+        self.lines.clear();
+        self.resume_state = None;
+    }
+
+    pub fn line(&self, line: usize) -> Option<&Vec<(Style, String)>> {
+        self.lines.get(&line)
+    }
+
+    pub fn insert(&mut self, line: usize, lexemes: Vec<(Style, String)>) {
+        self.highlight_count += 1;
+        self.lines.insert(line, lexemes);
+    }
+
+    /// The line following the cached lines, paired with
+    /// the render state required to continue from it.
+    pub fn resume_state(&self) -> Option<&(usize, RenderState)> {
+        self.resume_state.as_ref()
+    }
+
+    pub fn set_resume_state(&mut self, line: usize, state: RenderState) {
+        self.resume_state = Some((line, state));
+    }
+
+    /// The number of lines that have been highlighted and added to the cache.
+    #[cfg(test)]
+    pub fn highlight_count(&self) -> usize {
+        self.highlight_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HighlightCache, HighlightKey};
+    use syntect::highlighting::{Color, FontStyle, Style};
+
+    fn key(scroll_offset: usize) -> HighlightKey {
+        HighlightKey {
+            scroll_offset,
+            width: 10,
+            height: 10,
+            theme: String::from("solarized_dark"),
+        }
+    }
+
+    fn style() -> Style {
+        Style {
+            foreground: Color::BLACK,
+            background: Color::WHITE,
+            font_style: FontStyle::empty(),
+        }
+    }
+
+    #[test]
+    fn prepare_retains_lines_when_key_is_unchanged() {
+        let mut cache = HighlightCache::new();
+        cache.prepare(key(0));
+        cache.insert(0, vec![(style(), String::from("amp"))]);
+        cache.prepare(key(0));
+
+        assert!(cache.line(0).is_some());
+    }
+
+    #[test]
+    fn prepare_discards_lines_when_key_changes() {
+        let mut cache = HighlightCache::new();
+        cache.prepare(key(0));
+        cache.insert(0, vec![(style(), String::from("amp"))]);
+        cache.prepare(key(10));
+
+        assert!(cache.line(0).is_none());
+    }
+}
@@ -3,19 +3,56 @@ use std::iter::Iterator;
 
 pub const PADDING_WIDTH: usize = 2;
 
+/// The ways in which line numbers can be displayed in the gutter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineNumbering {
+    /// Every line shows its own (one-based) line number.
+    Absolute,
+    /// Every line shows its distance from the cursor line.
+    Relative,
+    /// Like relative numbering, but the cursor line shows its own number.
+    Hybrid,
+}
+
+impl LineNumbering {
+    /// The numbering that follows this one when cycling through them.
+    pub fn next(&self) -> LineNumbering {
+        match *self {
+            LineNumbering::Absolute => LineNumbering::Relative,
+            LineNumbering::Relative => LineNumbering::Hybrid,
+            LineNumbering::Hybrid => LineNumbering::Absolute,
+        }
+    }
+}
+
+impl Default for LineNumbering {
+    fn default() -> LineNumbering {
+        LineNumbering::Absolute
+    }
+}
+
 pub struct LineNumbers {
     current_number: usize,
+    cursor_number: usize,
     buffer_line_count_width: usize,
+    numbering: LineNumbering,
 }
 
 impl LineNumbers {
     pub fn new(buffer: &Buffer, offset: Option<usize>) -> LineNumbers {
         LineNumbers{
             current_number: offset.unwrap_or(0),
-            buffer_line_count_width: buffer.line_count().to_string().len()
+            cursor_number: buffer.cursor.line + 1,
+            buffer_line_count_width: buffer.line_count().to_string().len(),
+            numbering: LineNumbering::default(),
         }
     }
 
+    pub fn with_numbering(mut self, numbering: LineNumbering) -> LineNumbers {
+        self.numbering = numbering;
+        self
+    }
+
     pub fn width(&self) -> usize {
         self.buffer_line_count_width + PADDING_WIDTH
     }
@@ -26,10 +63,22 @@ impl Iterator for LineNumbers {
 
     fn next(&mut self) -> Option<String> {
         self.current_number += 1;
+        let distance = if self.current_number > self.cursor_number {
+            self.current_number - self.cursor_number
+        } else {
+            self.cursor_number - self.current_number
+        };
+        let number = match self.numbering {
+            LineNumbering::Absolute => self.current_number,
+            LineNumbering::Relative => distance,
+            LineNumbering::Hybrid if distance == 0 => self.current_number,
+            LineNumbering::Hybrid => distance,
+        };
+
         Some(
             format!(
                 " {:>width$} ",
-                self.current_number,
+                number,
                 width = self.buffer_line_count_width
             )
         )
@@ -108,4 +157,28 @@ mod tests {
         let mut line_numbers = LineNumbers::new(&buffer, None);
         assert_eq!(line_numbers.next().unwrap(), "   1 ");
     }
+
+    #[test]
+    fn relative_line_numbers_count_from_the_cursor_line() {
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc\nd");
+        buffer.cursor.move_down();
+        let line_numbers = LineNumbers::new(&buffer, None)
+            .with_numbering(LineNumbering::Relative);
+        let numbers: Vec<String> = line_numbers.take(4).collect();
+
+        assert_eq!(numbers, vec![" 1 ", " 0 ", " 1 ", " 2 "]);
+    }
+
+    #[test]
+    fn hybrid_line_numbers_show_the_cursor_line_number() {
+        let mut buffer = Buffer::new();
+        buffer.insert("a\nb\nc\nd");
+        buffer.cursor.move_down();
+        let line_numbers = LineNumbers::new(&buffer, None)
+            .with_numbering(LineNumbering::Hybrid);
+        let numbers: Vec<String> = line_numbers.take(4).collect();
+
+        assert_eq!(numbers, vec![" 1 ", " 2 ", " 1 ", " 2 "]);
+    }
 }
@@ -1,3 +1,4 @@
+mod highlight_cache;
 mod renderer;
 mod render_cache;
 mod render_state;
@@ -5,9 +6,10 @@ mod lexeme_mapper;
 mod line_numbers;
 mod scrollable_region;
 
+pub use self::highlight_cache::{HighlightCache, HighlightKey};
 pub use self::renderer::BufferRenderer;
 pub use self::render_cache::RenderCache;
 pub use self::render_state::RenderState;
 pub use self::lexeme_mapper::{LexemeMapper, MappedLexeme};
-pub use self::line_numbers::LineNumbers;
+pub use self::line_numbers::{LineNumbering, LineNumbers};
 pub use self::scrollable_region::ScrollableRegion;
@@ -4,14 +4,20 @@ use syntect::parsing::{ParseState, ScopeStack, SyntaxDefinition};
 #[derive(Clone, Debug, PartialEq)]
 pub struct RenderState {
     pub highlight: HighlightState,
-    pub parse: ParseState
+    pub parse: ParseState,
+
+    /// Whether parsing started partway through the buffer, rather than from
+    /// its first line or a cached state, in which case constructs opened
+    /// earlier on (e.g. a long block comment) may be misrepresented.
+    pub approximate: bool
 }
 
 impl RenderState {
     pub fn new(highlighter: &Highlighter, syntax: &SyntaxDefinition) -> RenderState {
         RenderState{
             highlight: HighlightState::new(&highlighter, ScopeStack::new()),
-            parse: ParseState::new(syntax)
+            parse: ParseState::new(syntax),
+            approximate: false
         }
     }
 }
@@ -1,37 +1,48 @@
 use models::application::Preferences;
-use scribe::buffer::{Buffer, Position, Range};
+use scribe::buffer::{Buffer, LineRange, Position, Range};
 use scribe::util::LineIterator;
-use view::buffer::{LexemeMapper, MappedLexeme, RenderState};
+use view::buffer::{HighlightCache, HighlightKey, LexemeMapper, MappedLexeme, RenderState};
 use view::buffer::line_numbers::*;
-use view::{Colors, RENDER_CACHE_FREQUENCY, RGBColor, Style};
+use view::{Colors, RENDER_CACHE_FREQUENCY, RGBColor, Style, TOKENIZATION_MARGIN};
 use view::color::ColorMap;
 use view::color::to_rgb_color;
 use view::terminal::Terminal;
 use std::cell::RefCell;
+use std::cmp;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::str::FromStr;
 use syntect::highlighting::{Highlighter, HighlightIterator, Theme};
 use syntect::highlighting::Style as ThemeStyle;
 use syntect::parsing::ScopeStack;
+use util::bracket::{self, BracketMatch};
 use errors::*;
 
 /// A one-time-use type that encapsulates all of the
 /// details involved in rendering a buffer to the screen.
 pub struct BufferRenderer<'a, 'b> {
+    annotations: Option<&'a [String]>,
+    bracket_match: Option<BracketMatch>,
     buffer: &'a Buffer,
     buffer_position: Position,
+    column_offset: usize,
+    content_width: Option<usize>,
     cursor_position: Option<Position>,
+    folds: Option<&'a [LineRange]>,
     gutter_width: usize,
+    highlight_cache: &'a Rc<RefCell<HighlightCache>>,
     highlights: Option<&'a [Range]>,
     stylist: Highlighter<'a>,
     current_style: ThemeStyle,
     lexeme_mapper: Option<&'b mut LexemeMapper>,
     line_numbers: LineNumbers,
+    line_numbers_visible: bool,
+    misspellings: Option<&'a [Range]>,
     preferences: &'a Preferences,
     render_cache: &'a Rc<RefCell<HashMap<usize, RenderState>>>,
     screen_position: Position,
     scroll_offset: usize,
+    reserved_columns: usize,
     terminal: &'a Terminal,
     theme: &'a Theme,
 }
@@ -40,7 +51,8 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
     pub fn new(buffer: &'a Buffer, highlights: Option<&'a [Range]>,
     lexeme_mapper: Option<&'b mut LexemeMapper>, scroll_offset: usize,
     terminal: &'a Terminal, theme: &'a Theme, preferences: &'a Preferences,
-    render_cache: &'a Rc<RefCell<HashMap<usize, RenderState>>>) -> BufferRenderer<'a, 'b> {
+    render_cache: &'a Rc<RefCell<HashMap<usize, RenderState>>>,
+    highlight_cache: &'a Rc<RefCell<HighlightCache>>) -> BufferRenderer<'a, 'b> {
         let line_numbers = LineNumbers::new(&buffer, Some(scroll_offset));
         let gutter_width = line_numbers.width() + 1;
 
@@ -50,34 +62,166 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
         let current_style = stylist.get_default();
 
         BufferRenderer{
+            annotations: None,
+            bracket_match: None,
             buffer,
+            column_offset: 0,
+            content_width: None,
             cursor_position: None,
+            folds: None,
             gutter_width,
+            highlight_cache,
             highlights,
             stylist,
             current_style,
             lexeme_mapper,
             line_numbers,
+            line_numbers_visible: true,
+            misspellings: None,
             buffer_position: Position{ line: 0, offset: 0 },
             preferences,
             render_cache,
             screen_position: Position{ line: 0, offset: 0 },
             scroll_offset,
+            reserved_columns: 0,
             terminal,
             theme,
         }
     }
 
+    /// Renders the provided per-line annotations (indexed by
+    /// buffer line) in a column preceding the line numbers.
+    pub fn with_annotations(mut self, annotations: &'a [String]) -> BufferRenderer<'a, 'b> {
+        self.gutter_width += self.annotation_width_for(annotations);
+        self.annotations = Some(annotations);
+        self
+    }
+
+    /// Numbers lines using the specified style (e.g. relative to the cursor).
+    pub fn with_line_numbering(mut self, numbering: LineNumbering) -> BufferRenderer<'a, 'b> {
+        self.line_numbers = self.line_numbers.with_numbering(numbering);
+        self
+    }
+
+    /// Skips the specified number of columns at the start of each line,
+    /// for use with horizontally scrolled (i.e. unwrapped) content.
+    pub fn with_column_offset(mut self, column_offset: usize) -> BufferRenderer<'a, 'b> {
+        self.column_offset = column_offset;
+        self
+    }
+
+    /// Hides the gutter and, if a width is provided, wraps content
+    /// to that width and centers it horizontally on the screen.
+    pub fn with_zen_mode(mut self, width: Option<usize>) -> BufferRenderer<'a, 'b> {
+        self.line_numbers_visible = false;
+        self.annotations = None;
+        self.gutter_width = width
+            .map(|width| self.screen_width().saturating_sub(width) / 2)
+            .unwrap_or(0);
+        self.content_width = width;
+        self
+    }
+
+    /// Leaves the rightmost columns free for other content
+    /// (e.g. a scrollbar or the outline pane).
+    pub fn with_reserved_columns(mut self, columns: usize) -> BufferRenderer<'a, 'b> {
+        self.reserved_columns = columns;
+        self
+    }
+
+    /// Underlines the provided ranges (e.g. misspelled words), which
+    /// must be sorted and non-overlapping.
+    pub fn with_misspellings(mut self, misspellings: &'a [Range]) -> BufferRenderer<'a, 'b> {
+        self.misspellings = Some(misspellings);
+        self
+    }
+
+    /// Collapses the provided line ranges, rendering each as its first line
+    /// followed by a summary. Folds containing the cursor are left expanded.
+    pub fn with_folds(mut self, folds: &'a [LineRange]) -> BufferRenderer<'a, 'b> {
+        self.folds = Some(folds);
+        self
+    }
+
+    fn annotation_width_for(&self, annotations: &[String]) -> usize {
+        annotations
+            .iter()
+            .map(|annotation| annotation.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The screen offset at which content wraps or is truncated.
+    fn content_end(&self) -> usize {
+        self.content_width
+            .map(|width| cmp::min(self.gutter_width + width, self.screen_width()))
+            .unwrap_or_else(|| self.screen_width())
+    }
+
+    /// The width of the screen, excluding any reserved columns.
+    fn screen_width(&self) -> usize {
+        self.terminal.width().saturating_sub(self.reserved_columns)
+    }
+
+    fn annotation_width(&self) -> usize {
+        self.annotations
+            .map(|annotations| self.annotation_width_for(annotations))
+            .unwrap_or(0)
+    }
+
+    /// The fold whose hidden lines include the specified line, provided
+    /// it's collapsed (i.e. the cursor isn't within its hidden lines).
+    fn collapsed_fold(&self, line: usize) -> Option<&LineRange> {
+        let cursor_line = self.buffer.cursor.line;
+
+        self.folds.and_then(|folds| {
+            folds.iter().find(|fold| {
+                fold.start() < line && line <= fold.end() &&
+                    !(fold.start() < cursor_line && cursor_line <= fold.end())
+            })
+        })
+    }
+
+    fn on_hidden_line(&self) -> bool {
+        self.collapsed_fold(self.buffer_position.line).is_some()
+    }
+
+    /// Follows the first line of a collapsed fold with
+    /// a summary of the number of lines it's hiding.
+    fn print_fold_summary(&mut self) {
+        let hidden_lines = match self.collapsed_fold(self.buffer_position.line + 1) {
+            Some(fold) if fold.start() == self.buffer_position.line => fold.end() - fold.start(),
+            _ => return,
+        };
+        let summary = format!(" ⋯ {} lines", hidden_lines);
+        let colors = self.theme.map_colors(Colors::Focused);
+
+        for character in summary.chars() {
+            if self.screen_position.offset >= self.content_end() + self.column_offset {
+                break;
+            }
+            self.print_content(Style::Default, colors, &character);
+            self.screen_position.offset += 1;
+        }
+    }
+
     fn on_cursor_line(&self) -> bool {
         self.buffer_position.line == self.buffer.cursor.line
     }
 
+    /// Whether the current line's content should be
+    /// rendered with the current line background color.
+    fn on_highlighted_line(&self) -> bool {
+        self.on_cursor_line() && self.preferences.highlight_current_line()
+    }
+
     fn print_rest_of_line(&mut self) {
-        let on_cursor_line = self.on_cursor_line();
+        let on_highlighted_line = self.on_highlighted_line();
         let guide_offset = self.length_guide_offset();
+        let start = self.visible_offset(self.screen_position.offset).unwrap_or(self.gutter_width);
 
-        for offset in self.screen_position.offset..self.terminal.width() {
-            let colors = if on_cursor_line || guide_offset.map(|go| go == offset).unwrap_or(false) {
+        for offset in start..self.screen_width() {
+            let colors = if on_highlighted_line || guide_offset.map(|go| go == offset).unwrap_or(false) {
                 Colors::Focused
             } else {
                 Colors::Blank
@@ -91,12 +235,36 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
     }
 
     fn length_guide_offset(&self) -> Option<usize> {
-        self.preferences.line_length_guide().map(|offset| self.gutter_width + offset)
+        self.preferences
+            .line_length_guide()
+            .and_then(|offset| self.visible_offset(self.gutter_width + offset))
+    }
+
+    /// Translates an unscrolled screen offset into its horizontally
+    /// scrolled equivalent, provided it hasn't been scrolled out of view.
+    fn visible_offset(&self, offset: usize) -> Option<usize> {
+        offset
+            .checked_sub(self.column_offset)
+            .and_then(|offset| if offset >= self.gutter_width { Some(offset) } else { None })
+    }
+
+    /// Prints a buffer character at the current screen position,
+    /// unless it has been scrolled out of view.
+    fn print_content(&self, style: Style, colors: Colors, character: &char) {
+        if let Some(offset) = self.visible_offset(self.screen_position.offset) {
+            self.terminal.print(
+                &Position{ line: self.screen_position.line, offset },
+                style,
+                colors,
+                character
+            );
+        }
     }
 
     fn advance_to_next_line(&mut self) {
-        if self.inside_visible_content() {
+        if self.inside_visible_content() && !self.on_hidden_line() {
             self.set_cursor();
+            self.print_fold_summary();
             self.print_rest_of_line();
 
             // It's important to only increase this once we've entered the
@@ -118,45 +286,78 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
     // which will compensate for scrolling, tab expansion, etc.
     fn set_cursor(&mut self) {
         if self.inside_visible_content() && *self.buffer.cursor == self.buffer_position {
-            self.cursor_position = Some(self.screen_position);
+            self.cursor_position = self
+                .visible_offset(self.screen_position.offset)
+                .map(|offset| Position{ line: self.screen_position.line, offset });
         }
     }
 
     fn current_char_style(&self, token_color: RGBColor) -> (Style, Colors) {
-        let (style, colors) = match self.highlights {
-            Some(highlight_ranges) => {
-                for range in highlight_ranges {
-                    if range.includes(&self.buffer_position) {
-                        // We're inside of one of the highlighted areas.
-                        // Return early with highlight colors.
-                        if range.includes(&self.buffer.cursor) {
-                            return (Style::Bold, self.theme.map_colors(Colors::SelectMode))
-                        } else {
-                            return (Style::Inverted, self.theme.map_colors(Colors::Default))
-                        }
+        if let Some(highlight_ranges) = self.highlights {
+            for range in highlight_ranges {
+                if range.includes(&self.buffer_position) {
+                    // We're inside of one of the highlighted areas.
+                    // Return early with highlight colors.
+                    if range.includes(&self.buffer.cursor) {
+                        return (Style::Bold, self.theme.map_colors(Colors::SelectMode))
+                    } else {
+                        return (Style::Inverted, self.theme.map_colors(Colors::Default))
                     }
                 }
-
-                // We aren't inside one of the highlighted areas.
-                // Fall back to other styling considerations.
-                if self.on_cursor_line() {
-                    (Style::Default, Colors::CustomFocusedForeground(token_color))
-                } else {
-                    (Style::Default, Colors::CustomForeground(token_color))
-                }
             }
-            None => {
-                if self.on_cursor_line() {
-                    (Style::Default, Colors::CustomFocusedForeground(token_color))
-                } else {
-                    (Style::Default, Colors::CustomForeground(token_color))
-                }
-            },
+        }
+
+        // We aren't inside one of the highlighted areas.
+        // Fall back to other styling considerations.
+        if let Some(bracket_style) = self.bracket_style() {
+            return bracket_style;
+        }
+        let colors = if self.on_highlighted_line() {
+            Colors::CustomFocusedForeground(token_color)
+        } else {
+            Colors::CustomForeground(token_color)
+        };
+        let style = if self.misspelled() {
+            Style::Underlined
+        } else {
+            Style::Default
         };
 
         (style, self.theme.map_colors(colors))
     }
 
+    /// Emphasizes the partner of the bracket under the cursor or,
+    /// if it doesn't have one, the bracket under the cursor itself.
+    fn bracket_style(&self) -> Option<(Style, Colors)> {
+        match self.bracket_match {
+            Some(BracketMatch::Matched(position)) if position == self.buffer_position => {
+                Some((Style::Bold, self.theme.map_colors(Colors::Inverted)))
+            }
+            Some(BracketMatch::Unmatched) if *self.buffer.cursor == self.buffer_position => {
+                Some((Style::Bold, self.theme.map_colors(Colors::Warning)))
+            }
+            _ => None,
+        }
+    }
+
+    fn misspelled(&self) -> bool {
+        self.misspellings
+            .map(|ranges| {
+                // Ranges are sorted and don't overlap, so only the last
+                // one starting at or before the position can include it.
+                let following = ranges
+                    .binary_search_by(|range| if range.start() <= self.buffer_position {
+                        cmp::Ordering::Less
+                    } else {
+                        cmp::Ordering::Greater
+                    })
+                    .unwrap_or_else(|index| index);
+
+                following > 0 && ranges[following - 1].includes(&self.buffer_position)
+            })
+            .unwrap_or(false)
+    }
+
     pub fn print_lexeme(&mut self, lexeme: &str) {
         for character in lexeme.chars() {
             // Ignore newline characters.
@@ -168,7 +369,7 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
             let token_color = to_rgb_color(self.current_style.foreground);
             let (style, color) = self.current_char_style(token_color);
 
-            if self.preferences.line_wrapping() && self.screen_position.offset == self.terminal.width() {
+            if self.preferences.line_wrapping() && self.screen_position.offset >= self.content_end() {
                 self.screen_position.line += 1;
                 self.screen_position.offset = self.gutter_width;
                 self.terminal.print(&self.screen_position, style, color, &character);
@@ -182,18 +383,18 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
                 let mut screen_tab_stop = buffer_tab_stop + self.gutter_width;
 
                 // Now that we know where we'd like to go, prevent it from being off-screen.
-                if screen_tab_stop > self.terminal.width() {
-                    screen_tab_stop = self.terminal.width();
+                if screen_tab_stop > self.content_end() + self.column_offset {
+                    screen_tab_stop = self.content_end() + self.column_offset;
                 }
 
                 // Print the sequence of spaces and move the offset accordingly.
                 for _ in self.screen_position.offset..screen_tab_stop {
-                    self.terminal.print(&self.screen_position, style, color, &' ');
+                    self.print_content(style, color, &' ');
                     self.screen_position.offset += 1;
                 }
                 self.buffer_position.offset += 1;
             } else {
-                self.terminal.print(&self.screen_position, style, color, &character);
+                self.print_content(style, color, &character);
                 self.screen_position.offset += 1;
                 self.buffer_position.offset += 1;
             }
@@ -226,62 +427,80 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
 
         let buffer_data = self.buffer.data();
         let lines = LineIterator::new(&buffer_data);
+        self.bracket_match = bracket::find_match(&buffer_data, &*self.buffer.cursor);
 
         let highlighter = Highlighter::new(&self.theme);
         let syntax_definition = self.buffer.syntax_definition.as_ref().ok_or("Buffer has no syntax definition")?;
 
+        // Reuse lines highlighted during previous renders, provided
+        // the buffer hasn't changed and nothing has been scrolled.
+        let highlight_cache = self.highlight_cache;
+        let mut highlight_cache = highlight_cache.borrow_mut();
+        highlight_cache.prepare(self.highlight_key());
+
         // Start or resume state from a previous cache point, if available.
-        let (cached_line_no, mut state) = self
-            .cached_render_state()
-            .unwrap_or((0, RenderState::new(&highlighter, syntax_definition)));
-        let (focused_style, blurred_style) = self.mapper_styles();
+        // Without one near the visible lines, highlighting starts afresh a
+        // short distance above them. The resulting state is approximate, and
+        // is never cached, so that it can't be resumed by later renders.
+        let margin_line_no = self.scroll_offset.saturating_sub(TOKENIZATION_MARGIN);
+        let (cached_line_no, mut state) = highlight_cache
+            .resume_state()
+            .cloned()
+            .or_else(|| self.cached_render_state())
+            .and_then(|(line_no, state)| {
+                if line_no >= margin_line_no {
+                    Some((line_no, state))
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_else(|| {
+                let mut state = RenderState::new(&highlighter, syntax_definition);
+                state.approximate = margin_line_no > 0;
+
+                (margin_line_no, state)
+            });
+        let mapper_styles = self.mapper_styles();
+        let mut last_parsed_line = None;
 
         'print: for (line_no, line) in lines {
-            // Skip past lines that precede the cached render state.
-            if line_no >= cached_line_no {
-                if line_no % RENDER_CACHE_FREQUENCY == 0 && line_no > 0 {
+            if highlight_cache.line(line_no).is_some() {
+                // This line was highlighted during a previous render.
+                for &(style, ref lexeme) in highlight_cache.line(line_no).unwrap() {
+                    if !self.print_styled_lexeme(style, lexeme, &mut lexeme_mapper, mapper_styles) {
+                        break 'print;
+                    }
+                }
+            } else if line_no >= cached_line_no {
+                // Lines preceding the cached render state are skipped.
+                if line_no % RENDER_CACHE_FREQUENCY == 0 && line_no > 0 && !state.approximate {
                     self.render_cache.borrow_mut().insert(line_no, state.clone());
                 }
 
                 let events = state.parse.parse_line(line);
-                let styled_lexemes = HighlightIterator::new(
+                let styled_lexemes: Vec<(ThemeStyle, &str)> = HighlightIterator::new(
                     &mut state.highlight,
                     &events,
                     line,
                     &highlighter
-                );
+                ).collect();
+                last_parsed_line = Some(line_no);
+
+                // Keep visible lines around for subsequent renders.
+                if line_no >= self.scroll_offset {
+                    highlight_cache.insert(
+                        line_no,
+                        styled_lexemes
+                            .iter()
+                            .map(|&(style, lexeme)| (style, lexeme.to_string()))
+                            .collect()
+                    );
+                }
 
                 for (style, lexeme) in styled_lexemes {
-                    // Move along until we've hit visible content.
-                    if self.before_visible_content() {
-                        continue;
-                    }
-
-                    // Stop the machine after we've printed all visible content.
-                    if self.after_visible_content() {
+                    if !self.print_styled_lexeme(style, lexeme, &mut lexeme_mapper, mapper_styles) {
                         break 'print;
                     }
-
-                    // We're in a visible area.
-                    if let Some(ref mut mapper) = lexeme_mapper {
-                        let mapped_lexemes = mapper.map(lexeme, self.buffer_position);
-                        for mapped_lexeme in mapped_lexemes {
-                            match mapped_lexeme {
-                                MappedLexeme::Focused(value) => {
-                                    self.current_style = focused_style;
-                                    self.print_lexeme(value);
-                                },
-                                MappedLexeme::Blurred(value) => {
-                                    self.current_style = blurred_style;
-                                    self.print_lexeme(value);
-                                }
-                            }
-
-                        }
-                    } else {
-                        self.current_style = style;
-                        self.print_lexeme(lexeme);
-                    }
                 }
             }
 
@@ -290,6 +509,12 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
             }
         }
 
+        // Track the state following the last highlighted line,
+        // in case more of the buffer becomes visible later on.
+        if let Some(line_no) = last_parsed_line {
+            highlight_cache.set_resume_state(line_no + 1, state);
+        }
+
         self.set_cursor();
 
         // One last call to this for the last line.
@@ -301,8 +526,65 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
         Ok(self.cursor_position)
     }
 
+    /// Prints a highlighted lexeme, deferring to the lexeme mapper if one is
+    /// present. Returns false once all of the visible content has been printed.
+    fn print_styled_lexeme(&mut self, style: ThemeStyle, lexeme: &str,
+    lexeme_mapper: &mut Option<&'b mut LexemeMapper>,
+    mapper_styles: (ThemeStyle, ThemeStyle)) -> bool {
+        // Move along until we've hit visible content.
+        if self.before_visible_content() || self.on_hidden_line() {
+            return true;
+        }
+
+        // Stop the machine after we've printed all visible content.
+        if self.after_visible_content() {
+            return false;
+        }
+
+        // We're in a visible area.
+        if let Some(ref mut mapper) = *lexeme_mapper {
+            let (focused_style, blurred_style) = mapper_styles;
+            let mapped_lexemes = mapper.map(lexeme, self.buffer_position);
+            for mapped_lexeme in mapped_lexemes {
+                match mapped_lexeme {
+                    MappedLexeme::Focused(value) => {
+                        self.current_style = focused_style;
+                        self.print_lexeme(value);
+                    },
+                    MappedLexeme::Blurred(value) => {
+                        self.current_style = blurred_style;
+                        self.print_lexeme(value);
+                    }
+                }
+            }
+        } else {
+            self.current_style = style;
+            self.print_lexeme(lexeme);
+        }
+
+        true
+    }
+
+    fn highlight_key(&self) -> HighlightKey {
+        HighlightKey {
+            scroll_offset: self.scroll_offset,
+            width: self.screen_width(),
+            height: self.terminal.height(),
+            theme: self.preferences.theme().to_string(),
+        }
+    }
+
     fn print_line_number(&mut self) {
         if !self.inside_visible_content() { return };
+        if self.on_hidden_line() {
+            // Keep line numbers in step with the buffer.
+            self.line_numbers.next();
+            return;
+        }
+        if !self.line_numbers_visible {
+            self.screen_position.offset = self.gutter_width;
+            return;
+        }
 
         let line_number = self.line_numbers.next().unwrap();
 
@@ -313,8 +595,19 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
             Style::Default
         };
 
+        // Annotations, when present, precede line numbers.
+        let annotation_width = self.annotation_width();
+        if let Some(annotation) = self.annotations.and_then(|a| a.get(self.buffer_position.line)) {
+            self.terminal.print(
+                &Position{ line: self.screen_position.line, offset: 0 },
+                Style::Default,
+                self.theme.map_colors(Colors::Focused),
+                annotation
+            );
+        }
+
         self.terminal.print(
-            &Position{ line: self.screen_position.line, offset: 0 },
+            &Position{ line: self.screen_position.line, offset: annotation_width },
             weight,
             self.theme.map_colors(Colors::Focused),
             &line_number
@@ -323,13 +616,13 @@ impl<'a, 'b> BufferRenderer<'a, 'b> {
         // Leave a one-column gap between line numbers and buffer content.
         if self.on_cursor_line() {
             self.terminal.print(
-                &Position{ line: self.screen_position.line, offset: self.line_numbers.width() },
+                &Position{ line: self.screen_position.line, offset: annotation_width + self.line_numbers.width() },
                 weight,
                 self.theme.map_colors(Colors::Focused),
                 &line_number
             );
         }
-        self.screen_position.offset = self.line_numbers.width() + 1;
+        self.screen_position.offset = self.gutter_width;
     }
 
     fn next_tab_stop(&self, offset: usize) -> usize {
@@ -379,13 +672,16 @@ fn has_trailing_newline(line: &str) -> bool {
 mod tests {
     use models::application::Preferences;
     use scribe::{Buffer, Workspace};
-    use scribe::buffer::Position;
+    use scribe::buffer::{Position, Range};
     use std::cell::RefCell;
     use std::collections::HashMap;
     use std::path::{Path, PathBuf};
     use std::rc::Rc;
     use super::{BufferRenderer, LexemeMapper, MappedLexeme};
-    use syntect::highlighting::ThemeSet;
+    use view::buffer::HighlightCache;
+    use view::{Colors, RGBColor, TOKENIZATION_MARGIN};
+    use view::color::ColorMap;
+    use syntect::highlighting::{Theme, ThemeSet};
     use view::terminal::{Terminal, TestTerminal};
     use yaml::yaml::YamlLoader;
 
@@ -411,7 +707,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &Rc::new(RefCell::new(HashMap::new()))
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
     }
 
@@ -439,7 +736,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &Rc::new(RefCell::new(HashMap::new()))
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         // Both tabs should fully expand.
@@ -470,7 +768,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &Rc::new(RefCell::new(HashMap::new()))
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         // The space between the tabs should just eat into the second tab's width.
@@ -498,7 +797,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &Rc::new(RefCell::new(HashMap::new()))
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         assert_eq!(
@@ -506,6 +806,109 @@ mod tests {
             " 1  amp ed\n    itor  \n 2  second\n     line \n 3        ");
     }
 
+    #[test]
+    fn render_leaves_the_last_column_free_for_the_scrollbar() {
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor\nsecond line\n");
+        workspace.add_buffer(buffer);
+
+        let mut terminal = TestTerminal::new();
+        let theme_set = ThemeSet::load_defaults();
+        let preferences = Preferences::new(None);
+
+        BufferRenderer::new(
+            workspace.current_buffer().unwrap(),
+            None,
+            None,
+            0,
+            &mut terminal,
+            &theme_set.themes["base16-ocean.dark"],
+            &preferences,
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
+        ).with_reserved_columns(1).render().unwrap();
+
+        let content = terminal.content();
+        assert!(content.starts_with(" 1  amp e\n    ditor"));
+        assert!(content.lines().all(|line| line.chars().count() < terminal.width()));
+    }
+
+    #[test]
+    fn render_only_highlights_lines_near_the_visible_region() {
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n".repeat(5000));
+        workspace.add_buffer(buffer);
+
+        let mut terminal = TestTerminal::new();
+        let theme_set = ThemeSet::load_defaults();
+        let preferences = Preferences::new(None);
+        let render_cache = Rc::new(RefCell::new(HashMap::new()));
+        let highlight_cache = Rc::new(RefCell::new(HighlightCache::new()));
+
+        BufferRenderer::new(
+            workspace.current_buffer().unwrap(),
+            None,
+            None,
+            4000,
+            &mut terminal,
+            &theme_set.themes["base16-ocean.dark"],
+            &preferences,
+            &render_cache,
+            &highlight_cache
+        ).render().unwrap();
+
+        // Parsing started afresh at the tokenization margin, so the
+        // resulting states are approximate, and none should be cached.
+        assert!(highlight_cache.borrow().resume_state().unwrap().1.approximate);
+        assert!(render_cache.borrow().is_empty());
+    }
+
+    #[test]
+    fn render_resumes_cached_states_near_the_visible_region() {
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n".repeat(5000));
+        workspace.add_buffer(buffer);
+
+        let mut terminal = TestTerminal::new();
+        let theme_set = ThemeSet::load_defaults();
+        let preferences = Preferences::new(None);
+        let render_cache = Rc::new(RefCell::new(HashMap::new()));
+
+        // Cache states parsed from the start of the buffer.
+        BufferRenderer::new(
+            workspace.current_buffer().unwrap(),
+            None,
+            None,
+            TOKENIZATION_MARGIN,
+            &mut terminal,
+            &theme_set.themes["base16-ocean.dark"],
+            &preferences,
+            &render_cache,
+            &Rc::new(RefCell::new(HighlightCache::new()))
+        ).render().unwrap();
+        assert_eq!(render_cache.borrow().keys().max(), Some(&TOKENIZATION_MARGIN));
+
+        // Scrolling within the margin of those states resumes them,
+        // so the states that follow are exact, and cached in turn.
+        let highlight_cache = Rc::new(RefCell::new(HighlightCache::new()));
+        BufferRenderer::new(
+            workspace.current_buffer().unwrap(),
+            None,
+            None,
+            2 * TOKENIZATION_MARGIN,
+            &mut terminal,
+            &theme_set.themes["base16-ocean.dark"],
+            &preferences,
+            &render_cache,
+            &highlight_cache
+        ).render().unwrap();
+        assert!(!highlight_cache.borrow().resume_state().unwrap().1.approximate);
+        assert_eq!(render_cache.borrow().keys().max(), Some(&(2 * TOKENIZATION_MARGIN)));
+    }
+
     // Used to test lexeme mapper usage.
     struct TestMapper {}
     impl LexemeMapper for TestMapper {
@@ -535,7 +938,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &Rc::new(RefCell::new(HashMap::new()))
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         assert_eq!(terminal.content(), " 1  mapped");
@@ -562,7 +966,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &Rc::new(RefCell::new(HashMap::new()))
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         assert_eq!(cursor_position, Some(Position{ line: 0, offset: 4 }));
@@ -593,7 +998,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &render_cache
+            &render_cache,
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         assert_eq!(render_cache.borrow().keys().count(), 5);
@@ -625,7 +1031,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &render_cache
+            &render_cache,
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         assert_eq!(render_cache.borrow().keys().count(), 1);
@@ -644,7 +1051,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &render_cache
+            &render_cache,
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         assert_eq!(render_cache.borrow().keys().count(), 5);
@@ -679,7 +1087,8 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &render_cache
+            &render_cache,
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         assert_eq!(render_cache.borrow().keys().count(), 1);
@@ -698,11 +1107,128 @@ mod tests {
             &mut terminal,
             &theme_set.themes["base16-ocean.dark"],
             &preferences,
-            &render_cache
+            &render_cache,
+            &Rc::new(RefCell::new(HighlightCache::new()))
         ).render().unwrap();
 
         assert_eq!(
             terminal.content(),
             " 201  line\n 202  line\n 203  line\n 204      ");
     }
+
+    fn render_colors(highlight_current_line: bool) -> [[Option<(char, Colors)>; 10]; 10] {
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert("ab\ncd");
+        workspace.add_buffer(buffer);
+
+        let mut terminal = TestTerminal::new();
+        let theme_set = ThemeSet::load_defaults();
+        let data = YamlLoader::load_from_str(
+            &format!("highlight_current_line: {}", highlight_current_line)
+        ).unwrap().into_iter().nth(0).unwrap();
+        let preferences = Preferences::new(Some(data));
+        let highlights = vec![
+            Range::new(Position{ line: 0, offset: 0 }, Position{ line: 0, offset: 1 })
+        ];
+
+        BufferRenderer::new(
+            workspace.current_buffer().unwrap(),
+            Some(&highlights),
+            None,
+            0,
+            &mut terminal,
+            &theme_set.themes["base16-ocean.dark"],
+            &preferences,
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
+        ).render().unwrap();
+
+        terminal.data()
+    }
+
+    #[test]
+    fn render_highlights_the_current_line_background() {
+        let data = render_colors(true);
+
+        // The selection takes precedence over the current line highlight.
+        assert_eq!(
+            data[0][4],
+            Some(('a', Colors::Custom(RGBColor(255, 255, 255), RGBColor(0, 120, 160))))
+        );
+        match data[0][5] {
+            Some(('b', Colors::Custom(_, _))) => (),
+            ref cell => panic!("Unexpected current line cell: {:?}", cell),
+        }
+        match data[1][4] {
+            Some(('c', Colors::CustomForeground(_))) => (),
+            ref cell => panic!("Unexpected cell: {:?}", cell),
+        }
+    }
+
+    #[test]
+    fn render_skips_the_current_line_background_when_disabled() {
+        let data = render_colors(false);
+
+        assert_eq!(
+            data[0][4],
+            Some(('a', Colors::Custom(RGBColor(255, 255, 255), RGBColor(0, 120, 160))))
+        );
+        match data[0][5] {
+            Some(('b', Colors::CustomForeground(_))) => (),
+            ref cell => panic!("Unexpected cell: {:?}", cell),
+        }
+    }
+
+    fn render_brackets(content: &str, cursor: Position) -> ([[Option<(char, Colors)>; 10]; 10], Theme) {
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.insert(content);
+        buffer.cursor.move_to(cursor);
+        workspace.add_buffer(buffer);
+
+        let mut terminal = TestTerminal::new();
+        let theme = ThemeSet::load_defaults().themes["base16-ocean.dark"].clone();
+        let preferences = Preferences::new(None);
+
+        BufferRenderer::new(
+            workspace.current_buffer().unwrap(),
+            None,
+            None,
+            0,
+            &mut terminal,
+            &theme,
+            &preferences,
+            &Rc::new(RefCell::new(HashMap::new())),
+            &Rc::new(RefCell::new(HighlightCache::new()))
+        ).render().unwrap();
+
+        (terminal.data(), theme)
+    }
+
+    #[test]
+    fn render_highlights_the_bracket_matching_the_one_under_the_cursor() {
+        let (data, theme) = render_brackets("(a)", Position{ line: 0, offset: 0 });
+
+        assert_eq!(data[0][6], Some((')', theme.map_colors(Colors::Inverted))));
+        match data[0][4] {
+            Some(('(', Colors::Custom(_, _))) => (),
+            ref cell => panic!("Unexpected cursor cell: {:?}", cell),
+        }
+        assert_ne!(data[0][4], Some(('(', theme.map_colors(Colors::Warning))));
+    }
+
+    #[test]
+    fn render_highlights_unmatched_brackets_under_the_cursor() {
+        let (data, theme) = render_brackets("(a", Position{ line: 0, offset: 0 });
+
+        assert_eq!(data[0][4], Some(('(', theme.map_colors(Colors::Warning))));
+    }
+
+    #[test]
+    fn render_does_not_highlight_brackets_when_the_cursor_is_elsewhere() {
+        let (data, theme) = render_brackets("(a)", Position{ line: 0, offset: 1 });
+
+        assert_ne!(data[0][6], Some((')', theme.map_colors(Colors::Inverted))));
+    }
 }
@@ -10,6 +10,8 @@ use view::terminal::Terminal;
 pub struct ScrollableRegion {
     terminal: Arc<Terminal>,
     line_offset: usize,
+    column_offset: usize,
+    reserved_columns: usize,
 }
 
 impl ScrollableRegion {
@@ -17,9 +19,17 @@ impl ScrollableRegion {
         ScrollableRegion {
             terminal,
             line_offset: 0,
+            column_offset: 0,
+            reserved_columns: 0,
         }
     }
 
+    /// Excludes the specified number of columns on the right-hand
+    /// side of the screen (e.g. for a scrollbar) from the region.
+    pub fn reserve_columns(&mut self, columns: usize) {
+        self.reserved_columns = columns;
+    }
+
     /// If necessary, moves the line offset such that the specified line is
     /// visible, using previous state to determine whether said line is at
     /// the top or bottom of the new visible range.
@@ -40,6 +50,29 @@ impl ScrollableRegion {
         }
     }
 
+    /// If necessary, moves the column offset such that the cursor is visible,
+    /// keeping the specified margin of columns on either side of it.
+    /// This only applies to regions without line wrapping.
+    pub fn scroll_horizontally_into_view(&mut self, buffer: &Buffer, tab_width: usize, margin: usize) {
+        // The buffer renderer adds a single-column margin
+        // to the right-hand side of the line number columns.
+        let gutter_width = LineNumbers::new(&buffer, None).width() + 1;
+        let content_width = self.content_width(gutter_width);
+
+        self.column_offset = column_offset(
+            self.column_offset,
+            cursor_column(buffer, tab_width),
+            content_width,
+            margin
+        );
+    }
+
+    /// The number of columns the region has scrolled over.
+    /// A value of zero represents an unscrolled region.
+    pub fn column_offset(&self) -> usize {
+        self.column_offset
+    }
+
     /// Moves the line offset such that the specified line is centered vertically.
     pub fn scroll_to_center(&mut self, buffer: &Buffer) {
         let limit = (self.height() as f32 / 2.0).ceil() as usize;
@@ -55,6 +88,10 @@ impl ScrollableRegion {
         self.line_offset
     }
 
+    pub fn set_line_offset(&mut self, line_offset: usize) {
+        self.line_offset = line_offset;
+    }
+
     pub fn scroll_up(&mut self, amount: usize) {
         self.line_offset = match self.line_offset.checked_sub(amount) {
             Some(amount) => amount,
@@ -69,7 +106,13 @@ impl ScrollableRegion {
     /// Scrollable regions occupy one line short of the full
     /// terminal height, which is reserved for the status line.
     fn height(&self) -> usize {
-        self.terminal.height() - 1
+        self.terminal.height().checked_sub(1).unwrap_or(0)
+    }
+
+    /// The number of columns available to buffer content, following
+    /// the gutter and preceding any reserved columns.
+    fn content_width(&self, gutter_width: usize) -> usize {
+        self.terminal.width().saturating_sub(gutter_width + self.reserved_columns)
     }
 
     /// Assuming that the buffer cursor is at the bottom of the screen,
@@ -93,7 +136,7 @@ impl ScrollableRegion {
             .take(line_count)
             .map(|line| {
                 let grapheme_count = line.graphemes(true).count().max(1) as f32;
-                let buffer_content_width = (self.terminal.width() - gutter_width) as f32;
+                let buffer_content_width = self.content_width(gutter_width).max(1) as f32;
                 let wrapped_line_count = grapheme_count / buffer_content_width;
 
                 wrapped_line_count.ceil() as usize
@@ -116,7 +159,7 @@ impl ScrollableRegion {
         // The lines() iterator used above doesn't yield a final line
         // for trailing newlines, but Amp considers there to be one.
         // This adjustment accounts for that difference.
-        if visual_line_counts.len() < line_count && preceding_line_count < limit - 1 {
+        if visual_line_counts.len() < line_count && preceding_line_count < limit.checked_sub(1).unwrap_or(0) {
             preceding_line_count += 1;
         }
 
@@ -124,6 +167,40 @@ impl ScrollableRegion {
     }
 }
 
+/// The cursor's on-screen column, relative to the start
+/// of its line, with tab characters expanded to tab stops.
+fn cursor_column(buffer: &Buffer, tab_width: usize) -> usize {
+    let data = buffer.data();
+    let line = data.lines().nth(buffer.cursor.line).unwrap_or("");
+
+    line.graphemes(true)
+        .take(buffer.cursor.offset)
+        .fold(0, |column, grapheme| {
+            if grapheme == "\t" {
+                (column / tab_width.max(1) + 1) * tab_width.max(1)
+            } else {
+                column + 1
+            }
+        })
+}
+
+/// Computes the column offset required to keep the cursor column visible
+/// within a viewport of the specified width, preferring the current offset.
+fn column_offset(current_offset: usize, cursor_column: usize, width: usize, margin: usize) -> usize {
+    // Narrow viewports can't accommodate the full margin on both sides.
+    let margin = margin.min(width.checked_sub(1).unwrap_or(0) / 2);
+
+    if cursor_column < current_offset + margin {
+        // Cursor is left of (or too close to) the visible range.
+        cursor_column.checked_sub(margin).unwrap_or(0)
+    } else if cursor_column + margin >= current_offset + width {
+        // Cursor is right of (or too close to) the visible range.
+        (cursor_column + margin + 1).checked_sub(width).unwrap_or(0)
+    } else {
+        current_offset
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -311,4 +388,51 @@ mod tests {
         region.scroll_up(5);
         assert_eq!(region.line_offset(), 0);
     }
+
+    #[test]
+    fn column_offset_is_unchanged_when_cursor_is_visible() {
+        assert_eq!(super::column_offset(0, 10, 80, 5), 0);
+        assert_eq!(super::column_offset(20, 50, 80, 5), 20);
+    }
+
+    #[test]
+    fn column_offset_advances_when_cursor_passes_right_edge() {
+        assert_eq!(super::column_offset(0, 80, 80, 5), 6);
+        assert_eq!(super::column_offset(0, 76, 80, 0), 0);
+        assert_eq!(super::column_offset(0, 80, 80, 0), 1);
+    }
+
+    #[test]
+    fn column_offset_recedes_when_cursor_returns_left() {
+        assert_eq!(super::column_offset(50, 52, 80, 5), 47);
+        assert_eq!(super::column_offset(50, 2, 80, 5), 0);
+    }
+
+    #[test]
+    fn column_offset_limits_margin_in_narrow_viewports() {
+        assert_eq!(super::column_offset(0, 10, 5, 5), 8);
+    }
+
+    #[test]
+    fn cursor_column_expands_tabs() {
+        let mut buffer = Buffer::new();
+        buffer.insert("\tamp\teditor");
+        buffer.cursor.move_to(Position{ line: 0, offset: 5 });
+
+        assert_eq!(super::cursor_column(&buffer, 4), 8);
+    }
+
+    #[test]
+    fn scroll_horizontally_into_view_keeps_cursor_visible() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut buffer = Buffer::new();
+        let mut region = ScrollableRegion::new(terminal);
+        buffer.insert("a".repeat(100));
+        buffer.cursor.move_to(Position{ line: 0, offset: 50 });
+        region.scroll_horizontally_into_view(&buffer, 2, 2);
+
+        // The gutter occupies four of the terminal's ten columns,
+        // leaving the cursor and its margin in the last three.
+        assert_eq!(region.column_offset(), 47);
+    }
 }
@@ -14,6 +14,7 @@ pub enum Colors {
     PathMode,      // white/pink
     SearchMode,    // white/purple
     SelectMode,    // white/blue
+    StatusLine(&'static str), // themed per mode (e.g. "insert"), with a fallback
     CustomForeground(RGBColor),
     CustomFocusedForeground(RGBColor),
     Custom(RGBColor, RGBColor),
@@ -1,7 +1,11 @@
+use std::cmp::Ordering;
 use syntect::highlighting::Theme;
+use syntect::parsing::Scope;
 use view::color::to_rgb_color;
 use view::color::{Colors, RGBColor};
 
+const STATUS_LINE_SCOPE: &str = "amp.status_line";
+
 pub trait ColorMap {
     fn map_colors(&self, colors: Colors) -> Colors;
 }
@@ -36,9 +40,101 @@ impl ColorMap for Theme {
             Colors::PathMode => Colors::Custom(RGBColor(255, 255, 255), RGBColor(255, 20, 147)),
             Colors::SearchMode => Colors::Custom(RGBColor(255, 255, 255), RGBColor(120, 0, 120)),
             Colors::SelectMode => Colors::Custom(RGBColor(255, 255, 255), RGBColor(0, 120, 160)),
+            Colors::StatusLine(mode) => status_line_colors(self, mode)
+                .unwrap_or_else(|| self.map_colors(default_status_line_colors(mode))),
             Colors::CustomForeground(f) => Colors::CustomForeground(f),
             Colors::CustomFocusedForeground(f) => Colors::Custom(f, alt_bg),
             Colors::Custom(custom_fg, custom_bg) => Colors::Custom(custom_fg, custom_bg),
         }
     }
 }
+
+/// Looks up a mode's status line colors using a theme scope
+/// named after it (e.g. `amp.status_line.insert`). Themes are
+/// only required to provide a background; the foreground is white
+/// if omitted, matching the built-in mode colors.
+fn status_line_colors(theme: &Theme, mode: &str) -> Option<Colors> {
+    let scope = Scope::new(&format!("{}.{}", STATUS_LINE_SCOPE, mode)).ok()?;
+    let style = theme.scopes
+        .iter()
+        .filter_map(|item| item.scope.does_match(&[scope]).map(|power| (power, &item.style)))
+        .max_by(|&(a, _), &(b, _)| a.partial_cmp(&b).unwrap_or(Ordering::Equal))
+        .map(|(_, style)| style)?;
+    let bg = style.background.map(to_rgb_color)?;
+    let fg = style.foreground.map(to_rgb_color).unwrap_or(RGBColor(255, 255, 255));
+
+    Some(Colors::Custom(fg, bg))
+}
+
+/// Status line colors for modes whose colors aren't defined by the theme.
+fn default_status_line_colors(mode: &str) -> Colors {
+    match mode {
+        "insert" => Colors::Insert,
+        "select" | "select_line" => Colors::SelectMode,
+        "search" => Colors::SearchMode,
+        "path" | "shell_command" | "line_filter" => Colors::PathMode,
+        "confirm" => Colors::Warning,
+        "find_char" | "line_jump" | "replace_char" | "separator" => Colors::Default,
+        _ => Colors::Inverted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorMap;
+    use std::str::FromStr;
+    use syntect::highlighting::{Color, ScopeSelectors, StyleModifier, Theme, ThemeItem};
+    use view::color::{Colors, RGBColor};
+
+    fn theme() -> Theme {
+        let mut theme = Theme::default();
+        theme.scopes.push(ThemeItem {
+            scope: ScopeSelectors::from_str("amp.status_line.insert").unwrap(),
+            style: StyleModifier {
+                foreground: Some(Color { r: 0, g: 0, b: 0, a: 255 }),
+                background: Some(Color { r: 0, g: 200, b: 0, a: 255 }),
+                font_style: None,
+            },
+        });
+        theme.scopes.push(ThemeItem {
+            scope: ScopeSelectors::from_str("amp.status_line.normal").unwrap(),
+            style: StyleModifier {
+                foreground: None,
+                background: Some(Color { r: 0, g: 0, b: 200, a: 255 }),
+                font_style: None,
+            },
+        });
+
+        theme
+    }
+
+    #[test]
+    fn status_line_colors_use_the_theme_scope_for_the_mode() {
+        assert_eq!(
+            theme().map_colors(Colors::StatusLine("insert")),
+            Colors::Custom(RGBColor(0, 0, 0), RGBColor(0, 200, 0))
+        );
+        assert_eq!(
+            theme().map_colors(Colors::StatusLine("normal")),
+            Colors::Custom(RGBColor(255, 255, 255), RGBColor(0, 0, 200))
+        );
+    }
+
+    #[test]
+    fn status_line_colors_fall_back_to_built_in_mode_colors() {
+        let theme = theme();
+
+        assert_eq!(
+            theme.map_colors(Colors::StatusLine("select")),
+            theme.map_colors(Colors::SelectMode)
+        );
+        assert_eq!(
+            theme.map_colors(Colors::StatusLine("jump")),
+            theme.map_colors(Colors::Inverted)
+        );
+        assert_eq!(
+            Theme::default().map_colors(Colors::StatusLine("insert")),
+            Theme::default().map_colors(Colors::Insert)
+        );
+    }
+}
@@ -5,3 +5,9 @@ pub struct StatusLineData {
     pub style: Style,
     pub colors: Colors,
 }
+
+/// Content shown in a pane along the right edge of the screen.
+pub struct Sidebar {
+    pub width: usize,
+    pub lines: Vec<StatusLineData>,
+}
@@ -0,0 +1,209 @@
+use scribe::buffer::Position;
+use view::{Colors, Style};
+
+/// The content and formatting of a single terminal cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cell {
+    pub content: String,
+    pub style: Style,
+    pub colors: Colors,
+}
+
+/// A grid of terminal cells, used to record a rendered screen so
+/// that it can be compared against the one that preceded it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<Cell>>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Frame {
+        Frame {
+            width,
+            height,
+            cells: vec![None; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Empties every cell, leaving the dimensions untouched.
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            *cell = None;
+        }
+    }
+
+    pub fn cell(&self, position: &Position) -> Option<&Cell> {
+        self.index(position).and_then(|index| self.cells[index].as_ref())
+    }
+
+    /// Stores the cell at the specified position.
+    /// Positions outside of the frame are ignored.
+    pub fn set_cell(&mut self, position: &Position, cell: Cell) {
+        if let Some(index) = self.index(position) {
+            self.cells[index] = Some(cell);
+        }
+    }
+
+    /// Serializes the frame's content as plain text, one line per row,
+    /// with empty cells treated as spaces and trailing whitespace removed.
+    pub fn to_text(&self) -> String {
+        let mut lines: Vec<String> = self.cells
+            .chunks(self.width.max(1))
+            .map(|row| {
+                let line: String = row
+                    .iter()
+                    .map(|cell| cell.as_ref().map(|cell| cell.content.as_str()).unwrap_or(" "))
+                    .collect();
+
+                line.trim_right().to_string()
+            })
+            .collect();
+
+        // Drop blank rows at the bottom of the screen.
+        while lines.last().map(|line| line.is_empty()).unwrap_or(false) {
+            lines.pop();
+        }
+
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    fn index(&self, position: &Position) -> Option<usize> {
+        if position.line < self.height && position.offset < self.width {
+            Some(position.line * self.width + position.offset)
+        } else {
+            None
+        }
+    }
+}
+
+/// Compares two frames, returning the positions of cells that differ, in
+/// row-major order. Frames with different dimensions can't be compared
+/// cell-by-cell, so every position in the current frame is returned.
+pub fn changed_cells(previous: &Frame, current: &Frame) -> Vec<Position> {
+    let resized = previous.width != current.width || previous.height != current.height;
+
+    current.cells
+        .iter()
+        .enumerate()
+        .filter(|&(index, cell)| resized || previous.cells[index] != *cell)
+        .map(|(index, _)| {
+            Position {
+                line: index / current.width,
+                offset: index % current.width,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{changed_cells, Cell, Frame};
+    use scribe::buffer::Position;
+    use view::{Colors, Style};
+
+    fn cell(content: &str) -> Cell {
+        Cell {
+            content: content.to_string(),
+            style: Style::Default,
+            colors: Colors::Default,
+        }
+    }
+
+    #[test]
+    fn changed_cells_returns_nothing_for_identical_frames() {
+        let mut frame = Frame::new(3, 2);
+        frame.set_cell(&Position{ line: 1, offset: 2 }, cell("a"));
+
+        assert!(changed_cells(&frame, &frame.clone()).is_empty());
+    }
+
+    #[test]
+    fn changed_cells_returns_added_modified_and_removed_cells() {
+        let mut previous = Frame::new(3, 2);
+        previous.set_cell(&Position{ line: 0, offset: 0 }, cell("a"));
+        previous.set_cell(&Position{ line: 0, offset: 1 }, cell("b"));
+        previous.set_cell(&Position{ line: 1, offset: 0 }, cell("c"));
+
+        let mut current = Frame::new(3, 2);
+        current.set_cell(&Position{ line: 0, offset: 0 }, cell("a"));
+        current.set_cell(&Position{ line: 0, offset: 1 }, cell("x"));
+        current.set_cell(&Position{ line: 1, offset: 2 }, cell("d"));
+
+        assert_eq!(
+            changed_cells(&previous, &current),
+            vec![
+                Position{ line: 0, offset: 1 },
+                Position{ line: 1, offset: 0 },
+                Position{ line: 1, offset: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_cells_includes_cells_with_changed_formatting() {
+        let mut previous = Frame::new(2, 1);
+        previous.set_cell(&Position{ line: 0, offset: 0 }, cell("a"));
+        previous.set_cell(&Position{ line: 0, offset: 1 }, cell("b"));
+
+        let mut current = previous.clone();
+        current.set_cell(
+            &Position{ line: 0, offset: 0 },
+            Cell { style: Style::Bold, ..cell("a") }
+        );
+        current.set_cell(
+            &Position{ line: 0, offset: 1 },
+            Cell { colors: Colors::Inverted, ..cell("b") }
+        );
+
+        assert_eq!(
+            changed_cells(&previous, &current),
+            vec![Position{ line: 0, offset: 0 }, Position{ line: 0, offset: 1 }]
+        );
+    }
+
+    #[test]
+    fn changed_cells_returns_every_position_when_dimensions_differ() {
+        let previous = Frame::new(1, 1);
+        let current = Frame::new(2, 2);
+
+        assert_eq!(
+            changed_cells(&previous, &current),
+            vec![
+                Position{ line: 0, offset: 0 },
+                Position{ line: 0, offset: 1 },
+                Position{ line: 1, offset: 0 },
+                Position{ line: 1, offset: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn set_cell_ignores_positions_outside_of_the_frame() {
+        let mut frame = Frame::new(2, 2);
+        frame.set_cell(&Position{ line: 0, offset: 2 }, cell("a"));
+        frame.set_cell(&Position{ line: 2, offset: 0 }, cell("b"));
+
+        assert_eq!(frame, Frame::new(2, 2));
+    }
+
+    #[test]
+    fn to_text_serializes_cell_contents() {
+        let mut frame = Frame::new(4, 3);
+        frame.set_cell(&Position{ line: 0, offset: 0 }, cell("a"));
+        frame.set_cell(&Position{ line: 0, offset: 2 }, cell("m"));
+        frame.set_cell(&Position{ line: 1, offset: 1 }, cell("p"));
+        frame.set_cell(&Position{ line: 1, offset: 3 }, cell(" "));
+
+        assert_eq!(frame.to_text(), "a m\n p\n");
+    }
+}
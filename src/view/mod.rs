@@ -3,12 +3,14 @@ pub mod terminal;
 mod buffer;
 mod data;
 mod event_listener;
+mod frame;
+mod scrollbar;
 mod style;
 mod theme_loader;
 
 // Published API
-pub use self::data::StatusLineData;
-pub use self::buffer::{LexemeMapper, MappedLexeme};
+pub use self::data::{Sidebar, StatusLineData};
+pub use self::buffer::{LexemeMapper, LineNumbering, MappedLexeme};
 pub use self::style::Style;
 pub use self::color::{Colors, RGBColor};
 
@@ -16,33 +18,56 @@ use errors::*;
 use input::Key;
 use models::application::{Event, Preferences};
 use self::color::ColorMap;
-use self::buffer::{BufferRenderer, RenderCache, RenderState};
+use self::buffer::{BufferRenderer, HighlightCache, RenderCache, RenderState};
 use self::buffer::ScrollableRegion;
 use self::event_listener::EventListener;
-use scribe::buffer::{Buffer, Position, Range};
+use scribe::buffer::{Buffer, LineRange, Position, Range, TokenSet};
 use pad::PadStr;
 use std::cmp;
 use std::collections::HashMap;
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::ops::Drop;
 use std::sync::mpsc::{self, Sender, SyncSender};
 use std::sync::Arc;
+use util::{spell_check, token};
 use self::theme_loader::ThemeLoader;
-use self::terminal::Terminal;
+use self::terminal::{BufferedTerminal, Terminal};
 use syntect::highlighting::ThemeSet;
 
 const RENDER_CACHE_FREQUENCY: usize = 100;
 
+/// The number of lines preceding the visible region that are tokenized for
+/// highlighting and spell checking. Scrolling further than this beyond the
+/// tokenized lines starts over from the margin, rather than from the start
+/// of the buffer, so that large files aren't tokenized in their entirety.
+const TOKENIZATION_MARGIN: usize = 500;
+
+/// Misspelled words found on a buffer's visible lines,
+/// along with the revision and lines they were found for.
+struct Misspellings {
+    revision: usize,
+    lines: (usize, usize),
+    ranges: Rc<Vec<Range>>,
+}
+
 pub struct View {
-    terminal: Arc<Terminal + Sync + Send>,
+    terminal: Arc<BufferedTerminal>,
     cursor_position: Option<Position>,
     scrollable_regions: HashMap<usize, ScrollableRegion>,
     render_caches: HashMap<usize, Rc<RefCell<HashMap<usize, RenderState>>>>,
+    highlight_caches: HashMap<usize, Rc<RefCell<HighlightCache>>>,
+    revisions: HashMap<usize, Rc<Cell<usize>>>,
+    folds: HashMap<usize, Rc<RefCell<Vec<LineRange>>>>,
+    misspellings: HashMap<usize, Misspellings>,
+    sidebar: Option<Sidebar>,
     pub theme_set: ThemeSet,
     preferences: Rc<RefCell<Preferences>>,
     pub last_key: Option<Key>,
+    pub line_numbering: LineNumbering,
+    pub zen_mode: bool,
+    pub typewriter_mode: bool,
     event_channel: Sender<Event>,
     event_listener_killswitch: SyncSender<()>
 }
@@ -51,6 +76,7 @@ impl View {
     pub fn new(terminal: Arc<Terminal + Sync + Send>, preferences: Rc<RefCell<Preferences>>, event_channel: Sender<Event>) -> Result<View> {
         let theme_path = preferences.borrow().theme_path()?;
         let theme_set = ThemeLoader::new(theme_path).load()?;
+        let terminal = Arc::new(BufferedTerminal::new(terminal));
 
         let (killswitch_tx, killswitch_rx) = mpsc::sync_channel(0);
         EventListener::start(terminal.clone(), event_channel.clone(), killswitch_rx);
@@ -59,9 +85,17 @@ impl View {
             terminal,
             cursor_position: None,
             last_key: None,
+            line_numbering: LineNumbering::default(),
+            zen_mode: false,
+            typewriter_mode: false,
             preferences,
             scrollable_regions: HashMap::new(),
             render_caches: HashMap::new(),
+            highlight_caches: HashMap::new(),
+            revisions: HashMap::new(),
+            folds: HashMap::new(),
+            misspellings: HashMap::new(),
+            sidebar: None,
             theme_set,
             event_channel,
             event_listener_killswitch: killswitch_tx
@@ -69,29 +103,121 @@ impl View {
     }
 
     pub fn draw_buffer(&mut self, buffer: &Buffer, highlights: Option<&[Range]>, lexeme_mapper: Option<&mut LexemeMapper>) -> Result<()> {
+        self.draw_annotated_buffer(buffer, highlights, lexeme_mapper, None)
+    }
+
+    /// Draws the buffer, with an optional set of per-line
+    /// annotations rendered in a column ahead of the line numbers.
+    pub fn draw_annotated_buffer(&mut self, buffer: &Buffer, highlights: Option<&[Range]>, lexeme_mapper: Option<&mut LexemeMapper>, annotations: Option<&[String]>) -> Result<()> {
         let scroll_offset = self.get_region(buffer)?.line_offset();
+        let column_offset = self.get_region(buffer)?.column_offset();
+        let misspellings = self.visible_misspellings(buffer)?;
         let preferences = self.preferences.borrow();
         let theme_name = preferences.theme();
         let theme = self.theme_set.themes
             .get(theme_name)
             .ok_or_else(|| format!("Couldn't find \"{}\" theme", theme_name))?;
-
-        let cursor_position = BufferRenderer::new(
-            buffer,
-            highlights,
-            lexeme_mapper,
-            scroll_offset,
-            &*self.terminal,
-            theme,
-            &self.preferences.borrow(),
-            self.get_render_cache(buffer)?
-        ).render()?;
+        let fold_cell = self.folds.get(&buffer_key(buffer)?).cloned();
+        let folds = fold_cell.as_ref().map(|folds| folds.borrow());
+
+        let cursor_position = {
+            let mut renderer = BufferRenderer::new(
+                buffer,
+                highlights,
+                lexeme_mapper,
+                scroll_offset,
+                &*self.terminal,
+                theme,
+                &preferences,
+                self.get_render_cache(buffer)?,
+                self.get_highlight_cache(buffer)?
+            ).with_line_numbering(self.line_numbering);
+            if let Some(annotations) = annotations {
+                renderer = renderer.with_annotations(annotations);
+            }
+            if self.zen_mode {
+                renderer = renderer.with_zen_mode(preferences.zen_mode_width());
+            }
+            let reserved_columns = self.reserved_columns();
+            if reserved_columns > 0 {
+                renderer = renderer.with_reserved_columns(reserved_columns);
+            }
+            if let Some(ref misspellings) = misspellings {
+                renderer = renderer.with_misspellings(misspellings);
+            }
+            if !preferences.line_wrapping() {
+                renderer = renderer.with_column_offset(column_offset);
+            }
+            if let Some(ref folds) = folds {
+                renderer = renderer.with_folds(folds);
+            }
+            renderer.render()?
+        };
 
         self.cursor_position = cursor_position;
 
         Ok(())
     }
 
+    /// Finds the misspelled words on the buffer's visible lines, if spell
+    /// checking is enabled, reusing them until the buffer changes or scrolls.
+    fn visible_misspellings(&mut self, buffer: &Buffer) -> Result<Option<Rc<Vec<Range>>>> {
+        let preferences = self.preferences.clone();
+        let preferences = preferences.borrow();
+        if !preferences.spell_check() {
+            return Ok(None);
+        }
+
+        // A missing dictionary shouldn't prevent the buffer from
+        // rendering; the spell check commands will report it instead.
+        let dictionary = match preferences.spell_check_dictionary() {
+            Ok(dictionary) => dictionary,
+            Err(_) => return Ok(None),
+        };
+
+        // Folded lines don't take up any space, revealing those below them.
+        let key = buffer_key(buffer)?;
+        let folded_lines: usize = self.folds
+            .get(&key)
+            .map(|folds| folds.borrow().iter().map(|fold| fold.end() - fold.start()).sum())
+            .unwrap_or(0);
+        let first_line = self.get_region(buffer)?.line_offset();
+        let lines = (first_line, first_line + self.height() + folded_lines);
+
+        let revision = self.buffer_revision(buffer).ok();
+        if let (Some(revision), Some(misspellings)) = (revision, self.misspellings.get(&key)) {
+            if misspellings.revision == revision && misspellings.lines == lines {
+                return Ok(Some(misspellings.ranges.clone()));
+            }
+        }
+
+        // Only tokenize the visible lines, and a margin above them.
+        let syntax_definition = match buffer.syntax_definition {
+            Some(ref syntax_definition) => syntax_definition,
+            None => return Ok(None),
+        };
+        let start_line = lines.0.saturating_sub(TOKENIZATION_MARGIN);
+        let data = buffer.data();
+        let tokens = TokenSet::new(token::line_window(&data, start_line, lines.1).to_string(), syntax_definition);
+        let ranges: Rc<Vec<Range>> = Rc::new(
+            spell_check::misspellings_between(tokens.iter(), &dictionary, lines.0 - start_line, lines.1 - start_line)
+                .into_iter()
+                .map(|range| {
+                    let (start, end) = (range.start(), range.end());
+                    Range::new(
+                        Position{ line: start.line + start_line, offset: start.offset },
+                        Position{ line: end.line + start_line, offset: end.offset }
+                    )
+                })
+                .collect()
+        );
+        if let Some(revision) = revision {
+            self.misspellings.insert(key, Misspellings{ revision, lines, ranges: ranges.clone() });
+        }
+
+        Ok(Some(ranges))
+    }
+
     /// Renders the app name, version and copyright info to the screen.
     pub fn draw_splash_screen(&mut self) -> Result<()> {
         let content = vec![
@@ -104,9 +230,10 @@ impl View {
         let vertical_offset = line_count / 2;
 
         for (line_no, line) in content.iter().enumerate() {
-            let mut position = Position{
-                line: self.height() / 2 + line_no - vertical_offset,
-                offset: self.width() / 2 - line.chars().count() / 2
+            // Terminals too small to fit the content clip it, rather than centering it.
+            let position = Position{
+                line: (self.height() / 2 + line_no).checked_sub(vertical_offset).unwrap_or(line_no),
+                offset: (self.width() / 2).checked_sub(line.chars().count() / 2).unwrap_or(0)
             };
 
             self.print(&position, Style::Default, Colors::Default, &line)?;
@@ -115,8 +242,55 @@ impl View {
         Ok(())
     }
 
+    /// Renders the lines as a left-aligned block, centered on the screen.
+    pub fn draw_centered_block(&mut self, content: &[String]) -> Result<()> {
+        let width = content.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let offset = (self.width() / 2).checked_sub(width / 2).unwrap_or(0);
+        let line_offset = (self.height() / 2).checked_sub(content.len() / 2).unwrap_or(0);
+
+        for (line_no, line) in content.iter().enumerate() {
+            let position = Position{ line: line_offset + line_no, offset };
+            self.print(&position, Style::Default, Colors::Default, &line)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws a scrollbar in the rightmost column, if the preference is
+    /// enabled, with the visible portion of the buffer emphasized.
+    pub fn draw_scrollbar(&mut self, buffer: &Buffer) -> Result<()> {
+        if !self.preferences.borrow().scrollbar() {
+            return Ok(());
+        }
+
+        let scroll_offset = self.get_region(buffer)?.line_offset();
+        let height = self.height().checked_sub(1).unwrap_or(0);
+        let column = match self.width().checked_sub(1 + self.sidebar_width()) {
+            Some(column) => column,
+            None => return Ok(()),
+        };
+
+        if let Some(segment) = scrollbar::segment(buffer.line_count(), height, scroll_offset) {
+            for line in 0..height {
+                let colors = if line >= segment.start && line < segment.start + segment.length {
+                    Colors::Inverted
+                } else {
+                    Colors::Focused
+                };
+                self.print(&Position{ line, offset: column }, Style::Default, colors, &' ')?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn draw_status_line(&self, data: &[StatusLineData]) {
-        let line = self.height() - 1;
+        // Zen mode hides everything but the buffer itself.
+        if self.zen_mode {
+            return;
+        }
+
+        let line = self.height().checked_sub(1).unwrap_or(0);
 
         data.iter().enumerate().fold(0, |offset, (index, element)| {
             let content = match data.len() {
@@ -127,7 +301,7 @@ impl View {
                 2 => {
                     if index == data.len() - 1 {
                         // Expand the last element to fill the remaining width.
-                        element.content.pad_to_width(self.width() - offset)
+                        element.content.pad_to_width(self.width().checked_sub(offset).unwrap_or(0))
                     } else {
                         element.content.clone()
                     }
@@ -135,7 +309,11 @@ impl View {
                 _ => {
                     if index == data.len() - 2 {
                         // Before-last element extends to fill unused space.
-                        element.content.pad_to_width(self.width() - offset - data[index+1].content.len())
+                        element.content.pad_to_width(
+                            self.width()
+                                .checked_sub(offset + data[index+1].content.len())
+                                .unwrap_or(0)
+                        )
                     } else {
                         element.content.clone()
                     }
@@ -152,12 +330,57 @@ impl View {
         });
     }
 
+    /// Draws the sidebar, if shown, in the rightmost columns above the
+    /// status line, padding (or truncating) its lines to fill its width.
+    fn draw_sidebar(&self) -> Result<()> {
+        let width = self.sidebar_width();
+        let sidebar = match self.sidebar {
+            Some(ref sidebar) if width > 0 => sidebar,
+            _ => return Ok(()),
+        };
+        let offset = self.width().saturating_sub(width);
+        let height = self.height().saturating_sub(1);
+
+        for line in 0..height {
+            match sidebar.lines.get(line) {
+                Some(data) => {
+                    let content: String = data.content.chars().take(width).collect();
+                    self.print(&Position{ line, offset }, data.style, data.colors, &content.pad_to_width(width))?;
+                },
+                None => {
+                    self.print(&Position{ line, offset }, Style::Default, Colors::Default, &String::new().pad_to_width(width))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     ///
     /// Scrollable region delegation methods.
     ///
 
     pub fn scroll_to_cursor(&mut self, buffer: &Buffer) -> Result<()> {
-        self.get_region(buffer)?.scroll_into_view(&buffer);
+        // Typewriter mode keeps the cursor line centered at all times.
+        if self.typewriter_mode {
+            self.get_region(buffer)?.scroll_to_center(&buffer);
+        } else {
+            self.get_region(buffer)?.scroll_into_view(&buffer);
+        }
+
+        // Wrapped lines are always fully visible; only
+        // scroll horizontally when wrapping is disabled.
+        let (line_wrapping, tab_width, margin) = {
+            let preferences = self.preferences.borrow();
+            (
+                preferences.line_wrapping(),
+                preferences.tab_width(buffer.path.as_ref()),
+                preferences.horizontal_scroll_margin()
+            )
+        };
+        if !line_wrapping {
+            self.get_region(buffer)?.scroll_horizontally_into_view(&buffer, tab_width, margin);
+        }
 
         Ok(())
     }
@@ -168,6 +391,18 @@ impl View {
         Ok(())
     }
 
+    /// The number of lines the buffer's region has scrolled over.
+    pub fn scroll_offset(&mut self, buffer: &Buffer) -> Result<usize> {
+        Ok(self.get_region(buffer)?.line_offset())
+    }
+
+    /// Restores a previously recorded scroll offset (e.g. after a preview).
+    pub fn set_scroll_offset(&mut self, buffer: &Buffer, offset: usize) -> Result<()> {
+        self.get_region(buffer)?.set_line_offset(offset);
+
+        Ok(())
+    }
+
     pub fn scroll_up(&mut self, buffer: &Buffer, amount: usize) -> Result<()> {
         self.get_region(buffer)?.scroll_up(amount);
 
@@ -203,19 +438,87 @@ impl View {
     pub fn forget_buffer(&mut self, buffer: &Buffer) -> Result<()> {
         self.scrollable_regions.remove(&buffer_key(buffer)?);
         self.render_caches.remove(&buffer_key(buffer)?);
+        self.highlight_caches.remove(&buffer_key(buffer)?);
+        self.revisions.remove(&buffer_key(buffer)?);
+        self.folds.remove(&buffer_key(buffer)?);
+        self.misspellings.remove(&buffer_key(buffer)?);
+
+        Ok(())
+    }
+
+    /// Discards the buffer's cached syntax highlighting, so that it's
+    /// highlighted from scratch (e.g. after its syntax has been changed).
+    pub fn invalidate_highlighting(&mut self, buffer: &Buffer) -> Result<()> {
+        if let Some(render_cache) = self.render_caches.get(&buffer_key(buffer)?) {
+            render_cache.borrow_mut().clear();
+        }
+        if let Some(highlight_cache) = self.highlight_caches.get(&buffer_key(buffer)?) {
+            highlight_cache.borrow_mut().invalidate();
+        }
+
+        Ok(())
+    }
+
+    /// Collapses the specified lines when rendering the buffer,
+    /// replacing any existing folds that overlap them.
+    pub fn fold(&mut self, buffer: &Buffer, range: LineRange) -> Result<()> {
+        let mut folds = self.folds
+            .entry(buffer_key(buffer)?)
+            .or_insert_with(|| Rc::new(RefCell::new(Vec::new())))
+            .borrow_mut();
+        folds.retain(|fold| fold.end() < range.start() || fold.start() > range.end());
+        folds.push(range);
 
         Ok(())
     }
 
+    /// Expands the innermost fold containing the specified
+    /// line, returning false if there isn't one to expand.
+    pub fn unfold(&mut self, buffer: &Buffer, line: usize) -> Result<bool> {
+        let mut folds = match self.folds.get(&buffer_key(buffer)?) {
+            Some(folds) => folds.borrow_mut(),
+            None => return Ok(false),
+        };
+        let index = folds
+            .iter()
+            .enumerate()
+            .filter(|&(_, fold)| fold.start() <= line && line <= fold.end())
+            .min_by_key(|&(_, fold)| fold.end() - fold.start())
+            .map(|(index, _)| index);
+
+        Ok(index.map(|index| { folds.remove(index); true }).unwrap_or(false))
+    }
+
     // Tries to fetch a scrollable region for the specified buffer,
     // inserting (and returning a reference to) a new one if not.
     fn get_region(&mut self, buffer: &Buffer) -> Result<&mut ScrollableRegion> {
-        Ok(self.scrollable_regions
+        // Content can't extend into the scrollbar's column or the sidebar.
+        let reserved_columns = self.reserved_columns();
+        let region = self.scrollable_regions
             .entry(buffer_key(buffer)?)
             .or_insert(
                 ScrollableRegion::new(self.terminal.clone())
-            )
-        )
+            );
+        region.reserve_columns(reserved_columns);
+
+        Ok(region)
+    }
+
+    /// The number of columns to the right of buffer content,
+    /// occupied by the scrollbar and sidebar, if shown.
+    fn reserved_columns(&self) -> usize {
+        let scrollbar_width = if self.preferences.borrow().scrollbar() { 1 } else { 0 };
+
+        scrollbar_width + self.sidebar_width()
+    }
+
+    fn sidebar_width(&self) -> usize {
+        // Zen mode hides everything but the buffer itself.
+        if self.zen_mode {
+            return 0;
+        }
+
+        self.sidebar.as_ref().map(|sidebar| sidebar.width).unwrap_or(0)
     }
 
     fn get_render_cache(&self, buffer: &Buffer) -> Result<&Rc<RefCell<HashMap<usize, RenderState>>>> {
@@ -226,6 +529,14 @@ impl View {
         Ok(cache)
     }
 
+    fn get_highlight_cache(&self, buffer: &Buffer) -> Result<&Rc<RefCell<HighlightCache>>> {
+        let cache = self.highlight_caches
+            .get(&buffer_key(buffer)?)
+            .ok_or("Buffer not properly initialized (highlight cache not present).")?;
+
+        Ok(cache)
+    }
+
     ///
     /// Terminal delegation methods.
     ///
@@ -246,11 +557,40 @@ impl View {
         self.terminal.clear()
     }
 
+    /// Shows (or, given None, hides) a pane along the right
+    /// edge of the screen, drawn each time the view is presented.
+    pub fn set_sidebar(&mut self, sidebar: Option<Sidebar>) {
+        self.sidebar = sidebar;
+    }
+
     pub fn present(&mut self) {
+        let _ = self.draw_sidebar();
         self.terminal.set_cursor(self.cursor_position);
         self.terminal.present();
     }
 
+    /// The content currently shown on screen, as plain text.
+    pub fn screen_text(&self) -> String {
+        self.terminal.presented_frame().to_text()
+    }
+
+    /// Forces the next present to redraw the entire screen, rather than
+    /// only the cells that have changed (e.g. after the terminal is resized).
+    pub fn invalidate(&mut self) {
+        self.terminal.invalidate();
+    }
+
+    /// Adapts to new terminal dimensions, forcing a full redraw and scrolling
+    /// the buffer (if one is provided) so that its cursor remains visible.
+    pub fn resize(&mut self, buffer: Option<&Buffer>) -> Result<()> {
+        self.invalidate();
+        if let Some(buffer) = buffer {
+            self.scroll_to_cursor(buffer)?;
+        }
+
+        Ok(())
+    }
+
     pub fn print(&self, position: &Position, style: Style, colors: Colors, content: &Display) -> Result<()> {
         let preferences = self.preferences.borrow();
         let theme_name = preferences.theme();
@@ -275,6 +615,15 @@ impl View {
         &self.last_key
     }
 
+    /// A counter that increases whenever the buffer's content changes,
+    /// for use in detecting changes without comparing buffer contents.
+    pub fn buffer_revision(&self, buffer: &Buffer) -> Result<usize> {
+        self.revisions
+            .get(&buffer_key(buffer)?)
+            .map(|revision| revision.get())
+            .ok_or_else(|| Error::from("Buffer revision isn't being tracked"))
+    }
+
     /// Sets up new buffers with render caches and cache invalidation callbacks.
     pub fn initialize_buffer(&mut self, buffer: &mut Buffer) -> Result<()> {
         // Build and store a new render cache for the buffer.
@@ -284,10 +633,36 @@ impl View {
             render_cache.clone()
         );
 
-        // Wire up the buffer's change callback to invalidate the render cache.
+        // Build and store a new highlight cache for the buffer.
+        let highlight_cache = Rc::new(RefCell::new(HighlightCache::new()));
+        self.highlight_caches.insert(
+            buffer_key(buffer)?,
+            highlight_cache.clone()
+        );
+
+        // Build and store a new revision counter for the buffer.
+        let revision = Rc::new(Cell::new(0));
+        self.revisions.insert(
+            buffer_key(buffer)?,
+            revision.clone()
+        );
+
+        // Build and store the buffer's (initially empty) folds.
+        let folds = Rc::new(RefCell::new(Vec::new()));
+        self.folds.insert(
+            buffer_key(buffer)?,
+            folds.clone()
+        );
+
+        // Wire up the buffer's change callback to invalidate the caches,
+        // advance the buffer's revision, and discard any folds that the
+        // change may have shifted or altered (i.e. those not above it).
         buffer.change_callback = Some(
             Box::new(move |change_position| {
+                folds.borrow_mut().retain(|fold: &LineRange| fold.end() < change_position.line);
                 render_cache.borrow_mut().invalidate_from(change_position.line);
+                highlight_cache.borrow_mut().invalidate();
+                revision.set(revision.get() + 1);
             })
         );
 
@@ -361,6 +736,51 @@ mod tests {
         assert_eq!(view.get_region(&buffer).unwrap().line_offset(), 0);
     }
 
+    #[test]
+    fn resize_scrolls_to_keep_the_cursor_within_the_smaller_viewport() {
+        let terminal = Arc::new(TestTerminal::new());
+        let preferences = Rc::new(RefCell::new(Preferences::new(None)));
+        let (tx, _) = mpsc::channel();
+        let mut view = View::new(terminal.clone(), preferences, tx).unwrap();
+
+        // Build a 20-line buffer, with the cursor visible near the
+        // bottom of the screen, without needing to scroll.
+        let mut buffer = Buffer::new();
+        buffer.id = Some(0);
+        for _ in 0..20 {
+            buffer.insert("line\n");
+        }
+        buffer.cursor.move_to(Position{ line: 8, offset: 0 });
+        view.scroll_to_cursor(&buffer).unwrap();
+        assert_eq!(view.get_region(&buffer).unwrap().line_offset(), 0);
+
+        // Shrink the terminal, leaving four lines for the buffer
+        // (the status line uses the fifth) and hiding the cursor.
+        terminal.resize(10, 5);
+        view.resize(Some(&buffer)).unwrap();
+
+        // The cursor should be on the last visible line.
+        assert_eq!(view.get_region(&buffer).unwrap().line_offset(), 5);
+    }
+
+    #[test]
+    fn resize_handles_terminals_too_small_to_display_the_buffer() {
+        let terminal = Arc::new(TestTerminal::new());
+        let preferences = Rc::new(RefCell::new(Preferences::new(None)));
+        let (tx, _) = mpsc::channel();
+        let mut view = View::new(terminal.clone(), preferences, tx).unwrap();
+
+        let mut buffer = Buffer::new();
+        buffer.id = Some(0);
+        buffer.insert("amp\neditor\n");
+        buffer.cursor.move_to(Position{ line: 1, offset: 0 });
+
+        terminal.resize(1, 1);
+        view.resize(Some(&buffer)).unwrap();
+
+        assert_eq!(view.get_region(&buffer).unwrap().line_offset(), 1);
+    }
+
     #[test]
     fn draw_buffer_caches_render_states() {
         let terminal = Arc::new(TestTerminal::new());
@@ -388,6 +808,7 @@ mod tests {
 
         // Draw the buffer and capture the terminal data.
         view.draw_buffer(workspace.current_buffer().unwrap(), None, None).unwrap();
+        view.present();
         let initial_data = terminal.data();
 
         // By inserting a single quote, we'll change the color of the entire
@@ -395,9 +816,43 @@ mod tests {
         // actually changed, because of the cache.
         workspace.current_buffer().unwrap().insert("\"");
         view.draw_buffer(workspace.current_buffer().unwrap(), None, None).unwrap();
+        view.present();
         assert_eq!(terminal.data(), initial_data);
     }
 
+    #[test]
+    fn draw_buffer_reuses_highlighting_until_buffer_is_edited() {
+        let terminal = Arc::new(TestTerminal::new());
+        let preferences = Rc::new(RefCell::new(Preferences::new(None)));
+        let (tx, _) = mpsc::channel();
+        let mut view = View::new(terminal.clone(), preferences, tx).unwrap();
+
+        // Set up a Rust-categorized buffer.
+        let mut workspace = Workspace::new(Path::new(".")).unwrap();
+        let mut buffer = Buffer::new();
+        buffer.id = Some(0);
+        buffer.path = Some(PathBuf::from("rust.rs"));
+        for _ in 0..20 {
+            buffer.insert("line\n");
+        }
+        view.initialize_buffer(&mut buffer).unwrap();
+        workspace.add_buffer(buffer);
+
+        // Draw the buffer once to populate the cache.
+        view.draw_buffer(workspace.current_buffer().unwrap(), None, None).unwrap();
+        let highlight_count = view.highlight_caches[&0].borrow().highlight_count();
+        assert!(highlight_count > 0);
+
+        // Drawing the unedited buffer again shouldn't highlight anything.
+        view.draw_buffer(workspace.current_buffer().unwrap(), None, None).unwrap();
+        assert_eq!(view.highlight_caches[&0].borrow().highlight_count(), highlight_count);
+
+        // Editing the buffer should invalidate the cache.
+        workspace.current_buffer().unwrap().insert("\"");
+        view.draw_buffer(workspace.current_buffer().unwrap(), None, None).unwrap();
+        assert!(view.highlight_caches[&0].borrow().highlight_count() > highlight_count);
+    }
+
     #[test]
     fn initialize_buffer_creates_render_cache_for_buffer() {
         let terminal = Arc::new(TestTerminal::new());
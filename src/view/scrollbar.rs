@@ -0,0 +1,46 @@
+use std::cmp;
+
+/// A scrollbar's visible portion: the first row and number of rows it spans.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Computes the portion of a scrollbar of `height` rows that represents the
+/// visible lines, proportional to the viewport/total line ratio. There's no
+/// segment if all of the lines fit in the viewport (or there's no room).
+pub fn segment(line_count: usize, height: usize, scroll_offset: usize) -> Option<Segment> {
+    if height == 0 || line_count <= height {
+        return None;
+    }
+
+    // Always show at least one row, so that the position is visible.
+    let length = cmp::max(1, height * height / line_count);
+    let start = cmp::min(scroll_offset * height / line_count, height - length);
+
+    Some(Segment { start, length })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{segment, Segment};
+
+    #[test]
+    fn segment_is_proportional_to_the_visible_lines() {
+        assert_eq!(segment(100, 10, 0), Some(Segment { start: 0, length: 1 }));
+        assert_eq!(segment(40, 10, 20), Some(Segment { start: 5, length: 2 }));
+        assert_eq!(segment(20, 10, 10), Some(Segment { start: 5, length: 5 }));
+    }
+
+    #[test]
+    fn segment_stays_on_screen_when_scrolled_past_the_end() {
+        assert_eq!(segment(40, 10, 39), Some(Segment { start: 8, length: 2 }));
+    }
+
+    #[test]
+    fn segment_is_omitted_when_everything_is_visible() {
+        assert_eq!(segment(10, 10, 0), None);
+        assert_eq!(segment(10, 0, 0), None);
+    }
+}
@@ -1,7 +1,8 @@
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Style {
     Default,
     Bold,
     Inverted,
     Italic,
+    Underlined,
 }
@@ -0,0 +1,187 @@
+use models::application::Event;
+use scribe::buffer::Position;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use super::Terminal;
+use unicode_segmentation::UnicodeSegmentation;
+use view::frame::{self, Cell, Frame};
+use view::{Colors, Style};
+
+/// Wraps a terminal, recording printed content in a back buffer rather than
+/// passing it along immediately. When presented, the back buffer is compared
+/// against the previously presented frame, and only the cells that have
+/// changed are sent to the underlying terminal.
+pub struct BufferedTerminal {
+    terminal: Arc<Terminal + Sync + Send>,
+    frames: Mutex<Frames>,
+}
+
+struct Frames {
+    current: Frame,
+    presented: Frame,
+}
+
+impl BufferedTerminal {
+    pub fn new(terminal: Arc<Terminal + Sync + Send>) -> BufferedTerminal {
+        let (width, height) = (terminal.width(), terminal.height());
+
+        BufferedTerminal {
+            terminal,
+            frames: Mutex::new(Frames {
+                current: Frame::new(width, height),
+                presented: Frame::new(width, height),
+            }),
+        }
+    }
+
+    /// The most recently presented screen content.
+    pub fn presented_frame(&self) -> Frame {
+        self.frames.lock().unwrap().presented.clone()
+    }
+
+    /// Clears the underlying terminal and forgets what was last presented,
+    /// so that the next call to present redraws every cell.
+    pub fn invalidate(&self) {
+        let mut frames = self.frames.lock().unwrap();
+        let (width, height) = (self.terminal.width(), self.terminal.height());
+
+        self.terminal.clear();
+        frames.presented = Frame::new(width, height);
+        if frames.current.width() != width || frames.current.height() != height {
+            frames.current = Frame::new(width, height);
+        }
+    }
+}
+
+impl Terminal for BufferedTerminal {
+    fn listen(&self) -> Option<Event> {
+        self.terminal.listen()
+    }
+
+    fn clear(&self) {
+        let resized = {
+            let frames = self.frames.lock().unwrap();
+
+            frames.current.width() != self.terminal.width() ||
+                frames.current.height() != self.terminal.height()
+        };
+
+        if resized {
+            self.invalidate();
+        } else {
+            self.frames.lock().unwrap().current.clear();
+        }
+    }
+
+    fn present(&self) {
+        let mut frames = self.frames.lock().unwrap();
+
+        for position in frame::changed_cells(&frames.presented, &frames.current) {
+            match frames.current.cell(&position) {
+                Some(cell) => self.terminal.print(&position, cell.style, cell.colors, &cell.content),
+                // The cell was emptied; blank out its previous content.
+                None => self.terminal.print(&position, Style::Default, Colors::Blank, &" "),
+            }
+        }
+        frames.presented = frames.current.clone();
+
+        self.terminal.present();
+    }
+
+    fn width(&self) -> usize {
+        self.terminal.width()
+    }
+
+    fn height(&self) -> usize {
+        self.terminal.height()
+    }
+
+    fn set_cursor(&self, position: Option<Position>) {
+        self.terminal.set_cursor(position);
+    }
+
+    fn print(&self, position: &Position, style: Style, colors: Colors, content: &Display) {
+        let mut frames = self.frames.lock().unwrap();
+        let content = content.to_string();
+
+        for (index, grapheme) in content.graphemes(true).enumerate() {
+            frames.current.set_cell(
+                &Position{ line: position.line, offset: position.offset + index },
+                Cell { content: grapheme.to_string(), style, colors }
+            );
+        }
+    }
+
+    fn suspend(&self) {
+        self.terminal.suspend();
+
+        // The screen may have been changed by other
+        // programs while we were suspended.
+        self.invalidate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BufferedTerminal;
+    use scribe::buffer::Position;
+    use std::sync::Arc;
+    use view::terminal::{Terminal, TestTerminal};
+    use view::{Colors, Style};
+
+    #[test]
+    fn print_defers_output_until_present() {
+        let terminal = Arc::new(TestTerminal::new());
+        let buffered_terminal = BufferedTerminal::new(terminal.clone());
+        buffered_terminal.print(&Position{ line: 0, offset: 0 }, Style::Default, Colors::Default, &"amp");
+
+        assert_eq!(terminal.content(), "");
+        buffered_terminal.present();
+        assert_eq!(terminal.content(), "amp");
+    }
+
+    #[test]
+    fn present_only_sends_changed_cells() {
+        let terminal = Arc::new(TestTerminal::new());
+        let buffered_terminal = BufferedTerminal::new(terminal.clone());
+        buffered_terminal.print(&Position{ line: 0, offset: 0 }, Style::Default, Colors::Default, &"amp");
+        buffered_terminal.present();
+
+        // Write directly to the underlying terminal; unchanged
+        // cells shouldn't be redrawn, leaving this content intact.
+        terminal.print(&Position{ line: 0, offset: 0 }, Style::Default, Colors::Default, &"x");
+        buffered_terminal.clear();
+        buffered_terminal.print(&Position{ line: 0, offset: 0 }, Style::Default, Colors::Default, &"amp");
+        buffered_terminal.print(&Position{ line: 1, offset: 0 }, Style::Default, Colors::Default, &"editor");
+        buffered_terminal.present();
+
+        assert_eq!(terminal.content(), "xmp\neditor");
+    }
+
+    #[test]
+    fn present_blanks_cells_that_were_cleared() {
+        let terminal = Arc::new(TestTerminal::new());
+        let buffered_terminal = BufferedTerminal::new(terminal.clone());
+        buffered_terminal.print(&Position{ line: 0, offset: 0 }, Style::Default, Colors::Default, &"amp");
+        buffered_terminal.present();
+        buffered_terminal.clear();
+        buffered_terminal.print(&Position{ line: 0, offset: 0 }, Style::Default, Colors::Default, &"a");
+        buffered_terminal.present();
+
+        assert_eq!(terminal.content(), "a  ");
+    }
+
+    #[test]
+    fn invalidate_redraws_every_cell_on_the_next_present() {
+        let terminal = Arc::new(TestTerminal::new());
+        let buffered_terminal = BufferedTerminal::new(terminal.clone());
+        buffered_terminal.print(&Position{ line: 0, offset: 0 }, Style::Default, Colors::Default, &"amp");
+        buffered_terminal.present();
+
+        terminal.print(&Position{ line: 0, offset: 0 }, Style::Default, Colors::Default, &"x");
+        buffered_terminal.invalidate();
+        buffered_terminal.present();
+
+        assert_eq!(terminal.content(), "amp");
+    }
+}
@@ -1,3 +1,4 @@
+mod buffered_terminal;
 mod rustbox_terminal;
 
 #[cfg(any(test, feature = "bench"))]
@@ -8,6 +9,7 @@ use scribe::buffer::Position;
 use std::fmt::Display;
 use view::{Colors, Style};
 
+pub use self::buffered_terminal::BufferedTerminal;
 pub use self::rustbox_terminal::RustboxTerminal;
 
 #[cfg(any(test, feature = "bench"))]
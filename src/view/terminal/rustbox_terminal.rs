@@ -57,6 +57,7 @@ impl Terminal for RustboxTerminal {
                     RustboxKey::PageDown => Some(Event::Key(Key::PageDown)),
                     RustboxKey::Char(c) => Some(Event::Key(Key::Char(c))),
                     RustboxKey::Ctrl(c) => Some(Event::Key(Key::Ctrl(c))),
+                    RustboxKey::F(n) => Some(Event::Key(Key::F(n as u8))),
                     _ => None,
                 }
             },
@@ -124,6 +125,7 @@ fn map_style(style: Style) -> rustbox::Style {
     match style {
         Style::Bold     => rustbox::RB_BOLD,
         Style::Inverted => rustbox::RB_REVERSE,
+        Style::Underlined => rustbox::RB_UNDERLINE,
         _               => rustbox::RB_NORMAL,
     }
 }
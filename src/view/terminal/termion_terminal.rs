@@ -105,6 +105,9 @@ impl Terminal for TermionTerminal {
                         TermionKey::Char('\t') => Some(Event::Key(Key::Tab)),
                         TermionKey::Char(c) => Some(Event::Key(Key::Char(c))),
                         TermionKey::Ctrl(c) => Some(Event::Key(Key::Ctrl(c))),
+                        TermionKey::Alt(c) => Some(Event::Key(Key::Alt(c))),
+                        TermionKey::F(n) => Some(Event::Key(Key::F(n))),
+                        TermionKey::BackTab => Some(Event::Key(Key::BackTab)),
                         _ => None,
                     }
                 })
@@ -224,5 +227,6 @@ fn map_style(style: Style) -> Option<Box<Display>> {
         Style::Bold => Some(Box::new(style::Bold)),
         Style::Inverted => Some(Box::new(style::Invert)),
         Style::Italic => Some(Box::new(style::Italic)),
+        Style::Underlined => Some(Box::new(style::Underline)),
     }
 }
@@ -14,6 +14,7 @@ const HEIGHT: usize = 10;
 pub struct TestTerminal {
     data: Mutex<[[Option<(char, Colors)>; WIDTH]; HEIGHT]>, // 2D array of chars to represent screen
     cursor: Mutex<Option<Position>>,
+    dimensions: Mutex<(usize, usize)>,
     key_sent: Mutex<bool>
 }
 
@@ -22,6 +23,7 @@ impl TestTerminal {
         TestTerminal {
             data: Mutex::new([[None; WIDTH]; HEIGHT]),
             cursor: Mutex::new(None),
+            dimensions: Mutex::new((WIDTH, HEIGHT)),
             key_sent: Mutex::new(false)
         }
     }
@@ -60,6 +62,12 @@ impl TestTerminal {
         data
     }
 
+    /// Changes the reported dimensions, as though the terminal had been
+    /// resized. These can't exceed the default 10x10 printable area.
+    pub fn resize(&self, width: usize, height: usize) {
+        *self.dimensions.lock().unwrap() = (width.min(WIDTH), height.min(HEIGHT));
+    }
+
     pub fn data(&self) -> [[Option<(char, Colors)>; WIDTH]; HEIGHT] {
         *self.data.lock().unwrap()
     }
@@ -85,8 +93,8 @@ impl Terminal for TestTerminal {
         }
     }
     fn present(&self) { }
-    fn width(&self) -> usize { 10 }
-    fn height(&self) -> usize { 10 }
+    fn width(&self) -> usize { self.dimensions.lock().unwrap().0 }
+    fn height(&self) -> usize { self.dimensions.lock().unwrap().1 }
     fn set_cursor(&self, position: Option<Position>) {
         let mut cursor = self.cursor.lock().unwrap();
         *cursor = position;
@@ -96,12 +104,13 @@ impl Terminal for TestTerminal {
         // Ignore lines beyond visible height.
         if position.line >= self.height() { return; }
 
+        let width = self.width();
         let mut data = self.data.lock().unwrap();
         let string_content = format!("{}", content);
 
         for (i, c) in string_content.chars().enumerate() {
             // Ignore characters beyond visible width.
-            if i+position.offset >= 10 { break; }
+            if i+position.offset >= width { break; }
 
             data[position.line][i+position.offset] = Some((c, colors));
         }